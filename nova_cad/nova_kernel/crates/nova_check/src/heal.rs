@@ -0,0 +1,865 @@
+//! Healing passes for imported bodies
+
+use crate::HealingOptions;
+use nalgebra::{Matrix3, Matrix4, Vector3, Vector4};
+use nova_geom::{CylindricalSurface, PlanarSurface, SphericalSurface, Surface, SurfaceType};
+use nova_math::{Point3, Vec3};
+use nova_topo::{Body, Edge, Entity, EntityId, EulerAdvanced, Face, Loop, Shell, TopologicalEntity, Vertex};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+/// Largest angle in radians between the normals of neighbouring facets
+/// gathered onto one curved surface by [`merge_faceted`]
+const FACET_SMOOTH_ANGLE: f64 = 0.7;
+
+/// Number of fixes made by each stage of [`repair`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RepairReport {
+    /// Degenerate edges removed
+    pub degenerate_edges: usize,
+    /// Vertices welded onto a coincident vertex
+    pub welded_vertices: usize,
+    /// Loops whose coedges were reordered or flipped
+    pub reordered_loops: usize,
+    /// Loops that could not be chained into a closed cycle
+    pub open_loops: usize,
+    /// Faces flipped to agree with their neighbours
+    pub flipped_faces: usize,
+    /// Surfaces replaced by planes
+    pub simplified_surfaces: usize,
+    /// Self-intersection fixing was requested but isn't supported, so any
+    /// self-intersections are left in place
+    pub self_intersections_unsupported: bool,
+}
+
+/// Clean up an imported body by running every healing pass in turn
+///
+/// The stages run in order: degenerate-edge removal, vertex welding and
+/// edge sharing (with `fix_gaps`), loop reordering, face orientation (with
+/// `fix_orientation`) and surface simplification (with `simplify`).
+/// Self-intersections are never fixed; the report says so when
+/// `fix_self_intersections` asks for it.
+pub fn repair(body: &mut Body, options: HealingOptions) -> RepairReport {
+    let tol = options.tolerance;
+    let mut report = RepairReport {
+        degenerate_edges: remove_degenerate_edges(body, tol),
+        self_intersections_unsupported: options.fix_self_intersections,
+        ..RepairReport::default()
+    };
+    if options.fix_gaps {
+        report.welded_vertices = weld_vertices(body, tol);
+    }
+    (report.reordered_loops, report.open_loops) = reorder_loops(body, tol);
+    if options.fix_orientation {
+        report.flipped_faces = orient_faces(body);
+    }
+    if options.simplify {
+        report.simplified_surfaces = simplify_surfaces(body, tol);
+    }
+    report
+}
+
+/// Weld vertices closer than `tol` and share the edges between them
+///
+/// Returns the number of vertices welded away.
+pub fn weld_vertices(body: &mut Body, tol: f64) -> usize {
+    body.shells_mut().iter_mut().map(|shell| EulerAdvanced::stitch_shell(shell, tol)).sum()
+}
+
+/// Chain every loop's coedges head-to-tail
+///
+/// Returns the number of loops changed and the number left open because
+/// their coedges don't close within `tol`.
+pub fn reorder_loops(body: &mut Body, tol: f64) -> (usize, usize) {
+    let (mut reordered, mut open) = (0, 0);
+    for shell in body.shells_mut() {
+        for lp in shell.faces_mut().iter_mut().flat_map(|f| f.loops_mut().iter_mut()) {
+            let before: Vec<_> = lp.coedges().iter().map(|c| (c.id(), c.sense())).collect();
+            if lp.reorder_and_orient(tol).is_err() {
+                open += 1;
+            } else if lp.coedges().iter().map(|c| (c.id(), c.sense())).ne(before) {
+                reordered += 1;
+            }
+        }
+    }
+    (reordered, open)
+}
+
+/// Flip faces so neighbours traverse shared edges in opposite directions
+///
+/// Each shell is flood-filled across its manifold edges, then flipped as a
+/// whole if its signed volume has the wrong sign: positive for outer shells,
+/// negative for voids. Returns the number of faces flipped.
+pub fn orient_faces(body: &mut Body) -> usize {
+    body.shells_mut().iter_mut().map(orient_shell).sum()
+}
+
+/// Flood-fill one shell's face orientations, see [`orient_faces`]
+fn orient_shell(shell: &mut Shell) -> usize {
+    // Per face, its edges and whether it runs along them after orientation
+    let uses: Vec<Vec<(EntityId, bool)>> = shell.faces().iter()
+        .map(|face| {
+            let reversed = face.orientation().is_reversed();
+            face.loops().iter()
+                .flat_map(|lp| lp.coedges())
+                .map(|c| (c.edge().id(), c.sense().is_same() != reversed))
+                .collect()
+        })
+        .collect();
+    let mut edge_faces: HashMap<EntityId, Vec<(usize, bool)>> = HashMap::new();
+    for (f, face_uses) in uses.iter().enumerate() {
+        for &(edge, forward) in face_uses {
+            edge_faces.entry(edge).or_default().push((f, forward));
+        }
+    }
+
+    let mut flip = vec![false; uses.len()];
+    let mut visited = vec![false; uses.len()];
+    for seed in 0..uses.len() {
+        if visited[seed] {
+            continue;
+        }
+        visited[seed] = true;
+        let mut queue = VecDeque::from([seed]);
+        while let Some(f) = queue.pop_front() {
+            for &(edge, forward) in &uses[f] {
+                let [(a, a_forward), (b, b_forward)] = edge_faces[&edge][..] else {
+                    continue;
+                };
+                let (g, g_forward) = if a == f { (b, b_forward) } else { (a, a_forward) };
+                if g == f || visited[g] {
+                    continue;
+                }
+                visited[g] = true;
+                // g must run against f along the shared edge
+                flip[g] = (forward != flip[f]) == g_forward;
+                queue.push_back(g);
+            }
+        }
+    }
+
+    for (face, &flipped) in shell.faces_mut().iter_mut().zip(&flip) {
+        if flipped {
+            face.reverse_orientation();
+        }
+    }
+    let volume = shell.signed_volume();
+    if (shell.is_outer() && volume < 0.0) || (!shell.is_outer() && volume > 0.0) {
+        for (face, flipped) in shell.faces_mut().iter_mut().zip(flip.iter_mut()) {
+            face.reverse_orientation();
+            *flipped = !*flipped;
+        }
+    }
+    flip.iter().filter(|&&f| f).count()
+}
+
+/// Replace curved surfaces that are flat within `tol` with planes
+///
+/// Surfaces are sampled on a grid over their parameter range; unbounded
+/// surfaces are left alone. Returns the number of surfaces replaced.
+pub fn simplify_surfaces(body: &mut Body, tol: f64) -> usize {
+    const SAMPLES: usize = 5;
+    let mut simplified = 0;
+    for face in body.shells_mut().iter_mut().flat_map(|s| s.faces_mut().iter_mut()) {
+        let Some(surface) = face.surface() else { continue };
+        let range = surface.uv_range();
+        let bounds = [range.u.start, range.u.end, range.v.start, range.v.end];
+        if surface.surface_type() == SurfaceType::Planar || bounds.iter().any(|b| !b.is_finite()) {
+            continue;
+        }
+        let (u, v) = ((range.u.start + range.u.end) / 2.0, (range.v.start + range.v.end) / 2.0);
+        let (origin, normal, (du, _)) = (surface.evaluate(u, v), surface.normal(u, v), surface.derivatives(u, v));
+        let flat = (0..SAMPLES * SAMPLES).all(|k| {
+            let s = (k % SAMPLES) as f64 / (SAMPLES - 1) as f64;
+            let t = (k / SAMPLES) as f64 / (SAMPLES - 1) as f64;
+            let p = surface.evaluate(
+                range.u.start + s * (range.u.end - range.u.start),
+                range.v.start + t * (range.v.end - range.v.start),
+            );
+            (p - origin).dot(&normal).abs() <= tol
+        });
+        if !flat {
+            continue;
+        }
+        if let Ok(plane) = PlanarSurface::new(origin, du, normal.cross(&du)) {
+            face.set_surface(Some(Arc::new(plane)));
+            simplified += 1;
+        }
+    }
+    simplified
+}
+
+/// Remove edges shorter than `tol`, merging their endpoints
+///
+/// Coedges on removed edges are dropped from their loops, and every edge
+/// touching a merged vertex is rebuilt on the merged vertex, which sits at
+/// the average of the vertices it replaces. Loops left empty are removed.
+/// Returns the number of edges removed.
+pub fn remove_degenerate_edges(body: &mut Body, tol: f64) -> usize {
+    let degenerate: Vec<&Edge> = body.edges().into_iter().filter(|e| e.length() < tol).collect();
+    if degenerate.is_empty() {
+        return 0;
+    }
+    let removed: Vec<EntityId> = degenerate.iter().map(|e| e.id()).collect();
+
+    // Union-find over the endpoints of degenerate edges
+    let mut parent: HashMap<EntityId, EntityId> = HashMap::new();
+    let mut endpoints: HashMap<EntityId, Vertex> = HashMap::new();
+    for edge in &degenerate {
+        let (a, b) = (edge.start_vertex(), edge.end_vertex());
+        endpoints.entry(a.id()).or_insert_with(|| a.clone());
+        endpoints.entry(b.id()).or_insert_with(|| b.clone());
+        let (ra, rb) = (find(&mut parent, a.id()), find(&mut parent, b.id()));
+        if ra != rb {
+            parent.insert(rb, ra);
+        }
+    }
+
+    // One merged vertex per cluster, keeping the root's id
+    let mut clusters: HashMap<EntityId, Vec<EntityId>> = HashMap::new();
+    // Sorted so each cluster averages its members in the same order every run
+    let mut ids: Vec<EntityId> = parent.keys().copied().collect();
+    ids.sort_unstable();
+    for id in ids {
+        let root = find(&mut parent, id);
+        clusters.entry(root).or_default().push(id);
+    }
+    let mut merged: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+    for (root, members) in &clusters {
+        let sum = members
+            .iter()
+            .fold(Vec3::ZERO, |acc, id| acc + endpoints[id].position().to_vector());
+        let mut vertex = endpoints[root].clone();
+        vertex.set_position(Point3::ORIGIN + sum / members.len() as f64);
+        let vertex = Arc::new(vertex);
+        for id in members {
+            merged.insert(*id, vertex.clone());
+        }
+    }
+
+    let mut kept: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+    let mut vertex_for = |v: &Vertex| -> Arc<Vertex> {
+        match merged.get(&v.id()) {
+            Some(m) => m.clone(),
+            None => kept.entry(v.id()).or_insert_with(|| Arc::new(v.clone())).clone(),
+        }
+    };
+    let mut rebuilt: HashMap<EntityId, Arc<Edge>> = HashMap::new();
+
+    for shell in body.shells_mut() {
+        for face in shell.faces_mut() {
+            for lp in face.loops_mut() {
+                lp.coedges_mut().retain(|c| !removed.contains(&c.edge().id()));
+                for coedge in lp.coedges_mut() {
+                    let edge = coedge.edge();
+                    let touches = merged.contains_key(&edge.start_vertex().id())
+                        || merged.contains_key(&edge.end_vertex().id());
+                    if !touches {
+                        continue;
+                    }
+                    let new_edge = match rebuilt.get(&edge.id()) {
+                        Some(e) => e.clone(),
+                        None => {
+                            let e = Arc::new(edge.remapped(
+                                vertex_for(edge.start_vertex()),
+                                vertex_for(edge.end_vertex()),
+                            ));
+                            rebuilt.insert(edge.id(), e.clone());
+                            e
+                        }
+                    };
+                    coedge.set_edge(new_edge);
+                }
+            }
+            face.loops_mut().retain(|lp| !lp.is_empty());
+        }
+    }
+
+    removed.len()
+}
+
+/// Split edges used by more than two coedges into manifold edges
+///
+/// The faces around such an edge are sorted radially about it, and each
+/// face is paired with its neighbour across the material between them when
+/// the two traverse the edge in opposite directions. Each pair, and each
+/// face left unpaired, gets its own edge; the first keeps the original.
+/// The new edges share the original's vertices and curve. Returns the
+/// number of edges added.
+pub fn split_nonmanifold_edges(body: &mut Body) -> usize {
+    // (shell, face, loop, coedge) indices of one use of an edge
+    type Location = (usize, usize, usize, usize);
+
+    let mut uses: HashMap<EntityId, Vec<(Location, EntityId, bool, f64)>> = HashMap::new();
+    let mut edges: HashMap<EntityId, &Edge> = HashMap::new();
+    for (si, shell) in body.shells().iter().enumerate() {
+        for (fi, face) in shell.faces().iter().enumerate() {
+            let normal = face.outer_loop().map_or(Vec3::ZERO, Loop::newell_normal);
+            let reversed = face.orientation().is_reversed();
+            for (li, lp) in face.loops().iter().enumerate() {
+                for (ci, coedge) in lp.coedges().iter().enumerate() {
+                    let edge = coedge.edge();
+                    edges.insert(edge.id(), edge);
+                    // A face lies to the left of its coedges, seen from its winding normal
+                    let along = coedge.end_vertex().position() - coedge.start_vertex().position();
+                    uses.entry(edge.id()).or_default().push((
+                        (si, fi, li, ci),
+                        coedge.id(),
+                        coedge.sense().is_same() != reversed,
+                        radial_angle(edge, normal.cross(&along)),
+                    ));
+                }
+            }
+        }
+    }
+
+    // Each split edge is built on placeholder vertices until its end
+    // vertices have recorded the new edges
+    let placeholder = Arc::new(Vertex::new(Point3::ORIGIN));
+    let mut updated: HashMap<EntityId, Vertex> = HashMap::new();
+    let mut splits: Vec<(Vec<Location>, Edge, EntityId, EntityId)> = Vec::new();
+    let mut nonmanifold = 0;
+    for (id, mut around) in uses.into_iter().filter(|(_, u)| u.len() > 2) {
+        around.sort_by(|a, b| a.3.total_cmp(&b.3));
+        // Material lies counterclockwise of a backward use, so it pairs with
+        // the next use around the edge when that one runs forward
+        let n = around.len();
+        let mut paired = vec![false; n];
+        let mut groups: Vec<Vec<usize>> = Vec::new();
+        for i in 0..n {
+            let j = (i + 1) % n;
+            if !around[i].2 && around[j].2 && !paired[i] && !paired[j] {
+                paired[i] = true;
+                paired[j] = true;
+                groups.push(vec![i, j]);
+            }
+        }
+        groups.extend((0..n).filter(|&i| !paired[i]).map(|i| vec![i]));
+
+        nonmanifold += 1;
+        let edge = edges[&id];
+        let (start, end) = (edge.start_vertex(), edge.end_vertex());
+        for (g, group) in groups.iter().enumerate() {
+            let mut split = if g == 0 {
+                let mut kept = edge.remapped(placeholder.clone(), placeholder.clone());
+                for other in groups[1..].iter().flatten() {
+                    kept.remove_coedge(around[*other].1);
+                }
+                kept
+            } else {
+                let mut split = Edge::new(placeholder.clone(), placeholder.clone());
+                split.set_curve(edge.curve().cloned());
+                split.set_tolerance(edge.tolerance());
+                for v in [start, end] {
+                    updated.entry(v.id()).or_insert_with(|| v.clone()).add_edge(split.id());
+                }
+                split
+            };
+            for &i in group {
+                split.add_coedge(around[i].1);
+            }
+            splits.push((group.iter().map(|&i| around[i].0).collect(), split, start.id(), end.id()));
+        }
+    }
+    if splits.is_empty() {
+        return 0;
+    }
+    let added = splits.len() - nonmanifold;
+
+    let merged: HashMap<EntityId, Arc<Vertex>> = updated.into_iter().map(|(id, v)| (id, Arc::new(v))).collect();
+    let mut assigned: HashMap<Location, Arc<Edge>> = HashMap::new();
+    for (locations, split, start, end) in splits {
+        let split = Arc::new(split.remapped(merged[&start].clone(), merged[&end].clone()));
+        for location in locations {
+            assigned.insert(location, split.clone());
+        }
+    }
+
+    // Rebuild the other edges at the split edges' vertices on the updated vertices
+    let mut kept: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+    let mut vertex_for = |v: &Vertex| -> Arc<Vertex> {
+        match merged.get(&v.id()) {
+            Some(m) => m.clone(),
+            None => kept.entry(v.id()).or_insert_with(|| Arc::new(v.clone())).clone(),
+        }
+    };
+    let mut rebuilt: HashMap<EntityId, Arc<Edge>> = HashMap::new();
+    for (si, shell) in body.shells_mut().iter_mut().enumerate() {
+        for (fi, face) in shell.faces_mut().iter_mut().enumerate() {
+            for (li, lp) in face.loops_mut().iter_mut().enumerate() {
+                for (ci, coedge) in lp.coedges_mut().iter_mut().enumerate() {
+                    if let Some(split) = assigned.get(&(si, fi, li, ci)) {
+                        coedge.set_edge(split.clone());
+                        continue;
+                    }
+                    let edge = coedge.edge();
+                    let touches = merged.contains_key(&edge.start_vertex().id())
+                        || merged.contains_key(&edge.end_vertex().id());
+                    if !touches {
+                        continue;
+                    }
+                    let new_edge = rebuilt.entry(edge.id())
+                        .or_insert_with(|| Arc::new(edge.remapped(
+                            vertex_for(edge.start_vertex()),
+                            vertex_for(edge.end_vertex()),
+                        )))
+                        .clone();
+                    coedge.set_edge(new_edge);
+                }
+            }
+        }
+    }
+
+    added
+}
+
+/// Merge neighbouring faces that lie in the same plane
+///
+/// Two faces sharing an edge are merged with [`EulerAdvanced::merge_faces`]
+/// when their normals agree and every vertex of the second lies within
+/// `tol` of the first's plane, which removes the shared edge. Faces on
+/// curved surfaces are left alone. Returns the number of faces merged away.
+pub fn merge_coplanar_faces(body: &mut Body, tol: f64) -> usize {
+    let mut merged = 0;
+    for shell in body.shells_mut() {
+        while let Some((i, j, face)) = next_coplanar_merge(shell, tol) {
+            shell.faces_mut()[i] = face;
+            shell.faces_mut().remove(j);
+            merged += 1;
+        }
+    }
+    merged
+}
+
+/// First pair of coplanar faces in `shell` that merges, with the merged face
+fn next_coplanar_merge(shell: &Shell, tol: f64) -> Option<(usize, usize, Face)> {
+    let faces = shell.faces();
+    let mut edge_faces: HashMap<EntityId, (&Edge, Vec<usize>)> = HashMap::new();
+    for (f, face) in faces.iter().enumerate() {
+        for coedge in face.loops().iter().flat_map(|lp| lp.coedges()) {
+            edge_faces.entry(coedge.edge().id()).or_insert_with(|| (coedge.edge(), Vec::new())).1.push(f);
+        }
+    }
+    let planes: Vec<Option<(Point3, Vec3)>> = faces.iter().map(Face::plane).collect();
+    let coplanar = |a: usize, b: usize| match (planes[a], planes[b]) {
+        (Some((origin, na)), Some((_, nb))) => {
+            na.cross(&nb).length() <= tol && na.dot(&nb) > 0.0
+                && faces[b].loops().iter().flat_map(|lp| lp.coedges())
+                    .all(|c| (c.start_vertex().position() - origin).dot(&na).abs() <= tol)
+        }
+        _ => false,
+    };
+    // Visit edges in face order so merges don't depend on hash order
+    let mut shared: Vec<(usize, usize, &Edge)> = edge_faces.values()
+        .filter_map(|(edge, users)| match users[..] {
+            [a, b] if a != b => Some((a.min(b), a.max(b), *edge)),
+            _ => None,
+        })
+        .collect();
+    shared.sort_by_key(|&(a, b, edge)| (a, b, edge.id()));
+    shared.into_iter()
+        .filter(|&(a, b, _)| coplanar(a, b))
+        .find_map(|(a, b, edge)| EulerAdvanced::merge_faces(&faces[a], &faces[b], edge).ok().map(|face| (a, b, face)))
+}
+
+/// Rebuild a faceted body, such as a triangle mesh import, from fewer and
+/// analytic faces
+///
+/// Vertices are welded and coplanar neighbours merged as in
+/// [`merge_coplanar_faces`], and the remaining flat faces get planes. Each
+/// run of three or more flat faces whose neighbours turn by less than about
+/// 40 degrees is then fitted with a cylinder, or failing that a sphere, and
+/// merged into one face on it when all its vertices lie within `tol` of the
+/// fit. Returns the number of faces merged away.
+pub fn merge_faceted(body: &mut Body, tol: f64) -> usize {
+    let before = body.faces().len();
+    weld_vertices(body, tol);
+    merge_coplanar_faces(body, tol);
+    for shell in body.shells_mut() {
+        for face in shell.faces_mut().iter_mut().filter(|f| f.surface().is_none()) {
+            let Some((origin, normal)) = face.plane() else { continue };
+            let Some(first) = face.outer_loop().and_then(|lp| lp.coedges().first()) else { continue };
+            let along = (first.end_vertex().position() - origin).reject_from(&normal);
+            if let Ok(plane) = PlanarSurface::new(origin, along, normal.cross(&along)) {
+                face.set_surface(Some(Arc::new(plane)));
+            }
+        }
+        for run in smooth_runs(shell) {
+            let faces: Vec<&Face> = shell.faces().iter().filter(|f| run.contains(&f.id())).collect();
+            if let Some((surface, same_sense)) = fit_run(&faces, tol) {
+                merge_run(shell, run, surface, same_sense);
+            }
+        }
+    }
+    before - body.faces().len()
+}
+
+/// Ids of flat faces joined through edges where their normals turn by less
+/// than [`FACET_SMOOTH_ANGLE`], in runs of at least three
+fn smooth_runs(shell: &Shell) -> Vec<HashSet<EntityId>> {
+    let faces = shell.faces();
+    let planes: Vec<Option<(Point3, Vec3)>> = faces.iter().map(Face::plane).collect();
+    let mut edge_faces: HashMap<EntityId, Vec<usize>> = HashMap::new();
+    for (f, face) in faces.iter().enumerate() {
+        for coedge in face.loops().iter().flat_map(|lp| lp.coedges()) {
+            edge_faces.entry(coedge.edge().id()).or_default().push(f);
+        }
+    }
+    let mut parent = HashMap::new();
+    for users in edge_faces.values() {
+        let [a, b] = users[..] else { continue };
+        if let (Some((_, na)), Some((_, nb))) = (planes[a], planes[b]) {
+            if a != b && na.angle_to(&nb) < FACET_SMOOTH_ANGLE {
+                let (ra, rb) = (find(&mut parent, faces[a].id()), find(&mut parent, faces[b].id()));
+                parent.insert(ra, rb);
+            }
+        }
+    }
+    let mut runs: HashMap<EntityId, HashSet<EntityId>> = HashMap::new();
+    let joined: Vec<EntityId> = faces.iter().map(|f| f.id()).filter(|id| parent.contains_key(id)).collect();
+    for id in joined {
+        runs.entry(find(&mut parent, id)).or_default().insert(id);
+    }
+    // Order runs by their first face so merges don't depend on hash order
+    let mut runs: Vec<(usize, HashSet<EntityId>)> = runs.into_values()
+        .filter(|run| run.len() >= 3)
+        .map(|run| (faces.iter().position(|f| run.contains(&f.id())).unwrap_or(0), run))
+        .collect();
+    runs.sort_by_key(|(first, _)| *first);
+    runs.into_iter().map(|(_, run)| run).collect()
+}
+
+/// Cylinder or sphere through every vertex of `faces` within `tol`, and
+/// whether the facets face the same way as its normal
+fn fit_run(faces: &[&Face], tol: f64) -> Option<(Arc<dyn Surface>, bool)> {
+    let mut seen = HashSet::new();
+    let points: Vec<Point3> = faces.iter()
+        .flat_map(|f| f.loops().iter().flat_map(|lp| lp.coedges()))
+        .map(|c| c.start_vertex())
+        .filter(|v| seen.insert(v.id()))
+        .map(|v| v.position())
+        .collect();
+    let normals: Vec<Vec3> = faces.iter().filter_map(|f| f.plane()).map(|(_, n)| n).collect();
+    let surface = fit_cylinder(&points, &normals, tol).or_else(|| fit_sphere(&points, tol))?;
+
+    let (origin, normal) = faces[0].plane()?;
+    let (u, v, _, _) = surface.closest_point(&origin).ok()?;
+    Some((surface.clone(), surface.normal(u, v).dot(&normal) > 0.0))
+}
+
+/// Cylinder about the direction the facet normals are all square to
+fn fit_cylinder(points: &[Point3], normals: &[Vec3], tol: f64) -> Option<Arc<dyn Surface>> {
+    let spread = normals.iter().fold(Matrix3::zeros(), |m, n| {
+        let n = Vector3::new(n.x(), n.y(), n.z());
+        m + n * n.transpose()
+    });
+    let eigen = spread.symmetric_eigen();
+    let least = eigen.eigenvalues.imin();
+    let axis = eigen.eigenvectors.column(least);
+    let axis = Vec3::new(axis[0], axis[1], axis[2]).normalized();
+    let e1 = if axis.x().abs() < 0.9 { Vec3::X } else { Vec3::Y }.reject_from(&axis).normalized();
+    let e2 = axis.cross(&e1);
+
+    // Circle x^2 + y^2 + d x + e y + f = 0 through the points seen down the axis
+    let centroid = points.iter().fold(Vec3::ZERO, |sum, p| sum + p.to_vector()) / points.len() as f64;
+    let origin = Point3::ORIGIN + centroid;
+    let (mut normal, mut rhs) = (Matrix3::zeros(), Vector3::zeros());
+    for p in points {
+        let (x, y) = ((*p - origin).dot(&e1), (*p - origin).dot(&e2));
+        let row = Vector3::new(x, y, 1.0);
+        normal += row * row.transpose();
+        rhs -= row * (x * x + y * y);
+    }
+    let [d, e, f] = normal.lu().solve(&rhs)?.into();
+    let (cx, cy) = (-d / 2.0, -e / 2.0);
+    let radius = (cx * cx + cy * cy - f).sqrt();
+    let center = origin + e1 * cx + e2 * cy;
+    let fits = radius.is_finite() && points.iter().all(|p| ((*p - center).reject_from(&axis).length() - radius).abs() <= tol);
+    if !fits {
+        return None;
+    }
+    let heights = points.iter().map(|p| (*p - center).dot(&axis));
+    let (low, high) = heights.fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), h| (lo.min(h), hi.max(h)));
+    let cylinder = CylindricalSurface::bounded(center, axis, radius, e1, low, high, 0.0, std::f64::consts::TAU).ok()?;
+    Some(Arc::new(cylinder))
+}
+
+/// Sphere x^2 + y^2 + z^2 + d x + e y + f z + g = 0 through the points
+fn fit_sphere(points: &[Point3], tol: f64) -> Option<Arc<dyn Surface>> {
+    let (mut normal, mut rhs) = (Matrix4::zeros(), Vector4::zeros());
+    for p in points {
+        let row = Vector4::new(p.x(), p.y(), p.z(), 1.0);
+        normal += row * row.transpose();
+        rhs -= row * p.to_vector().length_squared();
+    }
+    let [d, e, f, g] = normal.lu().solve(&rhs)?.into();
+    let center = Point3::new(-d / 2.0, -e / 2.0, -f / 2.0);
+    let radius = (center.to_vector().length_squared() - g).sqrt();
+    let fits = radius.is_finite() && points.iter().all(|p| (p.distance_to(&center) - radius).abs() <= tol);
+    if !fits {
+        return None;
+    }
+    Some(Arc::new(SphericalSurface::new(center, radius, Vec3::Z, Vec3::X).ok()?))
+}
+
+/// Merge the faces of `run` across their shared edges into faces on `surface`
+///
+/// Edges a merged face meets itself along, such as the seam closing a
+/// cylinder, are kept.
+fn merge_run(shell: &mut Shell, mut run: HashSet<EntityId>, surface: Arc<dyn Surface>, same_sense: bool) {
+    loop {
+        let faces = shell.faces();
+        let members: Vec<usize> = (0..faces.len()).filter(|&f| run.contains(&faces[f].id())).collect();
+        let shared = members.iter().enumerate().find_map(|(k, &a)| {
+            let edges: HashSet<EntityId> = faces[a].loops().iter().flat_map(|lp| lp.coedges()).map(|c| c.edge().id()).collect();
+            members[k + 1..].iter().find_map(|&b| {
+                faces[b].loops().iter().flat_map(|lp| lp.coedges())
+                    .filter(|c| edges.contains(&c.edge().id()))
+                    .find_map(|c| EulerAdvanced::merge_faces(&faces[a], &faces[b], c.edge()).ok())
+                    .map(|face| (a, b, face))
+            })
+        });
+        let Some((a, b, face)) = shared else { break };
+        run.remove(&shell.faces()[a].id());
+        run.remove(&shell.faces()[b].id());
+        run.insert(face.id());
+        shell.faces_mut()[a] = face;
+        shell.faces_mut().remove(b);
+    }
+    for face in shell.faces_mut().iter_mut().filter(|f| run.contains(&f.id())) {
+        face.set_surface(Some(surface.clone()));
+        face.set_same_sense(same_sense);
+    }
+}
+
+/// Angle of `direction` about `edge`, measured in the plane across its chord
+fn radial_angle(edge: &Edge, direction: Vec3) -> f64 {
+    let axis = (edge.end_vertex().position() - edge.start_vertex().position()).normalized();
+    let reference = if axis.x().abs() < 0.9 { Vec3::X } else { Vec3::Y };
+    let u = reference.cross(&axis).normalized();
+    let v = axis.cross(&u);
+    direction.dot(&v).atan2(direction.dot(&u))
+}
+
+/// Union-find root of `id`, with path compression
+fn find(parent: &mut HashMap<EntityId, EntityId>, id: EntityId) -> EntityId {
+    let p = *parent.entry(id).or_insert(id);
+    if p == id {
+        return id;
+    }
+    let root = find(parent, p);
+    parent.insert(id, root);
+    root
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_topo::{Coedge, Sense};
+
+    #[test]
+    fn test_remove_degenerate_edge() {
+        let points = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0 + 1e-9, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ];
+        let v: Vec<_> = points.iter().map(|&p| Arc::new(Vertex::new(p))).collect();
+        let coedges = (0..5)
+            .map(|i| Coedge::new(Arc::new(Edge::new(v[i].clone(), v[(i + 1) % 5].clone())), Sense::Same))
+            .collect();
+        let mut face = Face::new();
+        face.add_loop(Loop::from_coedges(coedges));
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut body = Body::new();
+        body.add_shell(shell);
+
+        assert_eq!(remove_degenerate_edges(&mut body, 1e-6), 1);
+        assert_eq!(body.edges().len(), 4);
+        assert_eq!(body.vertices().len(), 4);
+
+        let lp = &body.faces()[0].loops()[0];
+        assert!(lp.is_closed());
+        let coedges = lp.coedges();
+        for i in 0..coedges.len() {
+            let next = &coedges[(i + 1) % coedges.len()];
+            assert_eq!(coedges[i].end_vertex().id(), next.start_vertex().id());
+        }
+
+        assert_eq!(remove_degenerate_edges(&mut body, 1e-6), 0);
+    }
+
+    /// Face on its own vertices and edges through `points`, in order
+    fn polygon(points: &[Point3]) -> Face {
+        let v: Vec<_> = points.iter().map(|&p| Arc::new(Vertex::new(p))).collect();
+        let coedges = (0..v.len())
+            .map(|i| Coedge::new(Arc::new(Edge::new(v[i].clone(), v[(i + 1) % v.len()].clone())), Sense::Same))
+            .collect();
+        let mut face = Face::new();
+        face.add_loop(Loop::from_coedges(coedges));
+        face
+    }
+
+    #[test]
+    fn test_split_t_junction() {
+        let p = |x, y, z| Point3::new(x, y, z);
+        let mut shell = Shell::new();
+        shell.add_face(polygon(&[p(0.0, 0.0, 0.0), p(1.0, 0.0, 0.0), p(1.0, 1.0, 0.0), p(0.0, 1.0, 0.0)]));
+        shell.add_face(polygon(&[p(1.0, 0.0, 0.0), p(0.0, 0.0, 0.0), p(0.0, -1.0, 0.0), p(1.0, -1.0, 0.0)]));
+        shell.add_face(polygon(&[p(0.0, 0.0, 0.0), p(1.0, 0.0, 0.0), p(1.0, 0.0, 1.0), p(0.0, 0.0, 1.0)]));
+        let mut body = Body::new();
+        body.add_shell(shell);
+        weld_vertices(&mut body, 1e-6);
+        assert_eq!(body.edges().len(), 10);
+        assert!(body.edges().iter().any(|e| e.coedges().len() == 3));
+
+        assert_eq!(split_nonmanifold_edges(&mut body), 1);
+        assert_eq!(body.edges().len(), 11);
+        assert!(body.edges().iter().all(|e| e.coedges().len() <= 2));
+        for coedge in body.coedges() {
+            assert!(coedge.edge().coedges().contains(&coedge.id()));
+            for v in [coedge.edge().start_vertex(), coedge.edge().end_vertex()] {
+                assert!(v.edges().contains(&coedge.edge().id()));
+            }
+        }
+        // The two flat faces stay joined and the upright one comes away
+        let shared = |f: usize| body.faces()[f].loops()[0].coedges()[0].edge().id();
+        assert_eq!(shared(0), shared(1));
+        assert_ne!(shared(0), shared(2));
+
+        assert_eq!(split_nonmanifold_edges(&mut body), 0);
+    }
+
+    #[test]
+    fn test_repair_broken_import() {
+        let corner = |i: usize, f: usize| {
+            let jitter = 1e-8 * f as f64;
+            Point3::new((i & 1) as f64 + jitter, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64 - jitter)
+        };
+        let quads = [[0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4], [2, 6, 7, 3], [0, 4, 6, 2], [1, 3, 7, 5]];
+        let mut shell = Shell::new();
+        for (f, quad) in quads.iter().enumerate() {
+            let mut points: Vec<Point3> = quad.iter().map(|&i| corner(i, f)).collect();
+            match f {
+                // A sliver edge next to the first corner
+                0 => points.insert(1, points[0] + Vec3::new(1e-9, 0.0, 0.0)),
+                // Wound inward
+                4 => points.reverse(),
+                _ => {}
+            }
+            let mut face = polygon(&points);
+            if f == 2 {
+                // Coedges out of order, one running backwards
+                let coedges = face.loops_mut()[0].coedges_mut();
+                coedges.swap(1, 3);
+                coedges[2].reverse_sense();
+            }
+            shell.add_face(face);
+        }
+        let mut body = Body::new();
+        body.add_shell(shell);
+
+        let report = repair(&mut body, HealingOptions::default());
+        assert_eq!(report, RepairReport {
+            degenerate_edges: 1,
+            welded_vertices: 16,
+            reordered_loops: 1,
+            open_loops: 0,
+            flipped_faces: 1,
+            simplified_surfaces: 0,
+            self_intersections_unsupported: true,
+        });
+        let issues = nova_topo::validate_body(&body, &nova_math::ToleranceContext::default()).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert!(body.loops().iter().all(|lp| lp.is_closed()));
+        assert!((body.shells()[0].signed_volume() - 1.0).abs() < 1e-6);
+
+        assert_eq!(repair(&mut body, HealingOptions::default()), RepairReport {
+            self_intersections_unsupported: true,
+            ..RepairReport::default()
+        });
+    }
+
+    #[test]
+    fn test_merge_split_box_top() {
+        // Box profile in the XZ plane with a vertex halfway along its top
+        // and bottom, so extruding along Y splits both those faces in two
+        let points = [(0.0, 0.0), (1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0), (0.0, 1.0)]
+            .map(|(x, z)| Point3::new(x, 0.0, z));
+        let mut body = EulerAdvanced::extrude_face(&polygon(&points), -Vec3::Y, 1.0).unwrap();
+        // Record each edge's coedges and each vertex's edges
+        weld_vertices(&mut body, 1e-9);
+        let on_top = |body: &Body| body.faces().iter()
+            .filter(|f| f.loops()[0].coedges().iter().all(|c| c.start_vertex().position().z() == 1.0))
+            .count();
+        assert_eq!(body.faces().len(), 8);
+        assert_eq!(on_top(&body), 2);
+
+        assert_eq!(merge_coplanar_faces(&mut body, 1e-9), 2);
+        assert_eq!(body.faces().len(), 6);
+        assert_eq!(on_top(&body), 1);
+        assert!(body.loops().iter().all(|lp| lp.is_closed()));
+        let issues = nova_topo::validate_body(&body, &nova_math::ToleranceContext::default()).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert!((nova_topo::body_volume(&body).unwrap() - 2.0).abs() < 1e-9);
+
+        assert_eq!(merge_coplanar_faces(&mut body, 1e-9), 0);
+    }
+
+    /// Shell of loose triangles, each on its own vertices as read from STL
+    fn triangle_soup(triangles: &[[Point3; 3]]) -> Body {
+        let mut shell = Shell::new();
+        for triangle in triangles {
+            shell.add_face(polygon(triangle));
+        }
+        let mut body = Body::new();
+        body.add_shell(shell);
+        body
+    }
+
+    #[test]
+    fn test_merge_faceted_box() {
+        let corner = |i: usize| Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64);
+        let quads = [[0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4], [2, 6, 7, 3], [0, 4, 6, 2], [1, 3, 7, 5]];
+        let triangles: Vec<[Point3; 3]> = quads.iter()
+            .flat_map(|q| [[q[0], q[1], q[2]], [q[0], q[2], q[3]]])
+            .map(|t| t.map(corner))
+            .collect();
+        let mut body = triangle_soup(&triangles);
+        assert_eq!(body.faces().len(), 12);
+
+        assert_eq!(merge_faceted(&mut body, 1e-9), 6);
+        assert_eq!(body.faces().len(), 6);
+        assert!(body.faces().iter().all(|f| f.surface().is_some_and(|s| s.surface_type() == SurfaceType::Planar)));
+        let issues = nova_topo::validate_body(&body, &nova_math::ToleranceContext::default()).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert!((body.shells()[0].signed_volume() - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_merge_faceted_cylinder_wall() {
+        // Side of a cylinder of radius 2 and height 3 in 16 facets, each
+        // split into two triangles
+        let ring = |k: usize, z: f64| {
+            let angle = std::f64::consts::TAU * (k % 16) as f64 / 16.0;
+            Point3::new(2.0 * angle.cos(), 2.0 * angle.sin(), z)
+        };
+        let triangles: Vec<[Point3; 3]> = (0..16)
+            .flat_map(|k| [[ring(k, 0.0), ring(k + 1, 0.0), ring(k + 1, 3.0)], [ring(k, 0.0), ring(k + 1, 3.0), ring(k, 3.0)]])
+            .collect();
+        let mut body = triangle_soup(&triangles);
+
+        assert_eq!(merge_faceted(&mut body, 1e-9), 31);
+        let faces = body.faces();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].surface().unwrap().surface_type(), SurfaceType::Cylindrical);
+        assert!((nova_topo::measure_radius(faces[0]).unwrap() - 2.0).abs() < 1e-9);
+        assert_eq!(faces[0].seam_edges().len(), 1);
+        // Facing outward like the facets it replaces
+        let on_wall = faces[0].surface().unwrap().evaluate(0.0, 0.5);
+        assert!(faces[0].normal_at(0.0, 0.5).dot(&on_wall.to_vector().reject_from(&Vec3::Z)) > 0.0);
+    }
+}
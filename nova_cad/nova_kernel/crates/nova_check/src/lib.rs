@@ -5,6 +5,14 @@
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
+mod heal;
+
+pub use heal::{
+    merge_coplanar_faces, merge_faceted, orient_faces, remove_degenerate_edges, reorder_loops, repair,
+    simplify_surfaces, split_nonmanifold_edges, weld_vertices, RepairReport,
+};
+
+use nova_math::ToleranceContext;
 use nova_topo::Body;
 
 /// Validation error types
@@ -17,39 +25,96 @@ pub enum ValidationError {
     #[error("Invalid geometry: {0}")]
     InvalidGeometry(String),
     /// Self-intersection
-    #[error("Self-intersection detected")]
-    SelfIntersection,
+    #[error("Self-intersection detected (depth {depth})")]
+    SelfIntersection {
+        /// Penetration depth
+        depth: f64,
+    },
     /// Gap in boundary
-    #[error("Gap in boundary")]
-    GapInBoundary,
+    #[error("Gap in boundary (size {gap})")]
+    GapInBoundary {
+        /// Gap size
+        gap: f64,
+    },
     /// Degenerate geometry
-    #[error("Degenerate geometry")]
-    DegenerateGeometry,
+    #[error("Degenerate geometry (size {size})")]
+    DegenerateGeometry {
+        /// Size of the degenerate entity
+        size: f64,
+    },
     /// Orientation error
     #[error("Orientation error")]
     OrientationError,
 }
 
+impl ValidationError {
+    /// Measured magnitude in model units, if the error has one
+    pub fn magnitude(&self) -> Option<f64> {
+        match self {
+            ValidationError::SelfIntersection { depth } => Some(*depth),
+            ValidationError::GapInBoundary { gap } => Some(*gap),
+            ValidationError::DegenerateGeometry { size } => Some(*size),
+            _ => None,
+        }
+    }
+}
+
 /// Validation warning types
 #[derive(Debug, Clone, PartialEq)]
 pub enum ValidationWarning {
     /// Small edge
-    SmallEdge,
+    SmallEdge {
+        /// Edge length
+        length: f64,
+    },
     /// Small face
-    SmallFace,
+    SmallFace {
+        /// Face area
+        area: f64,
+    },
     /// Sharp angle
-    SharpAngle,
+    SharpAngle {
+        /// Angle in radians
+        angle: f64,
+    },
     /// Near degenerate
-    NearDegenerate,
+    NearDegenerate {
+        /// Size of the near-degenerate entity
+        size: f64,
+    },
+}
+
+impl ValidationWarning {
+    /// Measured magnitude in model units, if the warning has one
+    ///
+    /// A small face measures the side of a square of its area, so it ranks
+    /// against lengths. Angles are not lengths, so `SharpAngle` has no
+    /// magnitude.
+    pub fn magnitude(&self) -> Option<f64> {
+        match self {
+            ValidationWarning::SmallEdge { length } => Some(*length),
+            ValidationWarning::SmallFace { area } => Some(area.sqrt()),
+            ValidationWarning::SharpAngle { .. } => None,
+            ValidationWarning::NearDegenerate { size } => Some(*size),
+        }
+    }
 }
 
 /// Validation result
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
 pub struct ValidationResult {
     /// Errors found
     pub errors: Vec<ValidationError>,
     /// Warnings found
     pub warnings: Vec<ValidationWarning>,
+    /// Tolerance that severities are scaled by
+    pub tolerance: f64,
+}
+
+impl Default for ValidationResult {
+    fn default() -> Self {
+        Self::with_tolerance(ValidationOptions::default().tolerance)
+    }
 }
 
 impl ValidationResult {
@@ -58,6 +123,15 @@ impl ValidationResult {
         Self::default()
     }
     
+    /// Create a new empty validation result scaled by `tolerance`
+    pub fn with_tolerance(tolerance: f64) -> Self {
+        Self {
+            errors: Vec::new(),
+            warnings: Vec::new(),
+            tolerance,
+        }
+    }
+    
     /// Check if validation passed (no errors)
     pub fn is_valid(&self) -> bool {
         self.errors.is_empty()
@@ -78,6 +152,37 @@ impl ValidationResult {
         self.errors.extend(other.errors);
         self.warnings.extend(other.warnings);
     }
+    
+    /// Severity of a magnitude, in multiples of the tolerance
+    pub fn severity(&self, magnitude: f64) -> f64 {
+        magnitude / self.tolerance
+    }
+    
+    /// Largest severity over all errors and warnings with a magnitude
+    pub fn worst(&self) -> Option<f64> {
+        self.errors.iter().filter_map(ValidationError::magnitude)
+            .chain(self.warnings.iter().filter_map(ValidationWarning::magnitude))
+            .map(|m| self.severity(m))
+            .max_by(f64::total_cmp)
+    }
+    
+    /// Sort errors and warnings by descending magnitude
+    ///
+    /// Issues without a magnitude keep their relative order after the rest.
+    pub fn sort_by_severity(&mut self) {
+        self.errors.sort_by(|a, b| descending(a.magnitude(), b.magnitude()));
+        self.warnings.sort_by(|a, b| descending(a.magnitude(), b.magnitude()));
+    }
+}
+
+/// Order optional magnitudes largest first, with `None` last
+fn descending(a: Option<f64>, b: Option<f64>) -> std::cmp::Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => b.total_cmp(&a),
+        (Some(_), None) => std::cmp::Ordering::Less,
+        (None, Some(_)) => std::cmp::Ordering::Greater,
+        (None, None) => std::cmp::Ordering::Equal,
+    }
 }
 
 /// Validation options
@@ -123,12 +228,16 @@ impl Validator {
     }
     
     /// Validate a body
+    ///
+    /// Each issue `nova_topo::validate_body` finds, such as an edge not
+    /// shared by exactly two faces, is reported as an `InvalidTopology` error.
     pub fn validate(&self, body: &Body) -> ValidationResult {
-        let mut result = ValidationResult::new();
-        
-        // TODO: Implement actual validation
-        // For now, return empty result (valid)
-        
+        let mut result = ValidationResult::with_tolerance(self.options.tolerance);
+        let tolerance = ToleranceContext::with_resabs(self.options.tolerance);
+        match nova_topo::validate_body(body, &tolerance) {
+            Ok(issues) => issues.into_iter().for_each(|issue| result.add_error(ValidationError::InvalidTopology(issue))),
+            Err(err) => result.add_error(ValidationError::InvalidTopology(err.to_string())),
+        }
         result
     }
 }
@@ -196,10 +305,9 @@ impl Healer {
         Self { options }
     }
     
-    /// Heal a body
-    pub fn heal(&self, body: &mut Body) -> Result<(), ValidationError> {
-        // TODO: Implement actual healing
-        Ok(())
+    /// Heal a body, returning what each healing pass fixed
+    pub fn heal(&self, body: &mut Body) -> Result<RepairReport, ValidationError> {
+        Ok(repair(body, self.options))
     }
 }
 
@@ -210,7 +318,7 @@ impl Default for Healer {
 }
 
 /// Heal a body with default options
-pub fn heal_body(body: &mut Body) -> Result<(), ValidationError> {
+pub fn heal_body(body: &mut Body) -> Result<RepairReport, ValidationError> {
     let healer = Healer::new();
     healer.heal(body)
 }
@@ -218,6 +326,9 @@ pub fn heal_body(body: &mut Body) -> Result<(), ValidationError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use nova_math::Point3;
+    use nova_topo::{Coedge, Edge, Face, Loop, Sense, Shell, Vertex};
+    use std::sync::Arc;
 
     #[test]
     fn test_validation_result() {
@@ -228,11 +339,45 @@ mod tests {
         assert!(!result.is_valid());
     }
 
+    #[test]
+    fn test_severity_ordering() {
+        let mut result = ValidationResult::with_tolerance(1e-3);
+        assert_eq!(result.worst(), None);
+
+        result.add_warning(ValidationWarning::SharpAngle { angle: 0.01 });
+        result.add_warning(ValidationWarning::SmallEdge { length: 2e-3 });
+        result.add_warning(ValidationWarning::NearDegenerate { size: 5e-3 });
+        // Ranks as a 3e-3 square, between the two lengths
+        result.add_warning(ValidationWarning::SmallFace { area: 9e-6 });
+        result.sort_by_severity();
+
+        assert_eq!(result.warnings[0], ValidationWarning::NearDegenerate { size: 5e-3 });
+        assert_eq!(result.warnings[1], ValidationWarning::SmallFace { area: 9e-6 });
+        assert_eq!(result.warnings[2], ValidationWarning::SmallEdge { length: 2e-3 });
+        assert_eq!(result.warnings[3], ValidationWarning::SharpAngle { angle: 0.01 });
+        assert!((result.worst().unwrap() - 5.0).abs() < 1e-9);
+    }
+
     #[test]
     fn test_validator() {
         let validator = Validator::new();
         let body = Body::new();
         let result = validator.validate(&body);
         assert!(result.is_valid());
+
+        // A lone square leaves every edge with one coedge
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let mut face = Face::new();
+        face.add_loop(Loop::from_coedges(
+            (0..4).map(|i| Coedge::new(Arc::new(Edge::new(corners[i].clone(), corners[(i + 1) % 4].clone())), Sense::Same)).collect(),
+        ));
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut sheet = Body::new();
+        sheet.add_shell(shell);
+        let result = validator.validate(&sheet);
+        assert!(!result.is_valid());
+        assert!(result.errors.iter().all(|e| matches!(e, ValidationError::InvalidTopology(_))));
     }
 }
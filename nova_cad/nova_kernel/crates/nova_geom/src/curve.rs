@@ -1,7 +1,8 @@
 //! Curve types and implementations
 
 use crate::{GeomResult, GeometryError, ParamRange, CurveEvaluation, Tessellation, Tessellatable};
-use nova_math::{Point3, Vec3, Transform3};
+use nova_math::{integrate, Point3, Vec3, Transform3};
+use serde::{Deserialize, Serialize};
 
 /// Trait for all curve types
 pub trait Curve: Send + Sync {
@@ -37,6 +38,11 @@ pub trait Curve: Send + Sync {
         self.evaluate(self.param_range().end)
     }
 
+    /// Check if the curve ends where it starts, within `tol`
+    fn is_closed(&self, tol: f64) -> bool {
+        self.start_point().distance_to(&self.end_point()) <= tol
+    }
+
     /// Compute arc length from start to parameter t
     fn arc_length(&self, t: f64) -> f64;
 
@@ -52,11 +58,26 @@ pub trait Curve: Send + Sync {
     /// Transform the curve
     fn transform(&mut self, transform: &Transform3);
 
+    /// Uniformly scale about the world origin
+    fn scale(&mut self, factor: f64) {
+        self.transform(&Transform3::from_scale(factor));
+    }
+
     /// Get curve type
     fn curve_type(&self) -> CurveType;
 
     /// Clone into a boxed curve
     fn clone_box(&self) -> Box<dyn Curve>;
+
+    /// Transformed copy, leaving this curve as it is
+    fn transformed(&self, transform: &Transform3) -> Box<dyn Curve> {
+        let mut curve = self.clone_box();
+        curve.transform(transform);
+        curve
+    }
+
+    /// Access the concrete curve for downcasting
+    fn as_any(&self) -> &dyn std::any::Any;
 }
 
 /// Curve type enumeration
@@ -68,6 +89,8 @@ pub enum CurveType {
     CircularArc,
     /// Elliptical arc
     EllipseArc,
+    /// Piecewise-linear curve
+    Polyline,
     /// NURBS curve
     NurbsCurve,
     /// Intersection curve
@@ -79,7 +102,7 @@ pub enum CurveType {
 }
 
 /// 3D line (infinite or bounded)
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Line {
     /// Origin point
     origin: Point3,
@@ -177,10 +200,10 @@ impl Line {
         self.origin + self.direction * t
     }
 
-    /// Find intersection with another line
-    pub fn intersect_line(&self, other: &Line) -> Option<Point3> {
-        // For 3D lines, they may be skew (not intersecting and not parallel)
-        // We find the closest points and check if they're the same
+    /// Parameters of the closest points between the two infinite lines
+    ///
+    /// Returns None if the lines are parallel.
+    pub fn closest_parameters(&self, other: &Line) -> Option<(f64, f64)> {
         let n = self.direction.cross(&other.direction);
         
         if n.is_zero(1e-10) {
@@ -191,9 +214,16 @@ impl Line {
         let diff = other.origin - self.origin;
         let n_sq = n.length_squared();
         
-        // Compute parameters for closest points
         let t = diff.cross(&other.direction).dot(&n) / n_sq;
         let s = diff.cross(&self.direction).dot(&n) / n_sq;
+        Some((t, s))
+    }
+
+    /// Find intersection with another line
+    pub fn intersect_line(&self, other: &Line) -> Option<Point3> {
+        // For 3D lines, they may be skew (not intersecting and not parallel)
+        // We find the closest points and check if they're the same
+        let (t, s) = self.closest_parameters(other)?;
         
         let p1 = self.origin + self.direction * t;
         let p2 = other.origin + other.direction * s;
@@ -273,6 +303,14 @@ impl Curve for Line {
         self.direction = transform.apply_to_vector(&self.direction).normalized();
     }
 
+    fn scale(&mut self, factor: f64) {
+        self.origin = Point3::ORIGIN + self.origin.to_vector() * factor;
+        if let Some(ref mut range) = self.range {
+            range.start *= factor;
+            range.end *= factor;
+        }
+    }
+
     fn curve_type(&self) -> CurveType {
         CurveType::Line
     }
@@ -280,6 +318,10 @@ impl Curve for Line {
     fn clone_box(&self) -> Box<dyn Curve> {
         Box::new(*self)
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 impl Tessellatable for Line {
@@ -293,7 +335,7 @@ impl Tessellatable for Line {
 }
 
 /// Circular arc in 3D space
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct CircularArc {
     /// Center of the circle
     center: Point3,
@@ -493,6 +535,10 @@ impl Curve for CircularArc {
         ParamRange::new(0.0, 1.0)
     }
 
+    fn is_closed(&self, tol: f64) -> bool {
+        self.is_full_circle() || self.start_point().distance_to(&self.end_point()) <= tol
+    }
+
     fn arc_length(&self, t: f64) -> f64 {
         self.radius * self.sweep_angle.abs() * t
     }
@@ -580,6 +626,11 @@ impl Curve for CircularArc {
         self.radius *= scale;
     }
 
+    fn scale(&mut self, factor: f64) {
+        self.center = Point3::ORIGIN + self.center.to_vector() * factor;
+        self.radius *= factor;
+    }
+
     fn curve_type(&self) -> CurveType {
         CurveType::CircularArc
     }
@@ -587,6 +638,10 @@ impl Curve for CircularArc {
     fn clone_box(&self) -> Box<dyn Curve> {
         Box::new(*self)
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 impl Tessellatable for CircularArc {
@@ -614,7 +669,7 @@ impl Tessellatable for CircularArc {
 }
 
 /// Elliptical arc in 3D space
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct EllipseArc {
     /// Center of the ellipse
     center: Point3,
@@ -677,8 +732,40 @@ impl EllipseArc {
             + self.major_axis * (self.major_radius * cos_a)
             + minor_axis * (self.minor_radius * sin_a)
     }
+
+    /// Exact length of the full arc
+    ///
+    /// Integrates the speed |C'(t)| over [0, 1] with adaptive Gauss-Kronrod
+    /// quadrature, starting from `samples` uniform sub-intervals.
+    pub fn arc_length_exact(&self, samples: usize) -> f64 {
+        self.speed_integral(0.0, 1.0, samples)
+    }
+
+    /// Integrate the speed function over [t0, t1]
+    fn speed_integral(&self, t0: f64, t1: f64, samples: usize) -> f64 {
+        let samples = samples.max(1);
+        let step = (t1 - t0) / samples as f64;
+        let speed = |t: f64| self.derivative(t, 1).length();
+
+        (0..samples)
+            .map(|i| {
+                let a = t0 + step * i as f64;
+                let b = if i + 1 == samples { t1 } else { a + step };
+                integrate::adaptive_gauss_kronrod(&speed, a, b, ELLIPSE_LENGTH_TOL, ELLIPSE_LENGTH_MAX_DEPTH)
+            })
+            .sum()
+    }
 }
 
+/// Initial sub-intervals used by `EllipseArc::arc_length`
+const ELLIPSE_LENGTH_SAMPLES: usize = 8;
+
+/// Absolute error target per sub-interval for ellipse length quadrature
+const ELLIPSE_LENGTH_TOL: f64 = 1e-12;
+
+/// Maximum bisection depth for ellipse length quadrature
+const ELLIPSE_LENGTH_MAX_DEPTH: u32 = 20;
+
 impl Curve for EllipseArc {
     fn evaluate(&self, t: f64) -> Point3 {
         let angle = self.start_angle + t * self.sweep_angle;
@@ -724,14 +811,9 @@ impl Curve for EllipseArc {
         ParamRange::new(0.0, 1.0)
     }
 
-    fn arc_length(&self, _t: f64) -> f64 {
-        // Ellipse arc length requires elliptic integral
-        // For now, approximate
-        let a = self.major_radius;
-        let b = self.minor_radius;
-        let h = ((a - b) / (a + b)).powi(2);
-        let approx_circumference = std::f64::consts::PI * (a + b) * (1.0 + 3.0 * h / (10.0 + (4.0 - 3.0 * h).sqrt()));
-        approx_circumference * self.sweep_angle.abs() / std::f64::consts::TAU * _t
+    fn arc_length(&self, t: f64) -> f64 {
+        // Ellipse arc length is an incomplete elliptic integral; evaluate it numerically
+        self.speed_integral(0.0, t, ELLIPSE_LENGTH_SAMPLES)
     }
 
     fn parameter_at_length(&self, _length: f64) -> Option<f64> {
@@ -783,6 +865,12 @@ impl Curve for EllipseArc {
         self.minor_radius *= scale;
     }
 
+    fn scale(&mut self, factor: f64) {
+        self.center = Point3::ORIGIN + self.center.to_vector() * factor;
+        self.major_radius *= factor;
+        self.minor_radius *= factor;
+    }
+
     fn curve_type(&self) -> CurveType {
         CurveType::EllipseArc
     }
@@ -790,12 +878,313 @@ impl Curve for EllipseArc {
     fn clone_box(&self) -> Box<dyn Curve> {
         Box::new(*self)
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Piecewise-linear curve parameterized by arc length
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolylineCurve {
+    /// Vertices of the polyline
+    points: Vec<Point3>,
+    /// Cumulative arc length at each vertex
+    lengths: Vec<f64>,
+}
+
+impl PolylineCurve {
+    /// Create a polyline through the given points
+    ///
+    /// Consecutive coincident points are dropped.
+    pub fn new(points: Vec<Point3>) -> GeomResult<Self> {
+        let mut points = points;
+        points.dedup_by(|a, b| a.distance_to(b) < 1e-12);
+        if points.len() < 2 {
+            return Err(GeometryError::Degenerate(
+                "Polyline needs at least two distinct points".to_string()
+            ));
+        }
+
+        let lengths = Self::cumulative_lengths(&points);
+        Ok(Self { points, lengths })
+    }
+
+    /// Get the polyline vertices
+    pub fn points(&self) -> &[Point3] {
+        &self.points
+    }
+
+    /// Get the number of segments
+    pub fn num_segments(&self) -> usize {
+        self.points.len() - 1
+    }
+
+    /// Get the total length
+    pub fn length(&self) -> f64 {
+        self.lengths[self.lengths.len() - 1]
+    }
+
+    fn cumulative_lengths(points: &[Point3]) -> Vec<f64> {
+        let mut lengths = Vec::with_capacity(points.len());
+        let mut total = 0.0;
+        lengths.push(total);
+        for pair in points.windows(2) {
+            total += pair[0].distance_to(&pair[1]);
+            lengths.push(total);
+        }
+        lengths
+    }
+
+    /// Segment index containing t and the fraction along it
+    fn locate(&self, t: f64) -> (usize, f64) {
+        let t = t.clamp(0.0, self.length());
+        let i = self.lengths.partition_point(|&l| l <= t)
+            .saturating_sub(1)
+            .min(self.num_segments() - 1);
+        let seg_len = self.lengths[i + 1] - self.lengths[i];
+        (i, (t - self.lengths[i]) / seg_len)
+    }
+}
+
+impl Curve for PolylineCurve {
+    fn evaluate(&self, t: f64) -> Point3 {
+        let (i, frac) = self.locate(t);
+        self.points[i].lerp(&self.points[i + 1], frac)
+    }
+
+    fn derivative(&self, t: f64, order: u32) -> Vec3 {
+        match order {
+            0 => self.evaluate(t).to_vector(),
+            1 => {
+                let (i, _) = self.locate(t);
+                (self.points[i + 1] - self.points[i]).normalized()
+            }
+            _ => Vec3::ZERO,
+        }
+    }
+
+    fn curvature(&self, _t: f64) -> f64 {
+        0.0
+    }
+
+    fn param_range(&self) -> ParamRange {
+        ParamRange::new(0.0, self.length())
+    }
+
+    fn arc_length(&self, t: f64) -> f64 {
+        t.clamp(0.0, self.length())
+    }
+
+    fn parameter_at_length(&self, length: f64) -> Option<f64> {
+        if self.param_range().contains(length) {
+            Some(length)
+        } else {
+            None
+        }
+    }
+
+    fn closest_point(&self, point: &Point3) -> GeomResult<(f64, Point3, f64)> {
+        let mut best = (0.0, self.points[0], f64::INFINITY);
+
+        for (i, pair) in self.points.windows(2).enumerate() {
+            let seg = pair[1] - pair[0];
+            let seg_len = self.lengths[i + 1] - self.lengths[i];
+            let s = ((*point - pair[0]).dot(&seg) / (seg_len * seg_len)).clamp(0.0, 1.0);
+            let candidate = pair[0].lerp(&pair[1], s);
+            let dist = point.distance_to(&candidate);
+            if dist < best.2 {
+                best = (self.lengths[i] + s * seg_len, candidate, dist);
+            }
+        }
+
+        Ok(best)
+    }
+
+    fn reverse(&mut self) {
+        self.points.reverse();
+        self.lengths = Self::cumulative_lengths(&self.points);
+    }
+
+    fn transform(&mut self, transform: &Transform3) {
+        transform.apply_to_points(&mut self.points);
+        self.lengths = Self::cumulative_lengths(&self.points);
+    }
+
+    fn curve_type(&self) -> CurveType {
+        CurveType::Polyline
+    }
+
+    fn clone_box(&self) -> Box<dyn Curve> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+impl Tessellatable for PolylineCurve {
+    fn tessellate(&self, _tolerance: f64) -> Tessellation {
+        Tessellation::Polyline(self.points.clone())
+    }
+}
+
+/// Offset a planar curve by `distance` within the plane normal to `plane_normal`
+///
+/// Positive distances go to the left of the direction of travel, looking
+/// down `plane_normal`. Lines stay lines and circular arcs become
+/// concentric arcs; other curves are sampled into a polyline within `tol`.
+/// Fails if the curve leaves the plane or the offset would fold over
+/// itself, as when an arc's radius would drop to zero.
+pub fn offset_curve_planar(
+    curve: &dyn Curve,
+    plane_normal: Vec3,
+    distance: f64,
+    tol: f64,
+) -> GeomResult<Box<dyn Curve>> {
+    if plane_normal.is_zero(1e-10) {
+        return Err(GeometryError::Degenerate("Offset plane normal cannot be zero".to_string()));
+    }
+    let normal = plane_normal.normalized();
+    let range = curve.param_range();
+    let side = |t: f64| -> GeomResult<Vec3> {
+        let tangent = curve.unit_tangent(t);
+        if tangent.dot(&normal).abs() > 1e-6 {
+            return Err(GeometryError::InvalidParameter(
+                "Curve does not lie in the offset plane".to_string()
+            ));
+        }
+        Ok(normal.cross(&tangent).normalized())
+    };
+
+    if let Some(line) = curve.as_any().downcast_ref::<Line>() {
+        let shift = side(range.start)? * distance;
+        return Ok(Box::new(Line { origin: line.origin + shift, ..*line }));
+    }
+    if let Some(arc) = curve.as_any().downcast_ref::<CircularArc>() {
+        if arc.normal.cross(&normal).length() > 1e-6 {
+            return Err(GeometryError::InvalidParameter(
+                "Arc does not lie in the offset plane".to_string()
+            ));
+        }
+        // The left side points along or against the radius throughout
+        let radius = arc.radius + distance * side(range.start)?.dot(&arc.start_vector);
+        if radius <= tol {
+            return Err(GeometryError::Degenerate(format!(
+                "Offset by {} collapses an arc of radius {}", distance, arc.radius
+            )));
+        }
+        return Ok(Box::new(CircularArc { radius, ..*arc }));
+    }
+
+    const INITIAL_SEGMENTS: usize = 16;
+    let offset_at = |t: f64| -> GeomResult<Point3> { Ok(curve.evaluate(t) + side(t)? * distance) };
+    let step = (range.end - range.start) / INITIAL_SEGMENTS as f64;
+    let mut samples = vec![(range.start, offset_at(range.start)?)];
+    for i in 0..INITIAL_SEGMENTS {
+        let (a, b) = (range.start + step * i as f64, range.start + step * (i + 1) as f64);
+        let pa = samples[samples.len() - 1].1;
+        refine_offset(&offset_at, a, b, pa, offset_at(b)?, tol, 0, &mut samples)?;
+    }
+
+    // A segment running against the curve means the offset has looped back
+    for pair in samples.windows(2) {
+        let [(s, p), (t, q)] = [pair[0], pair[1]];
+        if (q - p).dot(&(curve.evaluate(t) - curve.evaluate(s))) <= 0.0 {
+            return Err(GeometryError::Degenerate(format!(
+                "Offset by {} folds over itself", distance
+            )));
+        }
+    }
+    Ok(Box::new(PolylineCurve::new(samples.into_iter().map(|(_, p)| p).collect())?))
+}
+
+/// Append offset samples on `(a, b]`, bisecting until the chord from `pa`
+/// to `pb` is within `tol` of the offset curve
+#[allow(clippy::too_many_arguments)]
+fn refine_offset<F: Fn(f64) -> GeomResult<Point3>>(
+    offset_at: &F,
+    a: f64,
+    b: f64,
+    pa: Point3,
+    pb: Point3,
+    tol: f64,
+    depth: u32,
+    samples: &mut Vec<(f64, Point3)>,
+) -> GeomResult<()> {
+    const MAX_DEPTH: u32 = 12;
+    let mid = (a + b) / 2.0;
+    let pm = offset_at(mid)?;
+    if depth < MAX_DEPTH && pm.distance_to(&pa.midpoint(&pb)) > tol {
+        refine_offset(offset_at, a, mid, pa, pm, tol, depth + 1, samples)?;
+        refine_offset(offset_at, mid, b, pm, pb, tol, depth + 1, samples)?;
+    } else {
+        samples.push((b, pb));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_offset_line() {
+        let line = Line::segment(Point3::new(0.0, 0.0, 0.0), Point3::new(4.0, 3.0, 0.0)).unwrap();
+        let offset = offset_curve_planar(&line, Vec3::Z, 0.5, 1e-6).unwrap();
+        assert_eq!(offset.curve_type(), CurveType::Line);
+        for i in 0..=10 {
+            let p = offset.evaluate(5.0 * i as f64 / 10.0);
+            assert!((line.distance_to_point(&p) - 0.5).abs() < 1e-12);
+            // Left of travel when looking down +Z
+            assert!(line.direction().cross(&(p - line.origin())).z() > 0.0);
+        }
+        assert!(offset_curve_planar(&line, Vec3::X, 0.5, 1e-6).is_err());
+    }
+
+    #[test]
+    fn test_offset_circle() {
+        let center = Point3::new(1.0, 2.0, 0.0);
+        let circle = CircularArc::circle(center, 2.0, Vec3::Z).unwrap();
+        // Counter-clockwise about +Z, so the left side is inward
+        for (distance, radius) in [(0.5, 1.5), (-0.5, 2.5)] {
+            let offset = offset_curve_planar(&circle, Vec3::Z, distance, 1e-6).unwrap();
+            assert_eq!(offset.curve_type(), CurveType::CircularArc);
+            for i in 0..=16 {
+                let p = offset.evaluate(i as f64 / 16.0);
+                assert!((p.distance_to(&center) - radius).abs() < 1e-12);
+                let (_, closest, gap) = circle.closest_point(&p).unwrap();
+                assert!((gap - distance.abs()).abs() < 1e-12);
+                assert!((closest.distance_to(&p) - distance.abs()).abs() < 1e-12);
+            }
+        }
+        assert!(matches!(
+            offset_curve_planar(&circle, Vec3::Z, 2.5, 1e-6),
+            Err(GeometryError::Degenerate(_))
+        ));
+    }
+
+    #[test]
+    fn test_offset_ellipse_polyline() {
+        let ellipse = EllipseArc::new(Point3::ORIGIN, 3.0, 2.0, Vec3::X, Vec3::Z, 0.0, std::f64::consts::PI).unwrap();
+        let offset = offset_curve_planar(&ellipse, Vec3::Z, -0.25, 1e-4).unwrap();
+        assert_eq!(offset.curve_type(), CurveType::Polyline);
+        // The ellipse's closest_point is approximate, so measure against samples
+        let dense: Vec<Point3> = (0..=20_000).map(|i| ellipse.evaluate(i as f64 / 20_000.0)).collect();
+        let range = offset.param_range();
+        for i in 0..=50 {
+            let p = offset.evaluate(range.start + (range.end - range.start) * i as f64 / 50.0);
+            let gap = dense.iter().map(|q| q.distance_to(&p)).fold(f64::INFINITY, f64::min);
+            assert!((gap - 0.25).abs() < 2e-4, "gap {}", gap);
+        }
+        // Tightest radius of curvature is 4/3 at the ends of the major axis
+        assert!(matches!(
+            offset_curve_planar(&ellipse, Vec3::Z, 1.5, 1e-4),
+            Err(GeometryError::Degenerate(_))
+        ));
+    }
+
     #[test]
     fn test_line_new() {
         let line = Line::infinite(Point3::ORIGIN, Vec3::X).unwrap();
@@ -826,6 +1215,16 @@ mod tests {
         assert_eq!(dist, 3.0);
     }
 
+    #[test]
+    fn test_line_transformed_leaves_original() {
+        let line = Line::segment(Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0)).unwrap();
+        let moved = line.transformed(&Transform3::from_translation(0.0, 2.0, 0.0));
+        assert_eq!(moved.start_point(), Point3::new(0.0, 2.0, 0.0));
+        assert_eq!(moved.end_point(), Point3::new(1.0, 2.0, 0.0));
+        assert_eq!(line.start_point(), Point3::ORIGIN);
+        assert_eq!(line.end_point(), Point3::new(1.0, 0.0, 0.0));
+    }
+
     #[test]
     fn test_circular_arc_new() {
         let arc = CircularArc::new(
@@ -857,6 +1256,103 @@ mod tests {
         assert_eq!(arc.curvature(0.5), 0.5);
     }
 
+    #[test]
+    fn test_curve_is_closed() {
+        let circle = CircularArc::circle(Point3::ORIGIN, 2.0, Vec3::Z).unwrap();
+        assert!(circle.is_closed(0.0));
+        let half = CircularArc::new(Point3::ORIGIN, 2.0, Vec3::Z, Vec3::X, std::f64::consts::PI).unwrap();
+        assert!(!half.is_closed(1e-6));
+        let line = Line::segment(Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0)).unwrap();
+        assert!(!line.is_closed(1e-6));
+    }
+
+    #[test]
+    fn test_ellipse_arc_length_exact() {
+        let ellipse = EllipseArc::new(
+            Point3::ORIGIN,
+            3.0,
+            1.0,
+            Vec3::X,
+            Vec3::Z,
+            0.0,
+            std::f64::consts::TAU,
+        ).unwrap();
+
+        let n = 200_000;
+        let mut polyline = 0.0;
+        let mut prev = ellipse.evaluate(0.0);
+        for i in 1..=n {
+            let p = ellipse.evaluate(i as f64 / n as f64);
+            polyline += prev.distance_to(&p);
+            prev = p;
+        }
+
+        let exact = ellipse.arc_length_exact(8);
+        assert!(((exact - polyline) / polyline).abs() < 1e-6);
+        assert!((ellipse.arc_length(1.0) - exact).abs() < 1e-9);
+        assert!((ellipse.arc_length(0.5) - 0.5 * exact).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_polyline_curve_evaluate() {
+        let poly = PolylineCurve::new(vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(2.0, 3.0, 0.0),
+        ]).unwrap();
+
+        assert_eq!(poly.num_segments(), 2);
+        assert_eq!(poly.param_range().end, 5.0);
+
+        // Segment boundaries
+        assert_eq!(poly.evaluate(0.0), Point3::new(0.0, 0.0, 0.0));
+        assert_eq!(poly.evaluate(2.0), Point3::new(2.0, 0.0, 0.0));
+        assert_eq!(poly.evaluate(5.0), Point3::new(2.0, 3.0, 0.0));
+
+        // Segment midpoints
+        assert_eq!(poly.evaluate(1.0), Point3::new(1.0, 0.0, 0.0));
+        assert_eq!(poly.evaluate(3.5), Point3::new(2.0, 1.5, 0.0));
+
+        assert_eq!(poly.derivative(1.0, 1), Vec3::X);
+        assert_eq!(poly.derivative(3.5, 1), Vec3::Y);
+        assert_eq!(poly.curvature(1.0), 0.0);
+    }
+
+    #[test]
+    fn test_polyline_curve_closest_point() {
+        let poly = PolylineCurve::new(vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(2.0, 3.0, 0.0),
+        ]).unwrap();
+
+        let (t, closest, dist) = poly.closest_point(&Point3::new(3.0, 1.0, 0.0)).unwrap();
+        assert!((t - 3.0).abs() < 1e-12);
+        assert_eq!(closest, Point3::new(2.0, 1.0, 0.0));
+        assert!((dist - 1.0).abs() < 1e-12);
+
+        assert!(PolylineCurve::new(vec![Point3::ORIGIN, Point3::ORIGIN]).is_err());
+    }
+
+    #[test]
+    fn test_default_scale_uses_transform() {
+        let mut poly = PolylineCurve::new(vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(2.0, 3.0, 0.0),
+        ]).unwrap();
+        poly.scale(2.0);
+
+        assert_eq!(poly.param_range().end, 10.0);
+        assert_eq!(poly.evaluate(10.0), Point3::new(4.0, 6.0, 0.0));
+
+        // Curves without an exact override scale radii through the transform too
+        let mut arc = CircularArc::circle(Point3::new(1.0, 0.0, 0.0), 2.0, Vec3::Z).unwrap();
+        arc.transform(&Transform3::from_scale(3.0));
+        assert!((arc.radius() - 6.0).abs() < 1e-12);
+        assert!(arc.center().distance_to(&Point3::new(3.0, 0.0, 0.0)) < 1e-12);
+    }
+
     #[test]
     fn test_circular_arc_tessellate() {
         let arc = CircularArc::circle(Point3::ORIGIN, 10.0, Vec3::Z).unwrap();
@@ -1,46 +1,382 @@
 //! Curve-curve and surface-surface intersection algorithms
 
-use crate::{GeomResult, GeometryError, Curve, Surface, Point3, Vec3};
+use crate::{GeomResult, GeometryError, Curve, Surface, Line, CircularArc, EllipseArc, Point3, Vec3, PolylineCurve,
+            ParamRange, IntersectionResult, PlanarSurface, CylindricalSurface, ConicalSurface};
+use crate::nurbs::NurbsCurve;
+use nova_math::BoundingBox3;
 
-/// Intersection between two curves
+/// Intersection points between two curves
+///
+/// Line-line and line-arc pairs are solved analytically; all other pairs
+/// fall back to recursive subdivision refined by Newton iteration. Returns
+/// the points where the curves come within `tolerance` of each other.
 pub fn curve_curve_intersection(
     curve1: &dyn Curve,
     curve2: &dyn Curve,
     tolerance: f64,
-) -> GeomResult<Vec<(f64, f64, Point3)>> {
-    let mut result = Vec::new();
-    
-    // Sample both curves and find close points
-    let n1 = 50;
-    let n2 = 50;
-    
-    let range1 = curve1.param_range();
-    let range2 = curve2.param_range();
-    
-    for i in 0..=n1 {
-        let t1 = range1.denormalize(i as f64 / n1 as f64);
-        let p1 = curve1.evaluate(t1);
-        
-        for j in 0..=n2 {
-            let t2 = range2.denormalize(j as f64 / n2 as f64);
-            let p2 = curve2.evaluate(t2);
-            
-            if p1.distance_to(&p2) < tolerance {
-                // Refine with closest point
-                if let Ok((refined_t2, refined_p2, dist)) = curve2.closest_point(&p1) {
-                    if dist < tolerance {
-                        result.push((t1, refined_t2, p1.midpoint(&refined_p2)));
-                    }
+) -> GeomResult<Vec<Point3>> {
+    let any1 = curve1.as_any();
+    let any2 = curve2.as_any();
+
+    let mut points = if let (Some(l1), Some(l2)) = (any1.downcast_ref::<Line>(), any2.downcast_ref::<Line>()) {
+        line_line_points(l1, l2, tolerance)
+    } else if let (Some(line), Some(arc)) = (any1.downcast_ref::<Line>(), any2.downcast_ref::<CircularArc>()) {
+        line_arc_points(line, arc, tolerance)
+    } else if let (Some(arc), Some(line)) = (any1.downcast_ref::<CircularArc>(), any2.downcast_ref::<Line>()) {
+        line_arc_points(line, arc, tolerance)
+    } else {
+        subdivision_points(curve1, curve2, tolerance)?
+    };
+
+    dedup_points(&mut points, tolerance);
+    Ok(points)
+}
+
+/// Check if a point lies on a curve within tolerance
+fn on_curve(curve: &dyn Curve, point: &Point3, tolerance: f64) -> bool {
+    curve.closest_point(point).is_ok_and(|(_, _, dist)| dist <= tolerance)
+}
+
+/// Remove points closer than tolerance to an earlier point
+fn dedup_points(points: &mut Vec<Point3>, tolerance: f64) {
+    let mut unique: Vec<Point3> = Vec::with_capacity(points.len());
+    for p in points.drain(..) {
+        if !unique.iter().any(|q| q.distance_to(&p) <= tolerance) {
+            unique.push(p);
+        }
+    }
+    *points = unique;
+}
+
+/// Line-line intersection using the closest points of the carrier lines
+fn line_line_points(l1: &Line, l2: &Line, tolerance: f64) -> Vec<Point3> {
+    match l1.closest_parameters(l2) {
+        Some((t, s)) => {
+            let r1 = l1.param_range();
+            let r2 = l2.param_range();
+            // Clamp onto the segments, then re-project once in each direction
+            let s = l2.closest_point(&l1.evaluate(r1.clamp(t))).map_or(r2.clamp(s), |c| c.0);
+            let t = l1.closest_point(&l2.evaluate(s)).map_or(r1.clamp(t), |c| c.0);
+            let p1 = l1.evaluate(t);
+            let p2 = l2.evaluate(s);
+            if p1.distance_to(&p2) <= tolerance {
+                vec![p1.midpoint(&p2)]
+            } else {
+                Vec::new()
+            }
+        }
+        None => {
+            // Parallel: report segment endpoints that lie on the other line
+            [l1.segment_start(), l1.segment_end()]
+                .into_iter()
+                .flatten()
+                .filter(|p| on_curve(l2, p, tolerance))
+                .chain(
+                    [l2.segment_start(), l2.segment_end()]
+                        .into_iter()
+                        .flatten()
+                        .filter(|p| on_curve(l1, p, tolerance)),
+                )
+                .collect()
+        }
+    }
+}
+
+/// Line-arc intersection solved in the plane of the arc
+fn line_arc_points(line: &Line, arc: &CircularArc, tolerance: f64) -> Vec<Point3> {
+    let center = arc.center();
+    let normal = arc.normal();
+    let radius = arc.radius();
+    let origin = line.origin();
+    let dir = line.direction();
+
+    let denom = dir.dot(&normal);
+    let candidates: Vec<Point3> = if denom.abs() > 1e-10 {
+        // Line pierces the arc plane at a single point
+        let t = (center - origin).dot(&normal) / denom;
+        let p = origin + dir * t;
+        if (p.distance_to(&center) - radius).abs() <= tolerance {
+            vec![p]
+        } else {
+            Vec::new()
+        }
+    } else if (origin - center).dot(&normal).abs() > tolerance {
+        // Parallel to the arc plane but off it
+        Vec::new()
+    } else {
+        // Line in the arc plane: solve |origin + t*dir - center| = radius
+        let w = origin - center;
+        let b = w.dot(&dir);
+        let c = w.length_squared() - radius * radius;
+        let disc = b * b - c;
+        if disc < 0.0 {
+            // Tangent within tolerance still counts as a touching point
+            let t = -b;
+            let p = origin + dir * t;
+            if (p.distance_to(&center) - radius).abs() <= tolerance {
+                vec![p]
+            } else {
+                Vec::new()
+            }
+        } else {
+            let root = disc.sqrt();
+            vec![origin + dir * (-b - root), origin + dir * (-b + root)]
+        }
+    };
+
+    candidates
+        .into_iter()
+        .filter(|p| on_curve(line, p, tolerance) && on_curve(arc, p, tolerance))
+        .collect()
+}
+
+/// Maximum subdivision depth for the general curve-curve fallback
+const SUBDIVISION_MAX_DEPTH: u32 = 24;
+
+/// General curve-curve intersection by recursive subdivision
+fn subdivision_points(
+    curve1: &dyn Curve,
+    curve2: &dyn Curve,
+    tolerance: f64,
+) -> GeomResult<Vec<Point3>> {
+    let r1 = curve1.param_range();
+    let r2 = curve2.param_range();
+    if !(r1.start.is_finite() && r1.end.is_finite() && r2.start.is_finite() && r2.end.is_finite()) {
+        return Err(GeometryError::IntersectionFailed(
+            "Cannot subdivide an unbounded curve".to_string()
+        ));
+    }
+
+    let mut points = Vec::new();
+    subdivide(
+        curve1, (r1.start, r1.end),
+        curve2, (r2.start, r2.end),
+        tolerance, SUBDIVISION_MAX_DEPTH, &mut points,
+    );
+    Ok(points)
+}
+
+/// Padded bounding box of a curve span and whether the span is flat
+fn span_bounds(curve: &dyn Curve, (t0, t1): (f64, f64), tolerance: f64) -> (BoundingBox3, bool) {
+    let p0 = curve.evaluate(t0);
+    let pm = curve.evaluate(0.5 * (t0 + t1));
+    let p1 = curve.evaluate(t1);
+
+    let chord = p1 - p0;
+    let sagitta = if chord.is_zero(1e-12) {
+        pm.distance_to(&p0)
+    } else {
+        (pm - p0).reject_from(&chord.normalized()).length()
+    };
+
+    let mut bbox = BoundingBox3::from_points(&p0, &p1);
+    bbox.expand(&pm);
+    bbox.expand_by_margin(2.0 * sagitta + tolerance);
+    (bbox, sagitta <= 0.5 * tolerance)
+}
+
+/// Recursively split both spans until they are flat, then refine
+fn subdivide(
+    curve1: &dyn Curve,
+    span1: (f64, f64),
+    curve2: &dyn Curve,
+    span2: (f64, f64),
+    tolerance: f64,
+    depth: u32,
+    out: &mut Vec<Point3>,
+) {
+    let (box1, flat1) = span_bounds(curve1, span1, tolerance);
+    let (box2, flat2) = span_bounds(curve2, span2, tolerance);
+    if !box1.intersects(&box2) {
+        return;
+    }
+
+    if (flat1 && flat2) || depth == 0 {
+        let s = 0.5 * (span1.0 + span1.1);
+        let t = 0.5 * (span2.0 + span2.1);
+        if let Some(p) = refine_intersection(curve1, span1, s, curve2, span2, t, tolerance) {
+            out.push(p);
+        }
+        return;
+    }
+
+    let halves = |(a, b): (f64, f64), flat: bool| {
+        let m = 0.5 * (a + b);
+        if flat { vec![(a, b)] } else { vec![(a, m), (m, b)] }
+    };
+    for h1 in halves(span1, flat1) {
+        for h2 in halves(span2, flat2) {
+            subdivide(curve1, h1, curve2, h2, tolerance, depth - 1, out);
+        }
+    }
+}
+
+/// Newton iteration minimizing |C1(s) - C2(t)| inside the given spans
+fn refine_intersection(
+    curve1: &dyn Curve,
+    span1: (f64, f64),
+    mut s: f64,
+    curve2: &dyn Curve,
+    span2: (f64, f64),
+    mut t: f64,
+    tolerance: f64,
+) -> Option<Point3> {
+    for _ in 0..32 {
+        let r = curve1.evaluate(s) - curve2.evaluate(t);
+        let d1 = curve1.derivative(s, 1);
+        let d2 = curve2.derivative(t, 1);
+
+        // Normal equations of the 2x2 least-squares system [d1, -d2] * delta = -r
+        let a11 = d1.dot(&d1);
+        let a12 = -d1.dot(&d2);
+        let a22 = d2.dot(&d2);
+        let b1 = -d1.dot(&r);
+        let b2 = d2.dot(&r);
+        let det = a11 * a22 - a12 * a12;
+        if det.abs() < 1e-14 {
+            break;
+        }
+
+        let ds = (b1 * a22 - a12 * b2) / det;
+        let dt = (a11 * b2 - a12 * b1) / det;
+        s = (s + ds).clamp(span1.0, span1.1);
+        t = (t + dt).clamp(span2.0, span2.1);
+        if ds.abs() < 1e-14 && dt.abs() < 1e-14 {
+            break;
+        }
+    }
+
+    let p1 = curve1.evaluate(s);
+    let p2 = curve2.evaluate(t);
+    if p1.distance_to(&p2) <= tolerance {
+        Some(p1.midpoint(&p2))
+    } else {
+        None
+    }
+}
+
+/// Tolerance for 2D parameter-space intersection tests
+const UV_EPS: f64 = 1e-10;
+
+/// An intersection between two UV polylines
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UvIntersection {
+    /// Intersection point in (u, v)
+    pub point: (f64, f64),
+    /// Position along the first polyline (segment index + fraction)
+    pub param_a: f64,
+    /// Position along the second polyline (segment index + fraction)
+    pub param_b: f64,
+}
+
+/// Intersection points between two polylines in surface parameter space
+///
+/// Crossings and tangential touches are both reported once.
+pub fn curve_curve_intersection_2d(a: &[(f64, f64)], b: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    polyline_intersections_2d(a, b).into_iter().map(|hit| hit.point).collect()
+}
+
+/// Intersections between two UV polylines with their positions along each
+pub fn polyline_intersections_2d(a: &[(f64, f64)], b: &[(f64, f64)]) -> Vec<UvIntersection> {
+    let mut hits: Vec<UvIntersection> = Vec::new();
+
+    for (i, sa) in a.windows(2).enumerate() {
+        for (j, sb) in b.windows(2).enumerate() {
+            for (point, ta, tb) in segment_intersections_2d(sa[0], sa[1], sb[0], sb[1]) {
+                let duplicate = hits.iter().any(|h| {
+                    (h.point.0 - point.0).abs() <= UV_EPS && (h.point.1 - point.1).abs() <= UV_EPS
+                });
+                if !duplicate {
+                    hits.push(UvIntersection {
+                        point,
+                        param_a: i as f64 + ta,
+                        param_b: j as f64 + tb,
+                    });
                 }
             }
         }
     }
-    
-    // Remove duplicates
-    result.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
-    result.dedup_by(|a, b| (a.0 - b.0).abs() < 1e-6 && (a.1 - b.1).abs() < 1e-6);
-    
-    Ok(result)
+
+    hits
+}
+
+/// Intersections of two 2D segments as (point, fraction on p, fraction on q)
+fn segment_intersections_2d(
+    p1: (f64, f64),
+    p2: (f64, f64),
+    q1: (f64, f64),
+    q2: (f64, f64),
+) -> Vec<((f64, f64), f64, f64)> {
+    let cross = |a: (f64, f64), b: (f64, f64)| a.0 * b.1 - a.1 * b.0;
+    let d = (p2.0 - p1.0, p2.1 - p1.1);
+    let e = (q2.0 - q1.0, q2.1 - q1.1);
+    let w = (q1.0 - p1.0, q1.1 - p1.1);
+    let len_d = d.0.hypot(d.1);
+    let len_e = e.0.hypot(e.1);
+    if len_d <= UV_EPS || len_e <= UV_EPS {
+        return Vec::new();
+    }
+
+    let denom = cross(d, e);
+    if denom.abs() > UV_EPS * len_d * len_e {
+        let t = cross(w, e) / denom;
+        let s = cross(w, d) / denom;
+        let t_eps = UV_EPS / len_d;
+        let s_eps = UV_EPS / len_e;
+        if t < -t_eps || t > 1.0 + t_eps || s < -s_eps || s > 1.0 + s_eps {
+            return Vec::new();
+        }
+        let t = t.clamp(0.0, 1.0);
+        return vec![((p1.0 + d.0 * t, p1.1 + d.1 * t), t, s.clamp(0.0, 1.0))];
+    }
+
+    // Parallel: only collinear segments can touch
+    if cross(w, d).abs() > UV_EPS * len_d {
+        return Vec::new();
+    }
+
+    // Overlap of q projected onto p, reported by its end points
+    let len_d_sq = len_d * len_d;
+    let project = |q: (f64, f64)| ((q.0 - p1.0) * d.0 + (q.1 - p1.1) * d.1) / len_d_sq;
+    let (q1_t, q2_t) = (project(q1), project(q2));
+    let lo = q1_t.min(q2_t).max(0.0);
+    let hi = q1_t.max(q2_t).min(1.0);
+    if lo > hi + UV_EPS / len_d {
+        return Vec::new();
+    }
+
+    let to_s = |t: f64| ((t - q1_t) / (q2_t - q1_t)).clamp(0.0, 1.0);
+    let at = |t: f64| (p1.0 + d.0 * t, p1.1 + d.1 * t);
+    if hi - lo <= UV_EPS / len_d {
+        vec![(at(lo), lo, to_s(lo))]
+    } else {
+        vec![(at(lo), lo, to_s(lo)), (at(hi), hi, to_s(hi))]
+    }
+}
+
+/// Map a 3D curve lying on a surface into a polyline in the surface's UV space
+///
+/// The curve is sampled uniformly at `samples` intervals and each sample is
+/// projected onto the surface.
+pub fn curve_to_uv(
+    curve: &dyn Curve,
+    surface: &dyn Surface,
+    samples: usize,
+) -> GeomResult<Vec<(f64, f64)>> {
+    let range = curve.param_range();
+    if !range.is_valid() || !range.start.is_finite() || !range.end.is_finite() {
+        return Err(GeometryError::InvalidParameter(
+            "Cannot map an unbounded curve to UV space".to_string()
+        ));
+    }
+
+    let samples = samples.max(1);
+    (0..=samples)
+        .map(|i| {
+            let p = curve.evaluate(range.denormalize(i as f64 / samples as f64));
+            surface.closest_point(&p).map(|(u, v, _, _)| (u, v))
+        })
+        .collect()
 }
 
 /// Intersection between a curve and a surface
@@ -69,16 +405,830 @@ pub fn curve_surface_intersection(
     Ok(result)
 }
 
+/// Step control for tracing an intersection between two surfaces
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarchingOptions {
+    /// Distance within which both surfaces must agree at each traced point
+    pub tolerance: f64,
+    /// Shortest step tried before giving up at a boundary or tangency
+    pub min_step: f64,
+    /// Longest step
+    pub max_step: f64,
+    /// Largest turn of the tangent across one step, in radians
+    pub max_turn: f64,
+    /// Most points traced per curve
+    pub max_points: usize,
+    /// Angle within which the normals at a contact count as parallel
+    pub angular_tolerance: f64,
+}
+
+impl Default for MarchingOptions {
+    fn default() -> Self {
+        Self {
+            tolerance: 1e-7,
+            min_step: 1e-5,
+            max_step: 0.25,
+            max_turn: 0.1,
+            max_points: 10_000,
+            angular_tolerance: 1e-8,
+        }
+    }
+}
+
+/// Cells across the smaller surface at which seed subdivision stops
+const SEED_CELLS: f64 = 32.0;
+
+/// Maximum subdivision depth when seeding surface-surface intersections
+const SEED_MAX_DEPTH: u32 = 16;
+
 /// Intersection between two surfaces
+///
+/// Every seed from [`find_intersection_seeds`] not already on a traced
+/// curve is marched with [`march_surface_intersection`], so each branch
+/// comes back as one `PolylineCurve`. Where the surfaces touch without
+/// crossing, the contact line is traced once instead, and a contact at a
+/// single point gives no curve. Both surfaces need bounded parameter
+/// ranges; otherwise no curves are found.
 pub fn surface_surface_intersection(
     surface1: &dyn Surface,
     surface2: &dyn Surface,
     tolerance: f64,
 ) -> GeomResult<Vec<Box<dyn Curve>>> {
-    // Surface-surface intersection is complex
-    // This is a simplified implementation that returns empty
-    // Full implementation would use marching methods
-    Ok(Vec::new())
+    let Some(cell) = seed_cell_size(surface1, surface2, tolerance) else {
+        return Ok(Vec::new());
+    };
+    let options = MarchingOptions {
+        tolerance,
+        max_step: cell,
+        min_step: (cell * 1e-5).max(tolerance),
+        ..MarchingOptions::default()
+    };
+    let mut curves: Vec<PolylineCurve> = Vec::new();
+    for seed in find_intersection_seeds(surface1, surface2, tolerance) {
+        let traced = curves.iter().any(|c| polyline_distance(c.points(), &seed) < cell);
+        if traced {
+            continue;
+        }
+        if let Some(tangency) = tangent_contact(surface1, surface2, &seed, &options) {
+            if let Some(curve) = tangency.and_then(|t| trace_tangent_contact(surface1, surface2, &t, &options)) {
+                curves.push(curve);
+            }
+            continue;
+        }
+        if let Ok(curve) = march_surface_intersection(surface1, surface2, &seed, &options) {
+            curves.push(curve);
+        }
+    }
+    Ok(curves.into_iter().map(|c| Box::new(c) as Box<dyn Curve>).collect())
+}
+
+/// Points on both surfaces found by recursive subdivision
+///
+/// Pairs of parameter cells whose padded bounding boxes overlap are split
+/// until both are about 1/32 of the smaller surface across, then Newton
+/// iteration pulls the cells' centers onto both surfaces. Seeds closer
+/// together than a cell are merged, leaving a handful on every branch of
+/// the intersection. Both surfaces need bounded parameter ranges;
+/// otherwise no seeds are found.
+pub fn find_intersection_seeds(surface1: &dyn Surface, surface2: &dyn Surface, tolerance: f64) -> Vec<Point3> {
+    let Some(cell) = seed_cell_size(surface1, surface2, tolerance) else {
+        return Vec::new();
+    };
+    let whole = |s: &dyn Surface| {
+        let range = s.uv_range();
+        [range.u.start, range.u.end, range.v.start, range.v.end]
+    };
+    let mut seeds = Vec::new();
+    subdivide_patches(
+        surface1, whole(surface1),
+        surface2, whole(surface2),
+        cell, tolerance, SEED_MAX_DEPTH, &mut seeds,
+    );
+    seeds
+}
+
+/// Seed cell size for two surfaces, or None if either is unbounded
+fn seed_cell_size(surface1: &dyn Surface, surface2: &dyn Surface, tolerance: f64) -> Option<f64> {
+    let size = |s: &dyn Surface| {
+        let range = s.uv_range();
+        let (bbox, _) = patch_bounds(s, [range.u.start, range.u.end, range.v.start, range.v.end], 0.0);
+        bbox.diagonal()
+    };
+    let smaller = size(surface1).min(size(surface2));
+    smaller.is_finite().then(|| (smaller / SEED_CELLS).max(tolerance))
+}
+
+/// Padded bounding box of a parameter cell `[u0, u1, v0, v1]` and its center
+///
+/// The box holds a 3 x 3 grid of samples, padded by twice how far the
+/// edge midpoints and center stray from the corners' bilinear patch.
+fn patch_bounds(surface: &dyn Surface, [u0, u1, v0, v1]: [f64; 4], tolerance: f64) -> (BoundingBox3, Point3) {
+    let grid: Vec<Vec<Point3>> = [u0, 0.5 * (u0 + u1), u1].iter()
+        .map(|&u| [v0, 0.5 * (v0 + v1), v1].iter().map(|&v| surface.evaluate(u, v)).collect())
+        .collect();
+    let bilinear = |i: usize, j: usize| {
+        let (s, t) = (i as f64 / 2.0, j as f64 / 2.0);
+        let bottom = grid[0][0].lerp(&grid[2][0], s);
+        let top = grid[0][2].lerp(&grid[2][2], s);
+        bottom.lerp(&top, t)
+    };
+    let mut bbox = BoundingBox3::empty();
+    let mut sagitta: f64 = 0.0;
+    for (i, row) in grid.iter().enumerate() {
+        for (j, p) in row.iter().enumerate() {
+            bbox.expand(p);
+            sagitta = sagitta.max(p.distance_to(&bilinear(i, j)));
+        }
+    }
+    bbox.expand_by_margin(2.0 * sagitta + tolerance);
+    (bbox, grid[1][1])
+}
+
+/// Recursively split the larger of two overlapping cells, then refine
+#[allow(clippy::too_many_arguments)]
+fn subdivide_patches(
+    surface1: &dyn Surface,
+    cell1: [f64; 4],
+    surface2: &dyn Surface,
+    cell2: [f64; 4],
+    size: f64,
+    tolerance: f64,
+    depth: u32,
+    out: &mut Vec<Point3>,
+) {
+    let (box1, center1) = patch_bounds(surface1, cell1, tolerance);
+    let (box2, center2) = patch_bounds(surface2, cell2, tolerance);
+    if !box1.intersects(&box2) {
+        return;
+    }
+
+    let (d1, d2) = (box1.diagonal(), box2.diagonal());
+    if (d1 <= size && d2 <= size) || depth == 0 {
+        let mid = center1.midpoint(&center2);
+        let uv_center = |[u0, u1, v0, v1]: [f64; 4]| (0.5 * (u0 + u1), 0.5 * (v0 + v1));
+        let guess = Contact {
+            uv1: project_uv(surface1, &mid, uv_center(cell1)),
+            uv2: project_uv(surface2, &mid, uv_center(cell2)),
+            point: mid,
+        };
+        if let Some(found) = converge(surface1, surface2, guess, None, tolerance) {
+            if out.iter().all(|p| p.distance_to(&found.point) >= size) {
+                out.push(found.point);
+            }
+        }
+        return;
+    }
+
+    let quarters = |[u0, u1, v0, v1]: [f64; 4]| {
+        let (um, vm) = (0.5 * (u0 + u1), 0.5 * (v0 + v1));
+        [[u0, um, v0, vm], [um, u1, v0, vm], [u0, um, vm, v1], [um, u1, vm, v1]]
+    };
+    if d1 >= d2 {
+        for quarter in quarters(cell1) {
+            subdivide_patches(surface1, quarter, surface2, cell2, size, tolerance, depth - 1, out);
+        }
+    } else {
+        for quarter in quarters(cell2) {
+            subdivide_patches(surface1, cell1, surface2, quarter, size, tolerance, depth - 1, out);
+        }
+    }
+}
+
+/// Trace the intersection of two surfaces through a point near both
+///
+/// Each step moves along the tangent `n1 x n2` and is pulled back onto both
+/// surfaces by Newton iteration on the two tangent planes and the plane
+/// square to the step. Steps halve while the tangent turns more than
+/// `max_turn` and grow again on flat stretches. Tracing runs both ways
+/// from the seed until it leaves either surface's parameter range, meets a
+/// tangency or closes a loop, in which case the polyline ends on its start.
+pub fn march_surface_intersection(
+    surface1: &dyn Surface,
+    surface2: &dyn Surface,
+    seed: &Point3,
+    options: &MarchingOptions,
+) -> GeomResult<PolylineCurve> {
+    let start = |s: &dyn Surface| s.closest_point(seed).map(|(u, v, _, _)| project_uv(s, seed, (u, v)));
+    let contact = Contact { uv1: start(surface1)?, uv2: start(surface2)?, point: *seed };
+    let seed = converge(surface1, surface2, contact, None, options.tolerance)
+        .ok_or_else(|| GeometryError::IntersectionFailed("Seed is not near both surfaces".to_string()))?;
+
+    let (mut forward, closed) = march_from(surface1, surface2, &seed, 1.0, options);
+    if !closed {
+        let (backward, _) = march_from(surface1, surface2, &seed, -1.0, options);
+        forward.splice(0..1, backward.into_iter().rev());
+    }
+    PolylineCurve::new(forward)
+}
+
+/// A point on both surfaces with its parameters on each
+#[derive(Debug, Clone, Copy)]
+struct Contact {
+    uv1: (f64, f64),
+    uv2: (f64, f64),
+    point: Point3,
+}
+
+/// March from `seed` along `sign` times the intersection tangent
+///
+/// Returns the points from the seed on and whether they closed a loop.
+fn march_from(
+    surface1: &dyn Surface,
+    surface2: &dyn Surface,
+    seed: &Contact,
+    sign: f64,
+    options: &MarchingOptions,
+) -> (Vec<Point3>, bool) {
+    let mut points = vec![seed.point];
+    let Some(mut tangent) = intersection_tangent(surface1, surface2, seed).map(|t| t * sign) else {
+        return (points, false);
+    };
+    let mut current = *seed;
+    let mut step = options.max_step;
+    while points.len() < options.max_points && step >= options.min_step {
+        let target = current.point + tangent * step;
+        let guess = Contact {
+            uv1: project_uv(surface1, &target, current.uv1),
+            uv2: project_uv(surface2, &target, current.uv2),
+            point: target,
+        };
+        let next = converge(surface1, surface2, guess, Some((target, tangent)), options.tolerance);
+        let Some(next) = next.filter(|n| (n.point - current.point).dot(&tangent) > 0.0) else {
+            step /= 2.0;
+            continue;
+        };
+        let Some(next_tangent) = intersection_tangent(surface1, surface2, &next) else {
+            points.push(next.point);
+            break;
+        };
+        let next_tangent = if next_tangent.dot(&tangent) < 0.0 { -next_tangent } else { next_tangent };
+        let turn = tangent.angle_to(&next_tangent);
+        if turn > options.max_turn && step / 2.0 >= options.min_step {
+            step /= 2.0;
+            continue;
+        }
+
+        // The loop closes once a step passes the seed
+        let chord = next.point - current.point;
+        let along = (seed.point - current.point).dot(&chord) / chord.length_squared();
+        if points.len() > 2 && (0.0..=1.0).contains(&along)
+            && (current.point + chord * along).distance_to(&seed.point) < step / 2.0 {
+            points.push(seed.point);
+            return (points, true);
+        }
+
+        points.push(next.point);
+        if on_boundary(surface1, next.uv1) || on_boundary(surface2, next.uv2) {
+            break;
+        }
+        current = next;
+        tangent = next_tangent;
+        if turn < options.max_turn / 4.0 {
+            step = (step * 1.5).min(options.max_step);
+        }
+    }
+    (points, false)
+}
+
+/// Unit tangent of the intersection curve, or None where the surfaces touch
+fn intersection_tangent(surface1: &dyn Surface, surface2: &dyn Surface, at: &Contact) -> Option<Vec3> {
+    let tangent = surface1.normal(at.uv1.0, at.uv1.1).cross(&surface2.normal(at.uv2.0, at.uv2.1));
+    (tangent.length() > 1e-9).then(|| tangent.normalized())
+}
+
+/// Newton iteration onto both surfaces, optionally held to the plane through
+/// `step.0` square to `step.1`
+fn converge(
+    surface1: &dyn Surface,
+    surface2: &dyn Surface,
+    mut at: Contact,
+    step: Option<(Point3, Vec3)>,
+    tolerance: f64,
+) -> Option<Contact> {
+    for _ in 0..32 {
+        let (p1, p2) = (surface1.evaluate(at.uv1.0, at.uv1.1), surface2.evaluate(at.uv2.0, at.uv2.1));
+        if p1.distance_to(&p2) <= tolerance {
+            at.point = p1.midpoint(&p2);
+            return Some(at);
+        }
+        let (n1, n2) = (surface1.normal(at.uv1.0, at.uv1.1), surface2.normal(at.uv2.0, at.uv2.1));
+        let (through, n3) = step.unwrap_or((p1.midpoint(&p2), n1.cross(&n2)));
+        // Meet of the two tangent planes and the third plane
+        let det = n1.dot(&n2.cross(&n3));
+        if det.abs() < 1e-12 {
+            return None;
+        }
+        let x = (n2.cross(&n3) * n1.dot(&p1.to_vector())
+            + n3.cross(&n1) * n2.dot(&p2.to_vector())
+            + n1.cross(&n2) * n3.dot(&through.to_vector())) / det;
+        let x = Point3::ORIGIN + x;
+        at.uv1 = project_uv(surface1, &x, at.uv1);
+        at.uv2 = project_uv(surface2, &x, at.uv2);
+    }
+    None
+}
+
+/// A contact where two surfaces touch along a line without crossing
+#[derive(Debug, Clone, Copy)]
+struct Tangency {
+    at: Contact,
+    /// Unit direction of the contact line
+    along: Vec3,
+}
+
+/// Gap from surface 1 to surface 2 near a contact, as a quadratic in
+/// lengths along surface 1's parameter directions
+#[derive(Debug, Clone, Copy)]
+struct GapModel {
+    gap: f64,
+    gradient: [f64; 2],
+    /// Hessian eigenvalues, largest magnitude first, and unit eigenvectors
+    curvatures: [f64; 2],
+    directions: [[f64; 2]; 2],
+    /// Parameter lengths on surface 1 per unit length along u and v
+    scale: [f64; 2],
+}
+
+/// Distance from surface 1 at `uv1` to surface 2 along the latter's normal
+fn surface_gap(surface1: &dyn Surface, surface2: &dyn Surface, uv1: (f64, f64), uv2: (f64, f64)) -> (f64, (f64, f64)) {
+    let p = surface1.evaluate(uv1.0, uv1.1);
+    let uv2 = project_uv(surface2, &p, uv2);
+    let gap = (p - surface2.evaluate(uv2.0, uv2.1)).dot(&surface2.normal(uv2.0, uv2.1));
+    (gap, uv2)
+}
+
+/// Fit the gap around `at` from central differences `h` apart
+fn gap_model(surface1: &dyn Surface, surface2: &dyn Surface, at: &Contact, h: f64) -> Option<GapModel> {
+    let (su, sv) = surface1.derivatives(at.uv1.0, at.uv1.1);
+    if su.length() < 1e-12 || sv.length() < 1e-12 {
+        return None;
+    }
+    let scale = [1.0 / su.length(), 1.0 / sv.length()];
+    let g = |i: f64, j: f64| {
+        let uv1 = (at.uv1.0 + i * h * scale[0], at.uv1.1 + j * h * scale[1]);
+        surface_gap(surface1, surface2, uv1, at.uv2).0
+    };
+    let g0 = g(0.0, 0.0);
+    let (gu, gv) = (g(1.0, 0.0) - g(-1.0, 0.0), g(0.0, 1.0) - g(0.0, -1.0));
+    let guu = (g(1.0, 0.0) - 2.0 * g0 + g(-1.0, 0.0)) / (h * h);
+    let gvv = (g(0.0, 1.0) - 2.0 * g0 + g(0.0, -1.0)) / (h * h);
+    let guv = (g(1.0, 1.0) - g(1.0, -1.0) - g(-1.0, 1.0) + g(-1.0, -1.0)) / (4.0 * h * h);
+
+    // Eigen-decomposition of the symmetric 2x2 Hessian
+    let mean = 0.5 * (guu + gvv);
+    let spread = (0.25 * (guu - gvv).powi(2) + guv * guv).sqrt();
+    let (high, low) = if mean >= 0.0 { (mean + spread, mean - spread) } else { (mean - spread, mean + spread) };
+    let angle = 0.5 * (2.0 * guv).atan2(guu - gvv);
+    let (major, minor) = ([angle.cos(), angle.sin()], [-angle.sin(), angle.cos()]);
+    // atan2 picks the eigenvector of the larger eigenvalue, not the larger magnitude
+    let directions = if high >= low { [major, minor] } else { [minor, major] };
+    Some(GapModel {
+        gap: g0,
+        gradient: [gu / (2.0 * h), gv / (2.0 * h)],
+        curvatures: [high, low],
+        directions,
+        scale,
+    })
+}
+
+/// Refine `at` onto the line or point where the gap between the surfaces
+/// is smallest, moving at most `reach`
+///
+/// Returns the refined contact and the gap model there, or None where the
+/// gap doesn't curve, as between crossing planes.
+fn gap_extremum(
+    surface1: &dyn Surface,
+    surface2: &dyn Surface,
+    mut at: Contact,
+    h: f64,
+    reach: f64,
+) -> Option<(Contact, GapModel)> {
+    let start = at.point;
+    for _ in 0..16 {
+        let model = gap_model(surface1, surface2, &at, h)?;
+        let [high, low] = model.curvatures;
+        if high.abs() < 1e-12 {
+            return None;
+        }
+        // Newton step in the curved directions; along a contact line the gap is flat
+        let mut step = [0.0; 2];
+        for (curvature, direction) in [(high, model.directions[0]), (low, model.directions[1])] {
+            if curvature.abs() > 1e-6 * high.abs() {
+                let slope = model.gradient[0] * direction[0] + model.gradient[1] * direction[1];
+                step[0] -= slope / curvature * direction[0];
+                step[1] -= slope / curvature * direction[1];
+            }
+        }
+        let target = surface1.evaluate(at.uv1.0 + step[0] * model.scale[0], at.uv1.1 + step[1] * model.scale[1]);
+        at.uv1 = project_uv(surface1, &target, at.uv1);
+        let (_, uv2) = surface_gap(surface1, surface2, at.uv1, at.uv2);
+        at.uv2 = uv2;
+        let moved = at.point.distance_to(&target);
+        at.point = target;
+        if at.point.distance_to(&start) > reach {
+            return None;
+        }
+        if moved < 1e-12 * (1.0 + reach) {
+            return gap_model(surface1, surface2, &at, h).map(|model| (at, model));
+        }
+    }
+    None
+}
+
+/// Tangent contact near `seed`, if the surfaces touch along a line there
+///
+/// The seed is moved onto the nearest extremum of the gap between the
+/// surfaces. It is a tangency if the gap there is within tolerance and the
+/// normals are parallel to within the angular tolerance. A contact at a
+/// single point is still a tangency, but one with no line to trace.
+fn tangent_contact(
+    surface1: &dyn Surface,
+    surface2: &dyn Surface,
+    seed: &Point3,
+    options: &MarchingOptions,
+) -> Option<Option<Tangency>> {
+    let (u, v, _, _) = surface1.closest_point(seed).ok()?;
+    let uv1 = project_uv(surface1, seed, (u, v));
+    let (u, v, _, _) = surface2.closest_point(seed).ok()?;
+    let at = Contact { uv1, uv2: project_uv(surface2, seed, (u, v)), point: *seed };
+    let h = options.max_step / 4.0;
+    let (at, model) = gap_extremum(surface1, surface2, at, h, options.max_step)?;
+
+    let (n1, n2) = (surface1.normal(at.uv1.0, at.uv1.1), surface2.normal(at.uv2.0, at.uv2.1));
+    let angle = n1.angle_to(&n2).min(n1.angle_to(&-n2));
+    if model.gap.abs() > options.tolerance || angle > options.angular_tolerance {
+        return None;
+    }
+    // The gap opens past tolerance within a step along a point contact
+    if 0.5 * model.curvatures[1].abs() * options.max_step * options.max_step > options.tolerance {
+        return Some(None);
+    }
+    let (su, sv) = surface1.derivatives(at.uv1.0, at.uv1.1);
+    let [du, dv] = model.directions[1];
+    let along = (su * (du * model.scale[0]) + sv * (dv * model.scale[1])).normalized();
+    Some(Some(Tangency { at, along }))
+}
+
+/// Trace the line where two surfaces touch both ways from a tangency
+fn trace_tangent_contact(
+    surface1: &dyn Surface,
+    surface2: &dyn Surface,
+    tangency: &Tangency,
+    options: &MarchingOptions,
+) -> Option<PolylineCurve> {
+    let (mut forward, closed) = trace_tangent_from(surface1, surface2, tangency, 1.0, options);
+    if !closed {
+        let (backward, _) = trace_tangent_from(surface1, surface2, tangency, -1.0, options);
+        forward.splice(0..1, backward.into_iter().rev());
+    }
+    PolylineCurve::new(forward).ok()
+}
+
+/// Follow a contact line from `start` along `sign` times its direction
+///
+/// Returns the points from the start on and whether they closed a loop.
+fn trace_tangent_from(
+    surface1: &dyn Surface,
+    surface2: &dyn Surface,
+    start: &Tangency,
+    sign: f64,
+    options: &MarchingOptions,
+) -> (Vec<Point3>, bool) {
+    let mut points = vec![start.at.point];
+    let mut current = Tangency { along: start.along * sign, ..*start };
+    let mut step = options.max_step;
+    let h = options.max_step / 4.0;
+    while points.len() < options.max_points && step >= options.min_step {
+        let target = current.at.point + current.along * step;
+        let guess = Contact {
+            uv1: project_uv(surface1, &target, current.at.uv1),
+            uv2: project_uv(surface2, &target, current.at.uv2),
+            point: target,
+        };
+        let refined = gap_extremum(surface1, surface2, guess, h, step)
+            .filter(|(at, model)| model.gap.abs() <= options.tolerance
+                && (at.point - current.at.point).dot(&current.along) > 0.0);
+        let Some((next, _)) = refined else {
+            step /= 2.0;
+            continue;
+        };
+
+        // The loop closes once a step passes the start
+        let chord = next.point - current.at.point;
+        let along = (start.at.point - current.at.point).dot(&chord) / chord.length_squared();
+        if points.len() > 2 && (0.0..=1.0).contains(&along)
+            && (current.at.point + chord * along).distance_to(&start.at.point) < step / 2.0 {
+            points.push(start.at.point);
+            return (points, true);
+        }
+
+        points.push(next.point);
+        if on_boundary(surface1, next.uv1) || on_boundary(surface2, next.uv2) {
+            break;
+        }
+        current = Tangency { at: next, along: chord.normalized() };
+        step = (step * 1.5).min(options.max_step);
+    }
+    (points, false)
+}
+
+/// Gauss-Newton projection of `point` onto `surface` from `uv`
+///
+/// Parameters wrap around in directions where the surface closes on itself
+/// and are clamped to the range otherwise.
+fn project_uv(surface: &dyn Surface, point: &Point3, (mut u, mut v): (f64, f64)) -> (f64, f64) {
+    let range = surface.uv_range();
+    let (closed_u, closed_v) = closed_directions(surface);
+    let keep = |x: f64, r: ParamRange, closed: bool| {
+        if closed {
+            r.start + (x - r.start).rem_euclid(r.length())
+        } else {
+            r.clamp(x)
+        }
+    };
+    for _ in 0..8 {
+        let (su, sv) = surface.derivatives(u, v);
+        let r = *point - surface.evaluate(u, v);
+        let (a, b, c) = (su.dot(&su), su.dot(&sv), sv.dot(&sv));
+        let det = a * c - b * b;
+        if det.abs() < 1e-20 {
+            break;
+        }
+        let (ru, rv) = (su.dot(&r), sv.dot(&r));
+        let (du, dv) = ((c * ru - b * rv) / det, (a * rv - b * ru) / det);
+        u = keep(u + du, range.u, closed_u);
+        v = keep(v + dv, range.v, closed_v);
+        if du.abs() + dv.abs() < 1e-14 {
+            break;
+        }
+    }
+    (u, v)
+}
+
+/// Whether the surface meets itself across its u and v parameter edges
+fn closed_directions(surface: &dyn Surface) -> (bool, bool) {
+    let range = surface.uv_range();
+    let samples = |r: ParamRange| [r.start, r.start + 0.5 * r.length(), r.end];
+    let closed_u = samples(range.v).iter()
+        .all(|&v| surface.evaluate(range.u.start, v).distance_to(&surface.evaluate(range.u.end, v)) < 1e-9);
+    let closed_v = samples(range.u).iter()
+        .all(|&u| surface.evaluate(u, range.v.start).distance_to(&surface.evaluate(u, range.v.end)) < 1e-9);
+    (closed_u, closed_v)
+}
+
+/// Check if `uv` lies on an open edge of the surface's parameter range
+fn on_boundary(surface: &dyn Surface, (u, v): (f64, f64)) -> bool {
+    let range = surface.uv_range();
+    let (closed_u, closed_v) = closed_directions(surface);
+    let eps = 1e-9;
+    let near = |x: f64, r: ParamRange| (x - r.start).abs() < eps || (r.end - x).abs() < eps;
+    (!closed_u && near(u, range.u)) || (!closed_v && near(v, range.v))
+}
+
+/// Distance from `point` to a polyline
+fn polyline_distance(points: &[Point3], point: &Point3) -> f64 {
+    points.windows(2).map(|w| {
+        let chord = w[1] - w[0];
+        let t = ((*point - w[0]).dot(&chord) / chord.length_squared()).clamp(0.0, 1.0);
+        (w[0] + chord * t).distance_to(point)
+    }).fold(f64::INFINITY, f64::min)
+}
+
+/// Angle below which a plane counts as parallel or perpendicular to an axis
+const ANGULAR_TOLERANCE: f64 = 1e-9;
+
+/// Kind of conic a plane cuts from a double cone
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConicSection {
+    /// Plane perpendicular to the axis
+    Circle,
+    /// Plane steeper than the cone's side
+    Ellipse,
+    /// Plane parallel to a generator
+    Parabola,
+    /// Plane shallower than the cone's side
+    Hyperbola,
+    /// Plane through the apex containing one or two generators
+    Lines,
+    /// Plane through the apex meeting nothing else
+    Point,
+}
+
+/// Intersection of a cylinder with a plane
+///
+/// A plane across the axis gives a circle or an `EllipseArc`; one parallel
+/// to the axis gives up to two lines spanning the cylinder's height range.
+/// Closed curves are whole, whatever the height range.
+pub fn cylinder_plane_intersection(
+    cylinder: &CylindricalSurface,
+    plane: &PlanarSurface,
+    tolerance: f64,
+) -> GeomResult<Vec<IntersectionResult>> {
+    let (axis, normal, radius) = (cylinder.axis(), plane.normal(), cylinder.radius());
+    let cos = axis.dot(&normal);
+    let offset = (cylinder.origin() - plane.origin()).dot(&normal);
+
+    if cos.abs() < ANGULAR_TOLERANCE {
+        if offset.abs() > radius + tolerance {
+            return Ok(Vec::new());
+        }
+        let foot = cylinder.origin() - normal * offset;
+        let across = axis.cross(&normal).normalized() * (radius * radius - offset * offset).max(0.0).sqrt();
+        let (h0, h1) = cylinder.height_range();
+        let line = |p: Point3| Line::with_range(p, axis, ParamRange::new(h0, h1))
+            .map(|l| IntersectionResult::Curve(Box::new(l)));
+        if across.length() <= tolerance {
+            return Ok(vec![line(foot)?]);
+        }
+        return Ok(vec![line(foot + across)?, line(foot - across)?]);
+    }
+
+    let center = cylinder.origin() - axis * (offset / cos);
+    if 1.0 - cos.abs() < ANGULAR_TOLERANCE {
+        return Ok(vec![IntersectionResult::Curve(Box::new(CircularArc::circle(center, radius, axis)?))]);
+    }
+    // The minor axis is square to the cylinder's axis, the major axis its shadow in the plane
+    let minor = axis.cross(&normal).normalized();
+    let major = normal.cross(&minor);
+    let ellipse = EllipseArc::new(center, radius / cos.abs(), radius, major, normal, 0.0, std::f64::consts::TAU)?;
+    Ok(vec![IntersectionResult::Curve(Box::new(ellipse))])
+}
+
+/// Classify the conic a plane cuts from the double cone through `cone`
+///
+/// Compares the angle between plane and axis with the half angle; a plane
+/// within `tolerance` of the apex gives lines or a point.
+pub fn classify_cone_plane(cone: &ConicalSurface, plane: &PlanarSurface, tolerance: f64) -> ConicSection {
+    let frame = ConeFrame::new(cone, plane);
+    let tilt = frame.along_normal.abs().min(1.0).asin();
+    let steepness = tilt - cone.half_angle();
+    if frame.apex_offset.abs() <= tolerance {
+        return if steepness > ANGULAR_TOLERANCE { ConicSection::Point } else { ConicSection::Lines };
+    }
+    if std::f64::consts::FRAC_PI_2 - tilt < ANGULAR_TOLERANCE {
+        ConicSection::Circle
+    } else if steepness > ANGULAR_TOLERANCE {
+        ConicSection::Ellipse
+    } else if steepness < -ANGULAR_TOLERANCE {
+        ConicSection::Hyperbola
+    } else {
+        ConicSection::Parabola
+    }
+}
+
+/// Intersection of a cone with a plane
+///
+/// Only the cone's own nappe, on the base side of the apex, is cut.
+/// Circles and ellipses are exact and whole; a parabola or the hyperbola
+/// branch becomes an exact rational quadratic `NurbsCurve` ending at the
+/// cone's maximum height, which must then be finite. A plane through the
+/// apex gives lines along the height range or the apex point.
+pub fn cone_plane_intersection(
+    cone: &ConicalSurface,
+    plane: &PlanarSurface,
+    tolerance: f64,
+) -> GeomResult<Vec<IntersectionResult>> {
+    let frame = ConeFrame::new(cone, plane);
+    let (apex, axis) = (cone.apex(), cone.axis());
+    let (h0, h1) = cone.height_range();
+    let kind = classify_cone_plane(cone, plane, tolerance);
+    let curve = |c: Box<dyn Curve>| IntersectionResult::Curve(c);
+
+    match kind {
+        ConicSection::Point => Ok(if h0 <= 0.0 { vec![IntersectionResult::Point(apex)] } else { Vec::new() }),
+        ConicSection::Lines => {
+            let spread = frame.a.max(0.0).sqrt();
+            let slopes: &[f64] = if frame.a <= ANGULAR_TOLERANCE { &[0.0] } else { &[spread, -spread] };
+            slopes.iter().map(|&slope| {
+                let direction = (frame.major + frame.minor * slope).normalized();
+                let rise = direction.dot(&axis);
+                let line = Line::with_range(apex, direction, ParamRange::new(h0.max(0.0) / rise, h1 / rise))?;
+                Ok(curve(Box::new(line)))
+            }).collect()
+        }
+        ConicSection::Circle => {
+            let height = -frame.apex_offset * frame.along_normal.signum();
+            if height <= 0.0 {
+                return Ok(Vec::new());
+            }
+            let circle = CircularArc::circle(apex + axis * height, cone.radius_at_height(height), axis)?;
+            Ok(vec![curve(Box::new(circle))])
+        }
+        ConicSection::Ellipse => {
+            let (Some(near), Some(far)) = frame.roots() else { return Ok(Vec::new()) };
+            if frame.height(near) < 0.0 {
+                return Ok(Vec::new());
+            }
+            let middle = (near + far) / 2.0;
+            let ellipse = EllipseArc::new(
+                frame.point(middle, 0.0), (far - near) / 2.0, frame.half_width(middle).sqrt(),
+                frame.major, frame.normal, 0.0, std::f64::consts::TAU,
+            )?;
+            Ok(vec![curve(Box::new(ellipse))])
+        }
+        ConicSection::Parabola | ConicSection::Hyperbola => {
+            if !h1.is_finite() {
+                return Err(GeometryError::InvalidParameter(
+                    "Open conics need a cone with a finite height range".to_string()
+                ));
+            }
+            // The branch opening toward the base, from its vertex to the top
+            let vertex = match kind {
+                ConicSection::Parabola if frame.b > 0.0 => -frame.c / frame.b,
+                ConicSection::Hyperbola => match frame.roots() {
+                    (_, Some(far)) => far,
+                    _ => return Ok(Vec::new()),
+                },
+                _ => return Ok(Vec::new()),
+            };
+            let end = (h1 + frame.apex_offset * frame.along_normal) / frame.along_axis;
+            if end <= vertex + tolerance {
+                return Ok(Vec::new());
+            }
+            let half = frame.half_width(end).sqrt();
+            // The end tangents meet on the symmetry axis, and the vertex is the
+            // curve's midpoint, which fixes the middle weight
+            let apex_x = end - 2.0 * frame.half_width(end) / (2.0 * frame.a * end + frame.b);
+            let weight = (end - vertex) / (vertex - apex_x);
+            let nurbs = NurbsCurve::from_points_and_weights(
+                2,
+                &[frame.point(end, -half), frame.point(apex_x, 0.0), frame.point(end, half)],
+                &[1.0, weight, 1.0],
+                vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+            )?;
+            Ok(vec![curve(Box::new(nurbs))])
+        }
+    }
+}
+
+/// A plane's coordinates for cutting a cone
+///
+/// Points of the plane are `foot + major * x + minor * y`, with `foot` the
+/// apex's projection and `major` the axis's shadow in the plane. The cut is
+/// then `y^2 = a x^2 + b x + c`, symmetric about the major line.
+struct ConeFrame {
+    foot: Point3,
+    major: Vec3,
+    minor: Vec3,
+    normal: Vec3,
+    /// Signed distance of the apex above the plane
+    apex_offset: f64,
+    /// Cosine between the cone axis and the plane normal
+    along_normal: f64,
+    /// Cosine between the cone axis and the major direction
+    along_axis: f64,
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl ConeFrame {
+    fn new(cone: &ConicalSurface, plane: &PlanarSurface) -> Self {
+        let (axis, normal) = (cone.axis(), plane.normal());
+        let apex_offset = (cone.apex() - plane.origin()).dot(&normal);
+        let along_normal = axis.dot(&normal);
+        let shadow = axis - normal * along_normal;
+        let major = if shadow.is_zero(ANGULAR_TOLERANCE) { plane.u_axis() } else { shadow.normalized() };
+        let along_axis = major.dot(&axis);
+        // Points with (p - apex) . axis = cos(half angle) |p - apex|
+        let k = cone.half_angle().cos().powi(2);
+        Self {
+            foot: cone.apex() - normal * apex_offset,
+            major,
+            minor: normal.cross(&major),
+            normal,
+            apex_offset,
+            along_normal,
+            along_axis,
+            a: (along_axis * along_axis - k) / k,
+            b: -2.0 * apex_offset * along_normal * along_axis / k,
+            c: apex_offset * apex_offset * (along_normal * along_normal - k) / k,
+        }
+    }
+
+    fn point(&self, x: f64, y: f64) -> Point3 {
+        self.foot + self.major * x + self.minor * y
+    }
+
+    /// Height above the apex along the axis of the point at `x` on the major line
+    fn height(&self, x: f64) -> f64 {
+        x * self.along_axis - self.apex_offset * self.along_normal
+    }
+
+    /// Squared half width `y^2` of the cut at `x`
+    fn half_width(&self, x: f64) -> f64 {
+        (self.a * x + self.b) * x + self.c
+    }
+
+    /// Roots of the half width in increasing order
+    fn roots(&self) -> (Option<f64>, Option<f64>) {
+        let discriminant = self.b * self.b - 4.0 * self.a * self.c;
+        if discriminant < 0.0 || self.a == 0.0 {
+            return (None, None);
+        }
+        let (r1, r2) = ((-self.b - discriminant.sqrt()) / (2.0 * self.a), (-self.b + discriminant.sqrt()) / (2.0 * self.a));
+        (Some(r1.min(r2)), Some(r1.max(r2)))
+    }
 }
 
 /// Ray-surface intersection
@@ -118,7 +1268,8 @@ pub fn point_in_surface(point: &Point3, surface: &dyn Surface) -> bool {
 mod tests {
     use super::*;
     use crate::curve::{Line, CircularArc};
-    use crate::surface::{PlanarSurface, CylindricalSurface};
+    use crate::surface::{PlanarSurface, CylindricalSurface, SphericalSurface};
+    use crate::CurveType;
 
     #[test]
     fn test_line_line_intersection() {
@@ -142,6 +1293,87 @@ mod tests {
         assert!(!intersections.is_empty());
     }
 
+    #[test]
+    fn test_crossing_segments() {
+        let a = Line::segment(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 2.0, 0.0)).unwrap();
+        let b = Line::segment(Point3::new(0.0, 2.0, 0.0), Point3::new(2.0, 0.0, 0.0)).unwrap();
+
+        let points = curve_curve_intersection(&a, &b, 1e-9).unwrap();
+        assert_eq!(points.len(), 1);
+        assert!(points[0].distance_to(&Point3::new(1.0, 1.0, 0.0)) < 1e-9);
+
+        // Segments whose carrier lines cross outside both segments
+        let c = Line::segment(Point3::new(3.0, 0.0, 0.0), Point3::new(4.0, -1.0, 0.0)).unwrap();
+        assert!(curve_curve_intersection(&a, &c, 1e-9).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_line_circle_intersection() {
+        let circle = CircularArc::circle(Point3::ORIGIN, 2.0, Vec3::Z).unwrap();
+        let line = Line::segment(Point3::new(-5.0, 1.0, 0.0), Point3::new(5.0, 1.0, 0.0)).unwrap();
+
+        let points = curve_curve_intersection(&line, &circle, 1e-9).unwrap();
+        assert_eq!(points.len(), 2);
+        let x = 3.0_f64.sqrt();
+        for expected in [Point3::new(-x, 1.0, 0.0), Point3::new(x, 1.0, 0.0)] {
+            assert!(points.iter().any(|p| p.distance_to(&expected) < 1e-9));
+        }
+
+        // Argument order does not matter
+        assert_eq!(curve_curve_intersection(&circle, &line, 1e-9).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_uv_polylines_crossing_once() {
+        let a = [(0.0, 0.0), (1.0, 1.0), (2.0, 1.0)];
+        let b = [(0.0, 1.0), (1.0, 0.0)];
+
+        let points = curve_curve_intersection_2d(&a, &b);
+        assert_eq!(points.len(), 1);
+        assert!((points[0].0 - 0.5).abs() < 1e-12);
+        assert!((points[0].1 - 0.5).abs() < 1e-12);
+
+        let hits = polyline_intersections_2d(&a, &b);
+        assert!((hits[0].param_a - 0.5).abs() < 1e-12);
+        assert!((hits[0].param_b - 0.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_uv_polylines_touching() {
+        // The vertex of `a` touches `b` from below without crossing
+        let a = [(0.0, 0.0), (1.0, 1.0), (2.0, 0.0)];
+        let b = [(0.0, 1.0), (2.0, 1.0)];
+
+        let points = curve_curve_intersection_2d(&a, &b);
+        assert_eq!(points.len(), 1);
+        assert!((points[0].0 - 1.0).abs() < 1e-12);
+        assert!((points[0].1 - 1.0).abs() < 1e-12);
+
+        // Separated polylines do not intersect
+        let c = [(0.0, 2.0), (2.0, 2.0)];
+        assert!(curve_curve_intersection_2d(&a, &c).is_empty());
+    }
+
+    #[test]
+    fn test_subdivision_fallback() {
+        use crate::curve::PolylineCurve;
+
+        let zigzag = PolylineCurve::new(vec![
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 2.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+        ]).unwrap();
+        let flat = PolylineCurve::new(vec![
+            Point3::new(-1.0, 1.0, 0.0),
+            Point3::new(3.0, 1.0, 0.0),
+        ]).unwrap();
+
+        let points = curve_curve_intersection(&zigzag, &flat, 1e-8).unwrap();
+        assert_eq!(points.len(), 2);
+        assert!(points.iter().any(|p| p.distance_to(&Point3::new(0.5, 1.0, 0.0)) < 1e-8));
+        assert!(points.iter().any(|p| p.distance_to(&Point3::new(1.5, 1.0, 0.0)) < 1e-8));
+    }
+
     #[test]
     fn test_line_plane_intersection() {
         let line = Line::infinite(
@@ -167,4 +1399,159 @@ mod tests {
         let (_, _, _, p) = intersections[0];
         assert!(p.z().abs() < 1e-6);
     }
+
+    /// Plane through `origin` containing X, tilted `degrees` from horizontal about it
+    fn tilted_plane(origin: Point3, degrees: f64) -> PlanarSurface {
+        let t = degrees.to_radians();
+        PlanarSurface::new(origin, Vec3::X, Vec3::new(0.0, t.cos(), t.sin())).unwrap()
+    }
+
+    #[test]
+    fn test_cylinder_plane_ellipse() {
+        let cylinder = CylindricalSurface::new(Point3::ORIGIN, Vec3::Z, 1.0, Vec3::X).unwrap();
+        let plane = tilted_plane(Point3::new(0.0, 0.0, 2.0), 45.0);
+        let result = cylinder_plane_intersection(&cylinder, &plane, 1e-9).unwrap();
+        let IntersectionResult::Curve(curve) = &result[0] else { panic!("expected a curve") };
+        assert_eq!(curve.curve_type(), CurveType::EllipseArc);
+        assert!(curve.is_closed(1e-9));
+        for i in 0..16 {
+            let p = curve.evaluate(i as f64 / 16.0);
+            assert!(((p.x() * p.x() + p.y() * p.y()).sqrt() - 1.0).abs() < 1e-9);
+            assert!((p - plane.origin()).dot(&plane.normal()).abs() < 1e-9);
+        }
+        // The same curve survives cloning the result
+        assert!(matches!(result[0].clone(), IntersectionResult::Curve(_)));
+
+        let across = cylinder_plane_intersection(&cylinder, &tilted_plane(Point3::ORIGIN, 0.0), 1e-9).unwrap();
+        let IntersectionResult::Curve(circle) = &across[0] else { panic!("expected a curve") };
+        assert_eq!(circle.curve_type(), CurveType::CircularArc);
+        let along = PlanarSurface::new(Point3::new(0.5, 0.0, 0.0), Vec3::Y, Vec3::Z).unwrap();
+        assert_eq!(cylinder_plane_intersection(&cylinder, &along, 1e-9).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_cone_plane_conics() {
+        // A 30 degree cone opening upward from the origin, ten units tall
+        let cone = ConicalSurface::bounded(Point3::ORIGIN, Vec3::Z, 30f64.to_radians(), Vec3::X, 0.0, 10.0, 0.0, std::f64::consts::TAU).unwrap();
+        let on_cone = |p: Point3| (p.x().hypot(p.y()) - p.z() * 30f64.to_radians().tan()).abs() < 1e-9 && p.z() > -1e-9 && p.z() < 10.0 + 1e-9;
+        let cases = [
+            (0.0, ConicSection::Circle, CurveType::CircularArc),
+            (20.0, ConicSection::Ellipse, CurveType::EllipseArc),
+            (60.0, ConicSection::Parabola, CurveType::NurbsCurve),
+            (80.0, ConicSection::Hyperbola, CurveType::NurbsCurve),
+            (90.0, ConicSection::Hyperbola, CurveType::NurbsCurve),
+        ];
+        // Planes through a point off the axis, so even the vertical one misses the apex
+        for (degrees, kind, curve_type) in cases {
+            let plane = tilted_plane(Point3::new(0.0, 1.0, 5.0), degrees);
+            assert_eq!(classify_cone_plane(&cone, &plane, 1e-9), kind, "{} degrees", degrees);
+            let result = cone_plane_intersection(&cone, &plane, 1e-9).unwrap();
+            assert_eq!(result.len(), 1);
+            let IntersectionResult::Curve(curve) = &result[0] else { panic!("expected a curve") };
+            assert_eq!(curve.curve_type(), curve_type);
+            for i in 0..=16 {
+                let p = curve.evaluate(i as f64 / 16.0);
+                assert!(on_cone(p), "{} degrees: {:?} is off the cone", degrees, p);
+                assert!((p - plane.origin()).dot(&plane.normal()).abs() < 1e-9);
+            }
+        }
+
+        // Through the apex: two generators, or only the apex itself
+        let steep = tilted_plane(Point3::ORIGIN, 80.0);
+        assert_eq!(classify_cone_plane(&cone, &steep, 1e-9), ConicSection::Lines);
+        let lines = cone_plane_intersection(&cone, &steep, 1e-9).unwrap();
+        assert_eq!(lines.len(), 2);
+        for line in &lines {
+            let IntersectionResult::Curve(line) = line else { panic!("expected a line") };
+            assert!(on_cone(line.end_point()));
+        }
+        let flat = tilted_plane(Point3::ORIGIN, 10.0);
+        assert_eq!(classify_cone_plane(&cone, &flat, 1e-9), ConicSection::Point);
+        assert!(matches!(cone_plane_intersection(&cone, &flat, 1e-9).unwrap()[..], [IntersectionResult::Point(_)]));
+    }
+
+    #[test]
+    fn test_nurbs_surfaces_meet_in_circle() {
+        use crate::nurbs::NurbsSurface;
+        use nova_math::Point4;
+
+        // Bi-quadratic Bezier patch of z = x^2 + y^2 - 1 over [-a, a]^2
+        let a = 1.5;
+        let xs = [-a, 0.0, a];
+        let f = [a * a, -a * a, a * a];
+        let knots = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let paraboloid_points = (0..3)
+            .map(|i| (0..3).map(|j| Point4::new(xs[i], xs[j], f[i] + f[j] - 1.0, 1.0)).collect())
+            .collect();
+        let paraboloid = NurbsSurface::new(2, 2, paraboloid_points, knots.clone(), knots.clone()).unwrap();
+        // Bilinear patch of the plane z = 0
+        let plane_points = [-2.0, 2.0].iter()
+            .map(|&x| [-2.0, 2.0].iter().map(|&y| Point4::new(x, y, 0.0, 1.0)).collect())
+            .collect();
+        let flat = vec![0.0, 0.0, 1.0, 1.0];
+        let plane = NurbsSurface::new(1, 1, plane_points, flat.clone(), flat).unwrap();
+
+        let loop_curve = march_surface_intersection(&paraboloid, &plane, &Point3::new(1.0, 0.05, 0.0), &MarchingOptions::default()).unwrap();
+        let points = loop_curve.points();
+        assert_eq!(points.first(), points.last());
+        let length: f64 = points.windows(2).map(|w| w[0].distance_to(&w[1])).sum();
+        assert!((length - 2.0 * std::f64::consts::PI).abs() < 0.02, "loop length {}", length);
+        for p in points {
+            assert!(p.z().abs() < 1e-6);
+            assert!((p.x() * p.x() + p.y() * p.y() - 1.0).abs() < 1e-6);
+        }
+
+        let curves = surface_surface_intersection(&paraboloid, &plane, 1e-7).unwrap();
+        assert_eq!(curves.len(), 1);
+        assert!(curves[0].is_closed(1e-9));
+    }
+
+    #[test]
+    fn test_seeds_on_both_cylinder_branches() {
+        use std::f64::consts::TAU;
+
+        // A thin cylinder along X passes through a wide one along Z, leaving
+        // one loop where it enters and one where it leaves
+        let wide = CylindricalSurface::bounded(Point3::ORIGIN, Vec3::Z, 1.0, Vec3::X, -2.0, 2.0, 0.0, TAU).unwrap();
+        let thin = CylindricalSurface::bounded(Point3::new(-2.0, 0.0, 0.0), Vec3::X, 0.5, Vec3::Y, 0.0, 4.0, 0.0, TAU).unwrap();
+
+        let seeds = find_intersection_seeds(&wide, &thin, 1e-9);
+        for p in &seeds {
+            assert!((p.x() * p.x() + p.y() * p.y() - 1.0).abs() < 1e-6);
+            assert!((p.y() * p.y() + p.z() * p.z() - 0.25).abs() < 1e-6);
+        }
+        assert!(seeds.iter().any(|p| p.x() > 0.0));
+        assert!(seeds.iter().any(|p| p.x() < 0.0));
+
+        let curves = surface_surface_intersection(&wide, &thin, 1e-9).unwrap();
+        assert_eq!(curves.len(), 2);
+        assert!(curves.iter().all(|c| c.is_closed(1e-9)));
+    }
+
+    #[test]
+    fn test_tangent_plane_touches_cylinder_along_one_line() {
+        use std::f64::consts::TAU;
+
+        // The plane z = 1 rests on top of a unit cylinder along X, and just
+        // inside tolerance of it when nudged either way
+        let cylinder = CylindricalSurface::bounded(Point3::ORIGIN, Vec3::X, 1.0, Vec3::Y, 0.0, 4.0, 0.0, TAU).unwrap();
+        for nudge in [0.0, 5e-10, -5e-10] {
+            let plane = PlanarSurface::bounded(Point3::new(-1.0, -2.0, 1.0 + nudge), Vec3::X, Vec3::Y, 0.0, 6.0, 0.0, 4.0).unwrap();
+            let curves = surface_surface_intersection(&cylinder, &plane, 1e-9).unwrap();
+            assert_eq!(curves.len(), 1);
+            let line = &curves[0];
+            assert!(!line.is_closed(1e-9));
+            let ends = [line.start_point(), line.end_point()];
+            assert!(ends.iter().any(|p| p.x().abs() < 1e-9) && ends.iter().any(|p| (p.x() - 4.0).abs() < 1e-9));
+            for i in 0..=8 {
+                let p = line.evaluate(i as f64 / 8.0);
+                assert!(p.y().abs() < 1e-6 && (p.z() - 1.0).abs() < 1e-9, "{:?}", p);
+            }
+        }
+
+        // A sphere touches the plane at a single point, which is no curve
+        let sphere = SphericalSurface::new(Point3::ORIGIN, 1.0, Vec3::Z, Vec3::X).unwrap();
+        let plane = PlanarSurface::bounded(Point3::new(-2.0, -2.0, 1.0), Vec3::X, Vec3::Y, 0.0, 4.0, 0.0, 4.0).unwrap();
+        assert!(surface_surface_intersection(&sphere, &plane, 1e-9).unwrap().is_empty());
+    }
 }
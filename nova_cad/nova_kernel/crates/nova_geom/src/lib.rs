@@ -14,12 +14,17 @@ pub mod curve;
 pub mod surface;
 pub mod nurbs;
 pub mod intersection;
+mod unbounded;
 
-pub use curve::{Curve, CurveType, Line, CircularArc, EllipseArc};
+pub use curve::{Curve, CurveType, Line, CircularArc, EllipseArc, PolylineCurve, offset_curve_planar};
+pub use intersection::{ConicSection, MarchingOptions, classify_cone_plane, cone_plane_intersection, cylinder_plane_intersection,
+                       find_intersection_seeds,
+                       march_surface_intersection};
 pub use surface::{Surface, SurfaceType, PlanarSurface, CylindricalSurface, 
-                  ConicalSurface, SphericalSurface, ToroidalSurface};
+                  ConicalSurface, SphericalSurface, ToroidalSurface, ExtrusionSurface, OffsetSurface};
 
 use nova_math::{Point3, Vec3};
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 /// Geometry-related errors
@@ -103,7 +108,7 @@ impl Clone for IntersectionResult {
     fn clone(&self) -> Self {
         match self {
             IntersectionResult::Point(p) => IntersectionResult::Point(*p),
-            IntersectionResult::Curve(_) => IntersectionResult::None, // Cannot clone dyn Curve
+            IntersectionResult::Curve(c) => IntersectionResult::Curve(c.clone_box()),
             IntersectionResult::None => IntersectionResult::None,
         }
     }
@@ -134,11 +139,13 @@ pub enum Tessellation {
 }
 
 /// Parameter range for curves
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ParamRange {
     /// Start parameter
+    #[serde(with = "crate::unbounded")]
     pub start: f64,
     /// End parameter
+    #[serde(with = "crate::unbounded")]
     pub end: f64,
 }
 
@@ -180,7 +187,7 @@ impl ParamRange {
 }
 
 /// UV parameter range for surfaces
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct UVRange {
     /// U parameter range
     pub u: ParamRange,
@@ -1,11 +1,18 @@
 //! NURBS (Non-Uniform Rational B-Spline) curves and surfaces
 
 use crate::{GeomResult, GeometryError, ParamRange, UVRange, Curve, CurveEvaluation, 
-            Surface, SurfaceEvaluation, Tessellation, Tessellatable, CurveType, SurfaceType};
-use nova_math::{Point3, Vec3, Point4, Vec4, Transform3};
+            Surface, SurfaceEvaluation, Tessellation, Tessellatable, CurveType, SurfaceType,
+            PolylineCurve};
+use nova_math::{Point3, Vec3, Point4, Vec4, Transform3, BoundingBox3};
+use nova_math::integrate::gauss_legendre;
+use nova_math::solve::{newton, NewtonOptions};
+use serde::{Deserialize, Serialize};
+
+/// Gauss-Legendre points per knot span for curve length and surface area
+const QUADRATURE_ORDER: usize = 12;
 
 /// NURBS curve
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NurbsCurve {
     /// Degree
     degree: u32,
@@ -110,6 +117,16 @@ impl NurbsCurve {
         self.control_points.get(i).map(|p| p.w())
     }
 
+    /// Get the knot vector
+    pub fn knots(&self) -> &[f64] {
+        &self.knots
+    }
+
+    /// Check if the curve is periodic
+    pub fn is_periodic(&self) -> bool {
+        self.periodic
+    }
+
     /// Check if the curve is rational (has varying weights)
     pub fn is_rational(&self) -> bool {
         let first_weight = self.control_points[0].w();
@@ -125,6 +142,10 @@ impl NurbsCurve {
         if t >= self.knots[n + 1] {
             return n;
         }
+        // Parameters before the start fall in the first span
+        if t < self.knots[p] {
+            return p;
+        }
         
         // Binary search
         let mut low = p;
@@ -204,6 +225,16 @@ impl NurbsCurve {
         self.degree += 1;
         Ok(())
     }
+
+    /// Approximate the curve by a polyline within the given chord tolerance
+    pub fn to_polyline_curve(&self, tolerance: f64) -> GeomResult<PolylineCurve> {
+        match self.tessellate(tolerance) {
+            Tessellation::Polyline(points) => PolylineCurve::new(points),
+            Tessellation::TriangleMesh { .. } => Err(GeometryError::Numerical(
+                "Curve tessellation produced a mesh".to_string()
+            )),
+        }
+    }
 }
 
 impl Curve for NurbsCurve {
@@ -255,20 +286,24 @@ impl Curve for NurbsCurve {
         self.param_range
     }
 
+    fn is_closed(&self, tol: f64) -> bool {
+        self.periodic || self.start_point().distance_to(&self.end_point()) <= tol
+    }
+
     fn arc_length(&self, t: f64) -> f64 {
-        // Numerical integration
+        // The speed is smooth within a knot span, so integrate span by span
         let start = self.param_range.start;
-        let n = 100;
-        let dt = (t - start) / n as f64;
+        let end = t.clamp(start, self.param_range.end);
+        let speed = |s: f64| self.derivative(s, 1).length();
+        let mut breaks: Vec<f64> = self.knots.iter().copied().filter(|&k| k > start && k < end).collect();
+        breaks.dedup();
+        breaks.push(end);
+        let mut a = start;
         let mut length = 0.0;
-        
-        let mut prev = self.evaluate(start);
-        for i in 1..=n {
-            let curr = self.evaluate(start + i as f64 * dt);
-            length += curr.distance_to(&prev);
-            prev = curr;
+        for b in breaks {
+            length += gauss_legendre(speed, a, b, QUADRATURE_ORDER);
+            a = b;
         }
-        
         length
     }
 
@@ -322,6 +357,10 @@ impl Curve for NurbsCurve {
     fn clone_box(&self) -> Box<dyn Curve> {
         Box::new(self.clone())
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 impl Tessellatable for NurbsCurve {
@@ -369,7 +408,7 @@ impl Tessellatable for NurbsCurve {
 }
 
 /// NURBS surface
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct NurbsSurface {
     /// Degree in U direction
     degree_u: u32,
@@ -442,6 +481,35 @@ impl NurbsSurface {
         (self.control_points.len(), self.control_points[0].len())
     }
 
+    /// Area of the untrimmed surface over its whole uv range
+    ///
+    /// Integrates |S_u x S_v| with a Gauss-Legendre rule in each direction,
+    /// patch by knot span patch.
+    pub fn area(&self) -> f64 {
+        let spans = |knots: &[f64], start: f64, end: f64| {
+            let mut breaks: Vec<f64> = knots.iter().copied().filter(|&k| k > start && k < end).collect();
+            breaks.dedup();
+            breaks.insert(0, start);
+            breaks.push(end);
+            breaks
+        };
+        let range = self.uv_range;
+        let us = spans(&self.knots_u, range.u.start, range.u.end);
+        let vs = spans(&self.knots_v, range.v.start, range.v.end);
+        let mut area = 0.0;
+        for u in us.windows(2) {
+            for v in vs.windows(2) {
+                area += gauss_legendre(|s| {
+                    gauss_legendre(|t| {
+                        let (du, dv) = self.derivatives(s, t);
+                        du.cross(&dv).length()
+                    }, v[0], v[1], QUADRATURE_ORDER)
+                }, u[0], u[1], QUADRATURE_ORDER);
+            }
+        }
+        area
+    }
+
     /// Evaluate at (u, v)
     pub fn evaluate_homogeneous(&self, u: f64, v: f64) -> Point4 {
         // Evaluate in U direction first
@@ -489,6 +557,86 @@ impl NurbsSurface {
         Point4::new(result.x(), result.y(), result.z(), result.w())
     }
 
+    /// Insert a U knot `times` times without changing the surface (Boehm)
+    pub fn insert_knot_u(&mut self, u: f64, times: u32) -> GeomResult<()> {
+        let columns = self.columns();
+        let mut knots = self.knots_u.clone();
+        let mut new_columns = Vec::with_capacity(columns.len());
+        for column in &columns {
+            let (k, points) = insert_knot_1d(self.degree_u, &self.knots_u, column, u, times)?;
+            knots = k;
+            new_columns.push(points);
+        }
+        self.knots_u = knots;
+        self.set_columns(new_columns);
+        Ok(())
+    }
+
+    /// Insert a V knot `times` times without changing the surface (Boehm)
+    pub fn insert_knot_v(&mut self, v: f64, times: u32) -> GeomResult<()> {
+        let mut knots = self.knots_v.clone();
+        let mut rows = Vec::with_capacity(self.control_points.len());
+        for row in &self.control_points {
+            let (k, points) = insert_knot_1d(self.degree_v, &self.knots_v, row, v, times)?;
+            knots = k;
+            rows.push(points);
+        }
+        self.knots_v = knots;
+        self.control_points = rows;
+        Ok(())
+    }
+
+    /// Raise the U degree by one without changing the surface
+    ///
+    /// Interior knots end up with full multiplicity; no knot removal is done.
+    pub fn elevate_degree_u(&mut self) -> GeomResult<()> {
+        let columns = self.columns();
+        let mut knots = self.knots_u.clone();
+        let mut new_columns = Vec::with_capacity(columns.len());
+        for column in &columns {
+            let (k, points) = elevate_degree_1d(self.degree_u, &self.knots_u, column)?;
+            knots = k;
+            new_columns.push(points);
+        }
+        self.degree_u += 1;
+        self.knots_u = knots;
+        self.set_columns(new_columns);
+        Ok(())
+    }
+
+    /// Raise the V degree by one without changing the surface
+    ///
+    /// Interior knots end up with full multiplicity; no knot removal is done.
+    pub fn elevate_degree_v(&mut self) -> GeomResult<()> {
+        let mut knots = self.knots_v.clone();
+        let mut rows = Vec::with_capacity(self.control_points.len());
+        for row in &self.control_points {
+            let (k, points) = elevate_degree_1d(self.degree_v, &self.knots_v, row)?;
+            knots = k;
+            rows.push(points);
+        }
+        self.degree_v += 1;
+        self.knots_v = knots;
+        self.control_points = rows;
+        Ok(())
+    }
+
+    /// Control points grouped by V index, each running along U
+    fn columns(&self) -> Vec<Vec<Point4>> {
+        let nv = self.control_points[0].len();
+        (0..nv)
+            .map(|j| self.control_points.iter().map(|row| row[j]).collect())
+            .collect()
+    }
+
+    /// Rebuild the control grid from columns running along U
+    fn set_columns(&mut self, columns: Vec<Vec<Point4>>) {
+        let nu = columns[0].len();
+        self.control_points = (0..nu)
+            .map(|i| columns.iter().map(|column| column[i]).collect())
+            .collect();
+    }
+
     fn find_span_u(&self, u: f64) -> usize {
         let n = self.control_points.len() - 1;
         let p = self.degree_u as usize;
@@ -496,6 +644,9 @@ impl NurbsSurface {
         if u >= self.knots_u[n + 1] {
             return n;
         }
+        if u < self.knots_u[p] {
+            return p;
+        }
         
         let mut low = p;
         let mut high = n + 1;
@@ -520,6 +671,9 @@ impl NurbsSurface {
         if v >= self.knots_v[n + 1] {
             return n;
         }
+        if v < self.knots_v[p] {
+            return p;
+        }
         
         let mut low = p;
         let mut high = n + 1;
@@ -586,6 +740,107 @@ impl NurbsSurface {
     }
 }
 
+/// Linear blend of homogeneous points, `a * (1 - t) + b * t`
+fn lerp4(a: &Point4, b: &Point4, t: f64) -> Point4 {
+    let s = 1.0 - t;
+    Point4::new(
+        a.x() * s + b.x() * t,
+        a.y() * s + b.y() * t,
+        a.z() * s + b.z() * t,
+        a.w() * s + b.w() * t,
+    )
+}
+
+/// Insert knot `t` into a B-spline `times` times (Boehm's algorithm)
+fn insert_knot_1d(
+    degree: u32,
+    knots: &[f64],
+    points: &[Point4],
+    t: f64,
+    times: u32,
+) -> GeomResult<(Vec<f64>, Vec<Point4>)> {
+    let p = degree as usize;
+    let n = points.len();
+    if t < knots[p] || t > knots[n] {
+        return Err(GeometryError::InvalidParameter(
+            "Knot outside parameter range".to_string()
+        ));
+    }
+    let multiplicity = knots.iter().filter(|&&k| k == t).count();
+    if multiplicity + times as usize > p {
+        return Err(GeometryError::InvalidParameter(
+            "Knot multiplicity would exceed degree".to_string()
+        ));
+    }
+
+    let mut knots = knots.to_vec();
+    let mut points = points.to_vec();
+    for _ in 0..times {
+        // Span k with knots[k] <= t < knots[k + 1]
+        let k = knots.iter().rposition(|&knot| knot <= t).unwrap().min(points.len() - 1);
+        let mut new_points = Vec::with_capacity(points.len() + 1);
+        new_points.extend_from_slice(&points[..=k - p]);
+        for i in k - p + 1..=k {
+            let alpha = (t - knots[i]) / (knots[i + p] - knots[i]);
+            new_points.push(lerp4(&points[i - 1], &points[i], alpha));
+        }
+        new_points.extend_from_slice(&points[k..]);
+        knots.insert(k + 1, t);
+        points = new_points;
+    }
+    Ok((knots, points))
+}
+
+/// Raise the degree of a clamped B-spline by one
+///
+/// The spline is split into Bezier segments by knot insertion, each segment
+/// is elevated, and the segments are joined with full-multiplicity knots.
+fn elevate_degree_1d(
+    degree: u32,
+    knots: &[f64],
+    points: &[Point4],
+) -> GeomResult<(Vec<f64>, Vec<Point4>)> {
+    let p = degree as usize;
+    let n = points.len();
+    let first = knots[0];
+    let last = knots[knots.len() - 1];
+    if knots[..=p].iter().any(|&k| k != first) || knots[n..].iter().any(|&k| k != last) {
+        return Err(GeometryError::InvalidParameter(
+            "Degree elevation requires a clamped knot vector".to_string()
+        ));
+    }
+
+    // Distinct interior knots, each raised to multiplicity p
+    let mut interior: Vec<f64> = knots[p + 1..n].to_vec();
+    interior.dedup();
+    let mut bezier_knots = knots.to_vec();
+    let mut bezier_points = points.to_vec();
+    for &u in &interior {
+        let multiplicity = bezier_knots.iter().filter(|&&k| k == u).count();
+        if multiplicity < p {
+            let (k, pts) = insert_knot_1d(degree, &bezier_knots, &bezier_points, u, (p - multiplicity) as u32)?;
+            bezier_knots = k;
+            bezier_points = pts;
+        }
+    }
+
+    let mut new_points = vec![bezier_points[0]];
+    for segment in bezier_points.windows(p + 1).step_by(p) {
+        for i in 1..=p + 1 {
+            let alpha = i as f64 / (p + 1) as f64;
+            let next = if i <= p { segment[i] } else { segment[p] };
+            new_points.push(lerp4(&next, &segment[i - 1], alpha));
+        }
+    }
+
+    let mut new_knots = vec![first; p + 2];
+    for &u in &interior {
+        new_knots.extend(std::iter::repeat(u).take(p + 1));
+    }
+    new_knots.extend(std::iter::repeat(last).take(p + 2));
+    Ok((new_knots, new_points))
+}
+
 impl Surface for NurbsSurface {
     fn evaluate(&self, u: f64, v: f64) -> Point3 {
         let h = self.evaluate_homogeneous(u, v);
@@ -648,6 +903,15 @@ impl Surface for NurbsSurface {
         self.uv_range
     }
 
+    /// Box around the control points, which hold the whole surface and so
+    /// any part of it when the weights are positive
+    fn bounding_box_over(&self, _range: &UVRange) -> BoundingBox3 {
+        self.control_points.iter().flatten().fold(BoundingBox3::empty(), |mut bbox, cp| {
+            bbox.expand(&cp.to_point3());
+            bbox
+        })
+    }
+
     fn u_isocurve(&self, u: f64) -> Option<Box<dyn Curve>> {
         // Extract control points at constant u
         // Simplified - would need proper curve extraction
@@ -659,7 +923,7 @@ impl Surface for NurbsSurface {
     }
 
     fn closest_point(&self, point: &Point3) -> GeomResult<(f64, f64, Point3, f64)> {
-        // Grid search
+        // Seed from a grid, then settle on the foot by Gauss-Newton
         let n = 20;
         let mut best_u = 0.5;
         let mut best_v = 0.5;
@@ -679,8 +943,27 @@ impl Surface for NurbsSurface {
             }
         }
         
-        let closest = self.evaluate(best_u, best_v);
-        Ok((best_u, best_v, closest, best_dist))
+        let range = self.uv_range;
+        let options = NewtonOptions {
+            bounds: Some(([range.u.start, range.v.start], [range.u.end, range.v.end])),
+            step_tolerance: 1e-14,
+            ..NewtonOptions::default()
+        };
+        let foot = newton(
+            |&[u, v]| {
+                let d = self.evaluate(u, v) - *point;
+                [d.x(), d.y(), d.z()]
+            },
+            |&[u, v]| {
+                let (du, dv) = self.derivatives(u, v);
+                [[du.x(), dv.x()], [du.y(), dv.y()], [du.z(), dv.z()]]
+            },
+            [best_u, best_v],
+            &options,
+        );
+        let [u, v] = foot.x;
+        let closest = self.evaluate(u, v);
+        Ok((u, v, closest, foot.residual))
     }
 
     fn transform(&mut self, transform: &Transform3) {
@@ -701,12 +984,83 @@ impl Surface for NurbsSurface {
     fn clone_box(&self) -> Box<dyn Surface> {
         Box::new(self.clone())
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Quadratic-by-cubic rational patch with an interior U knot
+    fn sample_surface() -> NurbsSurface {
+        let control_points: Vec<Vec<Point4>> = (0..4)
+            .map(|i| {
+                (0..4)
+                    .map(|j| {
+                        let w = 1.0 + 0.25 * ((i + j) % 3) as f64;
+                        let z = ((i * 3 + j * 5) % 4) as f64 * 0.5;
+                        Point4::new(i as f64 * w, j as f64 * w, z * w, w)
+                    })
+                    .collect()
+            })
+            .collect();
+        let knots_u = vec![0.0, 0.0, 0.0, 0.4, 1.0, 1.0, 1.0];
+        let knots_v = vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0];
+        NurbsSurface::new(2, 3, control_points, knots_u, knots_v).unwrap()
+    }
+
+    fn assert_same_surface(a: &NurbsSurface, b: &NurbsSurface) {
+        for i in 0..=8 {
+            for j in 0..=8 {
+                let (u, v) = (i as f64 / 8.0, j as f64 / 8.0);
+                let dist = a.evaluate(u, v).distance_to(&b.evaluate(u, v));
+                assert!(dist < 1e-10, "surfaces differ by {} at ({}, {})", dist, u, v);
+            }
+        }
+    }
+
+    #[test]
+    fn test_nurbs_surface_insert_knot() {
+        let original = sample_surface();
+
+        let mut surface = original.clone();
+        surface.insert_knot_u(0.7, 2).unwrap();
+        assert_eq!(surface.num_control_points(), (6, 4));
+        assert_same_surface(&original, &surface);
+
+        surface.insert_knot_v(0.3, 1).unwrap();
+        assert_eq!(surface.num_control_points(), (6, 5));
+        assert_same_surface(&original, &surface);
+
+        assert!(surface.insert_knot_u(0.7, 1).is_err());
+    }
+
+    #[test]
+    fn test_nurbs_surface_elevate_degree() {
+        let original = sample_surface();
+
+        let mut surface = original.clone();
+        surface.elevate_degree_u().unwrap();
+        assert_eq!(surface.degrees(), (3, 3));
+        assert_same_surface(&original, &surface);
+
+        surface.elevate_degree_v().unwrap();
+        assert_eq!(surface.degrees(), (3, 4));
+        assert_eq!(surface.num_control_points(), (7, 5));
+        assert_same_surface(&original, &surface);
+    }
+
+    #[test]
+    fn test_nurbs_surface_derivatives_at_corner() {
+        let surface = sample_surface();
+        let (du, dv) = surface.derivatives(0.0, 0.0);
+        assert!(du.length().is_finite() && dv.length().is_finite());
+        assert!(surface.evaluate(-1e-6, -1e-6).distance_to(&surface.evaluate(0.0, 0.0)) < 1e-4);
+    }
+
     #[test]
     fn test_nurbs_curve_new() {
         let control_points = vec![
@@ -721,6 +1075,27 @@ mod tests {
         assert_eq!(curve.num_control_points(), 3);
     }
 
+    #[test]
+    fn test_nurbs_curve_to_polyline_curve() {
+        let control_points = vec![
+            Point4::new(0.0, 0.0, 0.0, 1.0),
+            Point4::new(1.0, 2.0, 0.0, 1.0),
+            Point4::new(2.0, 0.0, 0.0, 1.0),
+        ];
+        let knots = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let curve = NurbsCurve::new(2, control_points, knots).unwrap();
+
+        let poly = curve.to_polyline_curve(1e-3).unwrap();
+        assert!(poly.num_segments() > 2);
+        assert!(poly.start_point().distance_to(&curve.start_point()) < 1e-10);
+        assert!(poly.end_point().distance_to(&curve.end_point()) < 1e-10);
+
+        // Every polyline vertex lies on the parabola y = 2x - x^2
+        for p in poly.points() {
+            assert!((p.y() - (2.0 * p.x() - p.x() * p.x())).abs() < 1e-9);
+        }
+    }
+
     #[test]
     fn test_nurbs_curve_evaluate() {
         // Simple quadratic Bezier curve (special case of NURBS)
@@ -768,4 +1143,44 @@ mod tests {
             assert!((dist - 1.0).abs() < 0.1); // Approximate circle
         }
     }
+
+    #[test]
+    fn test_nurbs_quarter_circle_length_and_cylinder_area() {
+        use std::f64::consts::{FRAC_1_SQRT_2, FRAC_PI_2};
+        let arc = [(1.0, 0.0, 1.0), (1.0, 1.0, FRAC_1_SQRT_2), (0.0, 1.0, 1.0)];
+        let curve = NurbsCurve::new(
+            2,
+            arc.iter().map(|&(x, y, w)| Point4::new(x * w, y * w, 0.0, w)).collect(),
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+        ).unwrap();
+        assert!((curve.arc_length(1.0) - FRAC_PI_2).abs() < 1e-8);
+        assert!((curve.arc_length(0.5) - FRAC_PI_2 / 2.0).abs() < 1e-8);
+
+        assert!((quarter_cylinder().area() - 3.0 * 2.0 * FRAC_PI_2).abs() < 1e-6);
+    }
+
+    /// Quarter of a cylinder of radius 2 and height 3 about the z axis
+    fn quarter_cylinder() -> NurbsSurface {
+        let w = std::f64::consts::FRAC_1_SQRT_2;
+        let control_points = [(1.0, 0.0, 1.0), (1.0, 1.0, w), (0.0, 1.0, 1.0)].iter()
+            .map(|&(x, y, w)| (0..2).map(|k| Point4::new(2.0 * x * w, 2.0 * y * w, 3.0 * k as f64 * w, w)).collect())
+            .collect();
+        let knots_u = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        NurbsSurface::new(2, 1, control_points, knots_u, vec![0.0, 0.0, 1.0, 1.0]).unwrap()
+    }
+
+    #[test]
+    fn test_nurbs_surface_closest_point_converges() {
+        let surface = quarter_cylinder();
+        let (u, v, foot, dist) = surface.closest_point(&Point3::new(2.0, 2.0, 1.1)).unwrap();
+        let expected = Point3::new(2f64.sqrt(), 2f64.sqrt(), 1.1);
+        assert!(foot.distance_to(&expected) < 1e-9, "{:?}", foot);
+        assert!((dist - (8f64.sqrt() - 2.0)).abs() < 1e-9);
+        assert!(surface.evaluate(u, v).distance_to(&foot) < 1e-15);
+
+        // Beyond the top edge the foot is held on the boundary
+        let (_, v, foot, _) = surface.closest_point(&Point3::new(0.0, 3.0, 4.0)).unwrap();
+        assert_eq!(v, 1.0);
+        assert!(foot.distance_to(&Point3::new(0.0, 2.0, 3.0)) < 1e-9, "{:?}", foot);
+    }
 }
@@ -2,7 +2,14 @@
 
 use crate::{GeomResult, GeometryError, UVRange, SurfaceEvaluation, Tessellation, Tessellatable,
             curve::{Curve, Line, CircularArc}};
-use nova_math::{Point3, Vec3, Transform3, Plane};
+use nova_math::{Point3, Vec3, Transform3, Plane, BoundingBox3};
+use serde::{Deserialize, Serialize};
+
+/// Grid intervals per direction when boxing a surface without a closed form box
+const SURFACE_BOX_SAMPLES: usize = 16;
+
+/// Curve spans sampled when boxing an extrusion
+const EXTRUSION_BOX_SAMPLES: usize = 64;
 
 /// Trait for all surface types
 pub trait Surface: Send + Sync {
@@ -36,6 +43,32 @@ pub trait Surface: Send + Sync {
     /// Get the UV parameter range
     fn uv_range(&self) -> UVRange;
 
+    /// Box around the surface over `range`, bulges between its corners included
+    ///
+    /// The default boxes a grid of samples and pads it by twice the most any
+    /// cell's center strays from the average of its corners; surfaces with a
+    /// closed form box override it.
+    fn bounding_box_over(&self, range: &UVRange) -> BoundingBox3 {
+        let n = SURFACE_BOX_SAMPLES as f64;
+        let at = |i: f64, j: f64| self.evaluate(range.u.start + range.u.length() * i / n, range.v.start + range.v.length() * j / n);
+        let grid: Vec<Vec<Point3>> = (0..=SURFACE_BOX_SAMPLES)
+            .map(|i| (0..=SURFACE_BOX_SAMPLES).map(|j| at(i as f64, j as f64)).collect())
+            .collect();
+        let mut bbox = BoundingBox3::empty();
+        let mut sagitta: f64 = 0.0;
+        for (i, row) in grid.iter().enumerate() {
+            for (j, p) in row.iter().enumerate() {
+                bbox.expand(p);
+                if i < SURFACE_BOX_SAMPLES && j < SURFACE_BOX_SAMPLES {
+                    let average = p.midpoint(&grid[i + 1][j]).midpoint(&grid[i][j + 1].midpoint(&grid[i + 1][j + 1]));
+                    sagitta = sagitta.max(at(i as f64 + 0.5, j as f64 + 0.5).distance_to(&average));
+                }
+            }
+        }
+        bbox.expand_by_margin(2.0 * sagitta);
+        bbox
+    }
+
     /// Check if UV is within valid range
     fn contains_uv(&self, u: f64, v: f64) -> bool {
         self.uv_range().contains(u, v)
@@ -53,11 +86,26 @@ pub trait Surface: Send + Sync {
     /// Transform the surface
     fn transform(&mut self, transform: &Transform3);
 
+    /// Uniformly scale about the world origin
+    fn scale(&mut self, factor: f64) {
+        self.transform(&Transform3::from_scale(factor));
+    }
+
     /// Get surface type
     fn surface_type(&self) -> SurfaceType;
 
     /// Clone into a boxed surface
     fn clone_box(&self) -> Box<dyn Surface>;
+
+    /// Transformed copy, leaving this surface as it is
+    fn transformed(&self, transform: &Transform3) -> Box<dyn Surface> {
+        let mut surface = self.clone_box();
+        surface.transform(transform);
+        surface
+    }
+
+    /// Access the concrete surface for downcasting
+    fn as_any(&self) -> &dyn std::any::Any;
 }
 
 /// Surface type enumeration
@@ -84,7 +132,7 @@ pub enum SurfaceType {
 }
 
 /// Planar surface
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct PlanarSurface {
     /// Origin point
     origin: Point3,
@@ -221,6 +269,13 @@ impl Surface for PlanarSurface {
         (0.0, 0.0) // Planes have zero curvature
     }
 
+    fn bounding_box_over(&self, range: &UVRange) -> BoundingBox3 {
+        let mut bbox = BoundingBox3::from_points(&self.evaluate(range.u.start, range.v.start), &self.evaluate(range.u.end, range.v.end));
+        bbox.expand(&self.evaluate(range.u.start, range.v.end));
+        bbox.expand(&self.evaluate(range.u.end, range.v.start));
+        bbox
+    }
+
     fn uv_range(&self) -> UVRange {
         self.uv_range
     }
@@ -261,6 +316,15 @@ impl Surface for PlanarSurface {
         self.v_axis = transform.apply_to_vector(&self.v_axis).normalized();
     }
 
+    fn scale(&mut self, factor: f64) {
+        self.origin = Point3::ORIGIN + self.origin.to_vector() * factor;
+        // u and v are distances along the axes
+        self.uv_range.u.start *= factor;
+        self.uv_range.u.end *= factor;
+        self.uv_range.v.start *= factor;
+        self.uv_range.v.end *= factor;
+    }
+
     fn surface_type(&self) -> SurfaceType {
         SurfaceType::Planar
     }
@@ -268,10 +332,14 @@ impl Surface for PlanarSurface {
     fn clone_box(&self) -> Box<dyn Surface> {
         Box::new(*self)
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 /// Cylindrical surface
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct CylindricalSurface {
     /// Origin (center of base circle)
     origin: Point3,
@@ -282,6 +350,7 @@ pub struct CylindricalSurface {
     /// Reference direction (unit vector perpendicular to axis)
     ref_direction: Vec3,
     /// Height range along axis
+    #[serde(with = "crate::unbounded::pair")]
     height_range: (f64, f64),
     /// Angle range (in radians)
     angle_range: (f64, f64),
@@ -355,6 +424,16 @@ impl CylindricalSurface {
         self.radius
     }
 
+    /// Get the height range along the axis, measured from the origin
+    pub fn height_range(&self) -> (f64, f64) {
+        self.height_range
+    }
+
+    /// Get the reference direction, where the angle parameter starts
+    pub fn ref_direction(&self) -> Vec3 {
+        self.ref_direction
+    }
+
     /// Get the perpendicular direction
     pub fn perpendicular_direction(&self) -> Vec3 {
         self.axis.cross(&self.ref_direction)
@@ -404,6 +483,24 @@ impl Surface for CylindricalSurface {
         (1.0 / self.radius, 0.0)
     }
 
+    fn bounding_box_over(&self, range: &UVRange) -> BoundingBox3 {
+        let angle = |u: f64| self.angle_range.0 + u * (self.angle_range.1 - self.angle_range.0);
+        let height = |v: f64| self.height_range.0 + v * (self.height_range.1 - self.height_range.0);
+        let (angles, heights) = ((angle(range.u.start), angle(range.u.end)), (height(range.v.start), height(range.v.end)));
+        let (x, y, z) = (self.ref_direction.to_array(), self.perpendicular_direction().to_array(), self.axis.to_array());
+        let origin = self.origin.to_array();
+        // Each coordinate is a circle term in the angle plus a line term in the height
+        let bounds: [(f64, f64); 3] = std::array::from_fn(|k| {
+            let (c0, c1) = cosine_bounds(self.radius * x[k], self.radius * y[k], angles);
+            let (h0, h1) = linear_bounds(z[k], heights);
+            (origin[k] + c0 + h0, origin[k] + c1 + h1)
+        });
+        BoundingBox3::new(
+            Point3::new(bounds[0].0, bounds[1].0, bounds[2].0),
+            Point3::new(bounds[0].1, bounds[1].1, bounds[2].1),
+        )
+    }
+
     fn uv_range(&self) -> UVRange {
         UVRange::new(0.0, 1.0, 0.0, 1.0)
     }
@@ -455,11 +552,7 @@ impl Surface for CylindricalSurface {
         let perp = self.perpendicular_direction();
         let x = to_proj.dot(&self.ref_direction);
         let y = to_proj.dot(&perp);
-        let angle = y.atan2(x);
-        
-        // Normalize angle to [0, 1]
-        let angle_normalized = (angle - self.angle_range.0) / (self.angle_range.1 - self.angle_range.0);
-        let angle_normalized = angle_normalized.clamp(0.0, 1.0);
+        let angle_normalized = angle_parameter(y.atan2(x), self.angle_range);
         
         let v = (height_clamped - self.height_range.0) / (self.height_range.1 - self.height_range.0);
         
@@ -482,6 +575,12 @@ impl Surface for CylindricalSurface {
         self.radius *= scale;
     }
 
+    fn scale(&mut self, factor: f64) {
+        self.origin = Point3::ORIGIN + self.origin.to_vector() * factor;
+        self.radius *= factor;
+        self.height_range = (self.height_range.0 * factor, self.height_range.1 * factor);
+    }
+
     fn surface_type(&self) -> SurfaceType {
         SurfaceType::Cylindrical
     }
@@ -489,10 +588,14 @@ impl Surface for CylindricalSurface {
     fn clone_box(&self) -> Box<dyn Surface> {
         Box::new(*self)
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 /// Spherical surface
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct SphericalSurface {
     /// Center
     center: Point3,
@@ -547,6 +650,16 @@ impl SphericalSurface {
         self.radius
     }
 
+    /// Get the north pole direction
+    pub fn axis(&self) -> Vec3 {
+        self.axis
+    }
+
+    /// Get the reference direction, where the azimuth starts
+    pub fn ref_direction(&self) -> Vec3 {
+        self.ref_direction
+    }
+
     /// Check if full sphere
     pub fn is_full_sphere(&self) -> bool {
         (self.u_range.1 - self.u_range.0 - std::f64::consts::TAU).abs() < 1e-10 &&
@@ -604,6 +717,27 @@ impl Surface for SphericalSurface {
         (1.0 / self.radius, 1.0 / self.radius)
     }
 
+    fn bounding_box_over(&self, range: &UVRange) -> BoundingBox3 {
+        let azimuth = |u: f64| self.u_range.0 + u * (self.u_range.1 - self.u_range.0);
+        let polar = |v: f64| self.v_range.0 + v * (self.v_range.1 - self.v_range.0);
+        let (azimuths, polars) = ((azimuth(range.u.start), azimuth(range.u.end)), (polar(range.v.start), polar(range.v.end)));
+        let (x, y, z) = (self.ref_direction.to_array(), self.axis.cross(&self.ref_direction).to_array(), self.axis.to_array());
+        let center = self.center.to_array();
+        // A coordinate is r (z cos(polar) + g sin(polar)) with g the azimuth
+        // circle term; sin(polar) is never negative, so the extremes pair
+        // with g's own
+        let bounds: [(f64, f64); 3] = std::array::from_fn(|k| {
+            let (g0, g1) = cosine_bounds(x[k], y[k], azimuths);
+            let low = cosine_bounds(z[k], g0, polars).0;
+            let high = cosine_bounds(z[k], g1, polars).1;
+            (center[k] + self.radius * low, center[k] + self.radius * high)
+        });
+        BoundingBox3::new(
+            Point3::new(bounds[0].0, bounds[1].0, bounds[2].0),
+            Point3::new(bounds[0].1, bounds[1].1, bounds[2].1),
+        )
+    }
+
     fn uv_range(&self) -> UVRange {
         UVRange::new(0.0, 1.0, 0.0, 1.0)
     }
@@ -656,17 +790,14 @@ impl Surface for SphericalSurface {
         
         // Compute UV from point on sphere
         let to_sphere = on_sphere - self.center;
-        let polar = to_sphere.dot(&self.axis).acos() / self.radius;
+        let polar = (to_sphere.dot(&self.axis) / self.radius).clamp(-1.0, 1.0).acos();
         
         let perp = self.axis.cross(&self.ref_direction);
         let x = to_sphere.dot(&self.ref_direction);
         let y = to_sphere.dot(&perp);
-        let azimuth = y.atan2(x);
         
-        let u = (azimuth - self.u_range.0) / (self.u_range.1 - self.u_range.0);
+        let u = angle_parameter(y.atan2(x), self.u_range);
         let v = (polar - self.v_range.0) / (self.v_range.1 - self.v_range.0);
-        
-        let u = u.clamp(0.0, 1.0);
         let v = v.clamp(0.0, 1.0);
         
         let closest = self.evaluate(u, v);
@@ -687,6 +818,11 @@ impl Surface for SphericalSurface {
         self.radius *= scale;
     }
 
+    fn scale(&mut self, factor: f64) {
+        self.center = Point3::ORIGIN + self.center.to_vector() * factor;
+        self.radius *= factor;
+    }
+
     fn surface_type(&self) -> SurfaceType {
         SurfaceType::Spherical
     }
@@ -694,10 +830,14 @@ impl Surface for SphericalSurface {
     fn clone_box(&self) -> Box<dyn Surface> {
         Box::new(*self)
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 /// Conical surface
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ConicalSurface {
     /// Apex point
     apex: Point3,
@@ -708,6 +848,7 @@ pub struct ConicalSurface {
     /// Reference direction
     ref_direction: Vec3,
     /// Height range from apex
+    #[serde(with = "crate::unbounded::pair")]
     height_range: (f64, f64),
     /// Angle range around axis
     angle_range: (f64, f64),
@@ -742,16 +883,49 @@ impl ConicalSurface {
         })
     }
 
+    /// Create a bounded cone, with heights measured from the apex
+    #[allow(clippy::too_many_arguments)]
+    pub fn bounded(
+        apex: Point3,
+        axis: Vec3,
+        half_angle: f64,
+        ref_direction: Vec3,
+        height_min: f64,
+        height_max: f64,
+        angle_min: f64,
+        angle_max: f64,
+    ) -> GeomResult<Self> {
+        let mut surf = Self::new(apex, axis, half_angle, ref_direction)?;
+        surf.height_range = (height_min, height_max);
+        surf.angle_range = (angle_min, angle_max);
+        Ok(surf)
+    }
+
     /// Get the apex
     pub fn apex(&self) -> Point3 {
         self.apex
     }
 
+    /// Get the axis, pointing from the apex toward the base
+    pub fn axis(&self) -> Vec3 {
+        self.axis
+    }
+
     /// Get the half angle
     pub fn half_angle(&self) -> f64 {
         self.half_angle
     }
 
+    /// Get the reference direction, where the angle around the axis starts
+    pub fn ref_direction(&self) -> Vec3 {
+        self.ref_direction
+    }
+
+    /// Get the height range along the axis, measured from the apex
+    pub fn height_range(&self) -> (f64, f64) {
+        self.height_range
+    }
+
     /// Get radius at a given height from apex
     pub fn radius_at_height(&self, height: f64) -> f64 {
         height * self.half_angle.tan()
@@ -843,12 +1017,9 @@ impl Surface for ConicalSurface {
         let perp = self.axis.cross(&self.ref_direction);
         let x = to_center.dot(&self.ref_direction);
         let y = to_center.dot(&perp);
-        let angle = y.atan2(x);
         
-        let u = (angle - self.angle_range.0) / (self.angle_range.1 - self.angle_range.0);
+        let u = angle_parameter(y.atan2(x), self.angle_range);
         let v = (height - self.height_range.0) / (self.height_range.1 - self.height_range.0);
-        
-        let u = u.clamp(0.0, 1.0);
         let v = v.clamp(0.0, 1.0);
         
         let closest = self.evaluate(u, v);
@@ -863,6 +1034,11 @@ impl Surface for ConicalSurface {
         self.ref_direction = transform.apply_to_vector(&self.ref_direction).normalized();
     }
 
+    fn scale(&mut self, factor: f64) {
+        self.apex = Point3::ORIGIN + self.apex.to_vector() * factor;
+        self.height_range = (self.height_range.0 * factor, self.height_range.1 * factor);
+    }
+
     fn surface_type(&self) -> SurfaceType {
         SurfaceType::Conical
     }
@@ -870,10 +1046,14 @@ impl Surface for ConicalSurface {
     fn clone_box(&self) -> Box<dyn Surface> {
         Box::new(*self)
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
 }
 
 /// Toroidal surface
-#[derive(Debug, Clone, Copy, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct ToroidalSurface {
     /// Center of torus
     center: Point3,
@@ -1052,45 +1232,18 @@ impl Surface for ToroidalSurface {
     }
 
     fn closest_point(&self, point: &Point3) -> GeomResult<(f64, f64, Point3, f64)> {
-        // Iterative closest point for torus
-        // Start with projection onto torus plane
+        // The nearest tube center lies toward the point around the axis, and
+        // the nearest point on that tube's circle toward the point from it
         let to_point = *point - self.center;
-        let height = to_point.dot(&self.axis);
-        
-        // Initial guess: project to major circle
         let perp = self.axis.cross(&self.ref_direction);
         let x = to_point.dot(&self.ref_direction);
         let y = to_point.dot(&perp);
-        let major_angle = y.atan2(x);
-        
-        let mut u = (major_angle - self.major_range.0) / (self.major_range.1 - self.major_range.0);
-        let mut v = 0.5; // Initial guess for minor angle
-        
-        // Simple iteration
-        for _ in 0..10 {
-            let p = self.evaluate(u, v);
-            let (du, dv) = self.derivatives(u, v);
-            let to_p = *point - p;
-            
-            // Newton step
-            let g11 = du.dot(&du);
-            let g12 = du.dot(&dv);
-            let g22 = dv.dot(&dv);
-            let det = g11 * g22 - g12 * g12;
-            
-            if det.abs() < 1e-10 {
-                break;
-            }
-            
-            let f1 = to_p.dot(&du);
-            let f2 = to_p.dot(&dv);
-            
-            let du_step = (f1 * g22 - f2 * g12) / det;
-            let dv_step = (f2 * g11 - f1 * g12) / det;
-            
-            u = (u + du_step).clamp(0.0, 1.0);
-            v = (v + dv_step).clamp(0.0, 1.0);
-        }
+        let u = angle_parameter(y.atan2(x), self.major_range);
+        
+        let major_angle = self.major_range.0 + u * (self.major_range.1 - self.major_range.0);
+        let radial = self.ref_direction * major_angle.cos() + perp * major_angle.sin();
+        let from_tube = to_point - radial * self.major_radius;
+        let v = angle_parameter(from_tube.dot(&radial).atan2(from_tube.dot(&self.axis)), self.minor_range);
         
         let closest = self.evaluate(u, v);
         let dist = point.distance_to(&closest);
@@ -1111,6 +1264,12 @@ impl Surface for ToroidalSurface {
         self.minor_radius *= scale;
     }
 
+    fn scale(&mut self, factor: f64) {
+        self.center = Point3::ORIGIN + self.center.to_vector() * factor;
+        self.major_radius *= factor;
+        self.minor_radius *= factor;
+    }
+
     fn surface_type(&self) -> SurfaceType {
         SurfaceType::Toroidal
     }
@@ -1118,11 +1277,328 @@ impl Surface for ToroidalSurface {
     fn clone_box(&self) -> Box<dyn Surface> {
         Box::new(*self)
     }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Surface of linear extrusion, a curve swept along a fixed direction
+///
+/// u is the curve's own parameter and v the distance along the unit
+/// direction, unbounded.
+pub struct ExtrusionSurface {
+    /// Profile curve
+    curve: Box<dyn Curve>,
+    /// Sweep direction (unit vector)
+    direction: Vec3,
+}
+
+impl ExtrusionSurface {
+    /// Sweep `curve` along `direction`
+    pub fn new(curve: Box<dyn Curve>, direction: Vec3) -> GeomResult<Self> {
+        if direction.is_zero(1e-10) {
+            return Err(GeometryError::Degenerate(
+                "Extrusion direction cannot be zero".to_string()
+            ));
+        }
+        Ok(Self { curve, direction: direction.normalized() })
+    }
+
+    /// Get the profile curve
+    pub fn curve(&self) -> &dyn Curve {
+        self.curve.as_ref()
+    }
+
+    /// Get the sweep direction
+    pub fn direction(&self) -> Vec3 {
+        self.direction
+    }
+}
+
+impl Clone for ExtrusionSurface {
+    fn clone(&self) -> Self {
+        Self { curve: self.curve.clone_box(), direction: self.direction }
+    }
+}
+
+impl std::fmt::Debug for ExtrusionSurface {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ExtrusionSurface")
+            .field("curve", &self.curve.curve_type())
+            .field("direction", &self.direction)
+            .finish()
+    }
+}
+
+impl Surface for ExtrusionSurface {
+    fn evaluate(&self, u: f64, v: f64) -> Point3 {
+        self.curve.evaluate(u) + self.direction * v
+    }
+
+    fn derivatives(&self, u: f64, _v: f64) -> (Vec3, Vec3) {
+        (self.curve.tangent(u), self.direction)
+    }
+
+    fn principal_curvatures(&self, u: f64, _v: f64) -> (f64, f64) {
+        // Straight rulings; the section across them bends with the curve
+        // where it is perpendicular to the direction
+        let tangent = self.curve.unit_tangent(u);
+        let across = 1.0 - tangent.dot(&self.direction).powi(2);
+        (self.curve.curvature(u) * across, 0.0)
+    }
+
+    fn uv_range(&self) -> UVRange {
+        let u = self.curve.param_range();
+        UVRange::new(u.start, u.end, f64::NEG_INFINITY, f64::INFINITY)
+    }
+
+    fn bounding_box_over(&self, range: &UVRange) -> BoundingBox3 {
+        // Straight along v, so only the curve is sampled, padded by twice the
+        // largest span sagitta, then swept to both ends of the v range
+        let n = EXTRUSION_BOX_SAMPLES as f64;
+        let at = |i: f64| self.curve.evaluate(range.u.start + range.u.length() * i / n);
+        let points: Vec<Point3> = (0..=EXTRUSION_BOX_SAMPLES).map(|i| at(i as f64)).collect();
+        let sagitta = points.windows(2).enumerate()
+            .map(|(i, w)| at(i as f64 + 0.5).distance_to(&w[0].midpoint(&w[1])))
+            .fold(0.0, f64::max);
+        let mut curve_box = points.iter().fold(BoundingBox3::empty(), |mut bbox, p| {
+            bbox.expand(p);
+            bbox
+        });
+        curve_box.expand_by_margin(2.0 * sagitta);
+        let (low, high, direction) = (curve_box.min.to_array(), curve_box.max.to_array(), self.direction.to_array());
+        let bounds: [(f64, f64); 3] = std::array::from_fn(|k| {
+            let (d0, d1) = linear_bounds(direction[k], (range.v.start, range.v.end));
+            (low[k] + d0, high[k] + d1)
+        });
+        BoundingBox3::new(
+            Point3::new(bounds[0].0, bounds[1].0, bounds[2].0),
+            Point3::new(bounds[0].1, bounds[1].1, bounds[2].1),
+        )
+    }
+
+    fn u_isocurve(&self, u: f64) -> Option<Box<dyn Curve>> {
+        Some(Box::new(Line::infinite(self.curve.evaluate(u), self.direction).ok()?))
+    }
+
+    fn v_isocurve(&self, v: f64) -> Option<Box<dyn Curve>> {
+        Some(self.curve.transformed(&Transform3::from_translation_vec(self.direction * v)))
+    }
+
+    fn closest_point(&self, point: &Point3) -> GeomResult<(f64, f64, Point3, f64)> {
+        // Alternate between sliding along the rulings and projecting onto
+        // the curve; one pass is exact when the curve lies across the direction
+        let start = self.curve.evaluate(self.curve.param_range().start);
+        let mut v = (*point - start).dot(&self.direction);
+        let mut u = 0.0;
+        for _ in 0..4 {
+            let (t, on_curve, _) = self.curve.closest_point(&(*point - self.direction * v))?;
+            u = t;
+            let next = (*point - on_curve).dot(&self.direction);
+            if (next - v).abs() <= 1e-12 * (1.0 + v.abs()) {
+                break;
+            }
+            v = next;
+        }
+        let closest = self.evaluate(u, v);
+        Ok((u, v, closest, point.distance_to(&closest)))
+    }
+
+    fn transform(&mut self, transform: &Transform3) {
+        self.curve.transform(transform);
+        self.direction = transform.apply_to_vector(&self.direction).normalized();
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.curve.scale(factor);
+    }
+
+    fn surface_type(&self) -> SurfaceType {
+        SurfaceType::SweptSurface
+    }
+
+    fn clone_box(&self) -> Box<dyn Surface> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Parameter step for the finite differences of an offset surface
+const OFFSET_DERIVATIVE_STEP: f64 = 1e-6;
+
+/// Surface at a fixed distance along the normals of a base surface
+///
+/// Shares the base's parameters; a positive distance moves along the base
+/// normal. The distance must stay below the base's radii of curvature on
+/// the side it moves toward, or the surface folds over itself.
+pub struct OffsetSurface {
+    /// Surface being offset
+    base: Box<dyn Surface>,
+    /// Signed distance along the base normal
+    distance: f64,
+}
+
+impl OffsetSurface {
+    /// Offset `base` by `distance` along its normals
+    pub fn new(base: Box<dyn Surface>, distance: f64) -> Self {
+        Self { base, distance }
+    }
+
+    /// Get the base surface
+    pub fn base(&self) -> &dyn Surface {
+        self.base.as_ref()
+    }
+
+    /// Get the signed offset distance
+    pub fn distance(&self) -> f64 {
+        self.distance
+    }
+}
+
+impl Clone for OffsetSurface {
+    fn clone(&self) -> Self {
+        Self { base: self.base.clone_box(), distance: self.distance }
+    }
+}
+
+impl std::fmt::Debug for OffsetSurface {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OffsetSurface")
+            .field("base", &self.base.surface_type())
+            .field("distance", &self.distance)
+            .finish()
+    }
+}
+
+impl Surface for OffsetSurface {
+    fn evaluate(&self, u: f64, v: f64) -> Point3 {
+        self.base.evaluate(u, v) + self.base.normal(u, v) * self.distance
+    }
+
+    fn derivatives(&self, u: f64, v: f64) -> (Vec3, Vec3) {
+        // Central differences, since the base exposes no normal derivatives
+        let h = OFFSET_DERIVATIVE_STEP;
+        let du = (self.evaluate(u + h, v) - self.evaluate(u - h, v)) / (2.0 * h);
+        let dv = (self.evaluate(u, v + h) - self.evaluate(u, v - h)) / (2.0 * h);
+        (du, dv)
+    }
+
+    fn normal(&self, u: f64, v: f64) -> Vec3 {
+        self.base.normal(u, v)
+    }
+
+    fn principal_curvatures(&self, u: f64, v: f64) -> (f64, f64) {
+        // The centers of curvature stay put, so each radius changes by the
+        // distance. Base curvatures come unsigned; area stretches by
+        // (1 - d k1)(1 - d k2) for curvatures signed toward the normal, so
+        // the signs that best match the actual stretch are used.
+        let (k1, k2) = self.base.principal_curvatures(u, v);
+        let d = self.distance;
+        let (bu, bv) = self.base.derivatives(u, v);
+        let (ou, ov) = self.derivatives(u, v);
+        let stretch = ou.cross(&ov).length() / bu.cross(&bv).length();
+        let mismatch = |[s1, s2]: [f64; 2]| ((1.0 - d * s1 * k1) * (1.0 - d * s2 * k2) - stretch).abs();
+        let [s1, s2] = [[1.0, 1.0], [1.0, -1.0], [-1.0, 1.0], [-1.0, -1.0]]
+            .into_iter()
+            .min_by(|a, b| mismatch(*a).total_cmp(&mismatch(*b)))
+            .unwrap_or([1.0, 1.0]);
+        ((k1 / (1.0 - d * s1 * k1)).abs(), (k2 / (1.0 - d * s2 * k2)).abs())
+    }
+
+    fn uv_range(&self) -> UVRange {
+        self.base.uv_range()
+    }
+
+    fn u_isocurve(&self, _u: f64) -> Option<Box<dyn Curve>> {
+        None
+    }
+
+    fn v_isocurve(&self, _v: f64) -> Option<Box<dyn Curve>> {
+        None
+    }
+
+    fn closest_point(&self, point: &Point3) -> GeomResult<(f64, f64, Point3, f64)> {
+        // Both surfaces share their normals, so the feet coincide
+        let (u, v, _, _) = self.base.closest_point(point)?;
+        let closest = self.evaluate(u, v);
+        Ok((u, v, closest, point.distance_to(&closest)))
+    }
+
+    fn transform(&mut self, transform: &Transform3) {
+        self.base.transform(transform);
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.base.scale(factor);
+        self.distance *= factor.abs();
+    }
+
+    fn surface_type(&self) -> SurfaceType {
+        SurfaceType::OffsetSurface
+    }
+
+    fn clone_box(&self) -> Box<dyn Surface> {
+        Box::new(self.clone())
+    }
+
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+}
+
+/// Parameter in [0, 1] of `angle` across an angular `range`
+///
+/// The angle is wrapped by whole turns into the range; outside a partial
+/// range it snaps to whichever end is nearer round the circle.
+fn angle_parameter(angle: f64, range: (f64, f64)) -> f64 {
+    let span = range.1 - range.0;
+    let ahead = (angle - range.0).rem_euclid(std::f64::consts::TAU);
+    if ahead <= span {
+        ahead / span
+    } else if ahead - span < std::f64::consts::TAU - ahead {
+        1.0
+    } else {
+        0.0
+    }
+}
+
+/// Lowest and highest value of `x cos(a) + y sin(a)` for `a` across `range`
+///
+/// The extremes are the ends of the range unless it reaches the peak at
+/// `atan2(y, x)` or the trough half a turn on.
+fn cosine_bounds(x: f64, y: f64, range: (f64, f64)) -> (f64, f64) {
+    let (a0, a1) = (range.0.min(range.1), range.0.max(range.1));
+    let value = |a: f64| x * a.cos() + y * a.sin();
+    let (mut low, mut high) = (value(a0).min(value(a1)), value(a0).max(value(a1)));
+    let reaches = |a: f64| (a - a0).rem_euclid(std::f64::consts::TAU) <= a1 - a0;
+    let peak = y.atan2(x);
+    if reaches(peak) {
+        high = x.hypot(y);
+    }
+    if reaches(peak + std::f64::consts::PI) {
+        low = -x.hypot(y);
+    }
+    (low, high)
+}
+
+/// Lowest and highest value of `slope * t` for `t` across `range`, zero for
+/// a zero slope even over an unbounded range
+fn linear_bounds(slope: f64, range: (f64, f64)) -> (f64, f64) {
+    if slope == 0.0 {
+        return (0.0, 0.0);
+    }
+    let (a, b) = (slope * range.0, slope * range.1);
+    (a.min(b), a.max(b))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::f64::consts::TAU;
 
     #[test]
     fn test_planar_surface() {
@@ -1174,6 +1650,27 @@ mod tests {
         assert!((p.x() - 10.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_closest_point_wraps_angle() {
+        // Points past half a turn sit at negative atan2 angles
+        let below = Point3::new(0.0, -3.0, 0.5);
+        let cylinder = CylindricalSurface::bounded(Point3::ORIGIN, Vec3::Z, 2.0, Vec3::X, 0.0, 1.0, 0.0, TAU).unwrap();
+        let (u, _, closest, dist) = cylinder.closest_point(&below).unwrap();
+        assert!((u - 0.75).abs() < 1e-12);
+        assert!(closest.distance_to(&Point3::new(0.0, -2.0, 0.5)) < 1e-12);
+        assert!((dist - 1.0).abs() < 1e-12);
+
+        let sphere = SphericalSurface::new(Point3::ORIGIN, 10.0, Vec3::Z, Vec3::X).unwrap();
+        let (u, v, closest, _) = sphere.closest_point(&Point3::new(0.0, -20.0, 0.0)).unwrap();
+        assert!((u - 0.75).abs() < 1e-12 && (v - 0.5).abs() < 1e-12);
+        assert!(closest.distance_to(&Point3::new(0.0, -10.0, 0.0)) < 1e-12);
+
+        let torus = ToroidalSurface::new(Point3::ORIGIN, Vec3::Z, 3.0, 1.0, Vec3::X).unwrap();
+        let (_, _, closest, dist) = torus.closest_point(&Point3::new(0.0, -3.0, 2.0)).unwrap();
+        assert!(closest.distance_to(&Point3::new(0.0, -3.0, 1.0)) < 1e-12);
+        assert!((dist - 1.0).abs() < 1e-12);
+    }
+
     #[test]
     fn test_surface_curvatures() {
         let plane = PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap();
@@ -1186,4 +1683,77 @@ mod tests {
         assert!((k1 - 0.2).abs() < 1e-10);
         assert!((k2 - 0.2).abs() < 1e-10);
     }
+
+    #[test]
+    fn test_cylinder_transformed_leaves_original() {
+        let cylinder = CylindricalSurface::bounded(Point3::ORIGIN, Vec3::Z, 1.0, Vec3::X, 0.0, 2.0, 0.0, TAU).unwrap();
+        let before = cylinder.evaluate(0.25, 0.5);
+        let moved = cylinder.transformed(&Transform3::from_translation(3.0, 0.0, 1.0));
+        assert!(moved.evaluate(0.25, 0.5).distance_to(&(before + Vec3::new(3.0, 0.0, 1.0))) < 1e-12);
+        assert_eq!(moved.surface_type(), SurfaceType::Cylindrical);
+        assert_eq!(cylinder.evaluate(0.25, 0.5), before);
+    }
+
+    #[test]
+    fn test_offset_sphere() {
+        let sphere = SphericalSurface::new(Point3::ORIGIN, 2.0, Vec3::Z, Vec3::X).unwrap();
+        // The sphere's normals point inward, so a negative offset grows it
+        let offset = OffsetSurface::new(Box::new(sphere), -1.0);
+        assert_eq!(offset.surface_type(), SurfaceType::OffsetSurface);
+
+        for (u, v) in [(0.1, 0.3), (0.7, 0.6)] {
+            let p = offset.evaluate(u, v);
+            assert!((p.distance_to(&Point3::ORIGIN) - 3.0).abs() < 1e-12);
+            let (du, dv) = offset.derivatives(u, v);
+            assert!(du.cross(&dv).normalized().dot(&offset.normal(u, v)) > 0.999_999);
+        }
+        let (k1, k2) = offset.principal_curvatures(0.2, 0.4);
+        assert!((k1 - 1.0 / 3.0).abs() < 1e-12 && (k2 - 1.0 / 3.0).abs() < 1e-12);
+
+        let (_, _, closest, dist) = offset.closest_point(&Point3::new(0.0, 5.0, 0.0)).unwrap();
+        assert!(closest.distance_to(&Point3::new(0.0, 3.0, 0.0)) < 1e-9);
+        assert!((dist - 2.0).abs() < 1e-9);
+
+        // Cylinder normals point outward instead
+        let cylinder = CylindricalSurface::bounded(Point3::ORIGIN, Vec3::Z, 2.0, Vec3::X, 0.0, 1.0, 0.0, TAU).unwrap();
+        let offset = OffsetSurface::new(Box::new(cylinder), 1.0);
+        let (k1, k2) = offset.principal_curvatures(0.3, 0.5);
+        assert!((k1 - 1.0 / 3.0).abs() < 1e-6 && k2.abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_bounding_box_over_curved_surfaces() {
+        // Box of a dense grid of samples, which the exact box must hold and
+        // barely exceed
+        let sampled = |surface: &dyn Surface, range: &UVRange| {
+            let n = 400;
+            let mut bbox = BoundingBox3::empty();
+            for i in 0..=n {
+                for j in 0..=n {
+                    let (s, t) = (i as f64 / n as f64, j as f64 / n as f64);
+                    bbox.expand(&surface.evaluate(range.u.start + range.u.length() * s, range.v.start + range.v.length() * t));
+                }
+            }
+            bbox
+        };
+        let tilted = Vec3::new(1.0, 1.0, 1.0);
+        let cylinder = CylindricalSurface::bounded(Point3::new(1.0, 2.0, 3.0), tilted, 2.0, Vec3::X, -1.0, 1.0, 0.0, TAU).unwrap();
+        let sphere = SphericalSurface::new(Point3::new(-1.0, 0.0, 2.0), 1.5, tilted, Vec3::Y).unwrap();
+        let cases: [(&dyn Surface, UVRange); 3] = [
+            (&cylinder, UVRange::new(0.1, 0.45, 0.0, 1.0)),
+            (&sphere, UVRange::new(0.05, 0.3, 0.2, 0.6)),
+            (&sphere, UVRange::new(0.6, 0.9, 0.0, 1.0)),
+        ];
+        for (surface, range) in cases {
+            let (mut exact, dense) = (surface.bounding_box_over(&range), sampled(surface, &range));
+            exact.expand_by_margin(1e-12);
+            assert!(exact.contains_bbox(&dense));
+            assert!(exact.min.distance_to(&dense.min) < 1e-4 && exact.max.distance_to(&dense.max) < 1e-4);
+        }
+
+        // Sampled default on a surface with no closed form box
+        let offset = OffsetSurface::new(Box::new(cylinder), 0.5);
+        let range = UVRange::new(0.1, 0.45, 0.0, 1.0);
+        assert!(offset.bounding_box_over(&range).contains_bbox(&sampled(&offset, &range)));
+    }
 }
@@ -0,0 +1,73 @@
+//! Serde for parameters that may be infinite
+//!
+//! JSON numbers can't hold infinities, so unbounded ranges would come back
+//! as `null`. Non-finite values are written as the strings "inf", "-inf"
+//! and "nan" instead; finite values stay numbers.
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum Value {
+    Finite(f64),
+    Named(String),
+}
+
+impl Value {
+    fn from_f64(x: f64) -> Self {
+        match x {
+            x if x.is_finite() => Value::Finite(x),
+            x if x == f64::INFINITY => Value::Named("inf".to_string()),
+            x if x == f64::NEG_INFINITY => Value::Named("-inf".to_string()),
+            _ => Value::Named("nan".to_string()),
+        }
+    }
+
+    fn into_f64<E: serde::de::Error>(self) -> Result<f64, E> {
+        match self {
+            Value::Finite(x) => Ok(x),
+            Value::Named(name) => match name.as_str() {
+                "inf" => Ok(f64::INFINITY),
+                "-inf" => Ok(f64::NEG_INFINITY),
+                "nan" => Ok(f64::NAN),
+                _ => Err(E::custom(format!("expected a number, \"inf\", \"-inf\" or \"nan\", found \"{}\"", name))),
+            },
+        }
+    }
+}
+
+pub(crate) fn serialize<S: Serializer>(x: &f64, serializer: S) -> Result<S::Ok, S::Error> {
+    Value::from_f64(*x).serialize(serializer)
+}
+
+pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Value::deserialize(deserializer)?.into_f64()
+}
+
+/// The same for `(min, max)` pairs
+pub(crate) mod pair {
+    use super::Value;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub(crate) fn serialize<S: Serializer>(&(a, b): &(f64, f64), serializer: S) -> Result<S::Ok, S::Error> {
+        (Value::from_f64(a), Value::from_f64(b)).serialize(serializer)
+    }
+
+    pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<(f64, f64), D::Error> {
+        let (a, b) = <(Value, Value)>::deserialize(deserializer)?;
+        Ok((a.into_f64()?, b.into_f64()?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ParamRange;
+
+    #[test]
+    fn test_infinite_range_round_trip() {
+        let range = ParamRange::new(f64::NEG_INFINITY, 2.5);
+        let json = serde_json::to_string(&range).unwrap();
+        assert_eq!(json, r#"{"start":"-inf","end":2.5}"#);
+        assert_eq!(serde_json::from_str::<ParamRange>(&json).unwrap(), range);
+    }
+}
@@ -0,0 +1,199 @@
+//! DXF Writer for 2D curves
+//!
+//! Writes planar sections and sketch profiles as DXF LINE, ARC and CIRCLE
+//! entities in a single layer, in the 2D coordinates of their plane.
+
+use crate::{IoError, IoResult, ExportOptions, Units};
+use nova_geom::{Curve, CircularArc, Line, PlanarSurface};
+use nova_math::Point2;
+use nova_ops::{Sketch, SketchSegment};
+use std::fmt::Write;
+
+/// Chords written for curves that are neither lines nor arcs
+const CURVE_SEGMENTS: usize = 64;
+
+/// DXF writer
+#[derive(Debug, Clone)]
+pub struct DxfWriter {
+    /// Layer every entity is written to
+    pub layer: String,
+}
+
+impl DxfWriter {
+    /// Create a new DXF writer on layer "0"
+    pub fn new() -> Self {
+        Self { layer: "0".to_string() }
+    }
+
+    /// Write curves projected into the (u, v) coordinates of `plane`
+    ///
+    /// Lines and circular arcs parallel to the plane are written exactly;
+    /// other curves are written as chains of lines.
+    pub fn write_curves(&self, curves: &[Box<dyn Curve>], plane: &PlanarSurface, options: &ExportOptions) -> IoResult<String> {
+        let project = |p| {
+            let (u, v) = plane.point_to_uv(&p);
+            Point2::new(u, v)
+        };
+        let mut segments = Vec::new();
+        for curve in curves {
+            let range = curve.param_range();
+            if !range.length().is_finite() {
+                return Err(IoError::InvalidData("Cannot write an unbounded curve to DXF".to_string()));
+            }
+            let (start, end) = (curve.evaluate(range.start), curve.evaluate(range.end));
+            let arc = curve.as_any().downcast_ref::<CircularArc>()
+                .filter(|arc| arc.normal().cross(&plane.normal()).is_zero(1e-9));
+            if let Some(arc) = arc {
+                let center = project(arc.center());
+                if arc.is_full_circle() {
+                    segments.push(SketchSegment::Circle { center, radius: arc.radius() });
+                    continue;
+                }
+                let angle = |p: Point2| (p.y() - center.y()).atan2(p.x() - center.x());
+                let start_angle = angle(project(start));
+                // Counter-clockwise in the plane when the arc turns about its normal
+                let sweep = arc.sweep_angle() * arc.normal().dot(&plane.normal()).signum();
+                segments.push(SketchSegment::Arc {
+                    center,
+                    radius: arc.radius(),
+                    start_angle,
+                    end_angle: start_angle + sweep,
+                });
+            } else if curve.as_any().is::<Line>() {
+                segments.push(SketchSegment::Line { start: project(start), end: project(end) });
+            } else {
+                let points: Vec<Point2> = (0..=CURVE_SEGMENTS)
+                    .map(|i| project(curve.evaluate(range.start + range.length() * i as f64 / CURVE_SEGMENTS as f64)))
+                    .collect();
+                segments.extend(points.windows(2).map(|w| SketchSegment::Line { start: w[0], end: w[1] }));
+            }
+        }
+        Ok(self.write_segments(&segments, options))
+    }
+
+    /// Write every closed contour of a sketch in its plane's coordinates
+    pub fn write_sketch(&self, sketch: &Sketch, options: &ExportOptions) -> IoResult<String> {
+        let segments: Vec<SketchSegment> = sketch.contours().iter().flatten().copied().collect();
+        Ok(self.write_segments(&segments, options))
+    }
+
+    /// Write the header and one entity per segment
+    fn write_segments(&self, segments: &[SketchSegment], options: &ExportOptions) -> String {
+        let scale = options.units.from_mm_factor();
+        let insunits = match options.units {
+            Units::Inches => 1,
+            Units::Feet => 2,
+            Units::Millimeters => 4,
+            Units::Centimeters => 5,
+            Units::Meters => 6,
+        };
+        let mut output = String::new();
+        let _ = write!(output, "0\nSECTION\n2\nHEADER\n9\n$INSUNITS\n70\n{}\n0\nENDSEC\n", insunits);
+        output.push_str("0\nSECTION\n2\nENTITIES\n");
+
+        for segment in segments {
+            match *segment {
+                SketchSegment::Line { start, end } => {
+                    let _ = write!(
+                        output,
+                        "0\nLINE\n8\n{}\n10\n{:.6}\n20\n{:.6}\n30\n0.0\n11\n{:.6}\n21\n{:.6}\n31\n0.0\n",
+                        self.layer, start.x() * scale, start.y() * scale, end.x() * scale, end.y() * scale
+                    );
+                }
+                SketchSegment::Circle { center, radius } => {
+                    let _ = write!(
+                        output,
+                        "0\nCIRCLE\n8\n{}\n10\n{:.6}\n20\n{:.6}\n30\n0.0\n40\n{:.6}\n",
+                        self.layer, center.x() * scale, center.y() * scale, radius * scale
+                    );
+                }
+                SketchSegment::Arc { center, radius, start_angle, end_angle } => {
+                    // DXF arcs always run counter-clockwise
+                    let (from, to) = if end_angle >= start_angle { (start_angle, end_angle) } else { (end_angle, start_angle) };
+                    let _ = write!(
+                        output,
+                        "0\nARC\n8\n{}\n10\n{:.6}\n20\n{:.6}\n30\n0.0\n40\n{:.6}\n50\n{:.6}\n51\n{:.6}\n",
+                        self.layer, center.x() * scale, center.y() * scale, radius * scale,
+                        from.to_degrees().rem_euclid(360.0), to.to_degrees().rem_euclid(360.0)
+                    );
+                }
+            }
+        }
+
+        output.push_str("0\nENDSEC\n0\nEOF\n");
+        output
+    }
+}
+
+impl Default for DxfWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_math::{Point3, ToleranceContext, Vec3};
+
+    /// Group code and value pairs of every entity of type `name`
+    fn entities(text: &str, name: &str) -> Vec<Vec<(i32, String)>> {
+        let lines: Vec<&str> = text.lines().collect();
+        let pairs: Vec<(i32, String)> = lines.chunks(2)
+            .map(|pair| (pair[0].trim().parse().unwrap(), pair[1].trim().to_string()))
+            .collect();
+        let mut found = Vec::new();
+        for (i, (code, value)) in pairs.iter().enumerate() {
+            if *code == 0 && value == name {
+                found.push(pairs[i + 1..].iter().take_while(|(code, _)| *code != 0).cloned().collect());
+            }
+        }
+        found
+    }
+
+    fn value(entity: &[(i32, String)], code: i32) -> f64 {
+        entity.iter().find(|(c, _)| *c == code).unwrap().1.parse().unwrap()
+    }
+
+    #[test]
+    fn test_sectioned_rectangle_lines() {
+        // The section of a box at z = 5, drawn in a plane whose origin sits at (1, 1, 5)
+        let corners = [(0.0, 0.0), (4.0, 0.0), (4.0, 2.0), (0.0, 2.0)].map(|(x, y)| Point3::new(x, y, 5.0));
+        let curves: Vec<Box<dyn Curve>> = (0..4)
+            .map(|i| Box::new(Line::segment(corners[i], corners[(i + 1) % 4]).unwrap()) as Box<dyn Curve>)
+            .collect();
+        let plane = PlanarSurface::new(Point3::new(1.0, 1.0, 5.0), Vec3::X, Vec3::Y).unwrap();
+
+        let text = DxfWriter::new().write_curves(&curves, &plane, &ExportOptions::default()).unwrap();
+        let lines = entities(&text, "LINE");
+        assert_eq!(lines.len(), 4);
+        let expected = [(-1.0, -1.0), (3.0, -1.0), (3.0, 1.0), (-1.0, 1.0)];
+        for (i, line) in lines.iter().enumerate() {
+            let (start, end) = (expected[i], expected[(i + 1) % 4]);
+            assert_eq!(line[0], (8, "0".to_string()));
+            assert_eq!((value(line, 10), value(line, 20)), start);
+            assert_eq!((value(line, 11), value(line, 21)), end);
+        }
+    }
+
+    #[test]
+    fn test_sketch_arc_and_circle() {
+        let tolerance = ToleranceContext::default();
+        let mut sketch = Sketch::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap());
+        sketch.add_circle(Point2::new(1.0, 2.0), 0.5);
+        sketch.close_contour(&tolerance).unwrap();
+        // A half disc: a line across the bottom and an arc over the top
+        sketch.add_line(Point2::new(-1.0, 0.0), Point2::new(1.0, 0.0));
+        sketch.add_arc(Point2::ORIGIN, 1.0, 0.0, std::f64::consts::PI);
+        sketch.close_contour(&tolerance).unwrap();
+
+        let writer = DxfWriter { layer: "SKETCH".to_string() };
+        let text = writer.write_sketch(&sketch, &ExportOptions::default()).unwrap();
+        let circle = &entities(&text, "CIRCLE")[0];
+        assert_eq!(circle[0], (8, "SKETCH".to_string()));
+        assert_eq!((value(circle, 10), value(circle, 20), value(circle, 40)), (1.0, 2.0, 0.5));
+        let arc = &entities(&text, "ARC")[0];
+        assert_eq!((value(arc, 50), value(arc, 51)), (0.0, 180.0));
+        assert_eq!(entities(&text, "LINE").len(), 1);
+    }
+}
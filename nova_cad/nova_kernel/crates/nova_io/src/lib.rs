@@ -4,11 +4,13 @@
 //! - STEP AP214/AP242 (.step, .stp)
 //! - IGES (.igs, .iges)
 //! - STL (.stl) - export only
+//! - DXF (.dxf) - 2D curve export only
 //! - Native (.nova)
 
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
+use nova_math::Transform3;
 use nova_topo::Body;
 use std::path::Path;
 use thiserror::Error;
@@ -21,11 +23,13 @@ pub use nova_topo::TopologyError;
 pub mod step;
 pub mod iges;
 pub mod stl;
+pub mod dxf;
 pub mod nova_format;
 
-pub use step::{StepReader, StepWriter, StepSchema, StepError, StepEntity, StepAttribute};
+pub use step::{StepReader, StepWriter, StepSchema, StepError, StepEntity, StepAttribute, StepFile};
 pub use iges::{IgesReader, IgesWriter};
 pub use stl::{StlWriter, StlFormat, StlTriangle};
+pub use dxf::DxfWriter;
 pub use nova_format::{NovaReader, NovaWriter, NovaFile, NovaMetadata};
 
 /// I/O error types
@@ -113,6 +117,45 @@ impl FileFormat {
         }
     }
     
+    /// Detect format from file contents
+    ///
+    /// Recognizes the ISO-10303-21 header of STEP files, ASCII and binary
+    /// STL, and the section flag in column 73 of IGES records. Returns `None`
+    /// when the bytes match none of them.
+    pub fn from_content(bytes: &[u8]) -> Option<Self> {
+        // Binary STL: 80-byte header, triangle count, 50 bytes per triangle.
+        // Checked first since the header may itself start with "solid".
+        if bytes.len() >= 84 {
+            let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+            if count.checked_mul(50).and_then(|n| n.checked_add(84)) == Some(bytes.len()) {
+                return Some(FileFormat::StlBinary);
+            }
+        }
+
+        let head = &bytes[..bytes.len().min(4096)];
+        let text = String::from_utf8_lossy(head);
+        let trimmed = text.trim_start_matches('\u{feff}').trim_start();
+
+        if trimmed.starts_with("ISO-10303-21") {
+            return Some(if trimmed.contains("AP242") {
+                FileFormat::StepAP242
+            } else {
+                FileFormat::StepAP214
+            });
+        }
+
+        if trimmed.starts_with("solid") && trimmed.contains("facet") {
+            return Some(FileFormat::StlAscii);
+        }
+
+        let first_line = text.lines().next().unwrap_or("");
+        if first_line.len() >= 73 && matches!(first_line.as_bytes()[72], b'S' | b'G') {
+            return Some(FileFormat::Iges);
+        }
+
+        None
+    }
+
     /// Get file extension for format
     pub fn extension(&self) -> &'static str {
         match self {
@@ -139,29 +182,47 @@ impl FileFormat {
     }
 }
 
+/// Healing tolerance used when neither the options nor the file set one
+pub const DEFAULT_IMPORT_TOLERANCE: f64 = 1e-6;
+
 /// Import options
 #[derive(Debug, Clone)]
 pub struct ImportOptions {
-    /// Tolerance for healing
-    pub tolerance: f64,
-    /// Whether to heal the geometry
+    /// Tolerance for healing; `None` uses the precision the file declares,
+    /// or [`DEFAULT_IMPORT_TOLERANCE`] if it declares none
+    pub tolerance: Option<f64>,
+    /// Whether to heal the geometry with `nova_check::repair`
     pub heal: bool,
+    /// Whether to validate each body after conversion and healing
+    pub validate: bool,
+    /// Whether bodies failing validation are rejected rather than kept
+    pub strict: bool,
     /// Whether to stitch faces into solids
     pub stitch: bool,
     /// Units to convert to (mm, inch, etc.)
     pub target_units: Units,
+    /// Transform applied to imported geometry, e.g. Z-up to Y-up
+    pub axis_transform: Option<Transform3>,
 }
 
 impl ImportOptions {
     /// Create default import options
     pub fn new() -> Self {
         Self {
-            tolerance: 1e-6,
+            tolerance: None,
             heal: true,
+            validate: false,
+            strict: true,
             stitch: true,
             target_units: Units::Millimeters,
+            axis_transform: None,
         }
     }
+    
+    /// Effective tolerance for a file declaring `declared` precision
+    pub fn tolerance_or(&self, declared: Option<f64>) -> f64 {
+        self.tolerance.or(declared).unwrap_or(DEFAULT_IMPORT_TOLERANCE)
+    }
 }
 
 impl Default for ImportOptions {
@@ -181,6 +242,14 @@ pub struct ExportOptions {
     pub author: Option<String>,
     /// Description
     pub description: Option<String>,
+    /// Transform applied to geometry before writing, e.g. Y-up to Z-up
+    pub axis_transform: Option<Transform3>,
+    /// Order of each facet's vertices seen from outside the solid
+    pub winding: Winding,
+    /// Write facet normals pointing into the solid
+    pub flip_normals: bool,
+    /// Gzip native .nova output
+    pub compress: bool,
 }
 
 impl ExportOptions {
@@ -191,6 +260,10 @@ impl ExportOptions {
             units: Units::Millimeters,
             author: None,
             description: None,
+            axis_transform: None,
+            winding: Winding::CounterClockwise,
+            flip_normals: false,
+            compress: false,
         }
     }
 }
@@ -201,37 +274,30 @@ impl Default for ExportOptions {
     }
 }
 
-/// Units for import/export
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum Units {
-    /// Millimeters
-    Millimeters,
-    /// Centimeters
-    Centimeters,
-    /// Meters
-    Meters,
-    /// Inches
-    Inches,
-    /// Feet
-    Feet,
+/// Vertex order of exported facets, seen from outside the solid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Winding {
+    /// Right-handed about the outward normal, as STL expects
+    #[default]
+    CounterClockwise,
+    /// Left-handed about the outward normal
+    Clockwise,
 }
 
-impl Units {
-    /// Get conversion factor to millimeters
-    pub fn to_mm_factor(&self) -> f64 {
-        match self {
-            Units::Millimeters => 1.0,
-            Units::Centimeters => 10.0,
-            Units::Meters => 1000.0,
-            Units::Inches => 25.4,
-            Units::Feet => 304.8,
-        }
-    }
-    
-    /// Get conversion factor from millimeters
-    pub fn from_mm_factor(&self) -> f64 {
-        1.0 / self.to_mm_factor()
-    }
+/// Units for import/export
+pub use nova_math::Units;
+
+/// Outcome of [`Importer::import_with_report`]
+#[derive(Debug, Clone, Default)]
+pub struct ImportReport {
+    /// Bodies returned to the caller
+    pub bodies: Vec<Body>,
+    /// Validation of each of `bodies`, empty unless validating
+    pub validation: Vec<nova_check::ValidationResult>,
+    /// Bodies that failed validation under `strict`, with their errors
+    pub rejected: Vec<(Body, nova_check::ValidationResult)>,
+    /// Fixes made to each body read, in file order, empty unless healing
+    pub repairs: Vec<nova_check::RepairReport>,
 }
 
 /// Universal importer
@@ -239,13 +305,31 @@ pub struct Importer;
 
 impl Importer {
     /// Import a file and return bodies
+    ///
+    /// Bodies rejected by validation are dropped; use
+    /// [`import_with_report`](Self::import_with_report) to see them.
     pub fn import<P: AsRef<Path>>(
         path: P,
         options: &ImportOptions,
     ) -> IoResult<Vec<Body>> {
+        Self::import_with_report(path, options).map(|report| report.bodies)
+    }
+
+    /// Import a file, then heal and validate its bodies as `options` ask
+    ///
+    /// With `validate` set, bodies with validation errors go to
+    /// `rejected` under `strict` and stay in `bodies` otherwise.
+    pub fn import_with_report<P: AsRef<Path>>(
+        path: P,
+        options: &ImportOptions,
+    ) -> IoResult<ImportReport> {
+        let bytes = std::fs::read(&path)
+            .map_err(|e| IoError::FileNotFound(e.to_string()))?;
+        
         let format = FileFormat::from_path(&path)
+            .or_else(|| FileFormat::from_content(&bytes))
             .ok_or_else(|| IoError::UnsupportedFormat(
-                "Unknown file extension".to_string()
+                "Unknown file format".to_string()
             ))?;
         
         if !format.supports_import() {
@@ -254,26 +338,64 @@ impl Importer {
             ));
         }
         
-        let content = std::fs::read_to_string(&path)
-            .map_err(|e| IoError::FileNotFound(e.to_string()))?;
+        // Native files may be compressed, so only text formats decode here
+        let content = || std::str::from_utf8(&bytes)
+            .map_err(|e| IoError::ParseError(e.to_string()));
         
-        match format {
+        let mut bodies = match format {
             FileFormat::StepAP214 | FileFormat::StepAP242 => {
                 let reader = StepReader::new();
-                reader.read(&content, options)
+                reader.read(content()?, options)
             }
             FileFormat::Iges => {
                 let reader = IgesReader::new();
-                reader.read(&content, options)
+                reader.read(content()?, options)
             }
             FileFormat::Nova => {
                 let reader = NovaReader::new();
-                reader.read(&content, options)
+                reader.read_bytes(&bytes, options)
             }
             _ => Err(IoError::UnsupportedFormat(
                 "Format not supported for import".to_string()
             ))
+        }?;
+        
+        if let Some(transform) = &options.axis_transform {
+            bodies = bodies.iter().map(|body| body.transformed(transform)).collect();
         }
+        
+        let mut report = ImportReport::default();
+        if options.heal {
+            for body in &mut bodies {
+                // Readers stamp the file's precision onto vertices
+                let declared = body.vertices_iter().map(|v| v.tolerance()).reduce(f64::max);
+                let healing = nova_check::HealingOptions {
+                    tolerance: options.tolerance_or(declared),
+                    ..Default::default()
+                };
+                report.repairs.push(nova_check::repair(body, healing));
+            }
+        }
+        
+        if !options.validate {
+            report.bodies = bodies;
+            return Ok(report);
+        }
+        for body in bodies {
+            let declared = body.vertices_iter().map(|v| v.tolerance()).reduce(f64::max);
+            let validator = nova_check::Validator::with_options(nova_check::ValidationOptions {
+                tolerance: options.tolerance_or(declared),
+                ..Default::default()
+            });
+            let result = validator.validate(&body);
+            if options.strict && !result.is_valid() {
+                report.rejected.push((body, result));
+            } else {
+                report.bodies.push(body);
+                report.validation.push(result);
+            }
+        }
+        Ok(report)
     }
 }
 
@@ -298,14 +420,23 @@ impl Exporter {
             ));
         }
         
+        let moved: Vec<Body>;
+        let bodies = match &options.axis_transform {
+            Some(transform) => {
+                moved = bodies.iter().map(|body| body.transformed(transform)).collect();
+                &moved[..]
+            }
+            None => bodies,
+        };
+        
         let content = match format {
             FileFormat::StepAP214 | FileFormat::StepAP242 => {
                 let writer = StepWriter::new();
-                writer.write(bodies, options)?
+                writer.write(bodies, options)?.into_bytes()
             }
             FileFormat::Iges => {
                 let writer = IgesWriter::new();
-                writer.write(bodies, options)?
+                writer.write(bodies, options)?.into_bytes()
             }
             FileFormat::StlAscii | FileFormat::StlBinary => {
                 let format = if matches!(format, FileFormat::StlAscii) {
@@ -314,11 +445,11 @@ impl Exporter {
                     StlFormat::Binary
                 };
                 let writer = StlWriter::new(format);
-                writer.write(bodies, options)?
+                writer.write(bodies, options)?.into_bytes()
             }
             FileFormat::Nova => {
                 let writer = NovaWriter::new();
-                writer.write(bodies, options)?
+                writer.write_bytes(bodies, options)?
             }
         };
         
@@ -361,6 +492,120 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_file_format_from_content() {
+        let step = b"ISO-10303-21;\nHEADER;\nFILE_SCHEMA(('AUTOMOTIVE_DESIGN'));\nENDSEC;\n";
+        assert_eq!(FileFormat::from_path("part.dat"), None);
+        assert_eq!(FileFormat::from_content(step), Some(FileFormat::StepAP214));
+
+        // Binary STL whose header starts with "solid", saved as .txt
+        let mut stl = vec![0u8; 80];
+        stl[..5].copy_from_slice(b"solid");
+        stl.extend_from_slice(&2u32.to_le_bytes());
+        stl.extend(std::iter::repeat(0u8).take(100));
+        assert_eq!(FileFormat::from_path("mesh.txt"), None);
+        assert_eq!(FileFormat::from_content(&stl), Some(FileFormat::StlBinary));
+
+        let ascii = b"solid part\n  facet normal 0 0 1\n";
+        assert_eq!(FileFormat::from_content(ascii), Some(FileFormat::StlAscii));
+
+        let iges = format!("{:<72}S      1\n", "Nova part");
+        assert_eq!(FileFormat::from_content(iges.as_bytes()), Some(FileFormat::Iges));
+
+        assert_eq!(FileFormat::from_content(b"hello"), None);
+    }
+
+    #[test]
+    fn test_import_detects_step_without_extension() {
+        let path = std::env::temp_dir().join(format!("nova_io_sniff_{}.dat", std::process::id()));
+        std::fs::write(&path, "ISO-10303-21;\nHEADER;\nENDSEC;\nDATA;\nENDSEC;\nEND-ISO-10303-21;\n").unwrap();
+        let result = Importer::import(&path, &ImportOptions::new());
+        std::fs::remove_file(&path).ok();
+        assert!(!matches!(result, Err(IoError::UnsupportedFormat(_))));
+    }
+
+    #[test]
+    fn test_import_axis_transform() {
+        use nova_math::{Point3, Vec3};
+        use nova_topo::{Coedge, Edge, Face, Loop, Sense, Vertex};
+        use std::sync::Arc;
+
+        let v: Vec<_> = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))))
+            .into();
+        let coedges = (0..4)
+            .map(|i| Coedge::new(Arc::new(Edge::new(v[i].clone(), v[(i + 1) % 4].clone())), Sense::Same))
+            .collect();
+        let mut profile = Face::new();
+        profile.add_loop(Loop::from_coedges(coedges));
+        let original = nova_topo::EulerAdvanced::extrude_face(&profile, Vec3::Z, 3.0).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("box.nova");
+        Exporter::export(&path, std::slice::from_ref(&original), &ExportOptions::new()).unwrap();
+
+        // Rotate -90 degrees about X so +Z becomes +Y
+        let options = ImportOptions {
+            axis_transform: Some(Transform3::from_axis_angle(&Vec3::X, -std::f64::consts::FRAC_PI_2)),
+            ..ImportOptions::new()
+        };
+        let bodies = Importer::import(&path, &options).unwrap();
+        let (before, after) = (original.vertices(), bodies[0].vertices());
+        assert_eq!(before.len(), after.len());
+        for (a, b) in before.iter().zip(&after) {
+            let (p, q) = (a.position(), b.position());
+            let expected = Point3::new(p.x(), p.z(), -p.y());
+            assert!((q - expected).length() < 1e-9, "{:?} -> {:?}", p, q);
+        }
+        let top = after.iter().map(|v| v.position().y()).fold(f64::MIN, f64::max);
+        assert!((top - 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_import_heals_and_validates() {
+        use nova_math::{Point3, Vec3};
+        use nova_topo::{Coedge, Edge, Face, Loop, Sense, Vertex};
+        use std::sync::Arc;
+
+        // An extruded box before welding: each face has its own copy of
+        // every edge, so no edge is shared by two faces
+        let v: Vec<_> = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))))
+            .into();
+        let coedges = (0..4)
+            .map(|i| Coedge::new(Arc::new(Edge::new(v[i].clone(), v[(i + 1) % 4].clone())), Sense::Same))
+            .collect();
+        let plane = nova_geom::PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap();
+        let mut profile = Face::with_surface(Arc::new(plane));
+        profile.add_loop(Loop::from_coedges(coedges));
+        let unwelded = nova_topo::EulerAdvanced::extrude_face(&profile, Vec3::Z, 3.0).unwrap();
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("box.step");
+        Exporter::export(&path, &[unwelded], &ExportOptions::new()).unwrap();
+
+        let raw = ImportOptions { heal: false, validate: true, ..ImportOptions::new() };
+        let report = Importer::import_with_report(&path, &raw).unwrap();
+        assert!(report.bodies.is_empty() && report.repairs.is_empty());
+        assert_eq!(report.rejected.len(), 1);
+        assert!(!report.rejected[0].1.is_valid());
+        assert!(Importer::import(&path, &raw).unwrap().is_empty());
+
+        let lenient = ImportOptions { strict: false, ..raw.clone() };
+        let report = Importer::import_with_report(&path, &lenient).unwrap();
+        assert_eq!(report.bodies.len(), 1);
+        assert!(!report.validation[0].is_valid());
+
+        let healed = ImportOptions { heal: true, ..raw };
+        let report = Importer::import_with_report(&path, &healed).unwrap();
+        assert!(report.rejected.is_empty());
+        assert_eq!(report.bodies.len(), 1);
+        assert!(report.validation[0].is_valid(), "{:?}", report.validation[0].errors);
+        assert!(report.repairs[0].welded_vertices > 0);
+        assert_eq!(report.bodies[0].edges().len(), 12);
+        assert!(nova_check::Validator::new().validate(&report.bodies[0]).is_valid());
+    }
+
     #[test]
     fn test_units_conversion() {
         assert_eq!(Units::Millimeters.to_mm_factor(), 1.0);
@@ -373,8 +618,10 @@ mod tests {
     #[test]
     fn test_import_export_options() {
         let import_opts = ImportOptions::new();
-        assert_eq!(import_opts.tolerance, 1e-6);
+        assert_eq!(import_opts.tolerance_or(None), 1e-6);
+        assert_eq!(import_opts.tolerance_or(Some(0.01)), 0.01);
         assert!(import_opts.heal);
+        assert!(!import_opts.validate && import_opts.strict);
         
         let export_opts = ExportOptions::new();
         assert_eq!(export_opts.tolerance, 1e-3);
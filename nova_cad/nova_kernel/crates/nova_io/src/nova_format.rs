@@ -4,8 +4,16 @@
 //! of B-Rep data with full fidelity.
 
 use crate::{IoError, IoResult, ImportOptions, ExportOptions};
-use nova_topo::{Body, Entity};
+use nova_geom::nurbs::{NurbsCurve, NurbsSurface};
+use nova_geom::{Curve, Surface, Line, CircularArc, EllipseArc, PolylineCurve,
+                PlanarSurface, CylindricalSurface, SphericalSurface, ConicalSurface, ToroidalSurface};
+use nova_math::Point3;
+use nova_topo::{Body, Coedge, Edge, Entity, Face, Loop, Orientation, Sense, Shell, Vertex};
 use serde::{Serialize, Deserialize};
+use serde::de::DeserializeOwned;
+use std::collections::{HashMap, HashSet};
+use std::io::{Read, Write};
+use std::sync::Arc;
 
 /// Native Nova format reader
 #[derive(Debug, Clone)]
@@ -16,7 +24,16 @@ pub struct NovaReader;
 pub struct NovaWriter;
 
 /// Nova file format version
-pub const NOVA_FORMAT_VERSION: u32 = 1;
+///
+/// Version 2 added the vertex and edge tables, real geometry and explicit
+/// face orientation and coedge senses.
+pub const NOVA_FORMAT_VERSION: u32 = 2;
+
+/// Leading bytes of a gzip stream, telling compressed files from plain JSON
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Color written for bodies that have none
+const DEFAULT_BODY_COLOR: nova_topo::Color = [0.8, 0.8, 0.8, 1.0];
 
 /// Root structure for Nova files
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -57,6 +74,10 @@ pub struct NovaBody {
     pub color: [f32; 4],
     /// Shells in the body
     pub shells: Vec<NovaShell>,
+    /// Vertices used by the body's edges
+    pub vertices: Vec<NovaVertex>,
+    /// Edges used by the body's coedges and wires
+    pub edges: Vec<NovaEdge>,
 }
 
 /// Serialized shell representation
@@ -66,6 +87,10 @@ pub struct NovaShell {
     pub id: u64,
     /// Faces in the shell
     pub faces: Vec<NovaFace>,
+    /// Edges of the shell's wires
+    pub wires: Vec<u64>,
+    /// Outer shell, or a void inside the body
+    pub is_outer: bool,
 }
 
 /// Serialized face representation
@@ -77,6 +102,8 @@ pub struct NovaFace {
     pub surface_type: String,
     /// Surface data (type-specific)
     pub surface_data: serde_json::Value,
+    /// Whether the face normal follows or opposes the surface normal
+    pub orientation: Orientation,
     /// Loops defining face boundary
     pub loops: Vec<NovaLoop>,
     /// Face color (optional)
@@ -99,8 +126,8 @@ pub struct NovaLoop {
 pub struct NovaCoedge {
     /// Edge reference
     pub edge_id: u64,
-    /// Orientation sense
-    pub sense: bool, // true = same, false = opposite
+    /// Direction of the coedge along its edge
+    pub sense: Sense,
 }
 
 /// Serialized edge representation
@@ -137,9 +164,30 @@ impl NovaReader {
         Self
     }
     
+    /// Read raw Nova file bytes, gunzipping them first if compressed
+    pub fn read_bytes(&self, bytes: &[u8], options: &ImportOptions) -> IoResult<Vec<Body>> {
+        if bytes.starts_with(&GZIP_MAGIC) {
+            let mut content = String::new();
+            flate2::read::GzDecoder::new(bytes).read_to_string(&mut content)
+                .map_err(|e| IoError::ParseError(format!("Failed to decompress Nova file: {}", e)))?;
+            return self.read(&content, options);
+        }
+        let content = std::str::from_utf8(bytes)
+            .map_err(|e| IoError::ParseError(e.to_string()))?;
+        self.read(content, options)
+    }
+
     /// Read Nova file content and return bodies
     pub fn read(&self, content: &str, _options: &ImportOptions) -> IoResult<Vec<Body>> {
-        let nova_file: NovaFile = serde_json::from_str(content)
+        let version: serde_json::Value = serde_json::from_str(content)
+            .map_err(|e| IoError::ParseError(format!("Failed to parse Nova file: {}", e)))?;
+        match version.get("version").and_then(|v| v.as_u64()) {
+            Some(v) if v == NOVA_FORMAT_VERSION as u64 => {}
+            other => return Err(IoError::UnsupportedFormat(format!(
+                "Nova format version {:?}, expected {}", other, NOVA_FORMAT_VERSION
+            ))),
+        }
+        let nova_file: NovaFile = serde_json::from_value(version)
             .map_err(|e| IoError::ParseError(format!("Failed to parse Nova file: {}", e)))?;
         
         // Convert NovaFile to Bodies
@@ -163,13 +211,92 @@ impl NovaReader {
     }
     
     /// Convert NovaBody to Body
+    ///
+    /// Vertices record their edges and edges their coedges before either is
+    /// shared, so edges start out on a placeholder vertex and coedges on a
+    /// placeholder edge, and are pointed at the real ones once all are built.
     fn convert_body(&self, nova_body: &NovaBody) -> IoResult<Body> {
-        // TODO: Implement full conversion from NovaBody to Body
-        // This requires reconstructing the full B-Rep topology
-        
-        Err(IoError::UnsupportedFormat(
-            "Nova body conversion not yet implemented".to_string()
-        ))
+        let mut vertices = Vec::with_capacity(nova_body.vertices.len());
+        let mut vertex_index = HashMap::new();
+        for (i, v) in nova_body.vertices.iter().enumerate() {
+            let mut vertex = Vertex::new(Point3::new(v.position[0], v.position[1], v.position[2]));
+            vertex.set_tolerance(v.tolerance);
+            vertices.push(vertex);
+            vertex_index.insert(v.id, i);
+        }
+        let find_vertex = |id: u64| vertex_index.get(&id).copied()
+            .ok_or_else(|| IoError::InvalidData(format!("Unknown vertex {}", id)));
+
+        let placeholder_vertex = Arc::new(Vertex::new(Point3::ORIGIN));
+        let mut edges = Vec::with_capacity(nova_body.edges.len());
+        let mut edge_ends = Vec::with_capacity(nova_body.edges.len());
+        let mut edge_index = HashMap::new();
+        for (i, e) in nova_body.edges.iter().enumerate() {
+            let (start, end) = (find_vertex(e.start_vertex)?, find_vertex(e.end_vertex)?);
+            let mut edge = Edge::new(placeholder_vertex.clone(), placeholder_vertex.clone());
+            edge.set_curve(decode_curve(&e.curve_type, &e.curve_data)?);
+            edge.set_tolerance(e.tolerance);
+            vertices[start].add_edge(edge.id());
+            vertices[end].add_edge(edge.id());
+            edges.push(edge);
+            edge_ends.push((start, end));
+            edge_index.insert(e.id, i);
+        }
+        let find_edge = |id: u64| edge_index.get(&id).copied()
+            .ok_or_else(|| IoError::InvalidData(format!("Unknown edge {}", id)));
+
+        // Coedges in shell, face, loop order, with the edge each one uses
+        let placeholder_edge = Arc::new(Edge::new(placeholder_vertex.clone(), placeholder_vertex));
+        let mut coedge_edges = Vec::new();
+        let mut shells = Vec::with_capacity(nova_body.shells.len());
+        for nova_shell in &nova_body.shells {
+            let mut shell = if nova_shell.is_outer { Shell::new() } else { Shell::void() };
+            for nova_face in &nova_shell.faces {
+                let mut face = match decode_surface(&nova_face.surface_type, &nova_face.surface_data)? {
+                    Some(surface) => Face::with_surface(surface),
+                    None => Face::new(),
+                };
+                face.set_same_sense(nova_face.orientation.is_forward());
+                face.set_color(nova_face.color);
+                for nova_loop in &nova_face.loops {
+                    let mut lp = Loop::new();
+                    for nova_coedge in &nova_loop.coedges {
+                        let edge = find_edge(nova_coedge.edge_id)?;
+                        let coedge = Coedge::new(placeholder_edge.clone(), nova_coedge.sense);
+                        edges[edge].add_coedge(coedge.id());
+                        coedge_edges.push(edge);
+                        lp.add_coedge(coedge);
+                    }
+                    face.add_loop(lp);
+                }
+                shell.add_face(face);
+            }
+            shells.push(shell);
+        }
+
+        let vertices: Vec<Arc<Vertex>> = vertices.into_iter().map(Arc::new).collect();
+        let edges: Vec<Arc<Edge>> = edges.iter().zip(&edge_ends)
+            .map(|(edge, &(start, end))| Arc::new(edge.remapped(vertices[start].clone(), vertices[end].clone())))
+            .collect();
+        let mut uses = coedge_edges.into_iter();
+        let mut body = Body::new();
+        body.set_color(Some(nova_body.color));
+        for (mut shell, nova_shell) in shells.into_iter().zip(&nova_body.shells) {
+            for face in shell.faces_mut() {
+                for lp in face.loops_mut() {
+                    for coedge in lp.coedges_mut() {
+                        if let Some(edge) = uses.next() {
+                            coedge.set_edge(edges[edge].clone());
+                        }
+                    }
+                }
+            }
+            for &wire in &nova_shell.wires {
+                shell.add_wire(edges[find_edge(wire)?].clone());
+            }
+            body.add_shell(shell);
+        }
+        Ok(body)
     }
 }
 
@@ -192,6 +319,18 @@ impl NovaWriter {
         serde_json::to_string_pretty(&nova_file)
             .map_err(|e| IoError::WriteError(format!("Failed to serialize Nova file: {}", e)))
     }
+
+    /// Write bodies to Nova file bytes, gzipped when `options.compress` is set
+    pub fn write_bytes(&self, bodies: &[Body], options: &ExportOptions) -> IoResult<Vec<u8>> {
+        let content = self.write(bodies, options)?;
+        if !options.compress {
+            return Ok(content.into_bytes());
+        }
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder.write_all(content.as_bytes())
+            .and_then(|_| encoder.finish())
+            .map_err(|e| IoError::WriteError(format!("Failed to compress Nova file: {}", e)))
+    }
     
     /// Convert Bodies to NovaFile
     fn convert_to_bodies(&self, bodies: &[Body], options: &ExportOptions) -> IoResult<NovaFile> {
@@ -230,43 +369,50 @@ impl NovaWriter {
     
     /// Convert Body to NovaBody
     fn convert_body(&self, body: &Body, id: u64) -> IoResult<NovaBody> {
+        let mut tables = EdgeTables::default();
         let mut shells = Vec::new();
         
         for (i, shell) in body.shells().iter().enumerate() {
-            let nova_shell = self.convert_shell(shell, id * 1000 + i as u64)?;
+            let nova_shell = self.convert_shell(shell, id * 1000 + i as u64, &mut tables)?;
             shells.push(nova_shell);
         }
         
         Ok(NovaBody {
             id,
             name: format!("Body_{}", id),
-            color: [0.8, 0.8, 0.8, 1.0], // Default gray
+            color: body.color().unwrap_or(DEFAULT_BODY_COLOR),
             shells,
+            vertices: tables.vertices,
+            edges: tables.edges,
         })
     }
     
     /// Convert Shell to NovaShell
-    fn convert_shell(&self, shell: &nova_topo::Shell, id: u64) -> IoResult<NovaShell> {
+    fn convert_shell(&self, shell: &Shell, id: u64, tables: &mut EdgeTables) -> IoResult<NovaShell> {
         let mut faces = Vec::new();
         
         for (i, face) in shell.faces().iter().enumerate() {
-            let nova_face = self.convert_face(face, id * 1000 + i as u64)?;
+            let nova_face = self.convert_face(face, id * 1000 + i as u64, tables)?;
             faces.push(nova_face);
         }
+        let mut wires = Vec::new();
+        for edge in shell.wires() {
+            wires.push(tables.add_edge(edge)?);
+        }
         
-        Ok(NovaShell { id, faces })
+        Ok(NovaShell { id, faces, wires, is_outer: shell.is_outer() })
     }
     
     /// Convert Face to NovaFace
-    fn convert_face(&self, face: &nova_topo::Face, id: u64) -> IoResult<NovaFace> {
-        let surface = face.surface().ok_or_else(|| IoError::InvalidData("Face has no surface".to_string()))?;
-        
-        // Determine surface type and serialize
-        let (surface_type, surface_data) = self.serialize_surface(&**surface)?;
+    fn convert_face(&self, face: &Face, id: u64, tables: &mut EdgeTables) -> IoResult<NovaFace> {
+        let (surface_type, surface_data) = match face.surface() {
+            Some(surface) => encode_surface(surface.as_ref())?,
+            None => ("NONE".to_string(), serde_json::Value::Null),
+        };
         
         let mut loops = Vec::new();
         for (i, loop_) in face.loops().iter().enumerate() {
-            let nova_loop = self.convert_loop(loop_, id * 1000 + i as u64)?;
+            let nova_loop = self.convert_loop(loop_, id * 1000 + i as u64, i == 0, tables)?;
             loops.push(nova_loop);
         }
         
@@ -274,32 +420,20 @@ impl NovaWriter {
             id,
             surface_type,
             surface_data,
+            orientation: if face.same_sense() { Orientation::Forward } else { Orientation::Reversed },
             loops,
-            color: None,
+            color: face.color(),
         })
     }
     
-    /// Serialize surface to JSON
-    fn serialize_surface(&self, surface: &dyn nova_geom::Surface) -> IoResult<(String, serde_json::Value)> {
-        // TODO: Implement proper surface serialization based on type
-        
-        let surface_type = "PLANE".to_string();
-        let surface_data = serde_json::json!({
-            "origin": [0.0, 0.0, 0.0],
-            "normal": [0.0, 0.0, 1.0],
-        });
-        
-        Ok((surface_type, surface_data))
-    }
-    
     /// Convert Loop to NovaLoop
-    fn convert_loop(&self, loop_: &nova_topo::Loop, id: u64) -> IoResult<NovaLoop> {
+    fn convert_loop(&self, loop_: &Loop, id: u64, is_outer: bool, tables: &mut EdgeTables) -> IoResult<NovaLoop> {
         let mut coedges = Vec::new();
         
         for coedge in loop_.coedges() {
             let nova_coedge = NovaCoedge {
-                edge_id: coedge.edge().id().0,
-                sense: matches!(coedge.sense(), nova_topo::Sense::Same),
+                edge_id: tables.add_edge(coedge.edge())?,
+                sense: coedge.sense(),
             };
             coedges.push(nova_coedge);
         }
@@ -307,11 +441,125 @@ impl NovaWriter {
         Ok(NovaLoop {
             id,
             coedges,
-            is_outer: true, // TODO: Determine if outer loop
+            is_outer,
         })
     }
 }
 
+/// Vertices and edges of a body being written, each listed once
+#[derive(Default)]
+struct EdgeTables {
+    vertices: Vec<NovaVertex>,
+    edges: Vec<NovaEdge>,
+    seen: HashSet<u64>,
+}
+
+impl EdgeTables {
+    /// List `edge` and its vertices if not yet listed, returning its id
+    fn add_edge(&mut self, edge: &Edge) -> IoResult<u64> {
+        let id = edge.id().0;
+        if !self.seen.insert(id) {
+            return Ok(id);
+        }
+        for vertex in [edge.start_vertex(), edge.end_vertex()] {
+            if self.seen.insert(vertex.id().0) {
+                let p = vertex.position();
+                self.vertices.push(NovaVertex {
+                    id: vertex.id().0,
+                    position: [p.x(), p.y(), p.z()],
+                    tolerance: vertex.tolerance(),
+                });
+            }
+        }
+        let (curve_type, curve_data) = match edge.curve() {
+            Some(curve) => encode_curve(curve.as_ref())?,
+            None => ("NONE".to_string(), serde_json::Value::Null),
+        };
+        self.edges.push(NovaEdge {
+            id,
+            start_vertex: edge.start_vertex().id().0,
+            end_vertex: edge.end_vertex().id().0,
+            curve_type,
+            curve_data,
+            tolerance: edge.tolerance(),
+        });
+        Ok(id)
+    }
+}
+
+/// Type name and data of a surface
+fn encode_surface(surface: &dyn Surface) -> IoResult<(String, serde_json::Value)> {
+    let any = surface.as_any();
+    let (name, data) = if let Some(s) = any.downcast_ref::<PlanarSurface>() {
+        ("PLANE", serde_json::to_value(s))
+    } else if let Some(s) = any.downcast_ref::<CylindricalSurface>() {
+        ("CYLINDER", serde_json::to_value(s))
+    } else if let Some(s) = any.downcast_ref::<SphericalSurface>() {
+        ("SPHERE", serde_json::to_value(s))
+    } else if let Some(s) = any.downcast_ref::<ConicalSurface>() {
+        ("CONE", serde_json::to_value(s))
+    } else if let Some(s) = any.downcast_ref::<ToroidalSurface>() {
+        ("TORUS", serde_json::to_value(s))
+    } else if let Some(s) = any.downcast_ref::<NurbsSurface>() {
+        ("NURBS", serde_json::to_value(s))
+    } else {
+        return Err(IoError::UnsupportedFormat(format!("{:?} surfaces", surface.surface_type())));
+    };
+    let data = data.map_err(|e| IoError::WriteError(e.to_string()))?;
+    Ok((name.to_string(), data))
+}
+
+/// Surface from its type name and data, None for "NONE"
+fn decode_surface(name: &str, data: &serde_json::Value) -> IoResult<Option<Arc<dyn Surface>>> {
+    Ok(Some(match name {
+        "NONE" => return Ok(None),
+        "PLANE" => Arc::new(decode::<PlanarSurface>(data)?),
+        "CYLINDER" => Arc::new(decode::<CylindricalSurface>(data)?),
+        "SPHERE" => Arc::new(decode::<SphericalSurface>(data)?),
+        "CONE" => Arc::new(decode::<ConicalSurface>(data)?),
+        "TORUS" => Arc::new(decode::<ToroidalSurface>(data)?),
+        "NURBS" => Arc::new(decode::<NurbsSurface>(data)?),
+        _ => return Err(IoError::ParseError(format!("Unknown surface type {}", name))),
+    }))
+}
+
+/// Type name and data of a curve
+fn encode_curve(curve: &dyn Curve) -> IoResult<(String, serde_json::Value)> {
+    let any = curve.as_any();
+    let (name, data) = if let Some(c) = any.downcast_ref::<Line>() {
+        ("LINE", serde_json::to_value(c))
+    } else if let Some(c) = any.downcast_ref::<CircularArc>() {
+        ("CIRCULAR_ARC", serde_json::to_value(c))
+    } else if let Some(c) = any.downcast_ref::<EllipseArc>() {
+        ("ELLIPSE_ARC", serde_json::to_value(c))
+    } else if let Some(c) = any.downcast_ref::<PolylineCurve>() {
+        ("POLYLINE", serde_json::to_value(c))
+    } else if let Some(c) = any.downcast_ref::<NurbsCurve>() {
+        ("NURBS", serde_json::to_value(c))
+    } else {
+        return Err(IoError::UnsupportedFormat(format!("{:?} curves", curve.curve_type())));
+    };
+    let data = data.map_err(|e| IoError::WriteError(e.to_string()))?;
+    Ok((name.to_string(), data))
+}
+
+/// Curve from its type name and data, None for "NONE"
+fn decode_curve(name: &str, data: &serde_json::Value) -> IoResult<Option<Arc<dyn Curve>>> {
+    Ok(Some(match name {
+        "NONE" => return Ok(None),
+        "LINE" => Arc::new(decode::<Line>(data)?),
+        "CIRCULAR_ARC" => Arc::new(decode::<CircularArc>(data)?),
+        "ELLIPSE_ARC" => Arc::new(decode::<EllipseArc>(data)?),
+        "POLYLINE" => Arc::new(decode::<PolylineCurve>(data)?),
+        "NURBS" => Arc::new(decode::<NurbsCurve>(data)?),
+        _ => return Err(IoError::ParseError(format!("Unknown curve type {}", name))),
+    }))
+}
+
+fn decode<T: DeserializeOwned>(data: &serde_json::Value) -> IoResult<T> {
+    T::deserialize(data).map_err(|e| IoError::ParseError(e.to_string()))
+}
+
 impl Default for NovaWriter {
     fn default() -> Self {
         Self::new()
@@ -356,4 +604,94 @@ mod tests {
         // Empty bodies is valid, just produces empty file
         assert!(result.is_ok());
     }
+
+    /// 2 x 1 x 1 box with shared edges, its top face rebuilt on a plane
+    /// facing down and flagged reversed so it still faces out
+    fn mixed_box() -> Body {
+        use nova_math::Vec3;
+        let v: Vec<_> = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))))
+            .into();
+        let coedges = (0..4)
+            .map(|i| Coedge::new(Arc::new(Edge::new(v[i].clone(), v[(i + 1) % 4].clone())), Sense::Same))
+            .collect();
+        let mut profile = Face::new();
+        profile.add_loop(Loop::from_coedges(coedges));
+        let mut body = nova_topo::EulerAdvanced::extrude_face(&profile, Vec3::Z, 1.0).unwrap();
+        nova_check::weld_vertices(&mut body, 1e-9);
+
+        let top = body.faces().iter().position(|f| f.normal_at(0.0, 0.0).z() > 0.5).unwrap();
+        let face = &mut body.shells_mut()[0].faces_mut()[top];
+        let down = PlanarSurface::new(Point3::new(0.0, 0.0, 1.0), Vec3::Y, Vec3::X).unwrap();
+        face.set_surface(Some(Arc::new(down)));
+        face.set_same_sense(false);
+        body
+    }
+
+    #[test]
+    fn test_orientation_round_trip() {
+        let original = mixed_box();
+        let tolerance = nova_math::ToleranceContext::default();
+        assert!(nova_topo::validate_body(&original, &tolerance).unwrap().is_empty());
+        let senses = |body: &Body| -> Vec<Vec<Sense>> {
+            body.faces().iter()
+                .map(|f| f.loops().iter().flat_map(|lp| lp.coedges()).map(|c| c.sense()).collect())
+                .collect()
+        };
+        let original_senses = senses(&original);
+        assert!(original_senses.concat().contains(&Sense::Same));
+        assert!(original_senses.concat().contains(&Sense::Opposite));
+
+        let json = NovaWriter::new().write(std::slice::from_ref(&original), &ExportOptions::new()).unwrap();
+        assert!(json.contains("Reversed"));
+        let bodies = NovaReader::new().read(&json, &ImportOptions::default()).unwrap();
+        assert_eq!(bodies.len(), 1);
+        let reloaded = &bodies[0];
+
+        let issues = nova_topo::validate_body(reloaded, &tolerance).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert_eq!(senses(reloaded), original_senses);
+        assert_eq!(reloaded.faces().len(), original.faces().len());
+        for (before, after) in original.faces().iter().zip(reloaded.faces()) {
+            assert_eq!(after.same_sense(), before.same_sense());
+            let (n0, n1) = (before.outward_normal_at(0.0, 0.0, &original), after.outward_normal_at(0.0, 0.0, reloaded));
+            assert!((n0 - n1).length() < 1e-12, "{:?} != {:?}", n0, n1);
+        }
+        let volume = |body: &Body| nova_topo::body_volume(body).unwrap();
+        assert!((volume(reloaded) - volume(&original)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_colors_round_trip() {
+        let mut body = mixed_box();
+        body.set_color(Some([1.0, 0.0, 0.0, 1.0]));
+        body.shells_mut()[0].faces_mut()[3].set_color(Some([0.0, 0.5, 1.0, 0.25]));
+
+        let json = NovaWriter::new().write(std::slice::from_ref(&body), &ExportOptions::new()).unwrap();
+        let reloaded = &NovaReader::new().read(&json, &ImportOptions::default()).unwrap()[0];
+        assert_eq!(reloaded.color(), body.color());
+        for (before, after) in body.faces().iter().zip(reloaded.faces()) {
+            assert_eq!(after.color(), before.color());
+        }
+    }
+
+    #[test]
+    fn test_compressed_round_trip() {
+        let body = mixed_box();
+        let plain = NovaWriter::new().write_bytes(std::slice::from_ref(&body), &ExportOptions::new()).unwrap();
+        let options = ExportOptions { compress: true, ..ExportOptions::default() };
+        let packed = NovaWriter::new().write_bytes(std::slice::from_ref(&body), &options).unwrap();
+        assert!(packed.starts_with(&GZIP_MAGIC));
+        assert!(packed.len() < plain.len(), "{} >= {}", packed.len(), plain.len());
+
+        let reader = NovaReader::new();
+        let unpacked = reader.read_bytes(&packed, &ImportOptions::default()).unwrap();
+        let direct = reader.read_bytes(&plain, &ImportOptions::default()).unwrap();
+        assert_eq!(unpacked.len(), 1);
+        let counts = |body: &Body| (body.faces().len(), body.edges().len(), body.vertices().len());
+        assert_eq!(counts(&unpacked[0]), counts(&direct[0]));
+        assert_eq!(counts(&unpacked[0]), counts(&body));
+        let volume = |body: &Body| nova_topo::body_volume(body).unwrap();
+        assert!((volume(&unpacked[0]) - volume(&body)).abs() < 1e-12);
+    }
 }
@@ -2,14 +2,62 @@
 //!
 //! Implements ISO 10303 (STEP) file format support for CAD data exchange.
 
-use crate::{IoError, IoResult, ImportOptions, ExportOptions};
-use nova_topo::{Body, Shell, Face, Loop, Coedge, Edge, Vertex, EulerOps, Sense, Orientation, Entity, new_entity_id};
-use nova_math::{Point3, Vec3, Transform3};
-use nova_geom::{Curve, Surface, PlanarSurface, CylindricalSurface, SphericalSurface, ConicalSurface, Line, CircularArc};
+use crate::{IoError, IoResult, ImportOptions, ExportOptions, Units};
+use nova_topo::{Body, Color, Shell, Face, Loop, Coedge, Edge, Vertex, EulerOps, Sense, Orientation, Entity, new_entity_id};
+use nova_math::{Point3, Vec3, Mat4, Transform3};
+use nova_geom::{Curve, CurveType, Surface, PlanarSurface, CylindricalSurface, SphericalSurface, ConicalSurface, Line, CircularArc};
+use nova_geom::nurbs::NurbsCurve;
 use nova_math::Plane;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+/// Samples per curved edge when measuring a face's extent
+const FACE_EXTENT_SAMPLES: usize = 16;
+
+/// Bound an unbounded cylinder to the heights its face spans
+///
+/// STEP cylinders are infinite, but their parameters run over a finite
+/// height, so the face's own extent is used instead. A seam edge, run
+/// along once each way by the face's loop, is where the face wraps round,
+/// so the angle parameter is turned to start on it. Returns `None` for
+/// faces on anything but an unbounded cylinder.
+fn fit_cylinder_to_face(face: &Face) -> IoResult<Option<CylindricalSurface>> {
+    let Some(cylinder) = face.surface().and_then(|s| s.as_any().downcast_ref::<CylindricalSurface>()) else {
+        return Ok(None);
+    };
+    let (low, high) = cylinder.height_range();
+    if low.is_finite() && high.is_finite() {
+        return Ok(None);
+    }
+    let (origin, axis) = (cylinder.origin(), cylinder.axis());
+    let samples = |edge: &Edge| -> Vec<Point3> {
+        match edge.curve() {
+            Some(curve) if curve.curve_type() != CurveType::Line => {
+                let range = curve.param_range();
+                (0..=FACE_EXTENT_SAMPLES).map(|i| curve.evaluate(range.start + range.length() * i as f64 / FACE_EXTENT_SAMPLES as f64)).collect()
+            }
+            _ => vec![edge.start_vertex().position(), edge.end_vertex().position()],
+        }
+    };
+    let heights: Vec<f64> = face.loops().iter()
+        .flat_map(|lp| lp.coedges())
+        .flat_map(|c| samples(c.edge()))
+        .map(|p| (p - origin).dot(&axis))
+        .collect();
+    let (low, high) = heights.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(lo, hi), &h| (lo.min(h), hi.max(h)));
+    if heights.is_empty() || high - low <= 1e-12 {
+        return Ok(None);
+    }
+
+    let seams = face.seam_edges();
+    let seam = face.loops().iter().flat_map(|lp| lp.coedges()).map(|c| c.edge()).find(|e| seams.contains(&e.id()));
+    let reference = seam
+        .map(|edge| (edge.start_vertex().position() - origin).reject_from(&axis))
+        .filter(|r| !r.is_zero(1e-12))
+        .unwrap_or_else(|| cylinder.ref_direction());
+    Ok(Some(CylindricalSurface::bounded(origin, axis, cylinder.radius(), reference, low, high, 0.0, std::f64::consts::TAU)?))
+}
+
 /// STEP reader
 #[derive(Debug, Clone)]
 pub struct StepReader {
@@ -72,12 +120,23 @@ pub enum StepError {
 pub struct StepEntity {
     /// Entity ID
     pub id: u64,
-    /// Entity type name
+    /// Entity type name; a complex instance joins its parts' names with `+`
     pub entity_type: String,
-    /// Entity attributes
+    /// Entity attributes; a complex instance has one list per part
     pub attributes: Vec<StepAttribute>,
 }
 
+impl StepEntity {
+    /// Attributes of the part named `name` of a complex instance
+    fn part(&self, name: &str) -> Option<&[StepAttribute]> {
+        self.entity_type.split('+').position(|part| part == name)
+            .and_then(|i| match self.attributes.get(i) {
+                Some(StepAttribute::List(attributes)) => Some(&attributes[..]),
+                _ => None,
+            })
+    }
+}
+
 /// STEP attribute types
 #[derive(Debug, Clone)]
 pub enum StepAttribute {
@@ -141,17 +200,23 @@ struct StepToBrepsConverter<'a> {
     options: &'a ImportOptions,
     vertex_map: HashMap<u64, Arc<Vertex>>,
     edge_map: HashMap<u64, Arc<Edge>>,
+    curve_map: HashMap<u64, Arc<dyn Curve>>,
     surface_map: HashMap<u64, Arc<dyn Surface>>,
+    /// Tolerance stamped on converted vertices and edges
+    tolerance: f64,
 }
 
 impl<'a> StepToBrepsConverter<'a> {
     fn new(step_file: &'a StepFile, options: &'a ImportOptions) -> Self {
+        let declared = step_file.uncertainty().map(|u| u * options.target_units.to_mm_factor());
         Self {
             step_file,
             options,
             vertex_map: HashMap::new(),
             edge_map: HashMap::new(),
+            curve_map: HashMap::new(),
             surface_map: HashMap::new(),
+            tolerance: options.tolerance_or(declared),
         }
     }
     
@@ -201,7 +266,89 @@ impl<'a> StepToBrepsConverter<'a> {
         let mut body = Body::new();
         body.add_shell(shell);
         
-        Ok(body)
+        let placement = self.assembly_placement(id)?;
+        if placement.approx_eq(&Transform3::identity(), 0.0, 0.0) {
+            Ok(body)
+        } else {
+            Ok(body.transformed(&placement))
+        }
+    }
+    
+    /// Placement of solid `brep` in the assembly above it
+    ///
+    /// Starting from the shape representation holding the solid, follows
+    /// SHAPE_REPRESENTATION_RELATIONSHIP links to the part's shape and then
+    /// each REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION up to its parent,
+    /// composing their ITEM_DEFINED_TRANSFORMATIONs. A part instanced more
+    /// than once is placed by its lowest-numbered relationship.
+    fn assembly_placement(&self, brep: u64) -> IoResult<Transform3> {
+        let mut ids: Vec<u64> = self.step_file.entities.keys().copied().collect();
+        ids.sort_unstable();
+        let entity = |id: &u64| &self.step_file.entities[id];
+        let holds_brep = |e: &StepEntity| e.entity_type.ends_with("SHAPE_REPRESENTATION")
+            && matches!(e.attributes.get(1), Some(StepAttribute::List(items)) if items.iter().any(|i| i.as_reference() == Some(brep)));
+        let Some(mut rep) = ids.iter().copied().find(|id| holds_brep(entity(id))) else {
+            return Ok(Transform3::identity());
+        };
+        
+        let mut placement = Transform3::identity();
+        let mut visited = HashSet::from([rep]);
+        'walk: loop {
+            for id in &ids {
+                let e = entity(id);
+                let step = if e.entity_type == "SHAPE_REPRESENTATION_RELATIONSHIP" {
+                    // Ties a part's shape to its B-rep, written either way round
+                    let reps = (e.attributes.get(2).and_then(|a| a.as_reference()), e.attributes.get(3).and_then(|a| a.as_reference()));
+                    match reps {
+                        (Some(a), Some(b)) if a == rep => Some((b, None)),
+                        (Some(a), Some(b)) if b == rep => Some((a, None)),
+                        _ => None,
+                    }
+                } else if let (Some(relation), Some(with)) = (e.part("REPRESENTATION_RELATIONSHIP"), e.part("REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION")) {
+                    // REPRESENTATION_RELATIONSHIP(name, description, child, parent)
+                    let child = relation.get(2).and_then(|a| a.as_reference());
+                    match (child, relation.get(3).and_then(|a| a.as_reference()), with.first().and_then(|a| a.as_reference())) {
+                        (Some(child), Some(parent), Some(transformation)) if child == rep => Some((parent, Some(transformation))),
+                        _ => None,
+                    }
+                } else {
+                    None
+                };
+                let Some((next, transformation)) = step else { continue };
+                if !visited.insert(next) {
+                    continue;
+                }
+                if let Some(transformation) = transformation {
+                    placement = self.convert_item_defined_transformation(transformation)? * placement;
+                }
+                rep = next;
+                continue 'walk;
+            }
+            return Ok(placement);
+        }
+    }
+    
+    /// Convert ITEM_DEFINED_TRANSFORMATION to the rigid motion carrying its
+    /// first frame onto its second
+    fn convert_item_defined_transformation(&self, id: u64) -> IoResult<Transform3> {
+        let entity = self.get_entity(id)?;
+        
+        // ITEM_DEFINED_TRANSFORMATION(name, description, item_1, item_2)
+        let frame = |index: usize| -> IoResult<Transform3> {
+            let placement_ref = entity.attributes.get(index).and_then(|a| a.as_reference())
+                .ok_or_else(|| IoError::StepError("ITEM_DEFINED_TRANSFORMATION missing attributes".to_string()))?;
+            let (origin, axis, ref_direction) = self.convert_axis2_placement_3d_with_ref(placement_ref)?;
+            let z = axis.normalized();
+            let x = ref_direction.reject_from(&z).normalized();
+            let y = z.cross(&x);
+            Transform3::from_matrix(&Mat4::from_row_major([
+                [x.x(), y.x(), z.x(), origin.x()],
+                [x.y(), y.y(), z.y(), origin.y()],
+                [x.z(), y.z(), z.z(), origin.z()],
+                [0.0, 0.0, 0.0, 1.0],
+            ])).ok_or_else(|| IoError::StepError(format!("AXIS2_PLACEMENT_3D #{} is not a rigid frame", placement_ref)))
+        };
+        Ok(frame(3)? * frame(2)?.inverse())
     }
     
     /// Convert BREP_WITH_VOIDS to Body
@@ -280,9 +427,13 @@ impl<'a> StepToBrepsConverter<'a> {
             _ => Vec::new(),
         };
         
+        let same_sense = entity.attributes[3].as_bool()
+            .ok_or_else(|| IoError::StepError("Invalid ADVANCED_FACE same_sense".to_string()))?;
+        
         let surface = self.convert_surface(surface_ref)?;
         
         let mut face = Face::with_surface(surface);
+        face.set_same_sense(same_sense);
         
         // Get bounds (loops)
         for (i, bound_ref) in bound_refs.iter().enumerate() {
@@ -291,6 +442,9 @@ impl<'a> StepToBrepsConverter<'a> {
                 face.add_loop(loop_);
             }
         }
+        if let Some(cylinder) = fit_cylinder_to_face(&face)? {
+            face.set_surface(Some(Arc::new(cylinder)));
+        }
         
         Ok(face)
     }
@@ -336,22 +490,25 @@ impl<'a> StepToBrepsConverter<'a> {
             }
         }
         
-        Ok(Loop::from_coedges(coedges))
+        // Exporters don't always list oriented edges head-to-tail
+        let mut loop_ = Loop::from_coedges(coedges);
+        loop_.reorder_and_orient(self.tolerance)?;
+        Ok(loop_)
     }
     
     /// Convert ORIENTED_EDGE to (Edge, Sense)
     fn convert_oriented_edge(&mut self, id: u64) -> IoResult<(Arc<Edge>, Sense)> {
         let entity = self.get_entity(id)?;
         
-        // ORIENTED_EDGE(name, edge_element, orientation)
-        if entity.attributes.len() < 3 {
+        // ORIENTED_EDGE(name, edge_start, edge_end, edge_element, orientation)
+        if entity.attributes.len() < 5 {
             return Err(IoError::StepError("ORIENTED_EDGE missing attributes".to_string()));
         }
         
-        let edge_ref = entity.attributes[1].as_reference()
+        let edge_ref = entity.attributes[3].as_reference()
             .ok_or_else(|| IoError::StepError("Invalid edge reference".to_string()))?;
         
-        let orientation = entity.attributes[2].as_bool()
+        let orientation = entity.attributes[4].as_bool()
             .unwrap_or(true);
         
         let edge = self.convert_edge_curve(edge_ref)?;
@@ -385,7 +542,9 @@ impl<'a> StepToBrepsConverter<'a> {
         let end = self.convert_vertex_point(end_ref)?;
         let curve = self.convert_curve(curve_ref)?;
         
-        let edge = Arc::new(Edge::with_curve(start, end, curve));
+        let mut edge = Edge::with_curve(start, end, curve);
+        edge.set_tolerance(self.tolerance);
+        let edge = Arc::new(edge);
         self.edge_map.insert(id, edge.clone());
         
         Ok(edge)
@@ -409,7 +568,9 @@ impl<'a> StepToBrepsConverter<'a> {
             .ok_or_else(|| IoError::StepError("Invalid vertex geometry reference".to_string()))?;
         
         let point = self.convert_cartesian_point(geom_ref)?;
-        let vertex = Arc::new(Vertex::new(point));
+        let mut vertex = Vertex::new(point);
+        vertex.set_tolerance(self.tolerance);
+        let vertex = Arc::new(vertex);
         
         self.vertex_map.insert(id, vertex.clone());
         Ok(vertex)
@@ -577,12 +738,20 @@ impl<'a> StepToBrepsConverter<'a> {
             .ok_or_else(|| IoError::StepError("Invalid location reference".to_string()))?;
         let axis_ref = entity.attributes[2].as_reference()
             .ok_or_else(|| IoError::StepError("Invalid axis reference".to_string()))?;
-        let ref_dir_ref = entity.attributes[3].as_reference()
-            .ok_or_else(|| IoError::StepError("Invalid ref_direction reference".to_string()))?;
-        
         let origin = self.convert_cartesian_point(location_ref)?;
         let axis = self.convert_direction(axis_ref)?;
-        let ref_direction = self.convert_direction(ref_dir_ref)?;
+        // ref_direction is optional; any direction perpendicular to the axis will do
+        let ref_direction = match &entity.attributes[3] {
+            StepAttribute::Undefined => {
+                let helper = if axis.x().abs() < 0.9 { Vec3::X } else { Vec3::Y };
+                axis.cross(&helper).cross(&axis).normalized()
+            }
+            attribute => {
+                let ref_dir_ref = attribute.as_reference()
+                    .ok_or_else(|| IoError::StepError("Invalid ref_direction reference".to_string()))?;
+                self.convert_direction(ref_dir_ref)?
+            }
+        };
         
         Ok((origin, axis, ref_direction))
     }
@@ -608,32 +777,50 @@ impl<'a> StepToBrepsConverter<'a> {
     }
     
     /// Convert curve entity
+    ///
+    /// Edges referencing the same entity share one curve.
     fn convert_curve(&mut self, id: u64) -> IoResult<Arc<dyn Curve>> {
+        // Check cache
+        if let Some(curve) = self.curve_map.get(&id) {
+            return Ok(curve.clone());
+        }
+        
         let entity = self.get_entity(id)?;
         
-        match entity.entity_type.as_str() {
-            "LINE" => self.convert_line(id),
-            "CIRCLE" => self.convert_circle(id),
-            "B_SPLINE_CURVE_WITH_KNOTS" => self.convert_b_spline_curve(id),
-            _ => Err(IoError::StepError(
+        let curve = match entity.entity_type.as_str() {
+            "LINE" => self.convert_line(id)?,
+            "CIRCLE" => self.convert_circle(id)?,
+            "B_SPLINE_CURVE_WITH_KNOTS" => self.convert_b_spline_curve(id)?,
+            complex if complex.split('+').any(|part| part == "RATIONAL_B_SPLINE_CURVE") => {
+                self.convert_rational_b_spline_curve(id)?
+            }
+            _ => return Err(IoError::StepError(
                 format!("Unsupported curve type: {}", entity.entity_type)
             )),
-        }
+        };
+        
+        self.curve_map.insert(id, curve.clone());
+        Ok(curve)
     }
     
     /// Convert LINE
     fn convert_line(&self, id: u64) -> IoResult<Arc<dyn Curve>> {
         let entity = self.get_entity(id)?;
         
-        // LINE(name, point, direction)
+        // LINE(name, point, vector), with VECTOR(name, orientation, magnitude)
         if entity.attributes.len() < 3 {
             return Err(IoError::StepError("LINE missing attributes".to_string()));
         }
         
         let point_ref = entity.attributes[1].as_reference()
             .ok_or_else(|| IoError::StepError("Invalid point reference".to_string()))?;
-        let direction_ref = entity.attributes[2].as_reference()
+        let mut direction_ref = entity.attributes[2].as_reference()
             .ok_or_else(|| IoError::StepError("Invalid direction reference".to_string()))?;
+        let vector = self.get_entity(direction_ref)?;
+        if vector.entity_type == "VECTOR" {
+            direction_ref = vector.attributes.get(1).and_then(StepAttribute::as_reference)
+                .ok_or_else(|| IoError::StepError("Invalid vector orientation reference".to_string()))?;
+        }
         
         let point = self.convert_cartesian_point(point_ref)?;
         let direction = self.convert_direction(direction_ref)?;
@@ -665,8 +852,73 @@ impl<'a> StepToBrepsConverter<'a> {
     
     /// Convert B_SPLINE_CURVE_WITH_KNOTS
     fn convert_b_spline_curve(&self, id: u64) -> IoResult<Arc<dyn Curve>> {
-        // TODO: Implement B-spline curve conversion
-        Err(IoError::StepError("B-spline curves not yet implemented".to_string()))
+        let entity = self.get_entity(id)?;
+        
+        // B_SPLINE_CURVE_WITH_KNOTS(name, degree, control_points, form, closed,
+        // self_intersect, multiplicities, knots, knot_spec)
+        if entity.attributes.len() < 8 {
+            return Err(IoError::StepError("B_SPLINE_CURVE_WITH_KNOTS missing attributes".to_string()));
+        }
+        let a = &entity.attributes;
+        self.b_spline_curve(&a[1], &a[2], &a[6], &a[7], None)
+    }
+    
+    /// Convert a complex instance of RATIONAL_B_SPLINE_CURVE with knots
+    fn convert_rational_b_spline_curve(&self, id: u64) -> IoResult<Arc<dyn Curve>> {
+        let entity = self.get_entity(id)?;
+        let part = |name: &str| entity.part(name)
+            .ok_or_else(|| IoError::StepError(format!("Rational B-spline #{} has no {}", id, name)));
+        
+        // B_SPLINE_CURVE(degree, control_points, form, closed, self_intersect),
+        // B_SPLINE_CURVE_WITH_KNOTS(multiplicities, knots, knot_spec) and
+        // RATIONAL_B_SPLINE_CURVE(weights)
+        let (curve, knots, rational) = (part("B_SPLINE_CURVE")?, part("B_SPLINE_CURVE_WITH_KNOTS")?, part("RATIONAL_B_SPLINE_CURVE")?);
+        if curve.len() < 2 || knots.len() < 2 || rational.is_empty() {
+            return Err(IoError::StepError("Rational B-spline missing attributes".to_string()));
+        }
+        self.b_spline_curve(&curve[0], &curve[1], &knots[0], &knots[1], Some(&rational[0]))
+    }
+    
+    /// Build a NURBS curve from B-spline attributes, expanding the knot multiplicities
+    fn b_spline_curve(
+        &self,
+        degree: &StepAttribute,
+        control_points: &StepAttribute,
+        multiplicities: &StepAttribute,
+        knots: &StepAttribute,
+        weights: Option<&StepAttribute>,
+    ) -> IoResult<Arc<dyn Curve>> {
+        let invalid = |what: &str| IoError::StepError(format!("Invalid B-spline {}", what));
+        let list = |attribute: &StepAttribute| match attribute {
+            StepAttribute::List(items) => Some(items.clone()),
+            _ => None,
+        };
+        let degree = degree.as_real().ok_or_else(|| invalid("degree"))? as u32;
+        let points = list(control_points).ok_or_else(|| invalid("control points"))?
+            .iter()
+            .map(|p| p.as_reference().ok_or_else(|| invalid("control point")).and_then(|id| self.convert_cartesian_point(id)))
+            .collect::<IoResult<Vec<Point3>>>()?;
+        let reals = |attribute: &StepAttribute, what: &str| {
+            list(attribute).ok_or_else(|| invalid(what))?
+                .iter()
+                .map(|v| v.as_real().ok_or_else(|| invalid(what)))
+                .collect::<IoResult<Vec<f64>>>()
+        };
+        let multiplicities = reals(multiplicities, "knot multiplicities")?;
+        let distinct = reals(knots, "knots")?;
+        if multiplicities.len() != distinct.len() {
+            return Err(invalid("knot multiplicities"));
+        }
+        let knots: Vec<f64> = distinct.iter().zip(&multiplicities)
+            .flat_map(|(&knot, &count)| std::iter::repeat(knot).take(count as usize))
+            .collect();
+        let weights = match weights {
+            Some(weights) => reals(weights, "weights")?,
+            None => vec![1.0; points.len()],
+        };
+        
+        let curve = NurbsCurve::from_points_and_weights(degree, &points, &weights, knots)?;
+        Ok(Arc::new(curve))
     }
     
     /// Get entity by ID
@@ -677,6 +929,89 @@ impl<'a> StepToBrepsConverter<'a> {
 }
 
 // Helper methods for StepAttribute
+impl StepFile {
+    /// Model precision from `UNCERTAINTY_MEASURE_WITH_UNIT`, in file units
+    ///
+    /// Takes the smallest value when several are declared.
+    pub fn uncertainty(&self) -> Option<f64> {
+        self.entities.values()
+            .filter(|e| e.entity_type == "UNCERTAINTY_MEASURE_WITH_UNIT")
+            .filter_map(|e| match e.attributes.first()? {
+                StepAttribute::Real(value) => Some(*value),
+                // Typed value such as LENGTH_MEASURE(1.E-3)
+                StepAttribute::String(typed) => {
+                    let open = typed.find('(')?;
+                    typed[open + 1..].strip_suffix(')')?.trim().parse().ok()
+                }
+                _ => None,
+            })
+            .filter(|value: &f64| *value > 0.0)
+            .reduce(f64::min)
+    }
+
+    /// Check that every reference resolves and that no entity reaches itself
+    ///
+    /// Returns the ids of entities holding a dangling reference or lying on
+    /// a reference cycle, in ascending order. The walk is iterative, so deep
+    /// or cyclic files cannot overflow the stack.
+    pub fn validate_references(&self) -> Result<(), Vec<u64>> {
+        #[derive(Clone, Copy, PartialEq)]
+        enum Visit {
+            Open,
+            Done,
+        }
+        let references = |entity: &StepEntity| {
+            let mut out = Vec::new();
+            let mut pending: Vec<&StepAttribute> = entity.attributes.iter().collect();
+            while let Some(attribute) = pending.pop() {
+                match attribute {
+                    StepAttribute::Reference(id) => out.push(*id),
+                    StepAttribute::List(items) => pending.extend(items),
+                    _ => {}
+                }
+            }
+            out
+        };
+
+        let mut offending = std::collections::BTreeSet::new();
+        let mut visits: HashMap<u64, Visit> = HashMap::with_capacity(self.entities.len());
+        let mut roots: Vec<u64> = self.entities.keys().copied().collect();
+        roots.sort_unstable();
+        for root in roots {
+            if visits.contains_key(&root) {
+                continue;
+            }
+            // Path of open entities, each with the references still to follow
+            let mut path = vec![(root, references(&self.entities[&root]))];
+            visits.insert(root, Visit::Open);
+            while let Some((id, targets)) = path.last_mut() {
+                let id = *id;
+                let Some(target) = targets.pop() else {
+                    visits.insert(id, Visit::Done);
+                    path.pop();
+                    continue;
+                };
+                let Some(entity) = self.entities.get(&target) else {
+                    offending.insert(id);
+                    continue;
+                };
+                match visits.get(&target) {
+                    None => {
+                        visits.insert(target, Visit::Open);
+                        path.push((target, references(entity)));
+                    }
+                    Some(Visit::Open) => {
+                        let start = path.iter().position(|&(open, _)| open == target).unwrap_or(0);
+                        offending.extend(path[start..].iter().map(|&(open, _)| open));
+                    }
+                    Some(Visit::Done) => {}
+                }
+            }
+        }
+        if offending.is_empty() { Ok(()) } else { Err(offending.into_iter().collect()) }
+    }
+}
+
 impl StepAttribute {
     fn as_reference(&self) -> Option<u64> {
         match self {
@@ -710,8 +1045,17 @@ impl StepReader {
     }
     
     /// Read STEP file content and return bodies
+    ///
+    /// Files with dangling or cyclic references are rejected before any
+    /// conversion, naming the entities at fault.
     pub fn read(&self, content: &str, options: &ImportOptions) -> IoResult<Vec<Body>> {
         let step_file = self.parse(content)?;
+        if let Err(ids) = step_file.validate_references() {
+            let ids: Vec<String> = ids.iter().map(|id| format!("#{}", id)).collect();
+            return Err(IoError::StepError(format!(
+                "Dangling or cyclic references in {}", ids.join(", ")
+            )));
+        }
         
         let mut converter = StepToBrepsConverter::new(&step_file, options);
         let bodies = converter.convert()?;
@@ -719,8 +1063,8 @@ impl StepReader {
         Ok(bodies)
     }
     
-    /// Parse STEP file content
-    fn parse(&self, content: &str) -> IoResult<StepFile> {
+    /// Parse STEP file content without converting it
+    pub fn parse(&self, content: &str) -> IoResult<StepFile> {
         if !content.contains("ISO-10303-21") {
             return Err(IoError::StepError(
                 "Not a valid STEP file (missing ISO-10303-21 header)".to_string()
@@ -812,6 +1156,20 @@ impl StepReader {
     fn parse_entity_line(&self, id: u64, line: &str) -> Option<StepEntity> {
         let line = line.trim().trim_end_matches(';');
         
+        // A complex instance lists its parts, each NAME(attributes), in brackets
+        if let Some(inner) = line.strip_prefix('(').and_then(|l| l.strip_suffix(')')) {
+            let mut names = Vec::new();
+            let mut attributes = Vec::new();
+            let mut rest = inner.trim();
+            while let Some(open) = rest.find('(') {
+                let close = matching_paren(rest, open)?;
+                names.push(rest[..open].trim().to_string());
+                attributes.push(StepAttribute::List(self.parse_attributes(&rest[open + 1..close])));
+                rest = rest[close + 1..].trim();
+            }
+            return (!names.is_empty()).then(|| StepEntity { id, entity_type: names.join("+"), attributes });
+        }
+        
         if let Some(paren_pos) = line.find('(') {
             let entity_type = line[..paren_pos].trim().to_string();
             let attr_str = &line[paren_pos + 1..line.len() - 1];
@@ -898,10 +1256,6 @@ impl StepReader {
             return StepAttribute::List(items);
         }
         
-        if value.starts_with('.') && value.ends_with('.') {
-            return StepAttribute::Enumeration(value[1..value.len()-1].to_string());
-        }
-        
         if value == ".T." || value == ".TRUE." {
             return StepAttribute::Boolean(true);
         }
@@ -909,6 +1263,10 @@ impl StepReader {
             return StepAttribute::Boolean(false);
         }
         
+        if value.starts_with('.') && value.ends_with('.') {
+            return StepAttribute::Enumeration(value[1..value.len()-1].to_string());
+        }
+        
         if value == "$" {
             return StepAttribute::Undefined;
         }
@@ -929,6 +1287,26 @@ impl StepReader {
     }
 }
 
+/// Index of the bracket closing the one opened at `open`, skipping strings
+fn matching_paren(text: &str, open: usize) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_string = false;
+    for (i, c) in text.char_indices().skip_while(|&(i, _)| i < open) {
+        match c {
+            '\'' => in_string = !in_string,
+            '(' if !in_string => depth += 1,
+            ')' if !in_string => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+            _ => {}
+        }
+    }
+    None
+}
+
 impl Default for StepReader {
     fn default() -> Self {
         Self::new()
@@ -980,26 +1358,126 @@ impl StepWriter {
         
         let mut entity_id: u64 = 100;
         let scale = options.units.from_mm_factor();
+        let context_id = self.write_context(&mut entity_id, output, options)?;
         
+        let mut body_ids = Vec::new();
+        let mut styles = Vec::new();
         for body in bodies {
-            self.write_body(&mut entity_id, output, body, scale)?;
+            body_ids.extend(self.write_body(&mut entity_id, output, body, scale, &mut styles)?);
+        }
+        
+        // Tie the solids to the unit context so readers know their units
+        let body_refs: Vec<String> = body_ids.iter().map(|id| format!("#{}", id)).collect();
+        output.push_str(&format!(
+            "#{}=ADVANCED_BREP_SHAPE_REPRESENTATION('',({}),#{});\n",
+            entity_id, body_refs.join(","), context_id)
+        );
+        entity_id += 1;
+        if !styles.is_empty() {
+            self.write_styles(&mut entity_id, output, &styles, context_id);
         }
         
         output.push_str("ENDSEC;\n");
         Ok(())
     }
     
-    /// Write a body to STEP
-    fn write_body(&self, next_id: &mut u64, output: &mut String, body: &Body, scale: f64) -> IoResult<()> {
+    /// Write the geometric context declaring the export length unit, radians,
+    /// steradians and the export tolerance
+    fn write_context(&self, next_id: &mut u64, output: &mut String, options: &ExportOptions) -> IoResult<u64> {
+        let (length_id, angle_id, solid_angle_id) = (*next_id, *next_id + 1, *next_id + 2);
+        let si_length = |id: u64, prefix: &str| format!(
+            "#{}=(LENGTH_UNIT()NAMED_UNIT(*)SI_UNIT({},.METRE.));\n", id, prefix
+        );
+        match options.units {
+            Units::Millimeters => output.push_str(&si_length(length_id, ".MILLI.")),
+            Units::Centimeters => output.push_str(&si_length(length_id, ".CENTI.")),
+            Units::Meters => output.push_str(&si_length(length_id, "$")),
+            Units::Inches | Units::Feet => {
+                // Imperial units convert from millimetres
+                let name = if options.units == Units::Inches { "INCH" } else { "FOOT" };
+                let (mm_id, measure_id, exponents_id) = (*next_id + 3, *next_id + 4, *next_id + 5);
+                output.push_str(&si_length(mm_id, ".MILLI."));
+                output.push_str(&format!(
+                    "#{}=LENGTH_MEASURE_WITH_UNIT(LENGTH_MEASURE({:.6}),#{});\n",
+                    measure_id, options.units.to_mm_factor(), mm_id)
+                );
+                output.push_str(&format!("#{}=DIMENSIONAL_EXPONENTS(1.0,0.0,0.0,0.0,0.0,0.0,0.0);\n", exponents_id));
+                output.push_str(&format!(
+                    "#{}=(CONVERSION_BASED_UNIT('{}',#{})LENGTH_UNIT()NAMED_UNIT(#{}));\n",
+                    length_id, name, measure_id, exponents_id)
+                );
+            }
+        }
+        output.push_str(&format!("#{}=(NAMED_UNIT(*)PLANE_ANGLE_UNIT()SI_UNIT($,.RADIAN.));\n", angle_id));
+        output.push_str(&format!("#{}=(NAMED_UNIT(*)SI_UNIT($,.STERADIAN.)SOLID_ANGLE_UNIT());\n", solid_angle_id));
+        *next_id += if matches!(options.units, Units::Inches | Units::Feet) { 6 } else { 3 };
+        
+        let (uncertainty_id, context_id) = (*next_id, *next_id + 1);
+        output.push_str(&format!(
+            "#{}=UNCERTAINTY_MEASURE_WITH_UNIT(LENGTH_MEASURE({:.6E}),#{},'distance_accuracy_value','');\n",
+            uncertainty_id, options.tolerance * options.units.from_mm_factor(), length_id)
+        );
+        output.push_str(&format!(
+            "#{}=(GEOMETRIC_REPRESENTATION_CONTEXT(3)GLOBAL_UNCERTAINTY_ASSIGNED_CONTEXT((#{}))GLOBAL_UNIT_ASSIGNED_CONTEXT((#{},#{},#{}))REPRESENTATION_CONTEXT('',''));\n",
+            context_id, uncertainty_id, length_id, angle_id, solid_angle_id)
+        );
+        *next_id += 2;
+        
+        Ok(context_id)
+    }
+    
+    /// Write the colors of faces as styled items presented in the model's context
+    ///
+    /// Faces of one color share a single style.
+    fn write_styles(&self, next_id: &mut u64, output: &mut String, styles: &[(u64, Color)], context_id: u64) {
+        let mut assignments: HashMap<[u32; 4], u64> = HashMap::new();
+        let mut items = Vec::with_capacity(styles.len());
+        for &(face_id, color) in styles {
+            let assignment = *assignments.entry(color.map(f32::to_bits)).or_insert_with(|| {
+                let id = *next_id;
+                let [r, g, b, _] = color;
+                output.push_str(&format!("#{}=COLOUR_RGB('',{:.6},{:.6},{:.6});\n", id, r, g, b));
+                output.push_str(&format!("#{}=FILL_AREA_STYLE_COLOUR('',#{});\n", id + 1, id));
+                output.push_str(&format!("#{}=FILL_AREA_STYLE('',(#{}));\n", id + 2, id + 1));
+                output.push_str(&format!("#{}=SURFACE_STYLE_FILL_AREA(#{});\n", id + 3, id + 2));
+                output.push_str(&format!("#{}=SURFACE_SIDE_STYLE('',(#{}));\n", id + 4, id + 3));
+                output.push_str(&format!("#{}=SURFACE_STYLE_USAGE(.BOTH.,#{});\n", id + 5, id + 4));
+                output.push_str(&format!("#{}=PRESENTATION_STYLE_ASSIGNMENT((#{}));\n", id + 6, id + 5));
+                *next_id += 7;
+                id + 6
+            });
+            output.push_str(&format!("#{}=STYLED_ITEM('color',(#{}),#{});\n", next_id, assignment, face_id));
+            items.push(format!("#{}", next_id));
+            *next_id += 1;
+        }
+        output.push_str(&format!(
+            "#{}=MECHANICAL_DESIGN_GEOMETRIC_PRESENTATION_REPRESENTATION('',({}),#{});\n",
+            next_id, items.join(","), context_id)
+        );
+        *next_id += 1;
+    }
+    
+    /// Write a body to STEP, returning its solid's id unless it has no shells
+    ///
+    /// Each colored face is added to `styles` with the color it shows.
+    fn write_body(
+        &self,
+        next_id: &mut u64,
+        output: &mut String,
+        body: &Body,
+        scale: f64,
+        styles: &mut Vec<(u64, Color)>,
+    ) -> IoResult<Option<u64>> {
         // Write each shell
         let mut shell_ids = Vec::new();
         
         for shell in body.shells() {
-            let shell_id = self.write_shell(next_id, output, shell, scale)?;
+            let shell_id = self.write_shell(next_id, output, body, shell, scale, styles)?;
             shell_ids.push(shell_id);
         }
         
         // Write MANIFOLD_SOLID_BREP or BREP_WITH_VOIDS
+        let solid_id = *next_id;
         if shell_ids.len() == 1 {
             output.push_str(&format!(
                 "#{}=MANIFOLD_SOLID_BREP('Body',#{});\n",
@@ -1013,18 +1491,31 @@ impl StepWriter {
                 next_id, shell_ids[0], voids.join(",")
             ));
             *next_id += 1;
+        } else {
+            return Ok(None);
         }
         
-        Ok(())
+        Ok(Some(solid_id))
     }
     
-    /// Write a shell to STEP
-    fn write_shell(&self, next_id: &mut u64, output: &mut String, shell: &Shell, scale: f64) -> IoResult<u64> {
+    /// Write a shell of `body` to STEP
+    fn write_shell(
+        &self,
+        next_id: &mut u64,
+        output: &mut String,
+        body: &Body,
+        shell: &Shell,
+        scale: f64,
+        styles: &mut Vec<(u64, Color)>,
+    ) -> IoResult<u64> {
         let mut face_ids = Vec::new();
         
         for face in shell.faces() {
             let face_id = self.write_face(next_id, output, face, scale)?;
             face_ids.push(face_id);
+            if let Some(color) = body.face_color(face) {
+                styles.push((face_id, color));
+            }
         }
         
         let shell_id = *next_id;
@@ -1052,16 +1543,17 @@ impl StepWriter {
         let mut is_first = true;
         
         for loop_ in face.loops() {
-            let bound_id = self.write_face_bound(next_id, output, loop_, is_first)?;
+            let bound_id = self.write_face_bound(next_id, output, loop_, is_first, scale)?;
             bound_ids.push(bound_id);
             is_first = false;
         }
         
         let face_id = *next_id;
         let bound_refs: Vec<String> = bound_ids.iter().map(|id| format!("#{}", id)).collect();
+        let same_sense = if face.same_sense() { ".T." } else { ".F." };
         output.push_str(&format!(
-            "#{}=ADVANCED_FACE('Face',({}),#{},.T.);\n",
-            face_id, bound_refs.join(","), surface_id)
+            "#{}=ADVANCED_FACE('Face',({}),#{},{});\n",
+            face_id, bound_refs.join(","), surface_id, same_sense)
         );
         *next_id += 1;
         
@@ -1069,29 +1561,60 @@ impl StepWriter {
     }
     
     /// Write a surface to STEP
+    ///
+    /// Planes, cylinders, spheres and cones are written on their own frames.
+    /// A cone's placement sits at its apex with zero radius there.
     fn write_surface(&self, next_id: &mut u64, output: &mut String, surface: &dyn Surface, scale: f64) -> IoResult<u64> {
-        // This would need surface type detection
-        // For now, write a placeholder plane
-        let surface_id = *next_id;
-        
-        // Write AXIS2_PLACEMENT_3D
-        let axis_id = *next_id + 1;
-        let origin_id = *next_id + 2;
-        let z_axis_id = *next_id + 3;
-        
-        output.push_str(&format!("#{}=CARTESIAN_POINT('Origin',(0.0,0.0,0.0));\n", origin_id));
-        output.push_str(&format!("#{}=DIRECTION('Z',(0.0,0.0,1.0));\n", z_axis_id));
-        output.push_str(&format!("#{}=AXIS2_PLACEMENT_3D('',#{},#{},$);\n", axis_id, origin_id, z_axis_id));
-        output.push_str(&format!("#{}=PLANE('',#{});\n", surface_id, axis_id));
+        let any = surface.as_any();
+        let entity = if let Some(plane) = any.downcast_ref::<PlanarSurface>() {
+            let axis_id = self.write_axis2_placement(next_id, output, &plane.origin(), &plane.normal(), &plane.u_axis(), scale)?;
+            format!("PLANE('',#{})", axis_id)
+        } else if let Some(cylinder) = any.downcast_ref::<CylindricalSurface>() {
+            let axis_id = self.write_axis2_placement(next_id, output, &cylinder.origin(), &cylinder.axis(), &cylinder.ref_direction(), scale)?;
+            format!("CYLINDRICAL_SURFACE('',#{},{:.6})", axis_id, cylinder.radius() * scale)
+        } else if let Some(sphere) = any.downcast_ref::<SphericalSurface>() {
+            let axis_id = self.write_axis2_placement(next_id, output, &sphere.center(), &sphere.axis(), &sphere.ref_direction(), scale)?;
+            format!("SPHERICAL_SURFACE('',#{},{:.6})", axis_id, sphere.radius() * scale)
+        } else if let Some(cone) = any.downcast_ref::<ConicalSurface>() {
+            let axis_id = self.write_axis2_placement(next_id, output, &cone.apex(), &cone.axis(), &cone.ref_direction(), scale)?;
+            format!("CONICAL_SURFACE('',#{},0.0,{:.6})", axis_id, cone.half_angle())
+        } else {
+            return Err(IoError::StepError(format!("Cannot write {:?} surfaces", surface.surface_type())));
+        };
         
-        *next_id += 4;
+        let surface_id = *next_id;
+        output.push_str(&format!("#{}={};
+", surface_id, entity));
+        *next_id += 1;
         Ok(surface_id)
     }
     
+    /// Write an AXIS2_PLACEMENT_3D at `origin` with z axis `axis` and x axis
+    /// `ref_direction`
+    fn write_axis2_placement(
+        &self,
+        next_id: &mut u64,
+        output: &mut String,
+        origin: &Point3,
+        axis: &Vec3,
+        ref_direction: &Vec3,
+        scale: f64,
+    ) -> IoResult<u64> {
+        let origin_id = self.write_cartesian_point(next_id, output, origin, scale)?;
+        let (axis_id, ref_id, placement_id) = (*next_id, *next_id + 1, *next_id + 2);
+        for (id, d) in [(axis_id, axis), (ref_id, ref_direction)] {
+            output.push_str(&format!("#{}=DIRECTION('',({:.6},{:.6},{:.6}));\n", id, d.x(), d.y(), d.z()));
+        }
+        output.push_str(&format!("#{}=AXIS2_PLACEMENT_3D('',#{},#{},#{});\n", placement_id, origin_id, axis_id, ref_id));
+        *next_id += 3;
+        
+        Ok(placement_id)
+    }
+    
     /// Write a face bound
-    fn write_face_bound(&self, next_id: &mut u64, output: &mut String, loop_: &Loop, is_outer: bool) -> IoResult<u64> {
+    fn write_face_bound(&self, next_id: &mut u64, output: &mut String, loop_: &Loop, is_outer: bool, scale: f64) -> IoResult<u64> {
         // Write edge loop
-        let loop_id = self.write_edge_loop(next_id, output, loop_)?;
+        let loop_id = self.write_edge_loop(next_id, output, loop_, scale)?;
         
         let bound_id = *next_id;
         if is_outer {
@@ -1105,11 +1628,11 @@ impl StepWriter {
     }
     
     /// Write an edge loop
-    fn write_edge_loop(&self, next_id: &mut u64, output: &mut String, loop_: &Loop) -> IoResult<u64> {
+    fn write_edge_loop(&self, next_id: &mut u64, output: &mut String, loop_: &Loop, scale: f64) -> IoResult<u64> {
         let mut oriented_edge_ids = Vec::new();
         
         for coedge in loop_.coedges() {
-            let edge_id = self.write_oriented_edge(next_id, output, coedge)?;
+            let edge_id = self.write_oriented_edge(next_id, output, coedge, scale)?;
             oriented_edge_ids.push(edge_id);
         }
         
@@ -1125,8 +1648,8 @@ impl StepWriter {
     }
     
     /// Write an oriented edge
-    fn write_oriented_edge(&self, next_id: &mut u64, output: &mut String, coedge: &Coedge) -> IoResult<u64> {
-        let edge_id = self.write_edge_curve(next_id, output, coedge.edge())?;
+    fn write_oriented_edge(&self, next_id: &mut u64, output: &mut String, coedge: &Coedge, scale: f64) -> IoResult<u64> {
+        let edge_id = self.write_edge_curve(next_id, output, coedge.edge(), scale)?;
         
         let oriented_id = *next_id;
         let orientation = if matches!(coedge.sense(), Sense::Same) { ".T." } else { ".F." };
@@ -1140,20 +1663,17 @@ impl StepWriter {
     }
     
     /// Write an edge curve
-    fn write_edge_curve(&self, next_id: &mut u64, output: &mut String, edge: &Edge) -> IoResult<u64> {
+    fn write_edge_curve(&self, next_id: &mut u64, output: &mut String, edge: &Edge, scale: f64) -> IoResult<u64> {
         // Write vertices
-        let start_id = self.write_vertex_point(next_id, output, edge.start_vertex())?;
-        let end_id = self.write_vertex_point(next_id, output, edge.end_vertex())?;
+        let start_id = self.write_vertex_point(next_id, output, edge.start_vertex(), scale)?;
+        let end_id = self.write_vertex_point(next_id, output, edge.end_vertex(), scale)?;
         
         // Write curve (or use line as default)
-        let curve_id = if let Some(curve) = edge.curve() {
-            self.write_curve(next_id, output, curve.as_ref())?
-        } else {
-            // Create line between vertices
-            let line_id = *next_id;
-            // Write line entity
-            *next_id += 1;
-            line_id
+        let curve_id = match edge.curve() {
+            Some(curve) if curve.curve_type() != CurveType::Line => {
+                self.write_curve(next_id, output, curve.as_ref(), scale)?
+            }
+            _ => self.write_line(next_id, output, &edge.start_vertex().position(), &edge.end_vertex().position(), scale)?,
         };
         
         let edge_id = *next_id;
@@ -1167,8 +1687,8 @@ impl StepWriter {
     }
     
     /// Write a vertex point
-    fn write_vertex_point(&self, next_id: &mut u64, output: &mut String, vertex: &Vertex) -> IoResult<u64> {
-        let point_id = self.write_cartesian_point(next_id, output, &vertex.position())?;
+    fn write_vertex_point(&self, next_id: &mut u64, output: &mut String, vertex: &Vertex, scale: f64) -> IoResult<u64> {
+        let point_id = self.write_cartesian_point(next_id, output, &vertex.position(), scale)?;
         
         let vertex_id = *next_id;
         output.push_str(&format!("#{}=VERTEX_POINT('',#{});\n", vertex_id, point_id));
@@ -1178,24 +1698,85 @@ impl StepWriter {
     }
     
     /// Write a cartesian point
-    fn write_cartesian_point(&self, next_id: &mut u64, output: &mut String, point: &Point3) -> IoResult<u64> {
+    fn write_cartesian_point(&self, next_id: &mut u64, output: &mut String, point: &Point3, scale: f64) -> IoResult<u64> {
         let point_id = *next_id;
         output.push_str(&format!(
             "#{}=CARTESIAN_POINT('',({:.6},{:.6},{:.6}));\n",
-            point_id, point.x(), point.y(), point.z())
+            point_id, point.x() * scale, point.y() * scale, point.z() * scale)
         );
         *next_id += 1;
         
         Ok(point_id)
     }
     
+    /// Write a line through two points
+    fn write_line(&self, next_id: &mut u64, output: &mut String, start: &Point3, end: &Point3, scale: f64) -> IoResult<u64> {
+        let point_id = self.write_cartesian_point(next_id, output, start, scale)?;
+        let offset = *end - *start;
+        let direction = offset.normalized();
+        let (direction_id, vector_id, line_id) = (*next_id, *next_id + 1, *next_id + 2);
+        output.push_str(&format!(
+            "#{}=DIRECTION('',({:.6},{:.6},{:.6}));\n",
+            direction_id, direction.x(), direction.y(), direction.z())
+        );
+        output.push_str(&format!("#{}=VECTOR('',#{},{:.6});\n", vector_id, direction_id, offset.length() * scale));
+        output.push_str(&format!("#{}=LINE('',#{},#{});\n", line_id, point_id, vector_id));
+        *next_id += 3;
+        
+        Ok(line_id)
+    }
+    
     /// Write a curve
-    fn write_curve(&self, next_id: &mut u64, output: &mut String, _curve: &dyn Curve) -> IoResult<u64> {
+    fn write_curve(&self, next_id: &mut u64, output: &mut String, curve: &dyn Curve, scale: f64) -> IoResult<u64> {
+        if let Some(nurbs) = curve.as_any().downcast_ref::<NurbsCurve>() {
+            return self.write_b_spline_curve(next_id, output, nurbs, scale);
+        }
         // Placeholder - would need to detect curve type
         let curve_id = *next_id;
         *next_id += 1;
         Ok(curve_id)
     }
+    
+    /// Write a NURBS curve as B_SPLINE_CURVE_WITH_KNOTS, as part of a complex
+    /// instance with RATIONAL_B_SPLINE_CURVE when its weights vary
+    fn write_b_spline_curve(&self, next_id: &mut u64, output: &mut String, curve: &NurbsCurve, scale: f64) -> IoResult<u64> {
+        let mut point_refs = Vec::with_capacity(curve.num_control_points());
+        for i in 0..curve.num_control_points() {
+            let point = curve.control_point_3d(i).unwrap_or(Point3::ORIGIN);
+            point_refs.push(format!("#{}", self.write_cartesian_point(next_id, output, &point, scale)?));
+        }
+        
+        // Distinct knots with their multiplicities
+        let mut knots: Vec<(f64, usize)> = Vec::new();
+        for &knot in curve.knots() {
+            match knots.last_mut() {
+                Some((last, count)) if *last == knot => *count += 1,
+                _ => knots.push((knot, 1)),
+            }
+        }
+        let multiplicities: Vec<String> = knots.iter().map(|(_, count)| count.to_string()).collect();
+        let values: Vec<String> = knots.iter().map(|(knot, _)| format!("{:.6}", knot)).collect();
+        let closed = if curve.is_closed(1e-9) { ".T." } else { ".F." };
+        
+        let curve_id = *next_id;
+        if curve.is_rational() {
+            let weights: Vec<String> = (0..curve.num_control_points())
+                .map(|i| format!("{:.6}", curve.weight(i).unwrap_or(1.0)))
+                .collect();
+            output.push_str(&format!(
+                "#{}=(BOUNDED_CURVE()B_SPLINE_CURVE({},({}),.UNSPECIFIED.,{},.F.)B_SPLINE_CURVE_WITH_KNOTS(({}),({}),.UNSPECIFIED.)CURVE()GEOMETRIC_REPRESENTATION_ITEM()RATIONAL_B_SPLINE_CURVE(({}))REPRESENTATION_ITEM(''));\n",
+                curve_id, curve.degree(), point_refs.join(","), closed, multiplicities.join(","), values.join(","), weights.join(","))
+            );
+        } else {
+            output.push_str(&format!(
+                "#{}=B_SPLINE_CURVE_WITH_KNOTS('',{},({}),.UNSPECIFIED.,{},.F.,({}),({}),.UNSPECIFIED.);\n",
+                curve_id, curve.degree(), point_refs.join(","), closed, multiplicities.join(","), values.join(","))
+            );
+        }
+        *next_id += 1;
+        
+        Ok(curve_id)
+    }
 }
 
 impl Default for StepWriter {
@@ -1239,4 +1820,393 @@ mod tests {
             StepAttribute::Real(v) if (v - 3.14).abs() < 1e-6
         ));
     }
+
+    #[test]
+    fn test_same_sense_round_trip() {
+        // A square on the plane x = 2, facing -X against its surface's +X
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .map(|(y, z)| Arc::new(Vertex::new(Point3::new(2.0, y, z))));
+        let mut lp = Loop::new();
+        for i in 0..4 {
+            let line = Line::segment(corners[i].position(), corners[(i + 1) % 4].position()).unwrap();
+            let edge = Edge::with_curve(corners[i].clone(), corners[(i + 1) % 4].clone(), Arc::new(line));
+            lp.add_coedge(Coedge::new(Arc::new(edge), Sense::Same));
+        }
+        let plane = PlanarSurface::new(Point3::new(2.0, 0.0, 0.0), Vec3::Y, Vec3::Z).unwrap();
+        let mut face = Face::with_surface(Arc::new(plane));
+        face.add_loop(lp);
+        face.set_same_sense(false);
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut body = Body::new();
+        body.add_shell(shell);
+
+        let text = StepWriter::new().write(&[body], &ExportOptions::default()).unwrap();
+        assert!(text.contains(",.F.);"));
+        let bodies = StepReader::new().read(&text, &ImportOptions::default()).unwrap();
+        let faces = bodies[0].faces();
+        assert_eq!(faces.len(), 1);
+        let plane = faces[0].surface().unwrap().as_any().downcast_ref::<PlanarSurface>().unwrap();
+        assert!((plane.normal() - Vec3::X).is_zero(1e-12));
+        assert!((plane.origin().x() - 2.0).abs() < 1e-12);
+        assert!(!faces[0].same_sense());
+        assert!((faces[0].normal_at(0.5, 0.5) + Vec3::X).is_zero(1e-12));
+    }
+
+    #[test]
+    fn test_nurbs_edge_round_trip() {
+        // A cubic bulging from (0,0,0) to (4,0,0) closed by a line, and a
+        // rational quadratic arc that round-trips through a complex instance
+        let cubic = NurbsCurve::from_points_and_weights(
+            3,
+            &[Point3::new(0.0, 0.0, 0.0), Point3::new(1.0, 2.0, 0.0), Point3::new(3.0, 2.0, 0.0), Point3::new(4.0, 0.0, 0.0)],
+            &[1.0; 4],
+            vec![0.0, 0.0, 0.0, 0.0, 1.0, 1.0, 1.0, 1.0],
+        ).unwrap();
+        let arc = NurbsCurve::from_points_and_weights(
+            2,
+            &[Point3::new(4.0, 0.0, 0.0), Point3::new(2.0, -2.0, 0.0), Point3::new(0.0, 0.0, 0.0)],
+            &[1.0, 0.5, 1.0],
+            vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+        ).unwrap();
+        let (a, b) = (Arc::new(Vertex::new(Point3::ORIGIN)), Arc::new(Vertex::new(Point3::new(4.0, 0.0, 0.0))));
+        let mut lp = Loop::new();
+        lp.add_coedge(Coedge::new(Arc::new(Edge::with_curve(a.clone(), b.clone(), Arc::new(cubic.clone()))), Sense::Same));
+        lp.add_coedge(Coedge::new(Arc::new(Edge::with_curve(b, a, Arc::new(arc.clone()))), Sense::Same));
+        let mut face = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        face.add_loop(lp);
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut body = Body::new();
+        body.add_shell(shell);
+
+        let text = StepWriter::new().write(&[body], &ExportOptions::default()).unwrap();
+        assert!(text.contains("B_SPLINE_CURVE_WITH_KNOTS('',3,"));
+        assert!(text.contains("RATIONAL_B_SPLINE_CURVE((1.000000,0.500000,1.000000))"));
+        let bodies = StepReader::new().read(&text, &ImportOptions::default()).unwrap();
+        let edges = bodies[0].edges();
+        assert_eq!(edges.len(), 2);
+        for (edge, original) in edges.iter().zip([&cubic, &arc]) {
+            let curve = edge.curve().unwrap();
+            let read = curve.as_any().downcast_ref::<NurbsCurve>().unwrap();
+            assert_eq!(read.degree(), original.degree());
+            assert_eq!(read.knots(), original.knots());
+            for t in [0.0, 0.2, 0.5, 0.9, 1.0] {
+                assert!(read.evaluate(t).distance_to(&original.evaluate(t)) < 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn test_inch_units_declared() {
+        let a = Arc::new(Vertex::new(Point3::ORIGIN));
+        let b = Arc::new(Vertex::new(Point3::new(25.4, 0.0, 0.0)));
+        let mut lp = Loop::new();
+        lp.add_coedge(Coedge::new(Arc::new(Edge::new(a, b)), Sense::Same));
+        let mut face = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        face.add_loop(lp);
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut body = Body::new();
+        body.add_shell(shell);
+
+        let options = ExportOptions { units: Units::Inches, ..ExportOptions::default() };
+        let text = StepWriter::new().write(&[body], &options).unwrap();
+        assert!(text.contains("CARTESIAN_POINT('',(1.000000,0.000000,0.000000))"));
+
+        let file = StepReader::new().parse(&text).unwrap();
+        let of_type = |name: &str| file.entities.values()
+            .filter(|e| e.entity_type.split('+').any(|part| part == name))
+            .collect::<Vec<_>>();
+        let inch = of_type("CONVERSION_BASED_UNIT");
+        assert_eq!(inch.len(), 1);
+        let StepAttribute::List(parts) = &inch[0].attributes[0] else { panic!("expected a complex instance") };
+        assert!(matches!(&parts[0], StepAttribute::String(name) if name == "INCH"));
+
+        // The shape representation points at the context, which assigns the inch
+        let representation = of_type("ADVANCED_BREP_SHAPE_REPRESENTATION");
+        let StepAttribute::Reference(context) = representation[0].attributes[2] else { panic!("expected a context") };
+        let context = &file.entities[&context];
+        assert!(context.entity_type.contains("GEOMETRIC_REPRESENTATION_CONTEXT"));
+        let units = context.entity_type.split('+').position(|part| part == "GLOBAL_UNIT_ASSIGNED_CONTEXT").unwrap();
+        let StepAttribute::List(assigned) = &context.attributes[units] else { panic!("expected units") };
+        let StepAttribute::List(assigned) = &assigned[0] else { panic!("expected a unit list") };
+        assert!(matches!(assigned[0], StepAttribute::Reference(id) if id == inch[0].id));
+    }
+
+    #[test]
+    fn test_face_colors_written() {
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let mut lp = Loop::new();
+        for i in 0..4 {
+            lp.add_coedge(Coedge::new(Arc::new(Edge::new(corners[i].clone(), corners[(i + 1) % 4].clone())), Sense::Same));
+        }
+        let mut face = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        face.add_loop(lp);
+        let mut blue = face.clone();
+        blue.set_color(Some([0.0, 0.0, 1.0, 1.0]));
+        let mut shell = Shell::new();
+        for face in [face.clone(), blue, face] {
+            shell.add_face(face);
+        }
+        let mut body = Body::new();
+        body.add_shell(shell);
+        body.set_color(Some([1.0, 0.0, 0.0, 1.0]));
+
+        let text = StepWriter::new().write(&[body], &ExportOptions::default()).unwrap();
+        assert!(text.contains("COLOUR_RGB('',1.000000,0.000000,0.000000)"));
+        assert!(text.contains("COLOUR_RGB('',0.000000,0.000000,1.000000)"));
+        let file = StepReader::new().parse(&text).unwrap();
+        let of_type = |name: &str| file.entities.values().filter(|e| e.entity_type == name).collect::<Vec<_>>();
+        assert_eq!(of_type("COLOUR_RGB").len(), 2);
+        let items = of_type("STYLED_ITEM");
+        assert_eq!(items.len(), 3);
+        for item in &items {
+            let StepAttribute::Reference(face) = item.attributes[2] else { panic!("expected a face") };
+            assert_eq!(file.entities[&face].entity_type, "ADVANCED_FACE");
+        }
+        let presentation = of_type("MECHANICAL_DESIGN_GEOMETRIC_PRESENTATION_REPRESENTATION");
+        assert!(matches!(&presentation[0].attributes[1], StepAttribute::List(styled) if styled.len() == 3));
+    }
+
+    #[test]
+    fn test_edges_share_line_entity() {
+        // Rectangle with a vertex halfway along its bottom side, both halves on LINE #30
+        let text = "ISO-10303-21;
+HEADER;
+FILE_SCHEMA(('AUTOMOTIVE_DESIGN'));
+ENDSEC;
+DATA;
+#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=CARTESIAN_POINT('',(1.,0.,0.));
+#3=CARTESIAN_POINT('',(2.,0.,0.));
+#4=CARTESIAN_POINT('',(2.,1.,0.));
+#5=CARTESIAN_POINT('',(0.,1.,0.));
+#11=VERTEX_POINT('',#1);
+#12=VERTEX_POINT('',#2);
+#13=VERTEX_POINT('',#3);
+#14=VERTEX_POINT('',#4);
+#15=VERTEX_POINT('',#5);
+#20=DIRECTION('',(1.,0.,0.));
+#21=DIRECTION('',(0.,1.,0.));
+#22=DIRECTION('',(0.,0.,1.));
+#30=LINE('',#1,#20);
+#31=LINE('',#3,#21);
+#32=LINE('',#5,#20);
+#33=LINE('',#1,#21);
+#40=EDGE_CURVE('',#11,#12,#30,.T.);
+#41=EDGE_CURVE('',#12,#13,#30,.T.);
+#42=EDGE_CURVE('',#13,#14,#31,.T.);
+#43=EDGE_CURVE('',#15,#14,#32,.T.);
+#44=EDGE_CURVE('',#11,#15,#33,.T.);
+#50=ORIENTED_EDGE('',*,*,#40,.T.);
+#51=ORIENTED_EDGE('',*,*,#41,.T.);
+#52=ORIENTED_EDGE('',*,*,#42,.T.);
+#53=ORIENTED_EDGE('',*,*,#43,.F.);
+#54=ORIENTED_EDGE('',*,*,#44,.F.);
+#60=EDGE_LOOP('',(#50,#51,#52,#53,#54));
+#61=FACE_OUTER_BOUND('',#60,.T.);
+#70=AXIS2_PLACEMENT_3D('',#1,#22,#20);
+#71=PLANE('',#70);
+#80=ADVANCED_FACE('',(#61),#71,.T.);
+#90=CLOSED_SHELL('',(#80));
+#100=MANIFOLD_SOLID_BREP('',#90);
+ENDSEC;
+END-ISO-10303-21;
+";
+        let bodies = StepReader::new().read(text, &ImportOptions::default()).unwrap();
+        let edges = bodies[0].edges();
+        assert_eq!(edges.len(), 5);
+        let on_bottom: Vec<_> = edges.iter()
+            .filter(|e| e.start_vertex().position().y().abs() < 1e-12 && e.end_vertex().position().y().abs() < 1e-12)
+            .map(|e| e.curve().unwrap())
+            .collect();
+        assert_eq!(on_bottom.len(), 2);
+        assert!(Arc::ptr_eq(on_bottom[0], on_bottom[1]));
+        let top = edges.iter().find(|e| e.start_vertex().position().y() > 0.5 && e.end_vertex().position().y() > 0.5).unwrap();
+        assert!(!Arc::ptr_eq(on_bottom[0], top.curve().unwrap()));
+    }
+
+    /// A STEP file around the given data section lines
+    fn step_text(data: &str) -> String {
+        format!("ISO-10303-21;\nHEADER;\nFILE_SCHEMA(('AUTOMOTIVE_DESIGN'));\nENDSEC;\nDATA;\n{}\nENDSEC;\nEND-ISO-10303-21;\n", data)
+    }
+
+    #[test]
+    fn test_dangling_reference_reported() {
+        let text = step_text("#1=CARTESIAN_POINT('',(0.,0.,0.));\n#2=VERTEX_POINT('',#9);\n#3=VERTEX_POINT('',#1);");
+        let file = StepReader::new().parse(&text).unwrap();
+        assert_eq!(file.validate_references(), Err(vec![2]));
+        let error = StepReader::new().read(&text, &ImportOptions::default()).unwrap_err();
+        assert!(error.to_string().contains("#2"), "{}", error);
+    }
+
+    #[test]
+    fn test_reference_cycles_reported() {
+        let own = step_text("#1=EDGE_LOOP('',(#1));\n#2=FACE_BOUND('',#1,.T.);");
+        assert_eq!(StepReader::new().parse(&own).unwrap().validate_references(), Err(vec![1]));
+
+        let ring = step_text("#1=FOO('',#2);\n#2=FOO('',(#3));\n#3=FOO('',#1);\n#4=FOO('',#1);");
+        assert_eq!(StepReader::new().parse(&ring).unwrap().validate_references(), Err(vec![1, 2, 3]));
+
+        // Shared but acyclic references are fine, as in any written file
+        let corners = [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)].map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let mut lp = Loop::new();
+        for i in 0..3 {
+            lp.add_coedge(Coedge::new(Arc::new(Edge::new(corners[i].clone(), corners[(i + 1) % 3].clone())), Sense::Same));
+        }
+        let mut face = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        face.add_loop(lp);
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut body = Body::new();
+        body.add_shell(shell);
+        let written = StepWriter::new().write(&[body], &ExportOptions::default()).unwrap();
+        assert_eq!(StepReader::new().parse(&written).unwrap().validate_references(), Ok(()));
+    }
+
+    #[test]
+    fn test_declared_uncertainty_sets_tolerance() {
+        // Square with a 0.005 sliver edge along its top
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.995, 1.0), (0.0, 1.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let mut lp = Loop::new();
+        for i in 0..5 {
+            lp.add_coedge(Coedge::new(Arc::new(Edge::new(corners[i].clone(), corners[(i + 1) % 5].clone())), Sense::Same));
+        }
+        let mut face = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        face.add_loop(lp);
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut body = Body::new();
+        body.add_shell(shell);
+
+        let export = ExportOptions { tolerance: 0.01, units: Units::Inches, ..ExportOptions::default() };
+        let text = StepWriter::new().write(&[body], &export).unwrap();
+        let declared = StepReader::new().parse(&text).unwrap().uncertainty().unwrap();
+        assert!((declared - 0.01 / 25.4).abs() < 1e-9);
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sliver.step");
+        std::fs::write(&path, &text).unwrap();
+        let inches = ImportOptions { target_units: Units::Inches, ..ImportOptions::default() };
+
+        let bodies = StepReader::new().read(&text, &inches).unwrap();
+        for vertex in bodies[0].vertices() {
+            assert!((vertex.tolerance() - 0.01).abs() < 1e-9);
+        }
+        let healed = crate::Importer::import(&path, &inches).unwrap();
+        assert_eq!(healed[0].edges().len(), 4);
+
+        let tight = ImportOptions { tolerance: Some(1e-6), ..inches };
+        let kept = crate::Importer::import(&path, &tight).unwrap();
+        assert_eq!(kept[0].edges().len(), 5);
+        assert!(kept[0].vertices().iter().all(|v| v.tolerance() == 1e-6));
+    }
+
+    #[test]
+    fn test_cylinder_seam_imported_once() {
+        // Side of a cylinder of radius 1 and height 2, closed by a seam line
+        // that the face's loop runs up and back down
+        let text = step_text("#1=CARTESIAN_POINT('',(0.,0.,0.));
+#2=DIRECTION('',(0.,0.,1.));
+#3=DIRECTION('',(1.,0.,0.));
+#4=AXIS2_PLACEMENT_3D('',#1,#2,#3);
+#5=CYLINDRICAL_SURFACE('',#4,1.);
+#6=CARTESIAN_POINT('',(1.,0.,0.));
+#7=CARTESIAN_POINT('',(1.,0.,2.));
+#8=VERTEX_POINT('',#6);
+#9=VERTEX_POINT('',#7);
+#10=CIRCLE('',#4,1.);
+#11=CARTESIAN_POINT('',(0.,0.,2.));
+#12=AXIS2_PLACEMENT_3D('',#11,#2,#3);
+#13=CIRCLE('',#12,1.);
+#14=VECTOR('',#2,1.);
+#15=LINE('',#6,#14);
+#16=EDGE_CURVE('',#8,#8,#10,.T.);
+#17=EDGE_CURVE('',#9,#9,#13,.T.);
+#18=EDGE_CURVE('',#8,#9,#15,.T.);
+#19=ORIENTED_EDGE('',*,*,#16,.T.);
+#20=ORIENTED_EDGE('',*,*,#18,.T.);
+#21=ORIENTED_EDGE('',*,*,#17,.F.);
+#22=ORIENTED_EDGE('',*,*,#18,.F.);
+#23=EDGE_LOOP('',(#19,#20,#21,#22));
+#24=FACE_OUTER_BOUND('',#23,.T.);
+#25=ADVANCED_FACE('',(#24),#5,.T.);
+#26=CLOSED_SHELL('',(#25));
+#27=MANIFOLD_SOLID_BREP('',#26);");
+        let bodies = StepReader::new().read(&text, &ImportOptions::default()).unwrap();
+        let faces = bodies[0].faces();
+        assert_eq!(faces.len(), 1);
+        let face = faces[0];
+        assert_eq!(face.loops().len(), 1);
+        assert_eq!(face.seam_edges().len(), 1);
+        let seam = face.outer_loop().unwrap().coedges().iter().find(|c| c.edge().id() == face.seam_edges()[0]).unwrap();
+        assert!(seam.edge().start_vertex().position().distance_to(&Point3::new(1.0, 0.0, 0.0)) < 1e-12);
+
+        // The surface is bounded to the face and wraps round at the seam
+        let surface = face.surface().unwrap();
+        let (u, _, _, _) = surface.closest_point(&Point3::new(1.0, 0.0, 1.0)).unwrap();
+        assert!(u.abs() < 1e-9 || (u - 1.0).abs() < 1e-9);
+        assert!(surface.evaluate(0.5, 1.0).distance_to(&Point3::new(-1.0, 0.0, 2.0)) < 1e-9);
+
+        let options = nova_tess::TessellationOptions { chord_tolerance: 1e-4, ..Default::default() };
+        let mesh = nova_tess::tessellate_face(face, &options).unwrap();
+        let area: f64 = mesh.triangles.iter().map(|tri| {
+            let [a, b, c] = tri.indices.map(|i| mesh.vertices[i as usize].position);
+            (b - a).cross(&(c - a)).length() / 2.0
+        }).sum();
+        assert!((area - 4.0 * std::f64::consts::PI).abs() < 1e-2, "{}", area);
+    }
+
+    #[test]
+    fn test_assembly_placement_kept() {
+        // A unit square part placed in an assembly frame turned a quarter
+        // turn about Z and moved to (10, 20, 0)
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let mut lp = Loop::new();
+        for i in 0..4 {
+            lp.add_coedge(Coedge::new(Arc::new(Edge::new(corners[i].clone(), corners[(i + 1) % 4].clone())), Sense::Same));
+        }
+        let mut face = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        face.add_loop(lp);
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut part = Body::new();
+        part.add_shell(shell);
+        let written = StepWriter::new().write(&[part], &ExportOptions::default()).unwrap();
+        let file = StepReader::new().parse(&written).unwrap();
+        let shape = file.entities.values().find(|e| e.entity_type == "ADVANCED_BREP_SHAPE_REPRESENTATION").unwrap().id;
+        let n = file.entities.keys().max().unwrap() + 1;
+        let assembly = format!("#{a}=CARTESIAN_POINT('',(0.,0.,0.));
+#{b}=DIRECTION('',(0.,0.,1.));
+#{c}=DIRECTION('',(1.,0.,0.));
+#{d}=AXIS2_PLACEMENT_3D('',#{a},#{b},#{c});
+#{e}=CARTESIAN_POINT('',(10.,20.,0.));
+#{f}=DIRECTION('',(0.,1.,0.));
+#{g}=AXIS2_PLACEMENT_3D('',#{e},#{b},#{f});
+#{h}=SHAPE_REPRESENTATION('assembly',(#{g}),$);
+#{i}=ITEM_DEFINED_TRANSFORMATION('','',#{d},#{g});
+#{j}=(REPRESENTATION_RELATIONSHIP('','',#{shape},#{h})REPRESENTATION_RELATIONSHIP_WITH_TRANSFORMATION(#{i})SHAPE_REPRESENTATION_RELATIONSHIP());
+", a = n, b = n + 1, c = n + 2, d = n + 3, e = n + 4, f = n + 5, g = n + 6, h = n + 7, i = n + 8, j = n + 9);
+        let end = written.rfind("ENDSEC;").unwrap();
+        let text = format!("{}{}{}", &written[..end], assembly, &written[end..]);
+
+        let bodies = StepReader::new().read(&text, &ImportOptions::default()).unwrap();
+        let body = &bodies[0];
+        let expected = Transform3::new(&nova_math::Quaternion::from_axis_angle(&Vec3::Z, std::f64::consts::FRAC_PI_2), Vec3::new(10.0, 20.0, 0.0));
+        assert!(body.placement().approx_eq(&expected, 1e-9, 1e-9));
+
+        let world = body.world_geometry().bounding_box();
+        assert!(world.min.distance_to(&Point3::new(9.0, 20.0, 0.0)) < 1e-9);
+        assert!(world.max.distance_to(&Point3::new(10.0, 21.0, 0.0)) < 1e-9);
+        let local = body.local_geometry();
+        for vertex in local.vertices() {
+            let placed = body.vertices().into_iter().find(|v| v.id() == vertex.id()).unwrap();
+            assert!(expected.apply_to_point(&vertex.position()).distance_to(&placed.position()) < 1e-9);
+            assert!(corners.iter().any(|c| c.position().distance_to(&vertex.position()) < 1e-9));
+        }
+    }
 }
@@ -2,9 +2,14 @@
 //!
 //! Implements StereoLithography format for 3D printing.
 
-use crate::{IoError, IoResult, ExportOptions};
+use crate::{IoError, IoResult, ExportOptions, Winding};
 use nova_topo::Body;
 use nova_math::{Point3, Vec3};
+use nova_tess::TessellationOptions;
+use std::io::Write;
+
+/// Solid name used when `ExportOptions::description` is unset
+const DEFAULT_SOLID_NAME: &str = "NOVA_CAD_Model";
 
 /// STL format type
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -20,6 +25,8 @@ pub enum StlFormat {
 pub struct StlWriter {
     /// Output format
     pub format: StlFormat,
+    /// Write each body as its own named ASCII solid
+    pub separate_solids: bool,
 }
 
 /// Triangle for STL export
@@ -38,11 +45,23 @@ pub struct StlTriangle {
 impl StlWriter {
     /// Create a new STL writer
     pub fn new(format: StlFormat) -> Self {
-        Self { format }
+        Self { format, separate_solids: false }
     }
     
     /// Write bodies to STL format
+    ///
+    /// The solid is named by `ExportOptions::description`. With
+    /// `separate_solids`, ASCII output gets one `solid <name>_<n>` block per body.
     pub fn write(&self, bodies: &[Body], options: &ExportOptions) -> IoResult<String> {
+        if self.separate_solids && self.format == StlFormat::Ascii {
+            let mut output = String::new();
+            for (i, body) in bodies.iter().enumerate() {
+                let triangles = self.tessellate_body(body, options)?;
+                output.push_str(&self.write_solid(&triangles, &body_solid_name(options, i)));
+            }
+            return Ok(output);
+        }
+        
         // Tessellate bodies to triangles
         let triangles = self.tessellate_bodies(bodies, options)?;
         
@@ -52,6 +71,91 @@ impl StlWriter {
         }
     }
     
+    /// Write each body as a standalone ASCII STL document
+    ///
+    /// `sink` receives the solid name and file contents for every body, e.g.
+    /// to write them to separate files.
+    pub fn write_each<F>(&self, bodies: &[Body], options: &ExportOptions, mut sink: F) -> IoResult<()>
+    where
+        F: FnMut(&str, String) -> IoResult<()>,
+    {
+        if self.format != StlFormat::Ascii {
+            return Err(IoError::UnsupportedFormat(
+                "Per-body STL output is ASCII only".to_string()
+            ));
+        }
+        for (i, body) in bodies.iter().enumerate() {
+            let name = body_solid_name(options, i);
+            let triangles = self.tessellate_body(body, options)?;
+            sink(&name, self.write_solid(&triangles, &name))?;
+        }
+        Ok(())
+    }
+    
+    /// Write bodies to `out` one face at a time
+    ///
+    /// Only a single face's triangles are held in memory, so models whose
+    /// full mesh would not fit can still be exported. Binary output makes a
+    /// counting pass first because the header needs the triangle total.
+    /// Returns the number of triangles written.
+    pub fn write_streaming<W: Write>(
+        &self,
+        bodies: &[Body],
+        options: &ExportOptions,
+        mut out: W,
+    ) -> IoResult<usize> {
+        let io_err = |e: std::io::Error| IoError::WriteError(e.to_string());
+        let mut written = 0;
+        
+        match self.format {
+            StlFormat::Ascii => {
+                let shared = options.description.as_deref().unwrap_or(DEFAULT_SOLID_NAME);
+                if !self.separate_solids {
+                    writeln!(out, "solid {}", shared).map_err(io_err)?;
+                }
+                for (i, body) in bodies.iter().enumerate() {
+                    let name = body_solid_name(options, i);
+                    if self.separate_solids {
+                        writeln!(out, "solid {}", name).map_err(io_err)?;
+                    }
+                    for face in body.faces() {
+                        for tri in self.tessellate_face(face, options)? {
+                            out.write_all(ascii_facet(&tri).as_bytes()).map_err(io_err)?;
+                            written += 1;
+                        }
+                    }
+                    if self.separate_solids {
+                        writeln!(out, "endsolid {}", name).map_err(io_err)?;
+                    }
+                }
+                if !self.separate_solids {
+                    writeln!(out, "endsolid {}", shared).map_err(io_err)?;
+                }
+            }
+            StlFormat::Binary => {
+                let mut count = 0usize;
+                for face in bodies.iter().flat_map(|b| b.faces()) {
+                    count += self.tessellate_face(face, options)?.len();
+                }
+                let count = u32::try_from(count).map_err(|_| IoError::WriteError(
+                    "Too many triangles for binary STL".to_string()
+                ))?;
+                
+                out.write_all(&binary_header()).map_err(io_err)?;
+                out.write_all(&count.to_le_bytes()).map_err(io_err)?;
+                for face in bodies.iter().flat_map(|b| b.faces()) {
+                    for tri in self.tessellate_face(face, options)? {
+                        out.write_all(&binary_facet(&tri)).map_err(io_err)?;
+                        written += 1;
+                    }
+                }
+            }
+        }
+        
+        out.flush().map_err(io_err)?;
+        Ok(written)
+    }
+    
     /// Tessellate bodies to triangles
     fn tessellate_bodies(
         &self,
@@ -85,14 +189,38 @@ impl StlWriter {
     }
     
     /// Tessellate a face to triangles
+    ///
+    /// Facets are wound to agree with the tessellator's vertex normals, which
+    /// follow the face orientation, so normals point out of the solid. The
+    /// options' `winding` and `flip_normals` are applied afterwards.
     fn tessellate_face(
         &self,
-        _face: &nova_topo::Face,
-        _options: &ExportOptions,
+        face: &nova_topo::Face,
+        options: &ExportOptions,
     ) -> IoResult<Vec<StlTriangle>> {
-        // TODO: Implement tessellation for surfaces
-        // This requires Tessellatable trait to be implemented for Surface types
-        Ok(Vec::new())
+        let tess_options = TessellationOptions {
+            chord_tolerance: options.tolerance,
+            ..TessellationOptions::default()
+        };
+        let mesh = nova_tess::tessellate_face(face, &tess_options)
+            .map_err(|e| IoError::WriteError(e.to_string()))?;
+        
+        Ok(mesh.triangles.iter().map(|tri| {
+            let [a, b, c] = tri.indices.map(|i| mesh.vertices[i as usize]);
+            let outward = a.normal + b.normal + c.normal;
+            let mut facet = if tri.normal.dot(&outward) < 0.0 {
+                StlTriangle { normal: -tri.normal, v1: a.position, v2: c.position, v3: b.position }
+            } else {
+                StlTriangle { normal: tri.normal, v1: a.position, v2: b.position, v3: c.position }
+            };
+            if options.winding == Winding::Clockwise {
+                std::mem::swap(&mut facet.v2, &mut facet.v3);
+            }
+            if options.flip_normals {
+                facet.normal = -facet.normal;
+            }
+            facet
+        }).collect())
     }
     
     /// Write triangles to ASCII STL
@@ -101,38 +229,23 @@ impl StlWriter {
         triangles: &[StlTriangle],
         options: &ExportOptions,
     ) -> IoResult<String> {
+        let name = options.description.as_deref().unwrap_or(DEFAULT_SOLID_NAME);
+        Ok(self.write_solid(triangles, name))
+    }
+    
+    /// Write one ASCII `solid` block
+    fn write_solid(&self, triangles: &[StlTriangle], name: &str) -> String {
         let mut output = String::new();
         
-        output.push_str(&format!(
-            "solid {}\n",
-            options.description.as_deref().unwrap_or("NOVA_CAD_Model")
-        ));
+        output.push_str(&format!("solid {}\n", name));
         
         for tri in triangles {
-            output.push_str(&format!(
-                "  facet normal {:.6} {:.6} {:.6}\n",
-                tri.normal.x(), tri.normal.y(), tri.normal.z()
-            ));
-            output.push_str("    outer loop\n");
-            output.push_str(&format!(
-                "      vertex {:.6} {:.6} {:.6}\n",
-                tri.v1.x(), tri.v1.y(), tri.v1.z()
-            ));
-            output.push_str(&format!(
-                "      vertex {:.6} {:.6} {:.6}\n",
-                tri.v2.x(), tri.v2.y(), tri.v2.z()
-            ));
-            output.push_str(&format!(
-                "      vertex {:.6} {:.6} {:.6}\n",
-                tri.v3.x(), tri.v3.y(), tri.v3.z()
-            ));
-            output.push_str("    endloop\n");
-            output.push_str("  endfacet\n");
+            output.push_str(&ascii_facet(tri));
         }
         
-        output.push_str("endsolid\n");
+        output.push_str(&format!("endsolid {}\n", name));
         
-        Ok(output)
+        output
     }
     
     /// Write triangles to binary STL
@@ -149,6 +262,51 @@ impl StlWriter {
     }
 }
 
+/// Format one triangle as an ASCII `facet` block
+fn ascii_facet(tri: &StlTriangle) -> String {
+    let mut output = format!(
+        "  facet normal {:.6} {:.6} {:.6}\n",
+        tri.normal.x(), tri.normal.y(), tri.normal.z()
+    );
+    output.push_str("    outer loop\n");
+    for v in [tri.v1, tri.v2, tri.v3] {
+        output.push_str(&format!("      vertex {:.6} {:.6} {:.6}\n", v.x(), v.y(), v.z()));
+    }
+    output.push_str("    endloop\n");
+    output.push_str("  endfacet\n");
+    output
+}
+
+/// Encode one triangle as a 50-byte binary STL record
+fn binary_facet(tri: &StlTriangle) -> [u8; 50] {
+    let mut record = [0u8; 50];
+    let values = [
+        tri.normal.x(), tri.normal.y(), tri.normal.z(),
+        tri.v1.x(), tri.v1.y(), tri.v1.z(),
+        tri.v2.x(), tri.v2.y(), tri.v2.z(),
+        tri.v3.x(), tri.v3.y(), tri.v3.z(),
+    ];
+    for (chunk, value) in record.chunks_exact_mut(4).zip(values) {
+        chunk.copy_from_slice(&(value as f32).to_le_bytes());
+    }
+    // Trailing attribute byte count stays 0
+    record
+}
+
+/// 80-byte binary STL header
+fn binary_header() -> [u8; 80] {
+    let mut header = [0u8; 80];
+    let text = b"NOVA CAD Binary STL";
+    header[..text.len()].copy_from_slice(text);
+    header
+}
+
+/// Solid name for the `index`-th body when writing one solid per body
+fn body_solid_name(options: &ExportOptions, index: usize) -> String {
+    let base = options.description.as_deref().unwrap_or(DEFAULT_SOLID_NAME);
+    format!("{}_{}", base, index + 1)
+}
+
 impl Default for StlWriter {
     fn default() -> Self {
         Self::new(StlFormat::Ascii)
@@ -160,41 +318,11 @@ pub fn write_binary_stl(bodies: &[Body], options: &ExportOptions) -> IoResult<Ve
     let writer = StlWriter::new(StlFormat::Binary);
     let triangles = writer.tessellate_bodies(bodies, options)?;
     
-    let mut bytes = Vec::new();
-    
-    // 80 byte header
-    let header = b"NOVA CAD Binary STL";
-    bytes.extend_from_slice(header);
-    bytes.resize(80, 0);
-    
-    // Number of triangles (u32)
-    let num_triangles = triangles.len() as u32;
-    bytes.extend_from_slice(&num_triangles.to_le_bytes());
-    
-    // Each triangle: 12 bytes normal + 12 bytes v1 + 12 bytes v2 + 12 bytes v3 + 2 bytes attribute
-    for tri in triangles {
-        // Normal (3 floats)
-        bytes.extend_from_slice(&(tri.normal.x() as f32).to_le_bytes());
-        bytes.extend_from_slice(&(tri.normal.y() as f32).to_le_bytes());
-        bytes.extend_from_slice(&(tri.normal.z() as f32).to_le_bytes());
-        
-        // Vertex 1
-        bytes.extend_from_slice(&(tri.v1.x() as f32).to_le_bytes());
-        bytes.extend_from_slice(&(tri.v1.y() as f32).to_le_bytes());
-        bytes.extend_from_slice(&(tri.v1.z() as f32).to_le_bytes());
-        
-        // Vertex 2
-        bytes.extend_from_slice(&(tri.v2.x() as f32).to_le_bytes());
-        bytes.extend_from_slice(&(tri.v2.y() as f32).to_le_bytes());
-        bytes.extend_from_slice(&(tri.v2.z() as f32).to_le_bytes());
-        
-        // Vertex 3
-        bytes.extend_from_slice(&(tri.v3.x() as f32).to_le_bytes());
-        bytes.extend_from_slice(&(tri.v3.y() as f32).to_le_bytes());
-        bytes.extend_from_slice(&(tri.v3.z() as f32).to_le_bytes());
-        
-        // Attribute byte count (usually 0)
-        bytes.extend_from_slice(&[0u8, 0]);
+    let mut bytes = Vec::with_capacity(84 + 50 * triangles.len());
+    bytes.extend_from_slice(&binary_header());
+    bytes.extend_from_slice(&(triangles.len() as u32).to_le_bytes());
+    for tri in &triangles {
+        bytes.extend_from_slice(&binary_facet(tri));
     }
     
     Ok(bytes)
@@ -222,7 +350,74 @@ mod tests {
             v3: Point3::new(0.0, 1.0, 0.0),
         };
         
-        assert_eq!(tri.normal.z, 1.0);
-        assert_eq!(tri.v1.x, 0.0);
+        assert_eq!(tri.normal.z(), 1.0);
+        assert_eq!(tri.v1.x(), 0.0);
+    }
+
+    #[test]
+    fn test_ascii_solid_per_body() {
+        let bodies = [nova_topo::build_cube(1.0).unwrap(), nova_topo::build_cube(2.0).unwrap()];
+        let options = ExportOptions {
+            description: Some("bracket".to_string()),
+            ..ExportOptions::default()
+        };
+        let mut writer = StlWriter::new(StlFormat::Ascii);
+        writer.separate_solids = true;
+
+        let output = writer.write(&bodies, &options).unwrap();
+        let solids: Vec<_> = output.lines().filter(|l| l.starts_with("solid ")).collect();
+        assert_eq!(solids, vec!["solid bracket_1", "solid bracket_2"]);
+        assert!(output.contains("endsolid bracket_2"));
+        assert_eq!(output.matches("facet normal").count(), 24);
+
+        let mut files = Vec::new();
+        writer.write_each(&bodies, &options, |name, contents| {
+            files.push((name.to_string(), contents));
+            Ok(())
+        }).unwrap();
+        assert_eq!(files.len(), 2);
+        assert!(files[1].1.starts_with("solid bracket_2\n"));
+    }
+
+    #[test]
+    fn test_streaming_matches_buffered() {
+        let bodies = [nova_topo::build_cube(1.0).unwrap()];
+        let options = ExportOptions::default();
+
+        let writer = StlWriter::new(StlFormat::Ascii);
+        let mut buffer = Vec::new();
+        let written = writer.write_streaming(&bodies, &options, &mut buffer).unwrap();
+        let streamed = String::from_utf8(buffer).unwrap();
+        assert_eq!(written, 12);
+        assert_eq!(streamed, writer.write(&bodies, &options).unwrap());
+
+        let writer = StlWriter::new(StlFormat::Binary);
+        let mut buffer = Vec::new();
+        let written = writer.write_streaming(&bodies, &options, &mut buffer).unwrap();
+        assert_eq!(written, 12);
+        assert_eq!(buffer.len(), 84 + 50 * written);
+        assert_eq!(buffer, write_binary_stl(&bodies, &options).unwrap());
+    }
+
+    #[test]
+    fn test_clockwise_winding_reverses_facets() {
+        let body = nova_topo::build_cube(1.0).unwrap();
+        let writer = StlWriter::new(StlFormat::Ascii);
+        let default = writer.tessellate_body(&body, &ExportOptions::default()).unwrap();
+        let options = ExportOptions {
+            winding: Winding::Clockwise,
+            flip_normals: true,
+            ..ExportOptions::default()
+        };
+        let flipped = writer.tessellate_body(&body, &options).unwrap();
+
+        assert_eq!(default.len(), flipped.len());
+        for (a, b) in default.iter().zip(&flipped) {
+            assert_eq!((a.v1, a.v2, a.v3), (b.v1, b.v3, b.v2));
+            assert_eq!(b.normal, -a.normal);
+            // The stored normal still follows the vertex order's right-hand rule
+            let turning = (b.v2 - b.v1).cross(&(b.v3 - b.v1));
+            assert!(turning.dot(&b.normal) > 0.0);
+        }
     }
 }
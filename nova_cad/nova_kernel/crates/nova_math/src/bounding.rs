@@ -0,0 +1,153 @@
+//! Minimal bounding spheres and cylinders
+
+use crate::{Point3, Vec3};
+
+/// Relative slack when testing whether a point lies inside a sphere
+const CONTAINS_EPS: f64 = 1e-12;
+
+/// Smallest sphere enclosing all points, as (center, radius)
+///
+/// Uses Welzl's algorithm in its iterative move-to-front form. An empty
+/// slice gives a zero sphere at the origin.
+pub fn bounding_sphere(points: &[Point3]) -> (Point3, f64) {
+    let Some(&first) = points.first() else {
+        return (Point3::ORIGIN, 0.0);
+    };
+    let mut sphere = (first, 0.0);
+    for i in 1..points.len() {
+        if contains(sphere, points[i]) {
+            continue;
+        }
+        sphere = (points[i], 0.0);
+        for j in 0..i {
+            if contains(sphere, points[j]) {
+                continue;
+            }
+            sphere = sphere2(points[i], points[j]);
+            for k in 0..j {
+                if contains(sphere, points[k]) {
+                    continue;
+                }
+                sphere = sphere3(points[i], points[j], points[k]);
+                for l in 0..k {
+                    if !contains(sphere, points[l]) {
+                        sphere = sphere4(points[i], points[j], points[k], points[l]);
+                    }
+                }
+            }
+        }
+    }
+    sphere
+}
+
+/// Smallest cylinder along `axis` enclosing all points, as (center, radius, height)
+///
+/// The center is the midpoint of the cylinder's axis segment. An empty
+/// slice or zero axis gives a zero cylinder at the origin.
+pub fn bounding_cylinder(points: &[Point3], axis: &Vec3) -> (Point3, f64, f64) {
+    if points.is_empty() || axis.is_zero(0.0) {
+        return (Point3::ORIGIN, 0.0, 0.0);
+    }
+    let axis = axis.normalized();
+    let (mut lo, mut hi) = (f64::INFINITY, f64::NEG_INFINITY);
+    // The smallest sphere around coplanar points is centred in their plane,
+    // so it is also their smallest enclosing circle
+    let projected: Vec<Point3> = points
+        .iter()
+        .map(|p| {
+            let h = p.to_vector().dot(&axis);
+            lo = lo.min(h);
+            hi = hi.max(h);
+            *p - axis * h
+        })
+        .collect();
+    let (center, radius) = bounding_sphere(&projected);
+    (center + axis * ((lo + hi) / 2.0), radius, hi - lo)
+}
+
+fn contains((center, radius): (Point3, f64), p: Point3) -> bool {
+    center.distance_to(&p) <= radius * (1.0 + CONTAINS_EPS) + CONTAINS_EPS
+}
+
+/// Sphere with `a` and `b` at opposite poles
+fn sphere2(a: Point3, b: Point3) -> (Point3, f64) {
+    (a.midpoint(&b), a.distance_to(&b) / 2.0)
+}
+
+/// Smallest sphere through three points, falling back to the widest pair
+/// when they are collinear
+fn sphere3(a: Point3, b: Point3, c: Point3) -> (Point3, f64) {
+    let (ab, ac) = (b - a, c - a);
+    let n = ab.cross(&ac);
+    let denom = 2.0 * n.length_squared();
+    if denom <= f64::EPSILON * ab.length_squared() * ac.length_squared() {
+        return covering(&[a, b, c]);
+    }
+    let offset = (n.cross(&ab) * ac.length_squared() + ac.cross(&n) * ab.length_squared()) / denom;
+    (a + offset, offset.length())
+}
+
+/// Sphere through four points, falling back to the smallest sphere on a
+/// subset that covers all four when they are coplanar
+fn sphere4(a: Point3, b: Point3, c: Point3, d: Point3) -> (Point3, f64) {
+    let (u, v, w) = (b - a, c - a, d - a);
+    let det = 2.0 * u.dot(&v.cross(&w));
+    let scale = u.length() * v.length() * w.length();
+    if det.abs() <= 1e-12 * scale {
+        return covering(&[a, b, c, d]);
+    }
+    let offset = (v.cross(&w) * u.length_squared() + w.cross(&u) * v.length_squared() + u.cross(&v) * w.length_squared()) / det;
+    (a + offset, offset.length())
+}
+
+/// Smallest pair sphere, or triple sphere for four points, containing every point
+fn covering(points: &[Point3]) -> (Point3, f64) {
+    let n = points.len();
+    let mut candidates = Vec::new();
+    for i in 0..n {
+        for j in i + 1..n {
+            candidates.push(sphere2(points[i], points[j]));
+            if n == 4 {
+                for k in j + 1..n {
+                    candidates.push(sphere3(points[i], points[j], points[k]));
+                }
+            }
+        }
+    }
+    candidates
+        .into_iter()
+        .filter(|&s| points.iter().all(|&p| contains(s, p)))
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .unwrap_or_else(|| sphere2(points[0], points[n - 1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sphere_through_tetrahedron() {
+        let points = [
+            Point3::new(1.0, 1.0, 1.0),
+            Point3::new(1.0, -1.0, -1.0),
+            Point3::new(-1.0, 1.0, -1.0),
+            Point3::new(-1.0, -1.0, 1.0),
+            Point3::new(0.1, 0.2, -0.3),
+        ];
+        let (center, radius) = bounding_sphere(&points);
+        assert!(center.distance_to(&Point3::ORIGIN) < 1e-9);
+        assert!((radius - 3.0_f64.sqrt()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_cylinder_of_square() {
+        let points = [(0.0, 0.0), (2.0, 0.0), (2.0, 2.0), (0.0, 2.0)]
+            .into_iter()
+            .flat_map(|(x, y)| [Point3::new(x, y, 1.0), Point3::new(x, y, 4.0)])
+            .collect::<Vec<_>>();
+        let (center, radius, height) = bounding_cylinder(&points, &Vec3::Z);
+        assert!(center.distance_to(&Point3::new(1.0, 1.0, 2.5)) < 1e-9);
+        assert!((radius - 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((height - 3.0).abs() < 1e-9);
+    }
+}
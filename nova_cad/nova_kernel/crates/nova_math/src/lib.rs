@@ -16,10 +16,16 @@ pub mod matrix;
 pub mod transform;
 pub mod quaternion;
 pub mod bbox;
+pub mod bounding;
 pub mod interval;
 pub mod tolerance;
 pub mod plane;
 pub mod predicates;
+pub mod units;
+pub mod spatial_hash;
+pub mod deterministic;
+pub mod integrate;
+pub mod solve;
 
 pub use point::{Point2, Point3, Point4};
 pub use vector::{Vec2, Vec3, Vec4};
@@ -27,10 +33,14 @@ pub use matrix::{Mat3, Mat4};
 pub use transform::Transform3;
 pub use quaternion::Quaternion;
 pub use bbox::{BoundingBox2, BoundingBox3};
+pub use bounding::{bounding_sphere, bounding_cylinder};
 pub use interval::Interval;
 pub use tolerance::{Tolerance, ToleranceContext};
 pub use plane::Plane;
 pub use predicates::{orient2d, orient3d, incircle, insphere};
+pub use units::Units;
+pub use spatial_hash::SpatialHashGrid;
+pub use deterministic::{DeterministicConfig, SeededRng};
 
 /// Default absolute resolution (SPAresabs equivalent)
 pub const DEFAULT_RESABS: f64 = 1e-6;
@@ -48,6 +58,11 @@ pub trait Transformable {
     
     /// Return a transformed copy
     fn transformed(&self, transform: &Transform3) -> Self where Self: Clone;
+
+    /// Uniformly scale about the world origin
+    fn scale(&mut self, factor: f64) {
+        self.transform(&Transform3::from_scale(factor));
+    }
 }
 
 /// Trait for entities with a bounding box
@@ -0,0 +1,159 @@
+//! Newton-Raphson iteration for small nonlinear systems
+
+use nalgebra::{DMatrix, DVector};
+
+/// Most times a step is halved looking for a smaller residual
+const LINE_SEARCH_HALVINGS: usize = 12;
+
+/// Settings for [`newton`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewtonOptions<const N: usize> {
+    /// Most iterations before giving up
+    pub max_iterations: usize,
+    /// Residual norm at which the root has been found
+    pub tolerance: f64,
+    /// Step length at which the iteration has settled
+    pub step_tolerance: f64,
+    /// Longest step taken; longer Newton steps are scaled down
+    pub max_step: Option<f64>,
+    /// Lower and upper bounds each unknown is clamped to
+    pub bounds: Option<([f64; N], [f64; N])>,
+}
+
+impl<const N: usize> Default for NewtonOptions<N> {
+    fn default() -> Self {
+        Self {
+            max_iterations: 50,
+            tolerance: 1e-12,
+            step_tolerance: 1e-15,
+            max_step: None,
+            bounds: None,
+        }
+    }
+}
+
+/// Outcome of [`newton`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NewtonResult<const N: usize> {
+    /// Final estimate
+    pub x: [f64; N],
+    /// Norm of the residual at `x`
+    pub residual: f64,
+    /// Iterations taken
+    pub iterations: usize,
+    /// Whether the residual vanished or the steps settled within the budget
+    pub converged: bool,
+}
+
+/// Drive the `M` residuals `f` towards zero over `N` unknowns from `x0`
+///
+/// Each step solves `jacobian(x) * dx = -f(x)` in the least-squares sense,
+/// so with more residuals than unknowns this is Gauss-Newton and settles
+/// on a local minimum of |f|. A step is halved until the residual does not
+/// grow, after scaling it down to `max_step` and clamping into `bounds`.
+/// The iteration stops once the residual is within `tolerance` or a step
+/// moves less than `step_tolerance`, both counting as converged, or when
+/// no halving of a step helps or the Jacobian is singular.
+pub fn newton<const M: usize, const N: usize, F, J>(
+    f: F,
+    jacobian: J,
+    x0: [f64; N],
+    options: &NewtonOptions<N>,
+) -> NewtonResult<N>
+where
+    F: Fn(&[f64; N]) -> [f64; M],
+    J: Fn(&[f64; N]) -> [[f64; N]; M],
+{
+    let clamp = |mut x: [f64; N]| {
+        if let Some((lower, upper)) = &options.bounds {
+            for ((x, lower), upper) in x.iter_mut().zip(lower).zip(upper) {
+                *x = x.clamp(*lower, *upper);
+            }
+        }
+        x
+    };
+    let norm = |r: [f64; M]| r.iter().map(|x| x * x).sum::<f64>().sqrt();
+
+    let mut x = clamp(x0);
+    let mut residual = norm(f(&x));
+    let mut iterations = 0;
+    while iterations < options.max_iterations {
+        if residual <= options.tolerance {
+            return NewtonResult { x, residual, iterations, converged: true };
+        }
+        let r = DVector::from_row_slice(&f(&x));
+        let jx = jacobian(&x);
+        let jac = DMatrix::from_fn(M, N, |i, j| jx[i][j]);
+        let Ok(mut step) = jac.svd(true, true).solve(&-r, 1e-14) else { break };
+        if let Some(max_step) = options.max_step {
+            if step.norm() > max_step {
+                step *= max_step / step.norm();
+            }
+        }
+
+        let mut accepted = None;
+        for _ in 0..=LINE_SEARCH_HALVINGS {
+            let trial = clamp(std::array::from_fn(|i| x[i] + step[i]));
+            let trial_residual = norm(f(&trial));
+            if trial_residual <= residual {
+                accepted = Some((trial, trial_residual));
+                break;
+            }
+            step *= 0.5;
+        }
+        let Some((next, next_residual)) = accepted else { break };
+        let moved = next.iter().zip(&x).map(|(a, b)| (a - b) * (a - b)).sum::<f64>().sqrt();
+        (x, residual) = (next, next_residual);
+        iterations += 1;
+        if moved <= options.step_tolerance {
+            return NewtonResult { x, residual, iterations, converged: true };
+        }
+    }
+    NewtonResult { x, residual, iterations, converged: residual <= options.tolerance }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newton_circle_meets_line() {
+        // x^2 + y^2 = 4 and y = x
+        let f = |x: &[f64; 2]| [x[0] * x[0] + x[1] * x[1] - 4.0, x[1] - x[0]];
+        let jacobian = |x: &[f64; 2]| [[2.0 * x[0], 2.0 * x[1]], [-1.0, 1.0]];
+        let result = newton(f, jacobian, [1.0, 0.5], &NewtonOptions::default());
+        assert!(result.converged);
+        assert!(result.iterations <= 8, "{}", result.iterations);
+        let root = 2f64.sqrt();
+        assert!((result.x[0] - root).abs() < 1e-12 && (result.x[1] - root).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_newton_bounds_and_damping() {
+        // x^2 = 4 has roots at -2 and 2; the bounds keep the positive one
+        let f = |x: &[f64; 1]| [x[0] * x[0] - 4.0];
+        let jacobian = |x: &[f64; 1]| [[2.0 * x[0]]];
+        let options = NewtonOptions { bounds: Some(([1.0], [10.0])), max_step: Some(0.5), ..Default::default() };
+        let result = newton(f, jacobian, [-3.0], &options);
+        assert!(result.converged);
+        assert!((result.x[0] - 2.0).abs() < 1e-12);
+
+        // Too few iterations for the damped steps to get there
+        let short = NewtonOptions { max_iterations: 2, ..options };
+        let result = newton(f, jacobian, [9.0], &short);
+        assert!(!result.converged);
+        assert_eq!(result.iterations, 2);
+        assert!((result.x[0] - 8.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_newton_least_squares_foot() {
+        // Foot of (1, 2, 5) on the plane z = 0 parametrised by (u, v)
+        let f = |x: &[f64; 2]| [x[0] - 1.0, x[1] - 2.0, -5.0];
+        let jacobian = |_: &[f64; 2]| [[1.0, 0.0], [0.0, 1.0], [0.0, 0.0]];
+        let result = newton(f, jacobian, [0.0, 0.0], &NewtonOptions::default());
+        assert!(result.converged);
+        assert!((result.x[0] - 1.0).abs() < 1e-12 && (result.x[1] - 2.0).abs() < 1e-12);
+        assert!((result.residual - 5.0).abs() < 1e-12);
+    }
+}
@@ -0,0 +1,103 @@
+//! Uniform hash grid for finding coincident points
+
+use crate::Point3;
+use std::collections::HashMap;
+
+/// Points with payloads, bucketed by their coordinates quantized to a tolerance
+///
+/// Cells are one tolerance wide, so every point within tolerance of a query
+/// lies in the query's cell or one of its 26 neighbours. Queries return
+/// payloads in insertion order, independent of hashing.
+#[derive(Debug, Clone)]
+pub struct SpatialHashGrid<T> {
+    tolerance: f64,
+    cells: HashMap<[i64; 3], Vec<usize>>,
+    entries: Vec<(Point3, T)>,
+}
+
+impl<T> SpatialHashGrid<T> {
+    /// Create an empty grid matching points within `tolerance`
+    pub fn new(tolerance: f64) -> Self {
+        Self { tolerance, cells: HashMap::new(), entries: Vec::new() }
+    }
+
+    /// Matching distance
+    pub fn tolerance(&self) -> f64 {
+        self.tolerance
+    }
+
+    /// Number of points inserted
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Check if no points have been inserted
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Add `point` carrying `payload`
+    pub fn insert(&mut self, point: Point3, payload: T) {
+        self.cells.entry(self.cell(&point)).or_default().push(self.entries.len());
+        self.entries.push((point, payload));
+    }
+
+    /// Payloads of every point within tolerance of `point`, oldest first
+    pub fn query_near(&self, point: Point3) -> Vec<&T> {
+        let [x, y, z] = self.cell(&point);
+        let mut found: Vec<usize> = Vec::new();
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(bucket) = self.cells.get(&[x + dx, y + dy, z + dz]) else { continue };
+                    found.extend(bucket.iter().filter(|&&i| self.entries[i].0.distance_to(&point) <= self.tolerance));
+                }
+            }
+        }
+        found.sort_unstable();
+        found.into_iter().map(|i| &self.entries[i].1).collect()
+    }
+
+    /// Quantized coordinates of the cell holding `point`
+    fn cell(&self, point: &Point3) -> [i64; 3] {
+        // A zero tolerance still buckets exact duplicates together
+        let size = if self.tolerance > 0.0 { self.tolerance } else { f64::MIN_POSITIVE };
+        [point.x(), point.y(), point.z()].map(|c| (c / size).floor() as i64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clustered_points() {
+        let mut grid = SpatialHashGrid::new(1e-3);
+        // Clusters straddling cell boundaries at the origin and at x = 1
+        let points = [
+            Point3::new(-4e-4, 0.0, 0.0),
+            Point3::new(1.0, 2.0, 3.0),
+            Point3::new(4e-4, 1e-4, -1e-4),
+            Point3::new(1.0 + 5e-4, 2.0, 3.0 - 5e-4),
+            Point3::new(2e-3, 0.0, 0.0),
+        ];
+        for (i, &p) in points.iter().enumerate() {
+            grid.insert(p, i);
+        }
+        assert_eq!(grid.len(), 5);
+
+        assert_eq!(grid.query_near(Point3::ORIGIN), vec![&0, &2]);
+        assert_eq!(grid.query_near(Point3::new(1.0, 2.0, 3.0)), vec![&1, &3]);
+        assert_eq!(grid.query_near(Point3::new(1.5e-3, 0.0, 0.0)), vec![&4]);
+        assert!(grid.query_near(Point3::new(0.5, 0.5, 0.5)).is_empty());
+    }
+
+    #[test]
+    fn test_distant_points_do_not_collide() {
+        let mut grid = SpatialHashGrid::new(0.1);
+        grid.insert(Point3::new(1e6, -1e6, 0.0), "far");
+        grid.insert(Point3::new(0.05, 0.0, 0.0), "near");
+        assert_eq!(grid.query_near(Point3::ORIGIN), vec![&"near"]);
+        assert!(grid.query_near(Point3::new(-1e6, 1e6, 0.0)).is_empty());
+    }
+}
@@ -5,17 +5,26 @@ use nalgebra as na;
 use serde::{Deserialize, Serialize};
 use std::ops::Mul;
 
-/// 3D rigid body transformation (rotation + translation)
+/// 3D rigid body transformation (rotation + translation), optionally with a
+/// uniform scale
 /// 
-/// This represents an isometry in 3D space - a transformation that preserves
-/// distances and angles. It consists of a rotation (stored as a unit quaternion)
-/// and a translation vector.
+/// Points are scaled about the origin, then rotated (the rotation is stored
+/// as a unit quaternion), then translated. Without a scale this is an
+/// isometry, preserving distances and angles; with one it still preserves
+/// angles.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
 pub struct Transform3 {
     /// Rotation component
     rotation: na::UnitQuaternion<f64>,
     /// Translation component
     translation: na::Vector3<f64>,
+    /// Uniform scale applied before the rotation
+    #[serde(default = "unit_scale")]
+    scale: f64,
+}
+
+fn unit_scale() -> f64 {
+    1.0
 }
 
 impl Transform3 {
@@ -24,6 +33,7 @@ impl Transform3 {
         Self {
             rotation: na::UnitQuaternion::identity(),
             translation: na::Vector3::new(0.0, 0.0, 0.0),
+            scale: 1.0,
         }
     }
 
@@ -33,6 +43,7 @@ impl Transform3 {
         Self {
             rotation: rotation.to_nalgebra(),
             translation: translation.to_nalgebra(),
+            scale: 1.0,
         }
     }
 
@@ -42,6 +53,7 @@ impl Transform3 {
         Self {
             rotation: na::UnitQuaternion::identity(),
             translation: na::Vector3::new(x, y, z),
+            scale: 1.0,
         }
     }
 
@@ -51,12 +63,21 @@ impl Transform3 {
         Self::from_translation(translation.x(), translation.y(), translation.z())
     }
 
+    /// Create a uniform scale about the origin
+    ///
+    /// A negative factor also inverts through the origin.
+    #[inline]
+    pub fn from_scale(factor: f64) -> Self {
+        Self { scale: factor, ..Self::identity() }
+    }
+
     /// Create a pure rotation from quaternion
     #[inline]
     pub fn from_rotation(rotation: &Quaternion) -> Self {
         Self {
             rotation: rotation.to_nalgebra(),
             translation: na::Vector3::zeros(),
+            scale: 1.0,
         }
     }
 
@@ -69,6 +90,7 @@ impl Transform3 {
                 angle,
             ),
             translation: na::Vector3::zeros(),
+            scale: 1.0,
         }
     }
 
@@ -78,6 +100,7 @@ impl Transform3 {
         Self {
             rotation: na::UnitQuaternion::from_euler_angles(roll, pitch, yaw),
             translation: na::Vector3::zeros(),
+            scale: 1.0,
         }
     }
 
@@ -88,6 +111,7 @@ impl Transform3 {
         Self {
             rotation,
             translation: eye.to_nalgebra().coords,
+            scale: 1.0,
         }
     }
 
@@ -97,6 +121,12 @@ impl Transform3 {
         Quaternion::from_nalgebra(self.rotation)
     }
 
+    /// Get the uniform scale factor, 1 for a rigid transformation
+    #[inline]
+    pub fn scale_factor(&self) -> f64 {
+        self.scale
+    }
+
     /// Get the translation component
     #[inline]
     pub fn translation(&self) -> Vec3 {
@@ -112,20 +142,39 @@ impl Transform3 {
     /// Apply transformation to a point
     #[inline]
     pub fn apply_to_point(&self, point: &Point3) -> Point3 {
-        Point3::from_nalgebra(self.rotation.transform_point(&point.to_nalgebra()) + self.translation)
+        Point3::from_nalgebra(self.rotation.transform_point(&(point.to_nalgebra() * self.scale)) + self.translation)
     }
 
     /// Apply transformation to a vector (ignores translation)
     #[inline]
     pub fn apply_to_vector(&self, vector: &Vec3) -> Vec3 {
-        Vec3::from_nalgebra(self.rotation.transform_vector(&vector.to_nalgebra()))
+        Vec3::from_nalgebra(self.rotation.transform_vector(&vector.to_nalgebra()) * self.scale)
+    }
+
+    /// Apply transformation to every point of a slice in place
+    ///
+    /// The rotation matrix is built once, which is much cheaper than calling
+    /// `apply_to_point` per element on large arrays.
+    pub fn apply_to_points(&self, points: &mut [Point3]) {
+        let matrix = self.rotation.to_rotation_matrix().into_inner() * self.scale;
+        for p in points {
+            p.inner = matrix * p.inner + self.translation;
+        }
+    }
+
+    /// Apply transformation to every vector of a slice in place (ignores translation)
+    pub fn apply_to_vectors(&self, vectors: &mut [Vec3]) {
+        let matrix = self.rotation.to_rotation_matrix().into_inner() * self.scale;
+        for v in vectors {
+            v.inner = matrix * v.inner;
+        }
     }
 
     /// Apply the inverse transformation to a point
     #[inline]
     pub fn inverse_apply_to_point(&self, point: &Point3) -> Point3 {
         let translated = point.to_nalgebra() - self.translation;
-        Point3::from_nalgebra(self.rotation.inverse().transform_point(&translated))
+        Point3::from_nalgebra(self.rotation.inverse().transform_point(&translated) / self.scale)
     }
 
     /// Compose two transformations (self * other)
@@ -133,7 +182,8 @@ impl Transform3 {
     pub fn compose(&self, other: &Transform3) -> Transform3 {
         Transform3 {
             rotation: self.rotation * other.rotation,
-            translation: self.rotation.transform_vector(&other.translation) + self.translation,
+            translation: self.rotation.transform_vector(&other.translation) * self.scale + self.translation,
+            scale: self.scale * other.scale,
         }
     }
 
@@ -143,7 +193,8 @@ impl Transform3 {
         let inv_rotation = self.rotation.inverse();
         Transform3 {
             rotation: inv_rotation,
-            translation: -inv_rotation.transform_vector(&self.translation),
+            translation: -inv_rotation.transform_vector(&self.translation) / self.scale,
+            scale: 1.0 / self.scale,
         }
     }
 
@@ -153,39 +204,45 @@ impl Transform3 {
         Transform3 {
             rotation: self.rotation.slerp(&other.rotation, t),
             translation: na::Vector3::lerp(&self.translation, &other.translation, t),
+            scale: self.scale + (other.scale - self.scale) * t,
         }
     }
 
     /// Convert to 4x4 matrix
     #[inline]
     pub fn to_matrix(&self) -> Mat4 {
-        let mut m = Mat4::from_nalgebra(self.rotation.to_homogeneous());
+        let mut rotation_scale = self.rotation.to_homogeneous();
+        rotation_scale.fixed_view_mut::<3, 3>(0, 0).scale_mut(self.scale);
+        let mut m = Mat4::from_nalgebra(rotation_scale);
         m.set(0, 3, self.translation.x);
         m.set(1, 3, self.translation.y);
         m.set(2, 3, self.translation.z);
         m
     }
 
-    /// Create from 4x4 matrix (assumes valid isometry)
+    /// Create from 4x4 matrix (assumes a valid isometry, possibly scaled
+    /// uniformly by a positive factor)
     #[inline]
     pub fn from_matrix(matrix: &Mat4) -> Option<Self> {
         let rotation_mat = matrix.rotation_scale();
         let na_mat = rotation_mat.to_nalgebra();
         
-        // Check if the matrix represents a valid rotation (orthogonal with det = 1)
+        // A rotation scaled by s has determinant s^3
         let det = na_mat.determinant();
-        if (det - 1.0).abs() > 1e-6 {
+        if det <= 1e-12 {
             return None;
         }
+        let scale = if (det - 1.0).abs() <= 1e-6 { 1.0 } else { det.cbrt() };
 
-        let rotation = na::UnitQuaternion::from_matrix(&na_mat);
+        let rotation = na::UnitQuaternion::from_matrix(&(na_mat / scale));
         Some(Self {
             rotation,
             translation: matrix.translation().to_nalgebra(),
+            scale,
         })
     }
 
-    /// Convert to nalgebra Isometry3
+    /// Convert to nalgebra Isometry3, dropping any scale
     #[inline]
     pub fn to_nalgebra(&self) -> na::Isometry3<f64> {
         na::Isometry3::from_parts(
@@ -200,6 +257,7 @@ impl Transform3 {
         Self {
             rotation: iso.rotation,
             translation: iso.translation.vector,
+            scale: 1.0,
         }
     }
 
@@ -208,7 +266,7 @@ impl Transform3 {
     pub fn approx_eq(&self, other: &Transform3, pos_tol: f64, rot_tol: f64) -> bool {
         let pos_diff = (self.translation - other.translation).norm();
         let rot_diff = self.rotation.angle_to(&other.rotation);
-        pos_diff <= pos_tol && rot_diff <= rot_tol
+        pos_diff <= pos_tol && rot_diff <= rot_tol && (self.scale - other.scale).abs() <= rot_tol
     }
 }
 
@@ -302,6 +360,33 @@ mod tests {
         assert!((result.z() - 3.0).abs() < 1e-10);
     }
 
+    #[test]
+    fn test_bulk_matches_per_point() {
+        let t = Transform3::new(
+            &Quaternion::from_axis_angle(&Vec3::new(1.0, 2.0, 3.0).normalized(), 0.7),
+            Vec3::new(-4.0, 5.0, 6.5),
+        );
+        let points: Vec<Point3> = (0..10_000)
+            .map(|i| {
+                let f = i as f64;
+                Point3::new(f.sin() * 100.0, f.cos() * 50.0, f * 0.01)
+            })
+            .collect();
+        let vectors: Vec<Vec3> = points.iter().map(|p| p.to_vector()).collect();
+
+        let mut bulk_points = points.clone();
+        t.apply_to_points(&mut bulk_points);
+        let mut bulk_vectors = vectors.clone();
+        t.apply_to_vectors(&mut bulk_vectors);
+
+        for (p, q) in points.iter().zip(&bulk_points) {
+            assert!(t.apply_to_point(p).distance_to(q) < 1e-9);
+        }
+        for (v, w) in vectors.iter().zip(&bulk_vectors) {
+            assert!((t.apply_to_vector(v) - *w).length() < 1e-9);
+        }
+    }
+
     #[test]
     fn test_to_matrix() {
         let t = Transform3::from_translation(1.0, 2.0, 3.0);
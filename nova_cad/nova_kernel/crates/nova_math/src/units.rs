@@ -0,0 +1,39 @@
+//! Length units and conversion factors
+
+/// Units of length
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Units {
+    /// Millimeters
+    Millimeters,
+    /// Centimeters
+    Centimeters,
+    /// Meters
+    Meters,
+    /// Inches
+    Inches,
+    /// Feet
+    Feet,
+}
+
+impl Units {
+    /// Get conversion factor to millimeters
+    pub fn to_mm_factor(&self) -> f64 {
+        match self {
+            Units::Millimeters => 1.0,
+            Units::Centimeters => 10.0,
+            Units::Meters => 1000.0,
+            Units::Inches => 25.4,
+            Units::Feet => 304.8,
+        }
+    }
+
+    /// Get conversion factor from millimeters
+    pub fn from_mm_factor(&self) -> f64 {
+        1.0 / self.to_mm_factor()
+    }
+
+    /// Get the factor converting lengths in `self` to lengths in `to`
+    pub fn factor_to(&self, to: Units) -> f64 {
+        self.to_mm_factor() * to.from_mm_factor()
+    }
+}
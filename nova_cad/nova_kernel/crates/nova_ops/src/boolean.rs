@@ -3,8 +3,11 @@
 //! Implements robust boolean operations on B-Rep bodies.
 
 use crate::{OpsError, OpsResult};
-use nova_math::ToleranceContext;
-use nova_topo::Body;
+use nova_math::{DeterministicConfig, Point3, ToleranceContext, Transform3, Vec3};
+use nova_tess::{Mesh, TessellationOptions};
+use nova_topo::{Body, BodyType, Entity, EntityId, Face, Shell, TopologicalEntity};
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 
 /// Boolean operation types
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -28,44 +31,513 @@ impl BooleanOp {
     }
 }
 
+/// Scratch buffers for boolean operations, reusable across calls
+///
+/// Pass one workspace to many `BooleanEngine::execute_in` calls so ray
+/// casting keeps its allocations. Each call clears the buffers on entry,
+/// so a call that fails part way leaves nothing behind for the next.
+#[derive(Debug, Default)]
+pub struct BooleanWorkspace {
+    /// Boundary samples of the body being classified
+    vertices: Vec<Point3>,
+    /// Tessellations of the faces rays are cast against, keyed by face id
+    ///
+    /// Transformed copies of a body keep its face ids, so this only ever
+    /// holds the faces of one body and is cleared before switching bodies.
+    meshes: HashMap<EntityId, FaceMesh>,
+    /// Crossings of the current ray
+    hits: Vec<RayCrossing>,
+}
+
+impl BooleanWorkspace {
+    /// Create an empty workspace
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empty every buffer, keeping its capacity
+    fn clear(&mut self) {
+        self.vertices.clear();
+        self.meshes.clear();
+        self.hits.clear();
+    }
+}
+
+/// A face tessellation with the mesh edges on its boundary
+#[derive(Debug)]
+struct FaceMesh {
+    mesh: Mesh,
+    boundary: HashSet<(u32, u32)>,
+}
+
+impl FaceMesh {
+    /// Tessellate `face` and find the mesh edges used by only one triangle
+    fn new(face: &Face) -> OpsResult<Self> {
+        let mesh = nova_tess::tessellate_face(face, &TessellationOptions::default())
+            .map_err(|e| OpsError::Geometry(e.to_string()))?;
+        let mut edge_use: HashMap<(u32, u32), usize> = HashMap::new();
+        for tri in &mesh.triangles {
+            for k in 0..3 {
+                let (a, b) = (tri.indices[k], tri.indices[(k + 1) % 3]);
+                *edge_use.entry((a.min(b), a.max(b))).or_default() += 1;
+            }
+        }
+        let boundary = edge_use.into_iter().filter(|&(_, n)| n == 1).map(|(e, _)| e).collect();
+        Ok(Self { mesh, boundary })
+    }
+}
+
 /// Boolean operation engine
 #[derive(Debug, Clone)]
 pub struct BooleanEngine {
     /// Operation type
     pub op: BooleanOp,
+    /// Seed for the extra ray directions tried during classification
+    pub deterministic: DeterministicConfig,
 }
 
 impl BooleanEngine {
     /// Create new boolean engine
     pub fn new(op: BooleanOp) -> Self {
-        Self { op }
+        Self { op, deterministic: DeterministicConfig::default() }
+    }
+
+    /// Draw random choices from `config` instead of the default seed
+    pub fn with_deterministic(mut self, config: DeterministicConfig) -> Self {
+        self.deterministic = config;
+        self
     }
     
-    /// Execute boolean operation (stub)
+    /// Execute boolean operation
+    ///
+    /// Only bodies whose boundaries do not cross are handled so far: disjoint
+    /// bodies and one body nested inside the other. Wire bodies bound no
+    /// region and are rejected with `InvalidBodies`.
     pub fn execute(
         &self,
-        _body1: &Body,
-        _body2: &Body,
-        _tolerance: &ToleranceContext,
+        body1: &Body,
+        body2: &Body,
+        tolerance: &ToleranceContext,
     ) -> OpsResult<Body> {
-        Err(OpsError::NotSupported(
-            format!("Boolean operation '{}' not yet implemented", self.op.name())
-        ))
+        self.execute_in(body1, body2, tolerance, &mut BooleanWorkspace::new())
+    }
+
+    /// Execute boolean operation using the scratch buffers in `workspace`
+    ///
+    /// Gives the same result as `execute`; reuse one workspace across many
+    /// calls to avoid reallocating.
+    pub fn execute_in(
+        &self,
+        body1: &Body,
+        body2: &Body,
+        tolerance: &ToleranceContext,
+        workspace: &mut BooleanWorkspace,
+    ) -> OpsResult<Body> {
+        for body in [body1, body2] {
+            if body.body_type() == BodyType::Wire {
+                return Err(OpsError::InvalidBodies(format!(
+                    "Boolean operation '{}' needs faces but got a wire body",
+                    self.op.name()
+                )));
+            }
+        }
+        workspace.clear();
+        let directions = ray_directions(&self.deterministic);
+        let containment = containment(body1, body2, tolerance, &directions, workspace)?;
+        self.build_result_body(body1, body2, containment)
+    }
+
+    /// Unite `a` and `b` once per absolute tolerance in `tolerances`
+    ///
+    /// Results come back in the order of `tolerances`; compare them with
+    /// `sweep_is_stable` to catch unions that hinge on the tolerance.
+    pub fn unite_tolerance_sweep(a: &Body, b: &Body, tolerances: &[f64]) -> Vec<OpsResult<Body>> {
+        let engine = Self::new(BooleanOp::Unite);
+        tolerances
+            .iter()
+            .map(|&resabs| engine.execute(a, b, &ToleranceContext::with_resabs(resabs)))
+            .collect()
+    }
+    
+    /// Material `after` has gained and lost relative to `before`, as the
+    /// bodies (`after - before`, `before - after`)
+    ///
+    /// Shells the two bodies share are set aside first, so a body that only
+    /// gained a cavity loses exactly the cavity's solid, which classifying
+    /// vertices on the shared outer boundary cannot tell. Unshared cavities
+    /// count as solids of removed or added material; the remaining outer
+    /// shells are subtracted both ways and fail as `execute` does, with
+    /// `NotSupported` for crossing boundaries. A side with no material is
+    /// an empty body.
+    pub fn material_diff(before: &Body, after: &Body, tolerance: &ToleranceContext) -> OpsResult<(Body, Body)> {
+        let alone = |shell: &Shell| {
+            let mut body = Body::new();
+            body.add_shell(shell.clone());
+            body
+        };
+        let mut unmatched: Vec<&Shell> = after.shells().iter().collect();
+        let mut before_only = Vec::new();
+        for shell in before.shells() {
+            let single = alone(shell);
+            let twin = unmatched.iter().position(|other| {
+                other.is_outer() == shell.is_outer() && nova_topo::bodies_equivalent(&single, &alone(other), tolerance)
+            });
+            match twin {
+                Some(i) => {
+                    unmatched.remove(i);
+                }
+                None => before_only.push(shell),
+            }
+        }
+
+        // (outer shells as a body, cavities turned into solids)
+        let split = |shells: &[&Shell]| {
+            let (mut outer, mut cavities) = (Body::new(), Body::new());
+            for &shell in shells {
+                let mut shell = shell.clone();
+                if shell.is_outer() {
+                    outer.add_shell(shell);
+                } else {
+                    shell.set_outer(true);
+                    for face in shell.faces_mut() {
+                        face.reverse_orientation();
+                    }
+                    cavities.add_shell(shell);
+                }
+            }
+            (outer, cavities)
+        };
+        let (before_outer, filled) = split(&before_only);
+        let (after_outer, emptied) = split(&unmatched);
+
+        let subtract = Self::new(BooleanOp::Subtract);
+        let difference = |a: &Body, b: &Body| match subtract.execute(a, b, tolerance) {
+            Err(OpsError::EmptyResult(_)) => Ok(Body::new()),
+            result => result,
+        };
+        let mut added = difference(&after_outer, &before_outer)?;
+        let mut removed = difference(&before_outer, &after_outer)?;
+        for (body, cavities) in [(&mut added, filled), (&mut removed, emptied)] {
+            for shell in cavities.transformed(&Transform3::identity()).shells_mut().drain(..) {
+                body.add_shell(shell);
+            }
+        }
+        Ok((added, removed))
+    }
+    
+    /// Assemble the result from copies of the input shells
+    ///
+    /// Returns `EmptyResult` when the operation legitimately leaves nothing,
+    /// and `NoIntersection` when intersecting disjoint bodies.
+    fn build_result_body(
+        &self,
+        body1: &Body,
+        body2: &Body,
+        containment: Containment,
+    ) -> OpsResult<Body> {
+        // Faces carry their body's color so they keep it in a merged result
+        let copy = |body: &Body| {
+            let mut copy = body.transformed(&Transform3::identity());
+            copy.bake_color();
+            copy
+        };
+        match (self.op, containment) {
+            (_, Containment::Crossing) => Err(OpsError::NotSupported(format!(
+                "Boolean operation '{}' on crossing boundaries not yet implemented",
+                self.op.name()
+            ))),
+            (BooleanOp::Unite, Containment::Disjoint) => {
+                let mut result = copy(body1);
+                for shell in copy(body2).shells_mut().drain(..) {
+                    result.add_shell(shell);
+                }
+                Ok(result)
+            }
+            (BooleanOp::Unite, Containment::FirstInside)
+            | (BooleanOp::Intersect, Containment::SecondInside) => Ok(copy(body2)),
+            (BooleanOp::Unite, Containment::SecondInside)
+            | (BooleanOp::Intersect, Containment::FirstInside)
+            | (BooleanOp::Subtract, Containment::Disjoint) => Ok(copy(body1)),
+            (BooleanOp::Subtract, Containment::FirstInside) => Err(OpsError::EmptyResult(
+                "first body lies entirely inside the subtracted body".to_string()
+            )),
+            (BooleanOp::Subtract, Containment::SecondInside) => {
+                // The tool's outer shells become voids, facing into the cavity
+                let mut result = copy(body1);
+                for mut shell in copy(body2).shells_mut().drain(..).filter(|s| s.is_outer()) {
+                    shell.set_outer(false);
+                    for face in shell.faces_mut() {
+                        face.reverse_orientation();
+                    }
+                    result.add_shell(shell);
+                }
+                Ok(result)
+            }
+            (BooleanOp::Intersect, Containment::Disjoint) => Err(OpsError::NoIntersection),
+        }
     }
 }
 
+/// Whether the results of a tolerance sweep all describe the same outcome
+///
+/// Successful results must be geometrically equivalent to within
+/// `tolerance`, as judged by `nova_topo::bodies_equivalent`; failures must
+/// all be the same kind of error. A mix of the two is unstable.
+pub fn sweep_is_stable(results: &[OpsResult<Body>], tolerance: f64) -> bool {
+    let Some(first) = results.first() else {
+        return true;
+    };
+    let tolerance = ToleranceContext::with_resabs(tolerance);
+    results[1..].iter().all(|result| match (first, result) {
+        (Ok(a), Ok(b)) => nova_topo::bodies_equivalent(a, b, &tolerance),
+        (Err(a), Err(b)) => std::mem::discriminant(a) == std::mem::discriminant(b),
+        _ => false,
+    })
+}
+
+/// How two bodies sit relative to each other
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Containment {
+    /// The bodies do not overlap
+    Disjoint,
+    /// The first body lies inside the second
+    FirstInside,
+    /// The second body lies inside the first
+    SecondInside,
+    /// The boundaries cross, or the vertices cannot tell
+    Crossing,
+}
+
+/// Classify two bodies against each other from sample points on their
+/// boundaries
+///
+/// Each body is sampled at its vertices and at the vertices and triangle
+/// centroids of its face tessellations, so a face that bulges or cuts
+/// through the other body between its vertices still shows as crossing.
+fn containment(
+    body1: &Body,
+    body2: &Body,
+    tolerance: &ToleranceContext,
+    directions: &[Vec3],
+    workspace: &mut BooleanWorkspace,
+) -> OpsResult<Containment> {
+    if !body1.bounding_box().intersects(&body2.bounding_box()) {
+        return Ok(Containment::Disjoint);
+    }
+
+    // (no sample outside, no sample inside or on the boundary)
+    let mut classify = |a: &Body, b: &Body| -> OpsResult<(bool, bool)> {
+        let (mut all_in, mut all_out) = (true, true);
+        let mut vertices = std::mem::take(&mut workspace.vertices);
+        vertices.clear();
+        workspace.meshes.clear();
+        boundary_samples(a, &mut vertices)?;
+        for vertex in &vertices {
+            match classify_point_in(b, vertex, tolerance, directions, workspace)? {
+                PointClassification::Outside => all_in = false,
+                PointClassification::Inside | PointClassification::OnBoundary => all_out = false,
+            }
+        }
+        workspace.vertices = vertices;
+        Ok((all_in, all_out))
+    };
+    let (first_in, first_out) = classify(body1, body2)?;
+    if first_in {
+        return Ok(Containment::FirstInside);
+    }
+    let (second_in, _) = classify(body2, body1)?;
+    Ok(if second_in && first_out {
+        Containment::SecondInside
+    } else {
+        // Overlapping boxes with every vertex outside can still cross, like a plus sign
+        Containment::Crossing
+    })
+}
+
+/// Push the vertices of `body` and the vertices and triangle centroids of
+/// its face tessellations onto `samples`
+fn boundary_samples(body: &Body, samples: &mut Vec<Point3>) -> OpsResult<()> {
+    samples.extend(body.vertices().iter().map(|v| v.position()));
+    for face in body.faces() {
+        let FaceMesh { mesh, .. } = FaceMesh::new(face)?;
+        samples.extend(mesh.vertices.iter().map(|v| v.position));
+        samples.extend(mesh.triangles.iter().map(|tri| {
+            let [a, b, c] = tri.indices.map(|i| mesh.vertices[i as usize].position.to_vector());
+            Point3::ORIGIN + (a + b + c) / 3.0
+        }));
+    }
+    Ok(())
+}
+
 /// Ray-face intersection result
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-#[allow(dead_code)]
 enum RayIntersection {
-    /// Ray hits the face
-    Hit,
-    /// Ray is on the surface
+    /// Ray crosses the face this many times
+    Hit(usize),
+    /// Ray grazes a boundary edge or vertex of the face, or starts on it
     OnSurface,
     /// Ray misses the face
     Miss,
 }
 
+/// Directions tried in turn when a ray grazes the boundary
+const RAY_DIRECTIONS: [(f64, f64, f64); 4] = [
+    (0.5773, 0.6213, 0.5297),
+    (-0.3313, 0.8517, 0.4061),
+    (0.7124, -0.2179, 0.6672),
+    (0.1291, 0.3847, -0.9140),
+];
+
+/// Random directions tried after `RAY_DIRECTIONS` all graze
+const EXTRA_RAY_DIRECTIONS: usize = 4;
+
+/// Ray directions for classification, the fixed ones first and then
+/// `EXTRA_RAY_DIRECTIONS` drawn from `config`
+fn ray_directions(config: &DeterministicConfig) -> Vec<Vec3> {
+    let mut rng = config.rng();
+    RAY_DIRECTIONS
+        .iter()
+        .map(|&(x, y, z)| Vec3::new(x, y, z))
+        .chain((0..EXTRA_RAY_DIRECTIONS).map(|_| rng.unit_vector()))
+        .collect()
+}
+
+/// Intersect a ray with a face through its tessellation
+///
+/// Touching a boundary edge or vertex of the face, or starting on it, is
+/// reported as `OnSurface`; otherwise each crossing counts once.
+fn ray_face_intersection(
+    origin: &Point3,
+    direction: &Vec3,
+    face: &Face,
+    tolerance: &ToleranceContext,
+    workspace: &mut BooleanWorkspace,
+) -> OpsResult<RayIntersection> {
+    let eps = tolerance.resabs;
+    let crossings = ray_face_crossings(origin, direction, face, tolerance, workspace)?;
+    if crossings.iter().any(|c| c.grazes || c.distance <= eps) {
+        return Ok(RayIntersection::OnSurface);
+    }
+    Ok(if crossings.is_empty() { RayIntersection::Miss } else { RayIntersection::Hit(crossings.len()) })
+}
+
+/// Where a ray meets a face
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct RayCrossing {
+    /// Distance from the ray origin along the unit direction
+    pub(crate) distance: f64,
+    /// Whether the ray meets the face on a boundary edge or vertex
+    pub(crate) grazes: bool,
+}
+
+/// Every place a ray meets a face, nearest first, found on its tessellation
+///
+/// Each triangle is tested with barycentric coordinates, so any face shape
+/// and surface type works. A crossing shared by neighbouring triangles is
+/// reported once. Crossings within the resolution behind the origin count.
+/// The face is tessellated once per workspace and reused for later rays.
+pub(crate) fn ray_face_crossings<'w>(
+    origin: &Point3,
+    direction: &Vec3,
+    face: &Face,
+    tolerance: &ToleranceContext,
+    workspace: &'w mut BooleanWorkspace,
+) -> OpsResult<&'w [RayCrossing]> {
+    let eps = tolerance.resabs;
+    let direction = direction.normalized();
+
+    let BooleanWorkspace { meshes, hits, .. } = workspace;
+    let FaceMesh { mesh, boundary } = match meshes.entry(face.id()) {
+        Entry::Occupied(entry) => entry.into_mut(),
+        Entry::Vacant(entry) => entry.insert(FaceMesh::new(face)?),
+    };
+    let on_boundary = |a: u32| boundary.iter().any(|&(i, j)| i == a || j == a);
+
+    hits.clear();
+    for tri in &mesh.triangles {
+        let [a, b, c] = tri.indices.map(|i| mesh.vertices[i as usize].position);
+        let (e1, e2) = (b - a, c - a);
+        let p = direction.cross(&e2);
+        let det = e1.dot(&p);
+        let area2 = e1.cross(&e2).length();
+        if area2 <= 0.0 || det.abs() <= 1e-12 * area2 {
+            continue;
+        }
+
+        let s = *origin - a;
+        let q = s.cross(&e1);
+        let t = e2.dot(&q) / det;
+        let (u, v) = (s.dot(&p) / det, direction.dot(&q) / det);
+        let bary = [1.0 - u - v, u, v];
+
+        // Tolerance on each coordinate is `eps` over the altitude from that vertex
+        let opposite = [c - b, a - c, b - a];
+        let bary_tol = opposite.map(|edge| eps * edge.length() / area2);
+        if (0..3).any(|k| bary[k] < -bary_tol[k]) || t < -eps {
+            continue;
+        }
+        let zero: Vec<usize> = (0..3).filter(|&k| bary[k] <= bary_tol[k]).collect();
+        let grazes = match zero[..] {
+            [k] => {
+                let (i, j) = (tri.indices[(k + 1) % 3], tri.indices[(k + 2) % 3]);
+                boundary.contains(&(i.min(j), i.max(j)))
+            }
+            [k1, k2] => on_boundary(tri.indices[3 - k1 - k2]),
+            _ => false,
+        };
+        hits.push(RayCrossing { distance: t, grazes });
+    }
+
+    hits.sort_by(|a, b| a.distance.total_cmp(&b.distance));
+    hits.dedup_by(|a, b| {
+        let same = (a.distance - b.distance).abs() <= eps;
+        b.grazes |= same && a.grazes;
+        same
+    });
+    Ok(hits)
+}
+
+/// Classify a point against a body by ray casting
+///
+/// The crossings of a ray from the point are counted over all faces. Rays
+/// that graze an edge or vertex are retried along another direction, and a
+/// point that grazes every ray lies on the boundary. Random directions come
+/// from the default seed.
+pub fn classify_point(
+    body: &Body,
+    point: &Point3,
+    tolerance: &ToleranceContext,
+) -> OpsResult<PointClassification> {
+    let directions = ray_directions(&DeterministicConfig::default());
+    classify_point_in(body, point, tolerance, &directions, &mut BooleanWorkspace::new())
+}
+
+/// `classify_point` with the ray casting buffers in `workspace`
+fn classify_point_in(
+    body: &Body,
+    point: &Point3,
+    tolerance: &ToleranceContext,
+    directions: &[Vec3],
+    workspace: &mut BooleanWorkspace,
+) -> OpsResult<PointClassification> {
+    'directions: for direction in directions {
+        let mut crossings = 0;
+        for face in body.faces() {
+            match ray_face_intersection(point, direction, face, tolerance, workspace)? {
+                RayIntersection::Hit(n) => crossings += n,
+                RayIntersection::OnSurface => continue 'directions,
+                RayIntersection::Miss => {}
+            }
+        }
+        return Ok(if crossings % 2 == 1 {
+            PointClassification::Inside
+        } else {
+            PointClassification::Outside
+        });
+    }
+    Ok(PointClassification::OnBoundary)
+}
+
 /// Point classification relative to a body
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum PointClassification {
@@ -76,3 +548,294 @@ pub enum PointClassification {
     /// Point is on the boundary
     OnBoundary,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_geom::{CircularArc, CylindricalSurface, Line, Surface};
+    use nova_topo::{Coedge, Edge, Entity, Loop, Sense, Vertex};
+    use std::f64::consts::PI;
+    use std::sync::Arc;
+
+    fn polygon_face(corners: &[Point3]) -> Face {
+        let vertices: Vec<_> = corners.iter().map(|&p| Arc::new(Vertex::new(p))).collect();
+        let coedges = (0..vertices.len())
+            .map(|i| {
+                let edge = Edge::new(vertices[i].clone(), vertices[(i + 1) % vertices.len()].clone());
+                Coedge::new(Arc::new(edge), Sense::Same)
+            })
+            .collect();
+        let mut face = Face::new();
+        face.add_loop(Loop::from_coedges(coedges));
+        face
+    }
+
+    /// Half cylinder of radius 1 around Z, on the +Y side, from z = 0 to 2
+    fn half_cylinder_face() -> Face {
+        let surface = CylindricalSurface::bounded(Point3::ORIGIN, Vec3::Z, 1.0, Vec3::X, 0.0, 2.0, 0.0, PI).unwrap();
+        let corners = [
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(-1.0, 0.0, 0.0),
+            Point3::new(-1.0, 0.0, 2.0),
+            Point3::new(1.0, 0.0, 2.0),
+        ];
+        let v: Vec<_> = corners.iter().map(|&p| Arc::new(Vertex::new(p))).collect();
+        let arc = |z: f64| Arc::new(CircularArc::new(Point3::new(0.0, 0.0, z), 1.0, Vec3::Z, Vec3::X, PI).unwrap());
+        let line = |a: usize, b: usize| Arc::new(Line::segment(corners[a], corners[b]).unwrap());
+        let edges = [
+            (Edge::with_curve(v[0].clone(), v[1].clone(), arc(0.0)), Sense::Same),
+            (Edge::with_curve(v[1].clone(), v[2].clone(), line(1, 2)), Sense::Same),
+            (Edge::with_curve(v[3].clone(), v[2].clone(), arc(2.0)), Sense::Opposite),
+            (Edge::with_curve(v[3].clone(), v[0].clone(), line(3, 0)), Sense::Same),
+        ];
+        let mut face = Face::with_surface(Arc::new(surface));
+        face.add_loop(Loop::from_coedges(edges.into_iter().map(|(e, s)| Coedge::new(Arc::new(e), s)).collect()));
+        face
+    }
+
+    #[test]
+    fn test_ray_cylindrical_face() {
+        let face = half_cylinder_face();
+        let tol = ToleranceContext::default();
+        let cast = |origin: Point3, direction: Vec3| ray_face_intersection(&origin, &direction, &face, &tol, &mut BooleanWorkspace::new()).unwrap();
+
+        assert_eq!(cast(Point3::new(0.0, 5.0, 1.0), -Vec3::Y), RayIntersection::Hit(1));
+        assert_eq!(cast(Point3::new(5.0, 0.5, 1.0), -Vec3::X), RayIntersection::Hit(2));
+        assert_eq!(cast(Point3::new(5.0, -0.5, 1.0), -Vec3::X), RayIntersection::Miss);
+        assert_eq!(cast(Point3::new(0.0, 5.0, 3.0), -Vec3::Y), RayIntersection::Miss);
+        assert_eq!(cast(Point3::new(0.0, 5.0, 2.0), -Vec3::Y), RayIntersection::OnSurface);
+    }
+
+    #[test]
+    fn test_ray_l_shaped_face() {
+        let face = polygon_face(&[
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(2.0, 0.0, 0.0),
+            Point3::new(2.0, 1.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(1.0, 2.0, 0.0),
+            Point3::new(0.0, 2.0, 0.0),
+        ]);
+        let tol = ToleranceContext::default();
+        let cast = |x: f64, y: f64| ray_face_intersection(&Point3::new(x, y, 1.0), &-Vec3::Z, &face, &tol, &mut BooleanWorkspace::new()).unwrap();
+
+        assert_eq!(cast(0.5, 1.5), RayIntersection::Hit(1));
+        assert_eq!(cast(1.5, 0.5), RayIntersection::Hit(1));
+        assert_eq!(cast(1.5, 1.5), RayIntersection::Miss);
+        assert_eq!(cast(1.0, 1.0), RayIntersection::OnSurface);
+        assert_eq!(cast(1.5, 1.0), RayIntersection::OnSurface);
+    }
+
+    #[test]
+    fn test_subtract_larger_box_is_empty() {
+        let small = nova_topo::build_cube(2.0).unwrap();
+        let large = nova_topo::build_cube(4.0).unwrap();
+        let tol = ToleranceContext::default();
+
+        let result = BooleanEngine::new(BooleanOp::Subtract).execute(&small, &large, &tol);
+        assert!(matches!(result, Err(OpsError::EmptyResult(_))));
+
+        let far = large.transformed(&Transform3::from_translation(10.0, 0.0, 0.0));
+        let result = BooleanEngine::new(BooleanOp::Intersect).execute(&small, &far, &tol);
+        assert!(matches!(result, Err(OpsError::NoIntersection)));
+
+        let hollow = BooleanEngine::new(BooleanOp::Subtract).execute(&large, &small, &tol).unwrap();
+        assert!((nova_topo::body_volume(&hollow).unwrap() - 56.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_box_union_tolerance_sweep() {
+        let outer = nova_topo::build_cube(2.0).unwrap();
+        let far = outer.transformed(&Transform3::from_translation(5.0, 0.0, 0.0));
+        let inner = nova_topo::build_cube(1.0).unwrap();
+        let tolerances = [1e-9, 1e-6, 1e-3];
+
+        for (b, volume) in [(&far, 16.0), (&inner, 8.0)] {
+            let results = BooleanEngine::unite_tolerance_sweep(&outer, b, &tolerances);
+            assert_eq!(results.len(), tolerances.len());
+            assert!(sweep_is_stable(&results, 1e-9));
+            for result in &results {
+                assert!((nova_topo::body_volume(result.as_ref().unwrap()).unwrap() - volume).abs() < 1e-9);
+            }
+        }
+
+        let mixed = [Ok(outer.transformed(&Transform3::identity())), Err(OpsError::NoIntersection)];
+        assert!(!sweep_is_stable(&mixed, 1e-9));
+        assert!(!sweep_is_stable(&[Ok(outer), Ok(inner)], 1e-9));
+    }
+
+    #[test]
+    fn test_material_diff_of_pocket() {
+        let block = nova_topo::build_cube(4.0).unwrap();
+        let pocket = nova_topo::build_cube(1.0).unwrap().transformed(&Transform3::from_translation(0.5, 0.0, 0.0));
+        let tol = ToleranceContext::default();
+        let cut = BooleanEngine::new(BooleanOp::Subtract).execute(&block, &pocket, &tol).unwrap();
+
+        let (added, removed) = BooleanEngine::material_diff(&block, &cut, &tol).unwrap();
+        assert_eq!(added.faces().len(), 0);
+        assert!((nova_topo::body_volume(&removed).unwrap() - 1.0).abs() < 1e-9);
+        assert!(nova_topo::bodies_equivalent(&removed, &pocket, &tol));
+
+        // Filling the pocket back in adds the same material
+        let (added, removed) = BooleanEngine::material_diff(&cut, &block, &tol).unwrap();
+        assert_eq!(removed.faces().len(), 0);
+        assert!((nova_topo::body_volume(&added).unwrap() - 1.0).abs() < 1e-9);
+
+        let (added, removed) = BooleanEngine::material_diff(&block, &block, &tol).unwrap();
+        assert_eq!(added.faces().len() + removed.faces().len(), 0);
+    }
+
+    #[test]
+    fn test_shared_workspace_matches_default() {
+        let large = nova_topo::build_cube(4.0).unwrap();
+        let small = nova_topo::build_cube(1.0).unwrap();
+        let tol = ToleranceContext::default();
+        let ops = [BooleanOp::Unite, BooleanOp::Subtract, BooleanOp::Intersect];
+        let mut workspace = BooleanWorkspace::new();
+
+        // Slides the small cube from nested, through crossing, to disjoint
+        for i in 0..100 {
+            let engine = BooleanEngine::new(ops[i % 3]);
+            let tool = small.transformed(&Transform3::from_translation(i as f64 * 0.05, 0.0, 0.0));
+            let shared = engine.execute_in(&large, &tool, &tol, &mut workspace);
+            let fresh = engine.execute(&large, &tool, &tol);
+            assert!(sweep_is_stable(&[shared, fresh], 0.0), "step {}", i);
+        }
+    }
+
+    #[test]
+    fn test_seeded_boolean_is_repeatable() {
+        let large = nova_topo::build_cube(4.0).unwrap();
+        let small = nova_topo::build_cube(1.0).unwrap();
+        let engine = BooleanEngine::new(BooleanOp::Subtract).with_deterministic(DeterministicConfig::new(42));
+        let run = || {
+            let body = engine.execute(&large, &small, &ToleranceContext::default()).unwrap();
+            let faces: Vec<_> = body.faces().iter().map(|f| f.id()).collect();
+            let positions: Vec<[u64; 3]> = body
+                .vertices()
+                .iter()
+                .map(|v| [v.position().x(), v.position().y(), v.position().z()].map(f64::to_bits))
+                .collect();
+            (faces, positions)
+        };
+
+        let (faces, positions) = run();
+        assert_eq!(faces.len(), 12);
+        assert_eq!(run(), (faces, positions));
+        assert_eq!(ray_directions(&engine.deterministic), ray_directions(&DeterministicConfig::new(42)));
+    }
+
+    #[test]
+    fn test_unite_keeps_face_colors() {
+        const RED: nova_topo::Color = [1.0, 0.0, 0.0, 1.0];
+        const BLUE: nova_topo::Color = [0.0, 0.0, 1.0, 1.0];
+        let mut cube = nova_topo::build_cube(2.0).unwrap();
+        cube.set_color(Some(RED));
+
+        let circle = CircularArc::circle(Point3::new(5.0, 0.0, 0.0), 1.0, Vec3::Z).unwrap();
+        let v = Arc::new(Vertex::new(nova_geom::Curve::start_point(&circle)));
+        let edge = Edge::with_curve(v.clone(), v, Arc::new(circle));
+        let plane = nova_geom::PlanarSurface::new(Point3::new(5.0, 0.0, 0.0), Vec3::X, Vec3::Y).unwrap();
+        let mut disc = Face::with_surface(Arc::new(plane));
+        disc.add_loop(Loop::from_coedges(vec![Coedge::new(Arc::new(edge), Sense::Same)]));
+        disc.set_color(Some(BLUE));
+        let mut shell = nova_topo::Shell::new();
+        shell.add_face(disc);
+        let mut profile = Body::new();
+        profile.add_shell(shell);
+        let cylinder = crate::FeatureEngine::new().extrude(&profile, &Default::default(), &ToleranceContext::default()).unwrap();
+        assert!(cylinder.faces().iter().all(|f| f.color() == Some(BLUE)));
+
+        let result = BooleanEngine::new(BooleanOp::Unite).execute(&cube, &cylinder, &ToleranceContext::default()).unwrap();
+        assert_eq!(result.faces().len(), 9);
+        for face in result.faces() {
+            let from_cylinder = face.outer_loop().unwrap().coedges()[0].start_vertex().position().x() > 3.0;
+            assert_eq!(result.face_color(face), Some(if from_cylinder { BLUE } else { RED }));
+        }
+    }
+
+    #[test]
+    fn test_wire_body_rejected() {
+        let cube = nova_topo::build_cube(2.0).unwrap();
+        let (a, b) = (Arc::new(Vertex::new(Point3::ORIGIN)), Arc::new(Vertex::new(Point3::new(1.0, 0.0, 0.0))));
+        let mut shell = nova_topo::Shell::new();
+        shell.add_wire(Arc::new(Edge::new(a, b)));
+        let mut wire = Body::new();
+        wire.add_shell(shell);
+
+        let result = BooleanEngine::new(BooleanOp::Unite).execute(&cube, &wire, &ToleranceContext::default());
+        assert!(matches!(result, Err(OpsError::InvalidBodies(_))));
+    }
+
+    #[test]
+    fn test_classify_point_in_cube() {
+        let cube = nova_topo::build_cube(2.0).unwrap();
+        let tol = ToleranceContext::default();
+        let classify = |p: Point3| classify_point(&cube, &p, &tol).unwrap();
+
+        assert_eq!(classify(Point3::new(0.2, -0.3, 0.1)), PointClassification::Inside);
+        assert_eq!(classify(Point3::new(3.0, 0.0, 0.0)), PointClassification::Outside);
+        assert_eq!(classify(Point3::new(1.0, 0.2, 0.3)), PointClassification::OnBoundary);
+    }
+
+    #[test]
+    fn test_classification_tessellates_each_face_once() {
+        let cube = nova_topo::build_cube(2.0).unwrap();
+        let tol = ToleranceContext::default();
+        let directions = ray_directions(&DeterministicConfig::default());
+        let mut workspace = BooleanWorkspace::new();
+
+        for x in [-0.5, 0.0, 0.5, 3.0] {
+            classify_point_in(&cube, &Point3::new(x, 0.1, 0.2), &tol, &directions, &mut workspace).unwrap();
+        }
+        assert_eq!(workspace.meshes.len(), cube.faces().len());
+    }
+
+    #[test]
+    fn test_quick_reject_sees_cylinder_bulge() {
+        // Cylindrical patch from 0.1 to 2.1 rad between straight edges, as a
+        // faceted import leaves it; its bulge at y = 1 is past every vertex
+        // and between the old grid samples
+        let surface = CylindricalSurface::bounded(Point3::ORIGIN, Vec3::Z, 1.0, Vec3::X, 0.0, 2.0, 0.1, 2.1).unwrap();
+        let corners: Vec<Point3> = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].iter().map(|&(u, v)| surface.evaluate(u, v)).collect();
+        let mut face = polygon_face(&corners);
+        face.set_surface(Some(Arc::new(surface)));
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut patch = Body::new();
+        patch.add_shell(shell);
+        assert!(patch.vertices().iter().all(|v| v.position().y() < 0.9));
+        assert!((patch.bounding_box().max.y() - 1.0).abs() < 1e-12);
+
+        // Probe dipping 2e-4 into the bulge
+        let probe = nova_topo::build_cube(0.2).unwrap().transformed(&Transform3::from_translation(0.0, 1.0998, 1.0));
+        let result = BooleanEngine::new(BooleanOp::Intersect).execute(&patch, &probe, &ToleranceContext::default());
+        assert!(matches!(result, Err(OpsError::NotSupported(_))), "{:?}", result);
+    }
+
+    #[test]
+    fn test_cylinder_bulging_out_of_cube_crosses() {
+        use nova_geom::{Curve, PlanarSurface};
+        use nova_topo::EulerAdvanced;
+
+        // Radius 1 about (-0.5, 0); the seam vertex at x = 0.5 sits inside
+        // the 2 x 2 x 2 cube but the side reaches out to x = -1.5
+        let circle = CircularArc::circle(Point3::new(-0.5, 0.0, -0.5), 1.0, Vec3::Z).unwrap();
+        let v = Arc::new(Vertex::new(circle.start_point()));
+        let edge = Edge::with_curve(v.clone(), v, Arc::new(circle));
+        let plane = PlanarSurface::new(Point3::new(-0.5, 0.0, -0.5), Vec3::X, Vec3::Y).unwrap();
+        let mut disc = Face::with_surface(Arc::new(plane));
+        disc.add_loop(Loop::from_coedges(vec![Coedge::new(Arc::new(edge), Sense::Same)]));
+        let cylinder = EulerAdvanced::extrude_face(&disc, Vec3::Z, 1.0).unwrap();
+        let cube = nova_topo::build_cube(2.0).unwrap();
+        let tol = ToleranceContext::default();
+        assert!(cylinder.vertices().iter().all(|v| classify_point(&cube, &v.position(), &tol).unwrap() == PointClassification::Inside));
+
+        for op in [BooleanOp::Unite, BooleanOp::Subtract, BooleanOp::Intersect] {
+            for (a, b) in [(&cylinder, &cube), (&cube, &cylinder)] {
+                let result = BooleanEngine::new(op).execute(a, b, &tol);
+                assert!(matches!(result, Err(OpsError::NotSupported(_))), "{:?} {:?}", op, result);
+            }
+        }
+    }
+}
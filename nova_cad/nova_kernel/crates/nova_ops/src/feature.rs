@@ -1,29 +1,54 @@
 //! Feature-based Operations - Extrude, Revolve, Sweep, Loft
 
-use crate::{OpsError, OpsResult};
-use nova_math::{Point3, Vec3, ToleranceContext};
-use nova_topo::Body;
+use crate::{BooleanWorkspace, OpsError, OpsResult};
+use nova_math::{Point3, Vec3, ToleranceContext, Transform3};
+use nova_tess::TessellationOptions;
+use nova_topo::{Body, EulerAdvanced, Face, Shell};
+use std::f64::consts::TAU;
+use std::sync::Arc;
+
+/// Where an extrusion stops
+#[derive(Debug, Clone)]
+pub enum ExtrudeTermination {
+    /// A fixed distance along the direction (can be negative)
+    Blind(f64),
+    /// Where the profile runs into this face
+    UpToFace(Face),
+    /// Where the profile first runs into this body
+    UpToBody(Body),
+    /// Far enough to pass through any model, `THROUGH_ALL_DISTANCE`
+    ThroughAll,
+}
+
+/// Length of a through-all extrusion, well past the kernel's working space
+pub const THROUGH_ALL_DISTANCE: f64 = 1.0e6;
 
 /// Extrusion options
 #[derive(Debug, Clone)]
 pub struct ExtrudeOptions {
-    /// Distance to extrude (can be negative)
-    pub distance: f64,
+    /// Where to stop
+    pub termination: ExtrudeTermination,
     /// Direction vector (defaults to profile normal)
     pub direction: Option<Vec3>,
     /// Whether to create a solid (cap ends)
     pub solid: bool,
     /// Draft angle in degrees
     pub draft_angle: f64,
+    /// Extrude half the distance to each side of the profile
+    pub symmetric: bool,
+    /// Extrude against the direction
+    pub reverse: bool,
 }
 
 impl Default for ExtrudeOptions {
     fn default() -> Self {
         Self {
-            distance: 1.0,
+            termination: ExtrudeTermination::Blind(1.0),
             direction: None,
             solid: true,
             draft_angle: 0.0,
+            symmetric: false,
+            reverse: false,
         }
     }
 }
@@ -88,26 +113,161 @@ impl FeatureEngine {
         Self
     }
     
-    /// Extrude a profile (stub)
+    /// Extrude the single face of a profile body
+    ///
+    /// Each profile edge becomes an exact side face: a plane for a line, a
+    /// cylinder for an arc about the direction and an extrusion surface for
+    /// any other curve. Without `solid` the end caps are left off. Every
+    /// profile loop must close within the context's resolution.
+    ///
+    /// A symmetric extrude starts half its distance behind the profile, and
+    /// `reverse` flips the direction for every termination.
+    ///
+    /// The up-to terminations extrude past the target and trim the far end
+    /// onto the surface of the face the profile runs into, so every profile
+    /// vertex must meet that same face.
     pub fn extrude(
         &self,
-        _profile: &Body,
-        _options: &ExtrudeOptions,
-        _tolerance: &ToleranceContext,
+        profile: &Body,
+        options: &ExtrudeOptions,
+        tolerance: &ToleranceContext,
     ) -> OpsResult<Body> {
-        Err(OpsError::NotSupported("Extrude not yet implemented".to_string()))
+        if options.draft_angle.abs() > tolerance.angle_tol {
+            return Err(OpsError::NotSupported("Drafted extrude not yet implemented".to_string()));
+        }
+        let faces = profile.faces();
+        let [face] = faces[..] else {
+            return Err(OpsError::InvalidParameters(format!("Profile must have one face, found {}", faces.len())));
+        };
+        if let Some(open) = face.loops().iter().position(|lp| !lp.is_geometrically_closed(tolerance.resabs)) {
+            return Err(OpsError::InvalidParameters(format!("Profile loop {} is not closed", open)));
+        }
+        let mut direction = options.direction.unwrap_or_else(|| face.normal_at(0.0, 0.0));
+        if direction.is_zero(tolerance.resabs) {
+            return Err(OpsError::InvalidParameters("Extrusion has zero length".to_string()));
+        }
+        if options.reverse {
+            direction = -direction;
+        }
+
+        let blind = |distance: f64| {
+            if !options.symmetric {
+                return extrude_blind(face, direction, distance, tolerance);
+            }
+            // Start half the distance back so the solid straddles the profile
+            let back = Transform3::from_translation_vec(direction.normalized() * (-distance / 2.0));
+            let start = profile.transformed(&back);
+            extrude_blind(start.faces()[0], direction, distance, tolerance)
+        };
+        let mut body = match &options.termination {
+            ExtrudeTermination::Blind(distance) => blind(*distance)?,
+            ExtrudeTermination::ThroughAll => blind(THROUGH_ALL_DISTANCE)?,
+            ExtrudeTermination::UpToFace(_) | ExtrudeTermination::UpToBody(_) if options.symmetric => {
+                return Err(OpsError::InvalidParameters("Symmetric extrude needs a blind or through-all termination".to_string()));
+            }
+            ExtrudeTermination::UpToFace(target) => extrude_up_to(face, direction, &[target], tolerance)?,
+            ExtrudeTermination::UpToBody(target) => extrude_up_to(face, direction, &target.faces(), tolerance)?,
+        };
+        if !options.solid {
+            for shell in body.shells_mut() {
+                shell.faces_mut().drain(..2);
+            }
+        }
+        inherit_color(&mut body, profile, face);
+        Ok(body)
     }
     
-    /// Revolve a profile (stub)
+    /// Revolve the single face of a profile body about the options' axis
+    ///
+    /// Each profile edge becomes an exact face of revolution: a plane, cone
+    /// or cylinder for a line and a sphere or torus for an arc. A partial
+    /// revolve is capped with planar faces unless `solid` is off.
     pub fn revolve(
         &self,
-        _profile: &Body,
-        _options: &RevolveOptions,
-        _tolerance: &ToleranceContext,
+        profile: &Body,
+        options: &RevolveOptions,
+        tolerance: &ToleranceContext,
     ) -> OpsResult<Body> {
-        Err(OpsError::NotSupported("Revolve not yet implemented".to_string()))
+        let faces = profile.faces();
+        let [face] = faces[..] else {
+            return Err(OpsError::InvalidParameters(format!("Profile must have one face, found {}", faces.len())));
+        };
+        let angle = options.angle.to_radians();
+        if options.axis_direction.is_zero(tolerance.resabs) || angle.abs() <= tolerance.angle_tol {
+            return Err(OpsError::InvalidParameters("Revolve needs an axis and a nonzero angle".to_string()));
+        }
+        if angle.abs() > TAU + tolerance.angle_tol {
+            return Err(OpsError::InvalidParameters("Revolve angle exceeds a full turn".to_string()));
+        }
+        if let Some(open) = face.loops().iter().position(|lp| !lp.is_geometrically_closed(tolerance.resabs)) {
+            return Err(OpsError::InvalidParameters(format!("Profile loop {} is not closed", open)));
+        }
+
+        let mut body = EulerAdvanced::revolve_face(face, options.axis_origin, options.axis_direction, angle)
+            .map_err(|e| OpsError::Topology(e.to_string()))?;
+        let partial = angle.abs() < TAU - tolerance.angle_tol;
+        if partial && !options.solid {
+            for shell in body.shells_mut() {
+                shell.faces_mut().drain(..2);
+            }
+        }
+        inherit_color(&mut body, profile, face);
+        Ok(body)
     }
     
+    /// Thicken a sheet face into a solid
+    ///
+    /// The solid grows `thickness` along the face's outward normal, or
+    /// against it when negative; with `both_sides` it straddles the face,
+    /// half on each side. Planar faces are extruded, while curved faces get
+    /// a far face on an offset of their surface joined by ruled sides.
+    pub fn thicken(
+        &self,
+        face: &Face,
+        thickness: f64,
+        both_sides: bool,
+        tolerance: &ToleranceContext,
+    ) -> OpsResult<Body> {
+        if thickness.abs() <= tolerance.resabs {
+            return Err(OpsError::InvalidParameters("Thickness is zero".to_string()));
+        }
+        if let Some(open) = face.loops().iter().position(|lp| !lp.is_geometrically_closed(tolerance.resabs)) {
+            return Err(OpsError::InvalidParameters(format!("Face loop {} is not closed", open)));
+        }
+        let topology = |e: nova_topo::TopologyError| OpsError::Topology(e.to_string());
+        let planar = face.surface().map_or(true, |s| s.surface_type() == nova_geom::SurfaceType::Planar);
+        if planar {
+            let mut shell = Shell::new();
+            shell.add_face(face.clone());
+            let mut sheet = Body::new();
+            sheet.add_shell(shell);
+            let options = ExtrudeOptions {
+                termination: ExtrudeTermination::Blind(thickness),
+                direction: Some(face.normal_at(0.0, 0.0)),
+                symmetric: both_sides,
+                ..Default::default()
+            };
+            let mut body = self.extrude(&sheet, &options, tolerance)?;
+            for shell in body.shells_mut() {
+                EulerAdvanced::stitch_shell(shell, tolerance.resabs);
+            }
+            return Ok(body);
+        }
+        let mut body = if both_sides {
+            let start = EulerAdvanced::offset_face(face, -thickness / 2.0).map_err(topology)?;
+            EulerAdvanced::thicken_face(&start, thickness).map_err(topology)?
+        } else {
+            EulerAdvanced::thicken_face(face, thickness).map_err(topology)?
+        };
+        for shell in body.shells_mut() {
+            EulerAdvanced::stitch_shell(shell, tolerance.resabs);
+            for feature_face in shell.faces_mut() {
+                feature_face.set_color(face.color());
+            }
+        }
+        Ok(body)
+    }
+
     /// Sweep a profile along a path (stub)
     pub fn sweep(
         &self,
@@ -130,8 +290,370 @@ impl FeatureEngine {
     }
 }
 
+/// Give every face of a feature the look of the profile face it grew from
+fn inherit_color(body: &mut Body, profile: &Body, face: &Face) {
+    body.set_color(profile.color());
+    for shell in body.shells_mut() {
+        for feature_face in shell.faces_mut() {
+            feature_face.set_color(face.color());
+        }
+    }
+}
+
+/// Extrude `face` a fixed distance along `direction`
+fn extrude_blind(face: &Face, direction: Vec3, distance: f64, tolerance: &ToleranceContext) -> OpsResult<Body> {
+    if distance.abs() <= tolerance.resabs {
+        return Err(OpsError::InvalidParameters("Extrusion has zero length".to_string()));
+    }
+    EulerAdvanced::extrude_face(face, direction, distance).map_err(|e| OpsError::Topology(e.to_string()))
+}
+
+/// Extrude `face` along `direction` until it runs into the nearest of `targets`
+///
+/// Rays from the profile vertices pick the target face; the blind extrusion
+/// then reaches past that face and is trimmed onto its surface.
+fn extrude_up_to(face: &Face, direction: Vec3, targets: &[&Face], tolerance: &ToleranceContext) -> OpsResult<Body> {
+    let direction = direction.normalized();
+    let vertices: Vec<Point3> = face.loops().iter()
+        .flat_map(|lp| lp.coedges())
+        .map(|c| c.start_vertex().position())
+        .collect();
+    let mut hit: Option<(usize, Point3)> = None;
+    let mut workspace = BooleanWorkspace::new();
+    for &p in &vertices {
+        let mut nearest: Option<(f64, usize)> = None;
+        for (i, target) in targets.iter().enumerate() {
+            let crossings = crate::boolean::ray_face_crossings(&p, &direction, target, tolerance, &mut workspace)?;
+            if let Some(c) = crossings.iter().find(|c| c.distance > tolerance.resabs) {
+                if nearest.map_or(true, |(d, _)| c.distance < d) {
+                    nearest = Some((c.distance, i));
+                }
+            }
+        }
+        let Some((distance, i)) = nearest else {
+            return Err(OpsError::InvalidParameters(format!("Extrusion from {:?} never meets the target", p)));
+        };
+        match hit {
+            Some((first, _)) if first != i => {
+                return Err(OpsError::NotSupported("Extrusion up to more than one target face".to_string()));
+            }
+            Some(_) => {}
+            None => hit = Some((i, p + direction * distance)),
+        }
+    }
+    let Some((index, first_hit)) = hit else {
+        return Err(OpsError::InvalidParameters("Profile has no vertices".to_string()));
+    };
+    let target = targets[index];
+    let surface = target.surface()
+        .ok_or_else(|| OpsError::InvalidParameters("Target face has no surface".to_string()))?
+        .clone();
+
+    // Long enough for the side faces to pass every point of the target
+    let mesh = nova_tess::tessellate_face(target, &TessellationOptions::default())
+        .map_err(|e| OpsError::Geometry(e.to_string()))?;
+    let height = |p: Point3| p.to_vector().dot(&direction);
+    let base = vertices.iter().map(|&p| height(p)).fold(f64::INFINITY, f64::min);
+    let top = mesh.vertices.iter().map(|v| height(v.position)).fold(height(first_hit), f64::max);
+    let reach = 2.0 * (top - base);
+
+    let plane_height = height(first_hit);
+    let project = |p: Point3| ray_onto_surface(p, direction, surface.as_ref(), plane_height - height(p), tolerance);
+    EulerAdvanced::extrude_face_to_surface(face, direction, reach, Arc::clone(&surface), &project)
+        .map_err(|e| OpsError::Topology(e.to_string()))
+}
+
+/// Newton steps allowed when carrying a point onto a surface along a ray
+const MAX_RAY_STEPS: usize = 50;
+
+/// Point where the ray from `origin` along unit `direction` meets `surface`,
+/// searching from `guess` along the ray
+///
+/// Each step moves to where the ray crosses the tangent plane at the
+/// closest surface point.
+fn ray_onto_surface(
+    origin: Point3,
+    direction: Vec3,
+    surface: &dyn nova_geom::Surface,
+    guess: f64,
+    tolerance: &ToleranceContext,
+) -> Option<Point3> {
+    let mut t = guess;
+    for _ in 0..MAX_RAY_STEPS {
+        let p = origin + direction * t;
+        let (u, v, closest, distance) = surface.closest_point(&p).ok()?;
+        if distance <= tolerance.resabs * 1e-3 {
+            return Some(p);
+        }
+        let normal = surface.normal(u, v);
+        let slope = direction.dot(&normal);
+        if slope.abs() <= tolerance.angle_tol {
+            return None;
+        }
+        t -= (p - closest).dot(&normal) / slope;
+    }
+    None
+}
+
 impl Default for FeatureEngine {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_geom::{CircularArc, EllipseArc, Line, PlanarSurface, SurfaceType};
+    use nova_topo::{Coedge, Edge, Face, Loop, Sense, Shell, Vertex};
+    use std::f64::consts::PI;
+    use std::sync::Arc;
+
+    /// Single-face body bounded by a line from (-1, 0) to (1, 0) and `upper` back
+    fn profile(upper: Arc<dyn nova_geom::Curve>) -> Body {
+        let (a, b) = (Point3::new(-1.0, 0.0, 0.0), Point3::new(1.0, 0.0, 0.0));
+        let (va, vb) = (Arc::new(Vertex::new(a)), Arc::new(Vertex::new(b)));
+        let line = Edge::with_curve(va.clone(), vb.clone(), Arc::new(Line::segment(a, b).unwrap()));
+        let arc = Edge::with_curve(vb, va, upper);
+        let mut face = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        face.add_loop(Loop::from_coedges(vec![
+            Coedge::new(Arc::new(line), Sense::Same),
+            Coedge::new(Arc::new(arc), Sense::Same),
+        ]));
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut body = Body::new();
+        body.add_shell(shell);
+        body
+    }
+
+    fn side_types(body: &Body) -> Vec<SurfaceType> {
+        body.faces()[2..].iter().map(|f| f.surface().unwrap().surface_type()).collect()
+    }
+
+    #[test]
+    fn test_extrude_line_and_arc_exactly() {
+        let half_disc = profile(Arc::new(CircularArc::new(Point3::ORIGIN, 1.0, Vec3::Z, Vec3::X, PI).unwrap()));
+        let options = ExtrudeOptions { termination: ExtrudeTermination::Blind(2.0), ..Default::default() };
+        let tolerance = ToleranceContext::default();
+        let body = FeatureEngine::new().extrude(&half_disc, &options, &tolerance).unwrap();
+
+        assert_eq!(body.faces().len(), 4);
+        assert_eq!(side_types(&body), vec![SurfaceType::Planar, SurfaceType::Cylindrical]);
+        // Every face normal points out of the half cylinder
+        for face in body.faces() {
+            let surface = face.surface().unwrap();
+            let corner = face.outer_loop().unwrap().coedges()[0].end_vertex().position();
+            let inside = Point3::new(0.0, 0.4, 1.0);
+            let probe = corner + (inside - corner) * 0.1;
+            let (u, v, on_surface, _) = surface.closest_point(&probe).unwrap();
+            assert!(face.normal_at(u, v).dot(&(on_surface - inside)) > 0.0);
+        }
+
+        let sheet = ExtrudeOptions { solid: false, ..options };
+        assert_eq!(FeatureEngine::new().extrude(&half_disc, &sheet, &tolerance).unwrap().faces().len(), 2);
+    }
+
+    /// Check that `face`'s normals point away from `inside` near each of its vertices
+    fn assert_faces_away(face: &Face, inside: Point3) {
+        let surface = face.surface().unwrap();
+        for coedge in face.outer_loop().unwrap().coedges() {
+            let corner = coedge.end_vertex().position();
+            let probe = corner + (inside - corner) * 0.1;
+            let (u, v, on_surface, _) = surface.closest_point(&probe).unwrap();
+            assert!(face.normal_at(u, v).dot(&(on_surface - inside)) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_revolve_semicircle_to_sphere() {
+        let half_disc = profile(Arc::new(CircularArc::new(Point3::ORIGIN, 1.0, Vec3::Z, Vec3::X, PI).unwrap()));
+        let options = RevolveOptions { axis_direction: Vec3::X, ..Default::default() };
+        let body = FeatureEngine::new().revolve(&half_disc, &options, &ToleranceContext::default()).unwrap();
+
+        // The diameter lies on the axis, so only the arc sweeps a face
+        let faces = body.faces();
+        assert_eq!(faces.len(), 1);
+        let surface = faces[0].surface().unwrap();
+        assert_eq!(surface.surface_type(), SurfaceType::Spherical);
+        for (u, v) in [(0.1, 0.2), (0.6, 0.5), (0.9, 0.8)] {
+            let p = surface.evaluate(u, v);
+            assert!((p.distance_to(&Point3::ORIGIN) - 1.0).abs() < 1e-12);
+            assert!(faces[0].normal_at(u, v).dot(&(p - Point3::ORIGIN)) > 0.0);
+        }
+        assert_eq!(body.edges().len(), 1);
+    }
+
+    #[test]
+    fn test_revolve_rectangle_quarter_turn() {
+        let corners = [(1.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 1.0)].map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let coedges = (0..4)
+            .map(|i| {
+                let (a, b) = (corners[i].clone(), corners[(i + 1) % 4].clone());
+                let line = Line::segment(a.position(), b.position()).unwrap();
+                Coedge::new(Arc::new(Edge::with_curve(a, b, Arc::new(line))), Sense::Same)
+            })
+            .collect();
+        let mut face = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        face.add_loop(Loop::from_coedges(coedges));
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut rectangle = Body::new();
+        rectangle.add_shell(shell);
+
+        let options = RevolveOptions { axis_direction: Vec3::Y, angle: 90.0, ..Default::default() };
+        let tolerance = ToleranceContext::default();
+        let body = FeatureEngine::new().revolve(&rectangle, &options, &tolerance).unwrap();
+        assert_eq!(body.faces().len(), 6);
+        assert_eq!(side_types(&body), vec![SurfaceType::Planar, SurfaceType::Cylindrical, SurfaceType::Planar, SurfaceType::Cylindrical]);
+        // Halfway round, at the middle of the profile
+        let inside = Point3::new(1.5 * (PI / 4.0).cos(), 0.5, -1.5 * (PI / 4.0).sin());
+        for face in body.faces() {
+            assert_faces_away(face, inside);
+        }
+
+        let sheet = RevolveOptions { solid: false, ..options };
+        assert_eq!(FeatureEngine::new().revolve(&rectangle, &sheet, &tolerance).unwrap().faces().len(), 4);
+    }
+
+    #[test]
+    fn test_extrude_rejects_open_profile() {
+        // A quarter arc stops at (0, 1), short of the line's start
+        let open = profile(Arc::new(CircularArc::new(Point3::ORIGIN, 1.0, Vec3::Z, Vec3::X, PI / 2.0).unwrap()));
+        let options = ExtrudeOptions { termination: ExtrudeTermination::Blind(1.0), ..Default::default() };
+        let result = FeatureEngine::new().extrude(&open, &options, &ToleranceContext::default());
+        assert!(matches!(result, Err(OpsError::InvalidParameters(_))));
+    }
+
+    #[test]
+    fn test_extrude_ellipse_sweeps_surface() {
+        let half_ellipse = profile(Arc::new(EllipseArc::new(Point3::ORIGIN, 1.0, 0.5, Vec3::X, Vec3::Z, 0.0, PI).unwrap()));
+        let options = ExtrudeOptions { termination: ExtrudeTermination::Blind(1.0), ..Default::default() };
+        let body = FeatureEngine::new().extrude(&half_ellipse, &options, &ToleranceContext::default()).unwrap();
+        assert_eq!(side_types(&body), vec![SurfaceType::Planar, SurfaceType::SweptSurface]);
+
+        let swept = body.faces()[3].surface().unwrap().clone();
+        let p = Point3::new(0.0, 0.5, 0.7);
+        let (_, v, closest, dist) = swept.closest_point(&p).unwrap();
+        assert!(dist < 1e-6 && closest.distance_to(&p) < 1e-6);
+        assert!((v - 0.7).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_extrude_up_to_angled_face() {
+        // Plane z = 2 + x / 2, bounded well beyond the profile
+        let slope = Vec3::new(1.0, 0.0, 0.5).normalized();
+        let plane = PlanarSurface::new(Point3::new(0.0, 0.0, 2.0), slope, Vec3::Y).unwrap();
+        let corners = [(-3.0, -3.0), (3.0, -3.0), (3.0, 3.0), (-3.0, 3.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 2.0 + x / 2.0))));
+        let coedges = (0..4)
+            .map(|i| Coedge::new(Arc::new(Edge::new(corners[i].clone(), corners[(i + 1) % 4].clone())), Sense::Same))
+            .collect();
+        let mut target = Face::with_surface(Arc::new(plane));
+        target.add_loop(Loop::from_coedges(coedges));
+        let on_target = |p: Point3| (p.z() - 2.0 - p.x() / 2.0).abs() < 1e-9;
+
+        let half_disc = profile(Arc::new(CircularArc::new(Point3::ORIGIN, 1.0, Vec3::Z, Vec3::X, PI).unwrap()));
+        let options = ExtrudeOptions { termination: ExtrudeTermination::UpToFace(target.clone()), ..Default::default() };
+        let body = FeatureEngine::new().extrude(&half_disc, &options, &ToleranceContext::default()).unwrap();
+
+        assert_eq!(body.faces().len(), 4);
+        assert_eq!(side_types(&body), vec![SurfaceType::Planar, SurfaceType::Cylindrical]);
+        let top = body.faces()[1];
+        assert_eq!(top.surface().unwrap().surface_type(), SurfaceType::Planar);
+        for coedge in top.outer_loop().unwrap().coedges() {
+            let curve = coedge.edge().curve().unwrap();
+            let range = curve.param_range();
+            for i in 0..=8 {
+                assert!(on_target(curve.evaluate(range.start + range.length() * i as f64 / 8.0)));
+            }
+        }
+        // The top faces up and out of the solid, like the plane's own normal
+        let (u, v, _, _) = top.surface().unwrap().closest_point(&Point3::new(0.0, 0.5, 2.0)).unwrap();
+        assert!(top.outward_normal_at(u, v, &body).dot(&Vec3::new(-0.5, 0.0, 1.0).normalized()) > 1.0 - 1e-9);
+        for face in body.faces() {
+            assert_faces_away(face, Point3::new(0.0, 0.4, 1.0));
+        }
+
+        let mut solid = Shell::new();
+        solid.add_face(target);
+        let mut block = Body::new();
+        block.add_shell(solid);
+        let options = ExtrudeOptions { termination: ExtrudeTermination::UpToBody(block), ..Default::default() };
+        let reached = FeatureEngine::new().extrude(&half_disc, &options, &ToleranceContext::default()).unwrap();
+        assert_eq!(reached.vertices().len(), body.vertices().len());
+    }
+
+    #[test]
+    fn test_extrude_symmetric_and_reversed() {
+        let half_disc = profile(Arc::new(CircularArc::new(Point3::ORIGIN, 1.0, Vec3::Z, Vec3::X, PI).unwrap()));
+        let tolerance = ToleranceContext::default();
+        let z_extent = |options: &ExtrudeOptions| {
+            let bbox = FeatureEngine::new().extrude(&half_disc, options, &tolerance).unwrap().bounding_box();
+            (bbox.min.z(), bbox.max.z())
+        };
+        let symmetric = ExtrudeOptions { termination: ExtrudeTermination::Blind(3.0), symmetric: true, ..Default::default() };
+        let (low, high) = z_extent(&symmetric);
+        assert!((low + 1.5).abs() < 1e-12 && (high - 1.5).abs() < 1e-12);
+        let both = ExtrudeOptions { reverse: true, ..symmetric.clone() };
+        assert_eq!(z_extent(&both), (low, high));
+
+        let reversed = ExtrudeOptions { termination: ExtrudeTermination::Blind(3.0), reverse: true, ..Default::default() };
+        let (low, high) = z_extent(&reversed);
+        assert!((low + 3.0).abs() < 1e-12 && high.abs() < 1e-12);
+
+        let body = FeatureEngine::new().extrude(&half_disc, &symmetric, &tolerance).unwrap();
+        for face in body.faces() {
+            assert_faces_away(face, Point3::new(0.0, 0.4, 0.0));
+        }
+    }
+
+    #[test]
+    fn test_thicken_disk_into_slab() {
+        let centre = Arc::new(Vertex::new(Point3::new(1.0, 0.0, 0.0)));
+        let circle = CircularArc::new(Point3::ORIGIN, 1.0, Vec3::Z, Vec3::X, 2.0 * PI).unwrap();
+        let rim = Edge::with_curve(centre.clone(), centre, Arc::new(circle));
+        let mut disk = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        disk.add_loop(Loop::from_coedges(vec![Coedge::new(Arc::new(rim), Sense::Same)]));
+        let tolerance = ToleranceContext::default();
+
+        let slab = FeatureEngine::new().thicken(&disk, 0.25, false, &tolerance).unwrap();
+        let bbox = slab.bounding_box();
+        assert!(bbox.min.z().abs() < 1e-12 && (bbox.max.z() - 0.25).abs() < 1e-12);
+        assert!(nova_topo::validate_body(&slab, &tolerance).unwrap().is_empty(), "{:?}", nova_topo::validate_body(&slab, &tolerance));
+        let volume = nova_tess::tessellate_body(&slab).unwrap().signed_volume();
+        assert!((volume - 0.25 * PI).abs() < 1e-2, "{}", volume);
+
+        let straddling = FeatureEngine::new().thicken(&disk, 0.25, true, &tolerance).unwrap().bounding_box();
+        assert!((straddling.min.z() + 0.125).abs() < 1e-12 && (straddling.max.z() - 0.125).abs() < 1e-12);
+        assert!(FeatureEngine::new().thicken(&disk, 0.0, false, &tolerance).is_err());
+    }
+
+    #[test]
+    fn test_thicken_cylinder_patch() {
+        let corner = |angle: f64, z: f64| Arc::new(Vertex::new(Point3::new(angle.cos(), angle.sin(), z)));
+        let (a, b, c, d) = (corner(0.0, 0.0), corner(PI / 2.0, 0.0), corner(PI / 2.0, 1.0), corner(0.0, 1.0));
+        let arc = |z: f64| Arc::new(CircularArc::new(Point3::new(0.0, 0.0, z), 1.0, Vec3::Z, Vec3::X, PI / 2.0).unwrap());
+        let line = |p: &Arc<Vertex>, q: &Arc<Vertex>| Arc::new(Line::segment(p.position(), q.position()).unwrap());
+        let surface = nova_geom::CylindricalSurface::bounded(Point3::ORIGIN, Vec3::Z, 1.0, Vec3::X, 0.0, 1.0, 0.0, PI / 2.0).unwrap();
+        let mut patch = Face::with_surface(Arc::new(surface));
+        patch.add_loop(Loop::from_coedges(vec![
+            Coedge::new(Arc::new(Edge::with_curve(a.clone(), b.clone(), arc(0.0))), Sense::Same),
+            Coedge::new(Arc::new(Edge::with_curve(b.clone(), c.clone(), line(&b, &c))), Sense::Same),
+            Coedge::new(Arc::new(Edge::with_curve(d.clone(), c.clone(), arc(1.0))), Sense::Opposite),
+            Coedge::new(Arc::new(Edge::with_curve(d.clone(), a.clone(), line(&d, &a))), Sense::Same),
+        ]));
+        let tolerance = ToleranceContext::default();
+
+        let shell = FeatureEngine::new().thicken(&patch, 0.5, false, &tolerance).unwrap();
+        assert_eq!(shell.faces().len(), 6);
+        assert_eq!(shell.faces()[1].surface().unwrap().surface_type(), SurfaceType::OffsetSurface);
+        let radii: Vec<f64> = shell.vertices().iter().map(|v| v.position().x().hypot(v.position().y())).collect();
+        assert!(radii.iter().all(|r| (r - 1.0).abs() < 1e-9 || (r - 1.5).abs() < 1e-9));
+        assert!(nova_topo::validate_body(&shell, &tolerance).unwrap().is_empty(), "{:?}", nova_topo::validate_body(&shell, &tolerance));
+
+        let straddling = FeatureEngine::new().thicken(&patch, 0.5, true, &tolerance).unwrap();
+        let radii: Vec<f64> = straddling.vertices().iter().map(|v| v.position().x().hypot(v.position().y())).collect();
+        assert!(radii.iter().all(|r| (r - 0.75).abs() < 1e-9 || (r - 1.25).abs() < 1e-9));
+    }
+}
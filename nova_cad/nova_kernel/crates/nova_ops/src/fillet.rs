@@ -1,8 +1,9 @@
 //! Fillet and Chamfer Operations
 
 use crate::{OpsError, OpsResult};
-use nova_math::ToleranceContext;
-use nova_topo::{Body, Edge};
+use nova_geom::{CurveType, CylindricalSurface, Line, SurfaceType};
+use nova_math::{Point3, ToleranceContext, Vec3};
+use nova_topo::{Body, Edge, Face};
 
 /// Fillet options
 #[derive(Debug, Clone)]
@@ -30,6 +31,15 @@ impl Default for ChamferOptions {
     }
 }
 
+/// Fillet surface an edge would get, before it is stitched into the body
+#[derive(Debug, Clone)]
+pub struct FilletPreview {
+    /// Rolling-ball surface between the two faces
+    pub surface: CylindricalSurface,
+    /// Lines where the fillet meets the first and second face
+    pub rails: [Line; 2],
+}
+
 /// Fillet and chamfer engine
 #[derive(Debug, Clone)]
 pub struct FilletEngine;
@@ -62,6 +72,57 @@ impl FilletEngine {
         Err(OpsError::NotSupported("Chamfer not yet implemented".to_string()))
     }
     
+    /// Largest fillet radius that fits on a straight edge between two flat faces
+    ///
+    /// A fillet of radius r cuts back `r / tan(a / 2)` into each face, where
+    /// `a` is the angle between the faces. The cut-back is held to half of
+    /// each face's width across the edge, leaving room for a matching
+    /// fillet on the far side. Returns 0 for edges that cannot be filleted:
+    /// curved edges, edges of curved faces, non-manifold edges and edges
+    /// between tangent faces.
+    pub fn max_radius(&self, body: &Body, edge: &Edge) -> f64 {
+        match fillet_corner(body, edge) {
+            Some(corner) => corner.widths[0].min(corner.widths[1]) / 2.0 * (corner.wedge / 2.0).tan(),
+            None => 0.0,
+        }
+    }
+
+    /// Fillet surface for `edge` at `options.radius`, without changing the body
+    ///
+    /// Fails with `InvalidParameters` if the edge cannot be filleted or the
+    /// radius is beyond [`FilletEngine::max_radius`].
+    pub fn preview(&self, body: &Body, edge: &Edge, options: &FilletOptions) -> OpsResult<FilletPreview> {
+        let corner = fillet_corner(body, edge)
+            .ok_or_else(|| OpsError::InvalidParameters("Edge is not a straight edge between two flat faces".to_string()))?;
+        let max = self.max_radius(body, edge);
+        let radius = options.radius;
+        if radius <= 0.0 || radius > max {
+            return Err(OpsError::InvalidParameters(format!(
+                "Fillet radius {} is outside (0, {}]", radius, max
+            )));
+        }
+
+        let setback = radius / (corner.wedge / 2.0).tan();
+        let rails = [0, 1].map(|i| Line::segment(corner.start + corner.inward[i] * setback, corner.end + corner.inward[i] * setback));
+        let [Ok(rail1), Ok(rail2)] = rails else {
+            return Err(OpsError::Geometry("Fillet rail is degenerate".to_string()));
+        };
+
+        // The ball rolls on the material side of a convex edge and the air
+        // side of a concave one, touching each face along its normal
+        let toward = if corner.convex { -1.0 } else { 1.0 };
+        let [n1, n2] = corner.normals;
+        let bisector = (n1 + n2).normalized() * toward;
+        let along = corner.end - corner.start;
+        // Turn from the first face's contact to the second's about the axis
+        let (origin, axis) = if along.dot(&n1.cross(&n2)) > 0.0 { (corner.start, along) } else { (corner.end, -along) };
+        let center = origin + bisector * (radius / (corner.wedge / 2.0).sin());
+        let surface = CylindricalSurface::bounded(
+            center, axis, radius, n1 * -toward, 0.0, along.length(), 0.0, n1.angle_to(&n2),
+        ).map_err(|e| OpsError::Geometry(e.to_string()))?;
+        Ok(FilletPreview { surface, rails: [rail1, rail2] })
+    }
+
     /// Propagate edge selection to tangent edges (stub)
     pub fn propagate_tangent_edges(
         &self,
@@ -78,3 +139,109 @@ impl Default for FilletEngine {
         Self::new()
     }
 }
+
+/// Straight edge between two flat faces, as seen by a fillet
+struct FilletCorner {
+    start: Point3,
+    end: Point3,
+    /// Outward unit normals of the two faces
+    normals: [Vec3; 2],
+    /// Unit directions from the edge into each face, square to the edge
+    inward: [Vec3; 2],
+    /// Furthest each face reaches from the edge
+    widths: [f64; 2],
+    /// Angle between the faces across the wedge the fillet fills
+    wedge: f64,
+    /// Whether the faces meet with material inside the wedge
+    convex: bool,
+}
+
+/// Describe `edge` of `body` for filleting, or None if it can't be filleted
+fn fillet_corner(body: &Body, edge: &Edge) -> Option<FilletCorner> {
+    if edge.curve().is_some_and(|c| c.curve_type() != CurveType::Line) {
+        return None;
+    }
+    let [face1, face2] = edge.faces(body)[..] else {
+        return None;
+    };
+    let (start, end) = (edge.start_vertex().position(), edge.end_vertex().position());
+    let direction = (end - start).normalized();
+    let flat = |face: &Face| face.surface().map_or(true, |s| s.surface_type() == SurfaceType::Planar);
+    if !flat(face1) || !flat(face2) || direction.is_zero(0.0) {
+        return None;
+    }
+
+    let normals = [face1, face2].map(|face| face.outward_normal_at(0.0, 0.0, body));
+    // Each face's reach from the edge line and the direction it reaches in
+    let reach = |face: &Face| {
+        face.loops().iter().flat_map(|lp| lp.coedges())
+            .map(|c| (c.start_vertex().position() - start).reject_from(&direction))
+            .fold(Vec3::ZERO, |best, v| if v.length() > best.length() { v } else { best })
+    };
+    let reaches = [reach(face1), reach(face2)];
+    let widths = reaches.map(|r| r.length());
+    let wedge = std::f64::consts::PI - normals[0].angle_to(&normals[1]);
+    if widths.iter().any(|&w| w <= 0.0) || normals.iter().any(|n| n.is_zero(0.0)) || wedge >= std::f64::consts::PI - 1e-9 {
+        return None;
+    }
+    Some(FilletCorner {
+        start,
+        end,
+        normals,
+        inward: reaches.map(|r| r.normalized()),
+        widths,
+        wedge,
+        convex: reaches[1].dot(&normals[0]) < 0.0,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_geom::{Curve, Surface};
+    use nova_topo::{Coedge, EulerAdvanced, Loop, Sense, Vertex};
+    use std::sync::Arc;
+
+    /// 4 x 2 x 1 box spanning y in [-2, 0], with shared edges
+    fn slab() -> Body {
+        let v: Vec<_> = [(0.0, 0.0), (4.0, 0.0), (4.0, 1.0), (0.0, 1.0)]
+            .map(|(x, z)| Arc::new(Vertex::new(Point3::new(x, 0.0, z))))
+            .into();
+        let coedges = (0..4)
+            .map(|i| Coedge::new(Arc::new(Edge::new(v[i].clone(), v[(i + 1) % 4].clone())), Sense::Same))
+            .collect();
+        let mut profile = Face::new();
+        profile.add_loop(Loop::from_coedges(coedges));
+        EulerAdvanced::extrude_face(&profile, -Vec3::Y, 2.0).unwrap()
+    }
+
+    #[test]
+    fn test_box_edge_fillet_preview() {
+        let body = slab();
+        // Edge along X between the top face (2 wide) and front face (1 wide)
+        let edges = body.edges();
+        let edge = edges.iter()
+            .find(|e| [e.start_vertex(), e.end_vertex()].iter().all(|v| v.position().y() == 0.0 && v.position().z() == 1.0))
+            .unwrap();
+        let engine = FilletEngine::new();
+        assert!((engine.max_radius(&body, edge) - 0.5).abs() < 1e-9);
+
+        let preview = engine.preview(&body, edge, &FilletOptions { radius: 0.25 }).unwrap();
+        assert!((preview.surface.radius() - 0.25).abs() < 1e-12);
+        let axis = Line::segment(preview.surface.origin(), preview.surface.origin() + preview.surface.axis()).unwrap();
+        assert!(axis.distance_to_point(&Point3::new(0.0, -0.25, 0.75)) < 1e-9);
+        // The surface runs from one rail to the other
+        for (u, rail) in [(0.0, &preview.rails[0]), (1.0, &preview.rails[1])] {
+            for v in [0.0, 0.5, 1.0] {
+                assert!(rail.distance_to_point(&preview.surface.evaluate(u, v)) < 1e-9);
+            }
+        }
+        let on_faces = [preview.rails[0].start_point(), preview.rails[1].start_point()];
+        assert!(on_faces.iter().any(|p| (p.z() - 1.0).abs() < 1e-12 && (p.y() + 0.25).abs() < 1e-12));
+        assert!(on_faces.iter().any(|p| p.y().abs() < 1e-12 && (p.z() - 0.75).abs() < 1e-12));
+
+        let too_big = FilletOptions { radius: 0.6 };
+        assert!(matches!(engine.preview(&body, edge, &too_big), Err(OpsError::InvalidParameters(_))));
+        assert_eq!(body.faces_iter().count(), 6);
+    }
+}
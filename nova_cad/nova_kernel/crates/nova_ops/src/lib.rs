@@ -12,14 +12,20 @@ use thiserror::Error;
 pub mod boolean;
 pub mod feature;
 pub mod fillet;
+pub mod sketch;
 pub mod split;
 pub mod transform;
-
-pub use boolean::{BooleanOp, BooleanEngine};
-pub use feature::{ExtrudeOptions, RevolveOptions, SweepOptions, LoftOptions, FeatureEngine};
-pub use fillet::{FilletOptions, ChamferOptions, FilletEngine};
-pub use split::{SplitOptions, SplitEngine, FaceSplit};
+#[cfg(feature = "fonts")]
+pub mod text;
+
+pub use boolean::{BooleanOp, BooleanEngine, BooleanWorkspace, PointClassification, classify_point, sweep_is_stable};
+pub use feature::{ExtrudeOptions, ExtrudeTermination, THROUGH_ALL_DISTANCE, RevolveOptions, SweepOptions, LoftOptions, FeatureEngine};
+pub use fillet::{FilletOptions, ChamferOptions, FilletEngine, FilletPreview};
+pub use sketch::{Sketch, SketchSegment};
+pub use split::{SplitOptions, SplitEngine, FaceSplit, Side};
 pub use transform::{TransformEngine, TransformOptions};
+#[cfg(feature = "fonts")]
+pub use text::{text_outlines, FontHandle};
 
 /// Operations error types
 #[derive(Debug, Error, Clone)]
@@ -43,6 +49,14 @@ pub enum OpsError {
     /// Operation not supported
     #[error("Operation not supported: {0}")]
     NotSupported(String),
+    
+    /// Input bodies do not intersect
+    #[error("Bodies do not intersect")]
+    NoIntersection,
+    
+    /// Operation is valid but leaves nothing
+    #[error("Empty result: {0}")]
+    EmptyResult(String),
 }
 
 /// Result type for operations
@@ -0,0 +1,329 @@
+//! 2D sketches for building extrude and revolve profiles
+
+use crate::{OpsError, OpsResult};
+use nova_geom::{CircularArc, Line, PlanarSurface};
+use nova_math::{Point2, Point3, ToleranceContext, Vec3};
+use nova_topo::{Coedge, Edge, Face, Loop, Sense, Vertex};
+use std::f64::consts::TAU;
+use std::sync::Arc;
+
+/// Points per full turn when measuring the area enclosed by arcs
+const ARC_SAMPLES: usize = 64;
+
+/// A segment of a sketch contour, in the sketch plane's (u, v) coordinates
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SketchSegment {
+    /// Straight line
+    Line {
+        /// Start point
+        start: Point2,
+        /// End point
+        end: Point2,
+    },
+    /// Circular arc, counter-clockwise when `end_angle > start_angle`
+    Arc {
+        /// Centre
+        center: Point2,
+        /// Radius
+        radius: f64,
+        /// Start angle in radians from the plane's u axis
+        start_angle: f64,
+        /// End angle in radians from the plane's u axis
+        end_angle: f64,
+    },
+    /// Full circle, a closed contour on its own
+    Circle {
+        /// Centre
+        center: Point2,
+        /// Radius
+        radius: f64,
+    },
+}
+
+impl SketchSegment {
+    /// Start point
+    pub fn start(&self) -> Point2 {
+        match *self {
+            SketchSegment::Line { start, .. } => start,
+            SketchSegment::Arc { center, radius, start_angle, .. } => polar(center, radius, start_angle),
+            SketchSegment::Circle { center, radius } => polar(center, radius, 0.0),
+        }
+    }
+
+    /// End point
+    pub fn end(&self) -> Point2 {
+        match *self {
+            SketchSegment::Line { end, .. } => end,
+            SketchSegment::Arc { center, radius, end_angle, .. } => polar(center, radius, end_angle),
+            SketchSegment::Circle { center, radius } => polar(center, radius, 0.0),
+        }
+    }
+
+    /// The same segment traversed the other way
+    pub fn reversed(&self) -> Self {
+        match *self {
+            SketchSegment::Line { start, end } => SketchSegment::Line { start: end, end: start },
+            SketchSegment::Arc { center, radius, start_angle, end_angle } => {
+                SketchSegment::Arc { center, radius, start_angle: end_angle, end_angle: start_angle }
+            }
+            circle @ SketchSegment::Circle { .. } => circle,
+        }
+    }
+
+    /// Points along the segment, excluding the end
+    fn samples(&self) -> Vec<Point2> {
+        let (center, radius, from, sweep) = match *self {
+            SketchSegment::Line { start, .. } => return vec![start],
+            SketchSegment::Arc { center, radius, start_angle, end_angle } => {
+                (center, radius, start_angle, end_angle - start_angle)
+            }
+            SketchSegment::Circle { center, radius } => (center, radius, 0.0, TAU),
+        };
+        let n = ((sweep.abs() / TAU * ARC_SAMPLES as f64).ceil() as usize).max(2);
+        (0..n).map(|i| polar(center, radius, from + sweep * i as f64 / n as f64)).collect()
+    }
+}
+
+/// Point at `angle` on the circle about `center`
+fn polar(center: Point2, radius: f64, angle: f64) -> Point2 {
+    Point2::new(center.x() + radius * angle.cos(), center.y() + radius * angle.sin())
+}
+
+/// A planar profile made of closed contours
+///
+/// Segments are added to an open contour, which `close_contour` checks and
+/// stores. The largest contour becomes the outer boundary of the face and
+/// the rest become holes.
+#[derive(Debug, Clone)]
+pub struct Sketch {
+    plane: PlanarSurface,
+    contours: Vec<Vec<SketchSegment>>,
+    open: Vec<SketchSegment>,
+}
+
+impl Sketch {
+    /// Create an empty sketch on `plane`
+    pub fn new(plane: PlanarSurface) -> Self {
+        Self { plane, contours: Vec::new(), open: Vec::new() }
+    }
+
+    /// Get the sketch plane
+    pub fn plane(&self) -> &PlanarSurface {
+        &self.plane
+    }
+
+    /// Get the closed contours
+    pub fn contours(&self) -> &[Vec<SketchSegment>] {
+        &self.contours
+    }
+
+    /// Add a line to the open contour
+    pub fn add_line(&mut self, start: Point2, end: Point2) -> &mut Self {
+        self.open.push(SketchSegment::Line { start, end });
+        self
+    }
+
+    /// Add an arc to the open contour
+    pub fn add_arc(&mut self, center: Point2, radius: f64, start_angle: f64, end_angle: f64) -> &mut Self {
+        self.open.push(SketchSegment::Arc { center, radius, start_angle, end_angle });
+        self
+    }
+
+    /// Add a full circle to the open contour
+    pub fn add_circle(&mut self, center: Point2, radius: f64) -> &mut Self {
+        self.open.push(SketchSegment::Circle { center, radius });
+        self
+    }
+
+    /// Check that the open contour closes head-to-tail and store it
+    pub fn close_contour(&mut self, tolerance: &ToleranceContext) -> OpsResult<()> {
+        let segments = std::mem::take(&mut self.open);
+        let invalid = |msg: String| Err(OpsError::InvalidParameters(msg));
+        if segments.is_empty() {
+            return invalid("Contour has no segments".to_string());
+        }
+        for segment in &segments {
+            let degenerate = match *segment {
+                SketchSegment::Line { start, end } => start.distance_to(&end) <= tolerance.resabs,
+                SketchSegment::Arc { radius, start_angle, end_angle, .. } => {
+                    radius <= tolerance.resabs || (end_angle - start_angle).abs() <= tolerance.angle_tol
+                }
+                SketchSegment::Circle { radius, .. } => radius <= tolerance.resabs,
+            };
+            if degenerate {
+                return invalid(format!("Degenerate segment {:?}", segment));
+            }
+        }
+        let circles = segments.iter().filter(|s| matches!(s, SketchSegment::Circle { .. })).count();
+        if circles > 0 && segments.len() > 1 {
+            return invalid("A circle must be a contour on its own".to_string());
+        }
+        for (i, segment) in segments.iter().enumerate() {
+            let next = &segments[(i + 1) % segments.len()];
+            let gap = segment.end().distance_to(&next.start());
+            if gap > tolerance.resabs {
+                return invalid(format!("Contour is open after segment {} (gap {})", i, gap));
+            }
+        }
+        self.contours.push(segments);
+        Ok(())
+    }
+
+    /// Build a planar face from the closed contours
+    ///
+    /// The outer contour is wound counter-clockwise about the plane normal
+    /// and holes clockwise. Consecutive segments share vertices, and each
+    /// circle is a single closed edge.
+    pub fn to_face(&self, tolerance: &ToleranceContext) -> OpsResult<Face> {
+        if !self.open.is_empty() {
+            return Err(OpsError::InvalidParameters("Sketch has an unclosed contour".to_string()));
+        }
+        let areas: Vec<f64> = self.contours.iter().map(|c| signed_area(c)).collect();
+        let outer = (0..areas.len())
+            .max_by(|&a, &b| areas[a].abs().total_cmp(&areas[b].abs()))
+            .ok_or_else(|| OpsError::InvalidParameters("Sketch has no contours".to_string()))?;
+
+        let (min, max) = bounds(&self.contours[outer]);
+        let mut face = Face::new();
+        face.add_loop(self.contour_loop(&self.contours[outer], areas[outer] < 0.0)?);
+        for (i, contour) in self.contours.iter().enumerate().filter(|&(i, _)| i != outer) {
+            let (lo, hi) = bounds(contour);
+            let eps = tolerance.resabs;
+            if lo.x() < min.x() - eps || lo.y() < min.y() - eps || hi.x() > max.x() + eps || hi.y() > max.y() + eps {
+                return Err(OpsError::InvalidParameters(format!("Contour {} lies outside the outer contour", i)));
+            }
+            face.add_loop(self.contour_loop(contour, areas[i] > 0.0)?);
+        }
+        face.set_surface(Some(Arc::new(self.plane)));
+        Ok(face)
+    }
+
+    /// Loop through a contour's segments, reversed if `reverse`
+    fn contour_loop(&self, contour: &[SketchSegment], reverse: bool) -> OpsResult<Loop> {
+        let segments: Vec<SketchSegment> = if reverse {
+            contour.iter().rev().map(|s| s.reversed()).collect()
+        } else {
+            contour.to_vec()
+        };
+        let vertices: Vec<Arc<Vertex>> = segments.iter().map(|s| Arc::new(Vertex::new(self.point(s.start())))).collect();
+
+        let mut lp = Loop::new();
+        for (i, segment) in segments.iter().enumerate() {
+            let (start, end) = (vertices[i].clone(), vertices[(i + 1) % vertices.len()].clone());
+            let edge = match *segment {
+                SketchSegment::Line { .. } => {
+                    let line = Line::segment(start.position(), end.position())
+                        .map_err(|e| OpsError::Geometry(e.to_string()))?;
+                    Edge::with_curve(start, end, Arc::new(line))
+                }
+                SketchSegment::Arc { center, radius, start_angle, end_angle } => {
+                    let arc = self.arc(center, radius, start_angle, end_angle - start_angle)?;
+                    Edge::with_curve(start, end, Arc::new(arc))
+                }
+                SketchSegment::Circle { center, radius } => {
+                    let sweep = if reverse { -TAU } else { TAU };
+                    Edge::with_curve(start, end, Arc::new(self.arc(center, radius, 0.0, sweep)?))
+                }
+            };
+            lp.add_coedge(Coedge::new(Arc::new(edge), Sense::Same));
+        }
+        Ok(lp)
+    }
+
+    /// Arc in the sketch plane starting at `start_angle` from the u axis
+    fn arc(&self, center: Point2, radius: f64, start_angle: f64, sweep: f64) -> OpsResult<CircularArc> {
+        let start = self.plane.u_axis() * start_angle.cos() + self.plane.v_axis() * start_angle.sin();
+        CircularArc::new(self.point(center), radius, self.normal(), start, sweep)
+            .map_err(|e| OpsError::Geometry(e.to_string()))
+    }
+
+    /// Unit normal of the sketch plane, u cross v
+    fn normal(&self) -> Vec3 {
+        self.plane.u_axis().cross(&self.plane.v_axis()).normalized()
+    }
+
+    /// World point at sketch coordinates `p`
+    fn point(&self, p: Point2) -> Point3 {
+        self.plane.uv_to_point(p.x(), p.y())
+    }
+}
+
+/// Signed area enclosed by a contour, positive when counter-clockwise
+fn signed_area(contour: &[SketchSegment]) -> f64 {
+    let points: Vec<Point2> = contour.iter().flat_map(|s| s.samples()).collect();
+    (0..points.len())
+        .map(|i| {
+            let (p, q) = (points[i], points[(i + 1) % points.len()]);
+            p.x() * q.y() - q.x() * p.y()
+        })
+        .sum::<f64>()
+        / 2.0
+}
+
+/// Bounding rectangle of a contour's samples
+fn bounds(contour: &[SketchSegment]) -> (Point2, Point2) {
+    let points: Vec<Point2> = contour.iter().flat_map(|s| s.samples()).collect();
+    let (mut lo, mut hi) = (points[0], points[0]);
+    for p in &points {
+        lo = Point2::new(lo.x().min(p.x()), lo.y().min(p.y()));
+        hi = Point2::new(hi.x().max(p.x()), hi.y().max(p.y()));
+    }
+    (lo, hi)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_topo::{EulerAdvanced, TopologicalEntity};
+
+    #[test]
+    fn test_plate_with_hole() {
+        let tolerance = ToleranceContext::default();
+        let plane = PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap();
+        let mut sketch = Sketch::new(plane);
+        // Clockwise rectangle, to be rewound as the outer boundary
+        let corners = [(0.0, 0.0), (0.0, 2.0), (4.0, 2.0), (4.0, 0.0)].map(|(x, y)| Point2::new(x, y));
+        for i in 0..4 {
+            sketch.add_line(corners[i], corners[(i + 1) % 4]);
+        }
+        sketch.close_contour(&tolerance).unwrap();
+        sketch.add_circle(Point2::new(2.0, 1.0), 0.5);
+        sketch.close_contour(&tolerance).unwrap();
+
+        let face = sketch.to_face(&tolerance).unwrap();
+        assert_eq!(face.loops().len(), 2);
+        assert_eq!(face.loops()[0].len(), 4);
+        assert!(face.loops().iter().all(|lp| lp.is_closed()));
+
+        let body = EulerAdvanced::extrude_face(&face, Vec3::Z, 0.5).unwrap();
+        let faces = body.faces();
+        // Bottom, top, four sides and the bore
+        assert_eq!(faces.len(), 7);
+        let bores: Vec<_> = faces.iter()
+            .filter(|f| f.surface().is_some_and(|s| s.surface_type() == nova_geom::SurfaceType::Cylindrical))
+            .collect();
+        assert_eq!(bores.len(), 1);
+        assert!(body.loops().iter().all(|lp| lp.is_closed()));
+
+        // Closed and consistently oriented: each edge is used twice, once each way
+        let mut uses: std::collections::HashMap<_, Vec<bool>> = std::collections::HashMap::new();
+        for f in &faces {
+            for c in f.loops().iter().flat_map(|lp| lp.coedges()) {
+                let forward = c.sense().is_same() != f.orientation().is_reversed();
+                uses.entry(nova_topo::Entity::id(c.edge())).or_default().push(forward);
+            }
+        }
+        assert!(uses.values().all(|u| u.len() == 2 && u[0] != u[1]));
+
+    }
+
+    #[test]
+    fn test_open_contour_rejected() {
+        let tolerance = ToleranceContext::default();
+        let mut sketch = Sketch::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap());
+        sketch.add_line(Point2::new(0.0, 0.0), Point2::new(1.0, 0.0))
+            .add_arc(Point2::new(1.0, 0.5), 0.5, -std::f64::consts::FRAC_PI_2, std::f64::consts::FRAC_PI_2);
+        assert!(sketch.close_contour(&tolerance).is_err());
+        assert!(sketch.to_face(&tolerance).is_err());
+    }
+}
@@ -1,8 +1,12 @@
 //! Split Operations - Divide bodies with surfaces or curves
 
 use crate::{OpsError, OpsResult};
-use nova_math::ToleranceContext;
-use nova_topo::{Body, Face};
+use nova_math::{Plane, Point3, ToleranceContext, Vec3};
+use nova_geom::intersection::{curve_curve_intersection, curve_to_uv, polyline_intersections_2d, surface_surface_intersection};
+use nova_geom::{CircularArc, CurveType, Line, ParamRange, PlanarSurface, PolylineCurve, Surface, SurfaceType};
+use nova_topo::{Body, Coedge, Edge, Entity, EntityId, EulerAdvanced, Face, Loop, Orientation, Sense, Shell, TopologicalEntity, Vertex};
+use std::collections::HashMap;
+use std::sync::Arc;
 
 /// Split operation options
 #[derive(Debug, Clone)]
@@ -17,6 +21,15 @@ impl Default for SplitOptions {
     }
 }
 
+/// Side of a plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Side {
+    /// The side the plane's normal points to
+    Front,
+    /// The side behind the plane
+    Back,
+}
+
 /// Result of splitting a face
 #[derive(Debug, Clone)]
 pub struct FaceSplit {
@@ -45,14 +58,657 @@ impl SplitEngine {
         Err(OpsError::NotSupported("Surface split not yet implemented".to_string()))
     }
     
-    /// Split face at curves (stub)
+    /// Split a face along curves lying on its surface
+    ///
+    /// The outer boundary and each cutting curve are mapped into the face's
+    /// UV space, and pieces are cut wherever a curve crosses the boundary
+    /// twice. New boundary edges are line segments between UV samples.
     pub fn split_face_at_curves(
         &self,
-        _face: &Face,
-        _curves: &[Box<dyn nova_geom::Curve>],
+        face: &Face,
+        curves: &[Box<dyn nova_geom::Curve>],
         _tolerance: &ToleranceContext,
     ) -> OpsResult<Vec<Face>> {
-        Err(OpsError::NotSupported("Face split not yet implemented".to_string()))
+        let surface = face.surface()
+            .ok_or_else(|| OpsError::InvalidParameters("Face has no surface".to_string()))?;
+        let outer = face.outer_loop()
+            .ok_or_else(|| OpsError::InvalidParameters("Face has no outer loop".to_string()))?;
+        
+        let boundary = loop_to_uv(outer, surface.as_ref())?;
+        let mut pieces = vec![boundary];
+        let mut split = false;
+        
+        for curve in curves {
+            let samples = if curve.curve_type() == CurveType::Line { 1 } else { CURVE_UV_SAMPLES };
+            let cutter = curve_to_uv(curve.as_ref(), surface.as_ref(), samples)
+                .map_err(|e| OpsError::Geometry(e.to_string()))?;
+            
+            let mut work = std::mem::take(&mut pieces);
+            while let Some(polygon) = work.pop() {
+                match split_polygon(&polygon, &cutter) {
+                    Some((a, b)) => {
+                        split = true;
+                        work.push(a);
+                        work.push(b);
+                    }
+                    None => pieces.push(polygon),
+                }
+            }
+        }
+        
+        if !split {
+            let mut unchanged = face.clone();
+            unchanged.set_surface(Some(surface.clone()));
+            return Ok(vec![unchanged]);
+        }
+        if !face.inner_loops().is_empty() {
+            return Err(OpsError::NotSupported(
+                "Splitting faces with inner loops".to_string()
+            ));
+        }
+        
+        pieces.iter()
+            .map(|polygon| face_from_uv_polygon(polygon, surface, face.orientation()))
+            .collect()
+    }
+
+    /// Cut `body` with the infinite plane of `plane`, keeping `keep_side`
+    ///
+    /// Faces wholly on the kept side stay and faces on the other side go.
+    /// Faces crossing the plane are split along their section with it by
+    /// `split_face_at_curves`, which needs them to have no inner loops, and
+    /// the pieces sorted by side. Faces lying in the plane stay when they
+    /// face away from the kept side. The open boundary left on the plane is
+    /// closed with planar cap faces, and each connected piece left of a cut
+    /// shell is stitched into a shell of its own. Shells clear of the plane
+    /// are kept or dropped whole.
+    pub fn trim_by_plane(
+        &self,
+        body: &Body,
+        plane: &PlanarSurface,
+        keep_side: Side,
+        tolerance: &ToleranceContext,
+    ) -> OpsResult<Body> {
+        let eps = tolerance.resabs;
+        let normal = match keep_side {
+            Side::Front => plane.normal(),
+            Side::Back => -plane.normal(),
+        };
+        let cut = Cut { origin: plane.origin(), normal, reach: body.bounding_box().diagonal().max(eps) };
+
+        let mut result = Body::new();
+        for shell in body.shells() {
+            let mut kept = Shell::new();
+            let mut whole = true;
+            for face in shell.faces() {
+                match cut.classify(face, eps)? {
+                    FaceSide::Kept => kept.add_face(face.clone()),
+                    FaceSide::Discarded => whole = false,
+                    FaceSide::Crossing => {
+                        whole = false;
+                        let curves = cut.section(face, eps)?;
+                        for piece in self.split_face_at_curves(face, &curves, tolerance)? {
+                            match cut.classify(&piece, eps)? {
+                                FaceSide::Kept => kept.add_face(piece),
+                                FaceSide::Discarded => {}
+                                FaceSide::Crossing => return Err(OpsError::NotSupported(
+                                    "Trimming a face the plane crosses more than once".to_string()
+                                )),
+                            }
+                        }
+                    }
+                }
+            }
+            if whole {
+                result.add_shell(shell.clone());
+                continue;
+            }
+
+            // Each connected piece of what is kept closes into its own shell
+            EulerAdvanced::stitch_shell(&mut kept, eps);
+            let mut pieces = Body::new();
+            pieces.add_shell(kept);
+            for faces in pieces.face_components() {
+                let mut piece = Shell::new();
+                piece.set_outer(shell.is_outer());
+                for face in faces {
+                    piece.add_face(face.clone());
+                }
+                for cap in cut.caps(&piece, eps)? {
+                    piece.add_face(cap);
+                }
+                EulerAdvanced::stitch_shell(&mut piece, eps);
+                result.add_shell(piece);
+            }
+        }
+        if result.shells().is_empty() {
+            return Err(OpsError::EmptyResult("the body lies entirely on the trimmed side".to_string()));
+        }
+        Ok(result)
+    }
+
+    /// Split curves at their mutual intersections
+    ///
+    /// Returns the arrangement: the pieces of each curve between the points
+    /// where it meets the others, in curve order. Lines and circular arcs
+    /// are trimmed exactly; other curves are split into polylines. Pass the
+    /// pieces to `Body::wire` for a wire body with shared vertices.
+    pub fn split_wires(
+        &self,
+        curves: &[Box<dyn nova_geom::Curve>],
+        tolerance: &ToleranceContext,
+    ) -> OpsResult<Vec<Box<dyn nova_geom::Curve>>> {
+        let eps = tolerance.resabs;
+        if curves.iter().any(|c| !c.param_range().length().is_finite()) {
+            return Err(OpsError::InvalidParameters("Cannot split an unbounded curve".to_string()));
+        }
+
+        let mut cuts: Vec<Vec<f64>> = curves.iter().map(|c| vec![c.param_range().start, c.param_range().end]).collect();
+        for i in 0..curves.len() {
+            for j in i + 1..curves.len() {
+                let points = curve_curve_intersection(curves[i].as_ref(), curves[j].as_ref(), eps)
+                    .map_err(|e| OpsError::Geometry(e.to_string()))?;
+                for p in points {
+                    for k in [i, j] {
+                        let (t, _, _) = curves[k].closest_point(&p).map_err(|e| OpsError::Geometry(e.to_string()))?;
+                        cuts[k].push(t);
+                    }
+                }
+            }
+        }
+
+        let mut pieces = Vec::new();
+        for (curve, mut params) in curves.iter().zip(cuts) {
+            params.sort_by(f64::total_cmp);
+            // Drop cuts landing on the previous one, keeping the curve's end
+            let mut kept = vec![params[0]];
+            for &t in &params[1..] {
+                if curve.evaluate(t).distance_to(&curve.evaluate(*kept.last().unwrap())) > eps {
+                    kept.push(t);
+                } else if t == curve.param_range().end {
+                    *kept.last_mut().unwrap() = t;
+                }
+            }
+            if kept.len() == 1 {
+                pieces.push(curve.clone_box());
+                continue;
+            }
+            for w in kept.windows(2) {
+                pieces.push(sub_curve(curve.as_ref(), w[0], w[1])?);
+            }
+        }
+        Ok(pieces)
+    }
+}
+
+/// Chords used for a piece of a curve that is neither a line nor an arc
+const CURVE_PIECE_SEGMENTS: usize = 32;
+
+/// The part of `curve` between parameters `t0` and `t1`
+fn sub_curve(curve: &dyn nova_geom::Curve, t0: f64, t1: f64) -> OpsResult<Box<dyn nova_geom::Curve>> {
+    let geometry = |e: nova_geom::GeometryError| OpsError::Geometry(e.to_string());
+    if let Some(line) = curve.as_any().downcast_ref::<Line>() {
+        let range = ParamRange::new(t0, t1);
+        return Ok(Box::new(Line::with_range(line.origin(), line.direction(), range).map_err(geometry)?));
+    }
+    if let Some(arc) = curve.as_any().downcast_ref::<CircularArc>() {
+        let start = curve.evaluate(t0) - arc.center();
+        let piece = CircularArc::new(arc.center(), arc.radius(), arc.normal(), start, (t1 - t0) * arc.sweep_angle());
+        return Ok(Box::new(piece.map_err(geometry)?));
+    }
+    let points = (0..=CURVE_PIECE_SEGMENTS)
+        .map(|i| curve.evaluate(t0 + (t1 - t0) * i as f64 / CURVE_PIECE_SEGMENTS as f64))
+        .collect();
+    Ok(Box::new(PolylineCurve::new(points).map_err(geometry)?))
+}
+
+/// Closed polygon in surface parameter space
+type UvPolygon = Vec<(f64, f64)>;
+
+/// Minimum UV distance and area for a cut to count as a split
+const UV_SPLIT_EPS: f64 = 1e-9;
+
+/// Samples used to map a cutting curve into UV space
+const CURVE_UV_SAMPLES: usize = 64;
+
+/// Samples per curved boundary edge when mapping a loop into UV space
+const EDGE_UV_SAMPLES: usize = 16;
+
+/// Grid nodes along each parameter when sampling inside a face
+const INTERIOR_GRID: usize = 8;
+
+/// Map a loop into a closed UV polygon (first point not repeated)
+fn loop_to_uv(lp: &Loop, surface: &dyn Surface) -> OpsResult<Vec<(f64, f64)>> {
+    let mut points = Vec::new();
+    
+    for coedge in lp.coedges() {
+        let edge = coedge.edge();
+        let samples = match edge.curve() {
+            Some(curve) if curve.curve_type() != CurveType::Line => {
+                let range = curve.param_range();
+                (0..EDGE_UV_SAMPLES)
+                    .map(|i| {
+                        let f = i as f64 / EDGE_UV_SAMPLES as f64;
+                        let f = if coedge.sense().is_same() { f } else { 1.0 - f };
+                        curve.evaluate(range.denormalize(f))
+                    })
+                    .collect()
+            }
+            _ => vec![coedge.start_vertex().position()],
+        };
+        
+        for p in samples {
+            let (u, v, _, _) = surface.closest_point(&p)
+                .map_err(|e| OpsError::Geometry(e.to_string()))?;
+            points.push((u, v));
+        }
+    }
+    
+    if points.len() < 3 {
+        return Err(OpsError::InvalidParameters("Face boundary is degenerate".to_string()));
+    }
+    Ok(points)
+}
+
+/// Split a closed UV polygon along a cutter polyline
+///
+/// Uses the first pair of consecutive crossings along the cutter whose
+/// connecting portion runs inside the polygon.
+fn split_polygon(
+    polygon: &[(f64, f64)],
+    cutter: &[(f64, f64)],
+) -> Option<(UvPolygon, UvPolygon)> {
+    let n = polygon.len();
+    let mut closed = polygon.to_vec();
+    closed.push(polygon[0]);
+    
+    let mut hits = polyline_intersections_2d(&closed, cutter);
+    hits.sort_by(|a, b| a.param_b.total_cmp(&b.param_b));
+    
+    for pair in hits.windows(2) {
+        let (h0, h1) = (pair[0], pair[1]);
+        let inner: Vec<(f64, f64)> = cutter.iter()
+            .enumerate()
+            .filter(|(k, _)| (*k as f64) > h0.param_b && (*k as f64) < h1.param_b)
+            .map(|(_, p)| *p)
+            .collect();
+        
+        // The cut must run through the inside of the polygon
+        let mut path = vec![h0.point];
+        path.extend(&inner);
+        path.push(h1.point);
+        let mid = polyline_midpoint(&path);
+        if !point_in_polygon(mid, polygon) || distance_to_boundary(mid, polygon) < UV_SPLIT_EPS {
+            continue;
+        }
+        
+        let forward = |from: f64, to: f64| -> Vec<(f64, f64)> {
+            let span = (to - from).rem_euclid(n as f64);
+            (1..=n)
+                .map(|k| from.floor() as usize + k)
+                .filter(|&k| {
+                    let d = (k as f64 - from).rem_euclid(n as f64);
+                    d > 1e-9 && d < span - 1e-9
+                })
+                .map(|k| polygon[k % n])
+                .collect()
+        };
+        
+        let mut a = vec![h0.point];
+        a.extend(forward(h0.param_a, h1.param_a));
+        a.push(h1.point);
+        a.extend(inner.iter().rev());
+        
+        let mut b = vec![h1.point];
+        b.extend(forward(h1.param_a, h0.param_a));
+        b.push(h0.point);
+        b.extend(&inner);
+        
+        if polygon_area(&a).abs() < UV_SPLIT_EPS || polygon_area(&b).abs() < UV_SPLIT_EPS {
+            continue;
+        }
+        return Some((a, b));
+    }
+    
+    None
+}
+
+/// Point halfway along a polyline by length
+fn polyline_midpoint(points: &[(f64, f64)]) -> (f64, f64) {
+    let seg_len = |a: (f64, f64), b: (f64, f64)| (b.0 - a.0).hypot(b.1 - a.1);
+    let total: f64 = points.windows(2).map(|w| seg_len(w[0], w[1])).sum();
+    let mut remaining = total * 0.5;
+    for w in points.windows(2) {
+        let len = seg_len(w[0], w[1]);
+        if len > 0.0 && remaining <= len {
+            let f = remaining / len;
+            return (w[0].0 + (w[1].0 - w[0].0) * f, w[0].1 + (w[1].1 - w[0].1) * f);
+        }
+        remaining -= len;
+    }
+    points[0]
+}
+
+/// Signed area of a UV polygon
+fn polygon_area(polygon: &[(f64, f64)]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let a = polygon[i];
+            let b = polygon[(i + 1) % n];
+            a.0 * b.1 - b.0 * a.1
+        })
+        .sum::<f64>()
+        * 0.5
+}
+
+/// Distance from a UV point to the nearest polygon edge
+fn distance_to_boundary(p: (f64, f64), polygon: &[(f64, f64)]) -> f64 {
+    let n = polygon.len();
+    (0..n)
+        .map(|i| {
+            let a = polygon[i];
+            let b = polygon[(i + 1) % n];
+            let d = (b.0 - a.0, b.1 - a.1);
+            let len_sq = d.0 * d.0 + d.1 * d.1;
+            let t = if len_sq > 0.0 {
+                (((p.0 - a.0) * d.0 + (p.1 - a.1) * d.1) / len_sq).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            (p.0 - a.0 - d.0 * t).hypot(p.1 - a.1 - d.1 * t)
+        })
+        .fold(f64::INFINITY, f64::min)
+}
+
+/// Even-odd point in polygon test in UV space
+fn point_in_polygon(p: (f64, f64), polygon: &[(f64, f64)]) -> bool {
+    let mut inside = false;
+    let n = polygon.len();
+    for i in 0..n {
+        let a = polygon[i];
+        let b = polygon[(i + 1) % n];
+        if (a.1 > p.1) != (b.1 > p.1) {
+            let x = a.0 + (p.1 - a.1) * (b.0 - a.0) / (b.1 - a.1);
+            if p.0 < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Build a face on a surface from a UV polygon with straight edges
+fn face_from_uv_polygon(
+    polygon: &[(f64, f64)],
+    surface: &Arc<dyn Surface>,
+    orientation: Orientation,
+) -> OpsResult<Face> {
+    let vertices: Vec<Arc<Vertex>> = polygon.iter()
+        .map(|&(u, v)| Arc::new(Vertex::new(surface.evaluate(u, v))))
+        .collect();
+    
+    let mut lp = Loop::new();
+    for i in 0..vertices.len() {
+        let start = vertices[i].clone();
+        let end = vertices[(i + 1) % vertices.len()].clone();
+        let line = Line::segment(start.position(), end.position())
+            .map_err(|e| OpsError::Geometry(e.to_string()))?;
+        let edge = Edge::with_curve(start, end, Arc::new(line));
+        lp.add_coedge(Coedge::new(Arc::new(edge), Sense::Same));
+    }
+    
+    let mut face = Face::with_surface(surface.clone());
+    face.set_orientation(orientation);
+    face.add_loop(lp);
+    Ok(face)
+}
+
+/// Where a face lies relative to a trimming plane
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FaceSide {
+    /// On the kept side, or in the plane facing away from it
+    Kept,
+    /// On the other side, or in the plane facing the kept side
+    Discarded,
+    /// On both sides
+    Crossing,
+}
+
+/// Trimming plane with its normal toward the kept side
+struct Cut {
+    origin: Point3,
+    normal: Vec3,
+    /// Length that covers the whole body, for bounding section curves
+    reach: f64,
+}
+
+impl Cut {
+    /// Signed distance of `p` toward the kept side
+    fn height(&self, p: Point3) -> f64 {
+        (p - self.origin).dot(&self.normal)
+    }
+
+    /// Which side of the plane `face` lies on, judged by its boundary and a
+    /// grid of points inside it
+    ///
+    /// The interior counts because a face can bulge across the plane while
+    /// its boundary stays on one side, as a dome bounded by its equator does
+    /// when cut above the equator.
+    fn classify(&self, face: &Face, eps: f64) -> OpsResult<FaceSide> {
+        let mut points = boundary_points(face);
+        points.extend(interior_points(face)?);
+        let low = points.iter().map(|&p| self.height(p)).fold(f64::INFINITY, f64::min);
+        let high = points.iter().map(|&p| self.height(p)).fold(f64::NEG_INFINITY, f64::max);
+        if low < -eps && high > eps {
+            return Ok(FaceSide::Crossing);
+        }
+        if low >= -eps && high > eps {
+            return Ok(FaceSide::Kept);
+        }
+        if high <= eps && low < -eps {
+            return Ok(FaceSide::Discarded);
+        }
+
+        // Every sample lies in the plane, so the face's normal decides
+        let surface = face.surface()
+            .ok_or_else(|| OpsError::InvalidParameters("Face has no surface".to_string()))?;
+        let (u, v, _, _) = surface.closest_point(&points[0]).map_err(|e| OpsError::Geometry(e.to_string()))?;
+        Ok(if face.normal_at(u, v).dot(&self.normal) < 0.0 { FaceSide::Kept } else { FaceSide::Discarded })
+    }
+
+    /// Curves where `face`'s surface meets the plane
+    fn section(&self, face: &Face, eps: f64) -> OpsResult<Vec<Box<dyn nova_geom::Curve>>> {
+        let geometry = |e: nova_geom::GeometryError| OpsError::Geometry(e.to_string());
+        let surface = face.surface()
+            .ok_or_else(|| OpsError::InvalidParameters("Face has no surface".to_string()))?;
+
+        if let Some(planar) = surface.as_any().downcast_ref::<PlanarSurface>() {
+            let n = planar.normal();
+            let direction = n.cross(&self.normal);
+            if direction.length() <= eps {
+                return Ok(Vec::new());
+            }
+            // Point on both planes, then the span of the line covering the body
+            let (d1, d2) = (n.dot(&planar.origin().to_vector()), self.normal.dot(&self.origin.to_vector()));
+            let on_both = Point3::ORIGIN + (self.normal.cross(&direction) * d1 - n.cross(&direction) * d2) / direction.length_squared();
+            let direction = direction.normalized();
+            let center = centroid(&boundary_points(face));
+            let middle = (center - on_both).dot(&direction);
+            let range = ParamRange::new(middle - self.reach, middle + self.reach);
+            return Ok(vec![Box::new(Line::with_range(on_both, direction, range).map_err(geometry)?)]);
+        }
+
+        let center = self.origin + (centroid(&boundary_points(face)) - self.origin).reject_from(&self.normal);
+        let (u_axis, v_axis) = Plane::new(self.origin, self.normal).basis_vectors();
+        let patch = PlanarSurface::bounded(center, u_axis, v_axis, -self.reach, self.reach, -self.reach, self.reach)
+            .map_err(geometry)?;
+        surface_surface_intersection(surface.as_ref(), &patch, eps).map_err(geometry)
+    }
+
+    /// Planar faces closing the open boundary of stitched `shell` on the plane
+    ///
+    /// Loops winding counterclockwise seen from the discarded side bound
+    /// caps; the others are holes in the cap around them.
+    fn caps(&self, shell: &Shell, eps: f64) -> OpsResult<Vec<Face>> {
+        let mut uses: HashMap<EntityId, usize> = HashMap::new();
+        for coedge in shell.faces().iter().flat_map(|f| f.loops()).flat_map(|lp| lp.coedges()) {
+            *uses.entry(coedge.edge().id()).or_default() += 1;
+        }
+        // Open coedges on the plane, run backwards for the cap; a reversed
+        // face's loops already run backwards
+        let open: Vec<Coedge> = shell.faces().iter()
+            .flat_map(|f| f.loops().iter().flat_map(|lp| lp.coedges()).map(move |c| (f.orientation().is_reversed(), c)))
+            .filter(|(_, c)| uses[&c.edge().id()] == 1 && coedge_points(c).iter().all(|&p| self.height(p).abs() <= eps))
+            .map(|(reversed, c)| {
+                let edge = c.edge();
+                let mut copy = Edge::new(Arc::new(edge.start_vertex().clone()), Arc::new(edge.end_vertex().clone()));
+                copy.set_curve(edge.curve().cloned());
+                Coedge::new(Arc::new(copy), if reversed { c.sense() } else { c.sense().reverse() })
+            })
+            .collect();
+        if open.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut from: HashMap<EntityId, Vec<usize>> = HashMap::new();
+        for (i, coedge) in open.iter().enumerate() {
+            from.entry(coedge.start_vertex().id()).or_default().push(i);
+        }
+        let mut used = vec![false; open.len()];
+        let mut loops = Vec::new();
+        for first in 0..open.len() {
+            if used[first] {
+                continue;
+            }
+            let mut coedges = Vec::new();
+            let mut at = first;
+            loop {
+                used[at] = true;
+                coedges.push(open[at].clone());
+                let end = open[at].end_vertex().id();
+                if end == open[first].start_vertex().id() {
+                    break;
+                }
+                match from.get(&end).and_then(|next| next.iter().find(|&&i| !used[i])) {
+                    Some(&next) => at = next,
+                    None => return Err(OpsError::Topology("Section of the body is not closed".to_string())),
+                }
+            }
+            loops.push(Loop::from_coedges(coedges));
+        }
+
+        // Seen from the discarded side, so outer loops have positive area
+        let (u_axis, v_axis) = Plane::new(self.origin, -self.normal).basis_vectors();
+        let surface: Arc<dyn Surface> = Arc::new(PlanarSurface::new(self.origin, u_axis, v_axis).map_err(|e| OpsError::Geometry(e.to_string()))?);
+        let uv = |lp: &Loop| -> UvPolygon {
+            lp.coedges().iter()
+                .flat_map(coedge_points)
+                .map(|p| ((p - self.origin).dot(&u_axis), (p - self.origin).dot(&v_axis)))
+                .collect()
+        };
+        let (outer, holes): (Vec<Loop>, Vec<Loop>) = loops.into_iter().partition(|lp| polygon_area(&uv(lp)) > 0.0);
+
+        let mut caps: Vec<(UvPolygon, Face)> = outer.into_iter()
+            .map(|lp| {
+                let mut face = Face::with_surface(surface.clone());
+                let polygon = uv(&lp);
+                face.add_loop(lp);
+                (polygon, face)
+            })
+            .collect();
+        for hole in holes {
+            let point = uv(&hole)[0];
+            let around = caps.iter_mut()
+                .filter(|(polygon, _)| point_in_polygon(point, polygon))
+                .min_by(|a, b| polygon_area(&a.0).total_cmp(&polygon_area(&b.0)));
+            match around {
+                Some((_, face)) => face.add_loop(hole),
+                None => return Err(OpsError::Topology("Hole in the section lies outside every cap".to_string())),
+            }
+        }
+        Ok(caps.into_iter().map(|(_, face)| face).collect())
+    }
+}
+
+/// Points of `face`'s surface at the nodes of a UV grid within its loops
+///
+/// Planar faces reach their extremes on their boundary and give none. An
+/// outer loop running once around the closed first parameter, like a dome's
+/// rim, bounds nothing in UV by itself; it is closed along the end of the
+/// second parameter on the face's side of the loop.
+fn interior_points(face: &Face) -> OpsResult<Vec<Point3>> {
+    let Some(surface) = face.surface().filter(|s| s.surface_type() != SurfaceType::Planar) else {
+        return Ok(Vec::new());
+    };
+    let range = surface.uv_range();
+    let period = range.u.length();
+    let middle = 0.5 * (range.v.start + range.v.end);
+    let closed = period.is_finite() && middle.is_finite()
+        && surface.evaluate(range.u.start, middle).distance_to(&surface.evaluate(range.u.end, middle)) <= UV_SPLIT_EPS;
+
+    let mut polygons: Vec<UvPolygon> = Vec::new();
+    for lp in face.loops() {
+        let mut polygon = loop_to_uv(lp, surface.as_ref())?;
+        if closed {
+            for i in 1..polygon.len() {
+                polygon[i].0 -= period * ((polygon[i].0 - polygon[i - 1].0) / period).round();
+            }
+            // Whole periods the loop advances in u on its way round
+            let (u, v) = polygon[0];
+            let turn = -period * ((u - polygon[polygon.len() - 1].0) / period).round();
+            if polygons.is_empty() && turn != 0.0 {
+                // The face lies to the left of its outer loop
+                let along = surface.evaluate(polygon[1].0, polygon[1].1) - surface.evaluate(u, v);
+                let left = face.normal_at(u, v).cross(&along);
+                let h = 1e-4 * range.v.length();
+                let toward_end = surface.evaluate(u, (v + h).min(range.v.end)) - surface.evaluate(u, (v - h).max(range.v.start));
+                let end = if left.dot(&toward_end) > 0.0 { range.v.end } else { range.v.start };
+                polygon.extend([(u + turn, v), (u + turn, end), (u, end)]);
+            }
+        }
+        polygons.push(polygon);
+    }
+    let Some((outer, holes)) = polygons.split_first() else {
+        return Ok(Vec::new());
+    };
+
+    let (u0, u1, v0, v1) = outer.iter().fold(
+        (f64::INFINITY, f64::NEG_INFINITY, f64::INFINITY, f64::NEG_INFINITY),
+        |(u0, u1, v0, v1), &(u, v)| (u0.min(u), u1.max(u), v0.min(v), v1.max(v)),
+    );
+    let node = |i: usize, start: f64, end: f64| start + (end - start) * (i as f64 + 0.5) / INTERIOR_GRID as f64;
+    Ok((0..INTERIOR_GRID)
+        .flat_map(|i| (0..INTERIOR_GRID).map(move |j| (node(i, u0, u1), node(j, v0, v1))))
+        .filter(|&uv| point_in_polygon(uv, outer) && !holes.iter().any(|hole| point_in_polygon(uv, hole)))
+        .map(|(u, v)| surface.evaluate(u, v))
+        .collect())
+}
+
+/// Points around every loop of `face`
+fn boundary_points(face: &Face) -> Vec<Point3> {
+    face.loops().iter().flat_map(|lp| lp.coedges()).flat_map(coedge_points).collect()
+}
+
+/// Mean of `points`
+fn centroid(points: &[Point3]) -> Point3 {
+    let sum = points.iter().fold(Vec3::ZERO, |sum, p| sum + p.to_vector());
+    Point3::ORIGIN + sum / points.len().max(1) as f64
+}
+
+/// Points along a coedge in its direction, without its end vertex
+fn coedge_points(coedge: &Coedge) -> Vec<Point3> {
+    match coedge.edge().curve() {
+        Some(curve) if curve.curve_type() != CurveType::Line && curve.param_range().length().is_finite() => {
+            let range = curve.param_range();
+            (0..EDGE_UV_SAMPLES)
+                .map(|i| {
+                    let f = i as f64 / EDGE_UV_SAMPLES as f64;
+                    curve.evaluate(range.denormalize(if coedge.sense().is_same() { f } else { 1.0 - f }))
+                })
+                .collect()
+        }
+        _ => vec![coedge.start_vertex().position()],
     }
 }
 
@@ -70,3 +726,194 @@ pub fn split_body_with_surface(
 ) -> OpsResult<Vec<Body>> {
     Err(OpsError::NotSupported("Body splitting not yet implemented".to_string()))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_topo::BodyType;
+
+    #[test]
+    fn test_split_crossing_segments() {
+        let p = |x, y| Point3::new(x, y, 0.0);
+        let curves: Vec<Box<dyn nova_geom::Curve>> = vec![
+            Box::new(Line::segment(p(0.0, 0.0), p(2.0, 2.0)).unwrap()),
+            Box::new(Line::segment(p(0.0, 2.0), p(2.0, 0.0)).unwrap()),
+        ];
+        let tolerance = ToleranceContext::default();
+        let pieces = SplitEngine::new().split_wires(&curves, &tolerance).unwrap();
+
+        assert_eq!(pieces.len(), 4);
+        let ends = [(p(0.0, 0.0), p(1.0, 1.0)), (p(1.0, 1.0), p(2.0, 2.0)), (p(0.0, 2.0), p(1.0, 1.0)), (p(1.0, 1.0), p(2.0, 0.0))];
+        for (piece, (start, end)) in pieces.iter().zip(ends) {
+            assert!(piece.start_point().distance_to(&start) < 1e-9);
+            assert!(piece.end_point().distance_to(&end) < 1e-9);
+        }
+
+        let wire = Body::wire(&pieces, tolerance.resabs);
+        assert_eq!(wire.body_type(), BodyType::Wire);
+        assert_eq!(wire.edges().len(), 4);
+        assert_eq!(wire.vertices().len(), 5);
+        let crossing = wire.vertices().into_iter().find(|v| v.position().distance_to(&p(1.0, 1.0)) < 1e-9).unwrap();
+        assert_eq!(crossing.edges().len(), 4);
+    }
+
+    /// Unit sphere made of eight octant faces bounded by quarter circles
+    fn octant_sphere() -> Body {
+        use nova_geom::SphericalSurface;
+
+        let mut shell = Shell::new();
+        for octant in 0..8 {
+            let sign = |bit: usize| if octant & bit == 0 { 1.0 } else { -1.0 };
+            let mut corners = [Vec3::X * sign(1), Vec3::Y * sign(2), Vec3::Z * sign(4)];
+            // Mirrored octants list their corners the other way round, so
+            // every loop runs counterclockwise seen from outside
+            if (octant as u32).count_ones() % 2 == 1 {
+                corners.swap(0, 1);
+            }
+            let vertices = corners.map(|c| Arc::new(Vertex::new(Point3::ORIGIN + c)));
+            let coedges = (0..3)
+                .map(|i| {
+                    let (a, b) = (corners[i], corners[(i + 1) % 3]);
+                    let arc = CircularArc::new(Point3::ORIGIN, 1.0, a.cross(&b), a, std::f64::consts::FRAC_PI_2).unwrap();
+                    let edge = Edge::with_curve(vertices[i].clone(), vertices[(i + 1) % 3].clone(), Arc::new(arc));
+                    Coedge::new(Arc::new(edge), Sense::Same)
+                })
+                .collect();
+            let center = (corners[0] + corners[1] + corners[2]).normalized();
+            let surface = SphericalSurface::new(Point3::ORIGIN, 1.0, center.cross(&Vec3::X), center).unwrap();
+            let mut face = Face::with_surface(Arc::new(surface));
+            face.add_loop(Loop::from_coedges(coedges));
+            shell.add_face(face);
+        }
+        EulerAdvanced::stitch_shell(&mut shell, 1e-9);
+        let mut body = Body::new();
+        body.add_shell(shell);
+        body
+    }
+
+    #[test]
+    fn test_trim_sphere_to_hemisphere() {
+        let sphere = octant_sphere();
+        let tolerance = ToleranceContext::default();
+        let equator = PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap();
+        let hemisphere = SplitEngine::new().trim_by_plane(&sphere, &equator, Side::Front, &tolerance).unwrap();
+
+        assert_eq!(hemisphere.body_type(), BodyType::Solid);
+        assert!(nova_topo::validate_body(&hemisphere, &tolerance).unwrap().is_empty(), "{:?}", nova_topo::validate_body(&hemisphere, &tolerance));
+        let faces = hemisphere.faces();
+        assert_eq!(faces.len(), 5);
+        assert!(hemisphere.vertices().iter().all(|v| v.position().z() >= -1e-12));
+
+        let caps: Vec<_> = faces.iter().filter(|f| f.surface().unwrap().surface_type() == nova_geom::SurfaceType::Planar).collect();
+        assert_eq!(caps.len(), 1);
+        let rim = caps[0].outer_loop().unwrap().coedges();
+        assert_eq!(rim.len(), 4);
+        for coedge in rim {
+            for p in coedge_points(coedge) {
+                assert!(p.z().abs() < 1e-12 && (p.to_vector().length() - 1.0).abs() < 1e-12);
+            }
+        }
+        assert!(caps[0].normal_at(0.0, 0.0).dot(&Vec3::Z) < -0.999);
+
+        let lower = SplitEngine::new().trim_by_plane(&sphere, &equator, Side::Back, &tolerance).unwrap();
+        assert!(lower.vertices().iter().all(|v| v.position().z() <= 1e-12));
+        let away = PlanarSurface::new(Point3::new(0.0, 0.0, 2.0), Vec3::X, Vec3::Y).unwrap();
+        let result = SplitEngine::new().trim_by_plane(&sphere, &away, Side::Front, &tolerance);
+        assert!(matches!(result, Err(OpsError::EmptyResult(_))));
+    }
+
+    #[test]
+    fn test_classify_dome_across_plane() {
+        use nova_geom::Curve;
+
+        // A dome bounded by its equator, whose boundary stays below a cut
+        // its top passes through
+        let rim = CircularArc::circle(Point3::ORIGIN, 1.0, Vec3::Z).unwrap();
+        let start = Arc::new(Vertex::new(rim.start_point()));
+        let edge = Edge::with_curve(start.clone(), start, Arc::new(rim));
+        let sphere = nova_geom::SphericalSurface::new(Point3::ORIGIN, 1.0, Vec3::Z, Vec3::X).unwrap();
+        let mut dome = Face::with_surface(Arc::new(sphere));
+        // The sphere's parameters face inward
+        dome.reverse_orientation();
+        dome.add_loop(Loop::from_coedges(vec![Coedge::new(Arc::new(edge), Sense::Same)]));
+
+        let at = |z: f64| Cut { origin: Point3::new(0.0, 0.0, z), normal: Vec3::Z, reach: 4.0 };
+        assert_eq!(at(0.5).classify(&dome, 1e-9).unwrap(), FaceSide::Crossing);
+        assert_eq!(at(-0.5).classify(&dome, 1e-9).unwrap(), FaceSide::Kept);
+        assert_eq!(at(1.5).classify(&dome, 1e-9).unwrap(), FaceSide::Discarded);
+    }
+
+    #[test]
+    fn test_trim_box_with_slanted_plane() {
+        let corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)].map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let coedges = (0..4)
+            .map(|i| {
+                let (a, b) = (corners[i].clone(), corners[(i + 1) % 4].clone());
+                let line = Line::segment(a.position(), b.position()).unwrap();
+                Coedge::new(Arc::new(Edge::with_curve(a, b, Arc::new(line))), Sense::Same)
+            })
+            .collect();
+        let mut face = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        face.add_loop(Loop::from_coedges(coedges));
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut square = Body::new();
+        square.add_shell(shell);
+        let tolerance = ToleranceContext::default();
+        let options = crate::ExtrudeOptions { termination: crate::ExtrudeTermination::Blind(2.0), ..Default::default() };
+        let block = crate::FeatureEngine::new().extrude(&square, &options, &tolerance).unwrap();
+
+        // Height falls from 1.2 at x = -1 to 0.8 at x = 1
+        let slant = PlanarSurface::new(Point3::new(0.0, 0.0, 1.0), Vec3::new(1.0, 0.0, -0.2), Vec3::Y).unwrap();
+        let lower = SplitEngine::new().trim_by_plane(&block, &slant, Side::Back, &tolerance).unwrap();
+        assert_eq!(lower.faces().len(), 6);
+        assert!(nova_topo::validate_body(&lower, &tolerance).unwrap().is_empty());
+        assert!((nova_topo::body_volume(&lower).unwrap() - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_trim_leaves_a_shell_per_piece() {
+        // A U open toward +Y, cut across both of its legs
+        let corners = [(0.0, 0.0), (3.0, 0.0), (3.0, 2.0), (2.0, 2.0), (2.0, 1.0), (1.0, 1.0), (1.0, 2.0), (0.0, 2.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let coedges = (0..8)
+            .map(|i| {
+                let (a, b) = (corners[i].clone(), corners[(i + 1) % 8].clone());
+                let line = Line::segment(a.position(), b.position()).unwrap();
+                Coedge::new(Arc::new(Edge::with_curve(a, b, Arc::new(line))), Sense::Same)
+            })
+            .collect();
+        let mut face = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        face.add_loop(Loop::from_coedges(coedges));
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut profile = Body::new();
+        profile.add_shell(shell);
+        let tolerance = ToleranceContext::default();
+        let options = crate::ExtrudeOptions { termination: crate::ExtrudeTermination::Blind(1.0), ..Default::default() };
+        let block = crate::FeatureEngine::new().extrude(&profile, &options, &tolerance).unwrap();
+        // Halve the U-shaped ends into Ls, which the cut crosses once each
+        let engine = SplitEngine::new();
+        let mut shell = Shell::new();
+        for face in block.faces() {
+            let z = face.loops()[0].coedges()[0].start_vertex().position().z();
+            if !face.loops()[0].coedges().iter().all(|c| c.start_vertex().position().z() == z) {
+                shell.add_face(face.clone());
+                continue;
+            }
+            let middle: Box<dyn nova_geom::Curve> = Box::new(Line::segment(Point3::new(1.5, -1.0, z), Point3::new(1.5, 3.0, z)).unwrap());
+            for piece in engine.split_face_at_curves(face, &[middle], &tolerance).unwrap() {
+                shell.add_face(piece);
+            }
+        }
+        let mut block = Body::new();
+        block.add_shell(shell);
+
+        let across = PlanarSurface::new(Point3::new(0.0, 1.5, 0.0), Vec3::Z, Vec3::X).unwrap();
+        let legs = engine.trim_by_plane(&block, &across, Side::Front, &tolerance).unwrap();
+        assert_eq!(legs.shells().len(), 2);
+        assert!(legs.shells().iter().all(|shell| shell.faces().len() == 6 && shell.is_outer()));
+        assert!(nova_topo::validate_body(&legs, &tolerance).unwrap().is_empty());
+        assert!((nova_topo::body_volume(&legs).unwrap() - 1.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,329 @@
+//! Glyph outlines for engraving text
+
+use crate::sketch::Sketch;
+use nova_geom::{GeomResult, GeometryError, PlanarSurface};
+use nova_math::{Point2, Point3, ToleranceContext, Vec3};
+use ttf_parser::{Face, GlyphId, OutlineBuilder};
+
+/// Largest chord height of flattened glyph curves, as a fraction of the text size
+const FLATNESS: f64 = 1e-3;
+
+/// Upper bound on line segments per flattened curve
+const MAX_CURVE_SEGMENTS: usize = 32;
+
+/// A TrueType or OpenType font
+#[derive(Debug, Clone)]
+pub struct FontHandle {
+    data: Vec<u8>,
+}
+
+impl FontHandle {
+    /// Load a font from the contents of a font file
+    pub fn from_bytes(data: Vec<u8>) -> GeomResult<Self> {
+        parse(&data)?;
+        Ok(Self { data })
+    }
+
+    fn face(&self) -> GeomResult<Face<'_>> {
+        parse(&self.data)
+    }
+}
+
+fn parse(data: &[u8]) -> GeomResult<Face<'_>> {
+    Face::parse(data, 0).map_err(|e| GeometryError::InvalidParameter(format!("Unreadable font: {}", e)))
+}
+
+/// Outline `text` as sketches on the XY plane, ready to extrude or cut
+///
+/// `size` is the font's em height, and the first line's baseline lies on
+/// the X axis starting at the origin. Each connected island of a glyph
+/// becomes one sketch, with the counters of letters such as 'o' as holes.
+/// Curves are flattened into lines, and characters missing from the font
+/// use its fallback glyph.
+pub fn text_outlines(text: &str, font: &FontHandle, size: f64) -> GeomResult<Vec<Sketch>> {
+    if size.is_nan() || size <= 0.0 {
+        return Err(GeometryError::InvalidParameter(format!("Text size must be positive, got {}", size)));
+    }
+    let face = font.face()?;
+    let scale = size / face.units_per_em() as f64;
+    let line_height = (face.height() + face.line_gap()) as f64 * scale;
+    let plane = PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y)?;
+    let tolerance = ToleranceContext::default();
+
+    let mut sketches = Vec::new();
+    let mut pen = Point2::new(0.0, 0.0);
+    for ch in text.chars() {
+        if ch == '\n' {
+            pen = Point2::new(0.0, pen.y() - line_height);
+            continue;
+        }
+        let glyph = face.glyph_index(ch).unwrap_or(GlyphId(0));
+        let mut builder = ContourBuilder {
+            scale,
+            pen,
+            flatness: size * FLATNESS,
+            contours: Vec::new(),
+            current: Vec::new(),
+        };
+        face.outline_glyph(glyph, &mut builder);
+        builder.close();
+        sketches.extend(island_sketches(&plane, builder.contours, &tolerance)?);
+        pen = Point2::new(pen.x() + face.glyph_hor_advance(glyph).unwrap_or(0) as f64 * scale, pen.y());
+    }
+    Ok(sketches)
+}
+
+/// Group glyph contours into islands, each an outer contour with its holes
+fn island_sketches(
+    plane: &PlanarSurface,
+    contours: Vec<Vec<Point2>>,
+    tolerance: &ToleranceContext,
+) -> GeomResult<Vec<Sketch>> {
+    let contours: Vec<Vec<Point2>> = contours.into_iter().map(|c| clean(c, tolerance.resabs)).filter(|c| c.len() >= 3).collect();
+    // A contour nested inside an odd number of others is a hole in the
+    // smallest of them
+    let parents: Vec<Vec<usize>> = (0..contours.len())
+        .map(|i| (0..contours.len()).filter(|&j| j != i && contains(&contours[j], contours[i][0])).collect())
+        .collect();
+    let to_error = |e: crate::OpsError| GeometryError::Degenerate(e.to_string());
+
+    let mut sketches = Vec::new();
+    for (i, outer) in contours.iter().enumerate().filter(|&(i, _)| parents[i].len() % 2 == 0) {
+        let mut sketch = Sketch::new(*plane);
+        add_polygon(&mut sketch, outer);
+        sketch.close_contour(tolerance).map_err(to_error)?;
+        for (hole, _) in parents.iter().enumerate().filter(|(_, p)| p.len() == parents[i].len() + 1 && p.contains(&i)) {
+            add_polygon(&mut sketch, &contours[hole]);
+            sketch.close_contour(tolerance).map_err(to_error)?;
+        }
+        sketches.push(sketch);
+    }
+    Ok(sketches)
+}
+
+fn add_polygon(sketch: &mut Sketch, points: &[Point2]) {
+    for (i, &p) in points.iter().enumerate() {
+        sketch.add_line(p, points[(i + 1) % points.len()]);
+    }
+}
+
+/// Drop repeated points, including a closing point equal to the first
+fn clean(points: Vec<Point2>, tol: f64) -> Vec<Point2> {
+    let mut out: Vec<Point2> = Vec::with_capacity(points.len());
+    for p in points {
+        if out.last().map_or(true, |q| q.distance_to(&p) > tol) {
+            out.push(p);
+        }
+    }
+    while out.len() > 1 && out[0].distance_to(&out[out.len() - 1]) <= tol {
+        out.pop();
+    }
+    out
+}
+
+/// Even-odd test of `p` against a closed polygon
+fn contains(polygon: &[Point2], p: Point2) -> bool {
+    let mut inside = false;
+    for i in 0..polygon.len() {
+        let (a, b) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+        if (a.y() > p.y()) != (b.y() > p.y()) {
+            let x = a.x() + (p.y() - a.y()) / (b.y() - a.y()) * (b.x() - a.x());
+            if p.x() < x {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+/// Second difference of three control points, how far a curve bows from its chord
+fn bend(a: Point2, b: Point2, c: Point2) -> f64 {
+    (a.x() - 2.0 * b.x() + c.x()).hypot(a.y() - 2.0 * b.y() + c.y())
+}
+
+/// Collects flattened glyph contours in sketch coordinates
+struct ContourBuilder {
+    scale: f64,
+    pen: Point2,
+    flatness: f64,
+    contours: Vec<Vec<Point2>>,
+    current: Vec<Point2>,
+}
+
+impl ContourBuilder {
+    fn point(&self, x: f32, y: f32) -> Point2 {
+        Point2::new(self.pen.x() + x as f64 * self.scale, self.pen.y() + y as f64 * self.scale)
+    }
+
+    fn last(&self) -> Point2 {
+        self.current.last().copied().unwrap_or(self.pen)
+    }
+
+    /// Segments needed to keep a curve whose control polygon bends by
+    /// `deviation` within the flatness
+    fn segments(&self, deviation: f64) -> usize {
+        ((deviation / self.flatness).sqrt().ceil() as usize).clamp(1, MAX_CURVE_SEGMENTS)
+    }
+}
+
+impl OutlineBuilder for ContourBuilder {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.close();
+        let p = self.point(x, y);
+        self.current.push(p);
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.current.push(p);
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (p0, p1, p2) = (self.last(), self.point(x1, y1), self.point(x, y));
+        let n = self.segments(bend(p0, p1, p2));
+        for i in 1..=n {
+            let t = i as f64 / n as f64;
+            let s = 1.0 - t;
+            self.current.push(Point2::new(
+                s * s * p0.x() + 2.0 * s * t * p1.x() + t * t * p2.x(),
+                s * s * p0.y() + 2.0 * s * t * p1.y() + t * t * p2.y(),
+            ));
+        }
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (p0, p1, p2, p3) = (self.last(), self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        let n = self.segments(bend(p0, p1, p2).max(bend(p1, p2, p3)));
+        for i in 1..=n {
+            let t = i as f64 / n as f64;
+            let s = 1.0 - t;
+            let (a, b, c, d) = (s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t);
+            self.current.push(Point2::new(
+                a * p0.x() + b * p1.x() + c * p2.x() + d * p3.x(),
+                a * p0.y() + b * p1.y() + c * p2.y() + d * p3.y(),
+            ));
+        }
+    }
+
+    fn close(&mut self) {
+        if !self.current.is_empty() {
+            self.contours.push(std::mem::take(&mut self.current));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SketchSegment;
+
+    /// Minimal TrueType font with 1000 units per em mapping 'I' to a
+    /// full-height bar and 'o' to a square ring
+    fn test_font() -> FontHandle {
+        fn be16(out: &mut Vec<u8>, values: &[i32]) {
+            for v in values {
+                out.extend_from_slice(&(*v as u16).to_be_bytes());
+            }
+        }
+        fn glyph(out: &mut Vec<u8>, end_points: &[i32], points: &[(i32, i32)]) {
+            let (xs, ys): (Vec<i32>, Vec<i32>) = points.iter().copied().unzip();
+            be16(out, &[end_points.len() as i32, 0, 0, *xs.iter().max().unwrap(), *ys.iter().max().unwrap()]);
+            be16(out, end_points);
+            be16(out, &[0]);
+            out.extend(std::iter::repeat(0x01).take(points.len()));
+            let deltas = |v: &[i32]| (0..v.len()).map(|i| v[i] - if i == 0 { 0 } else { v[i - 1] }).collect::<Vec<_>>();
+            be16(out, &deltas(&xs));
+            be16(out, &deltas(&ys));
+        }
+
+        let mut glyf = Vec::new();
+        glyph(&mut glyf, &[3], &[(100, 0), (100, 1000), (300, 1000), (300, 0)]);
+        let o_offset = glyf.len();
+        glyph(&mut glyf, &[3, 7], &[
+            (0, 0), (0, 500), (500, 500), (500, 0),
+            (100, 100), (400, 100), (400, 400), (100, 400),
+        ]);
+        let loca_end = glyf.len();
+
+        let mut cmap = Vec::new();
+        let (first, last) = ('I' as i32, 'o' as i32);
+        be16(&mut cmap, &[0, 1, 3, 1, 0, 12, 6, 10 + 2 * (last - first + 1), 0, first, last - first + 1]);
+        be16(&mut cmap, &(first..=last).map(|c| if c == first { 1 } else if c == last { 2 } else { 0 }).collect::<Vec<_>>());
+
+        let mut head = Vec::new();
+        be16(&mut head, &[1, 0, 1, 0, 0, 0, 0x5F0F, 0x3CF5, 0, 1000]);
+        head.extend_from_slice(&[0; 16]);
+        be16(&mut head, &[0, 0, 500, 1000, 0, 8, 2, 0, 0]);
+        let mut hhea = Vec::new();
+        be16(&mut hhea, &[1, 0, 800, -200, 0, 600, 0, 0, 500, 1, 0, 0, 0, 0, 0, 0, 0, 3]);
+        let mut hmtx = Vec::new();
+        be16(&mut hmtx, &[500, 0, 400, 100, 600, 0]);
+        let mut loca = Vec::new();
+        be16(&mut loca, &[0, 0, o_offset as i32 / 2, loca_end as i32 / 2]);
+        let mut maxp = Vec::new();
+        be16(&mut maxp, &[0, 0x5000, 3]);
+
+        let tables: [(&[u8; 4], Vec<u8>); 7] = [
+            (b"cmap", cmap), (b"glyf", glyf), (b"head", head), (b"hhea", hhea),
+            (b"hmtx", hmtx), (b"loca", loca), (b"maxp", maxp),
+        ];
+        let mut font = Vec::new();
+        be16(&mut font, &[1, 0, tables.len() as i32, 64, 2, 48]);
+        let mut offset = 12 + 16 * tables.len();
+        for (tag, data) in &tables {
+            font.extend_from_slice(*tag);
+            font.extend_from_slice(&[0; 4]);
+            font.extend_from_slice(&(offset as u32).to_be_bytes());
+            font.extend_from_slice(&(data.len() as u32).to_be_bytes());
+            offset += (data.len() + 3) & !3;
+        }
+        for (_, data) in &tables {
+            font.extend_from_slice(data);
+            font.resize((font.len() + 3) & !3, 0);
+        }
+        FontHandle::from_bytes(font).unwrap()
+    }
+
+    fn points(contour: &[SketchSegment]) -> Vec<Point2> {
+        contour.iter().map(|s| s.start()).collect()
+    }
+
+    #[test]
+    fn test_letter_i_is_one_rectangle() {
+        let sketches = text_outlines("I", &test_font(), 10.0).unwrap();
+        assert_eq!(sketches.len(), 1);
+        let contours = sketches[0].contours();
+        assert_eq!(contours.len(), 1);
+        assert_eq!(contours[0].len(), 4);
+        assert!(contours[0].iter().all(|s| matches!(s, SketchSegment::Line { .. })));
+
+        let pts = points(&contours[0]);
+        let (min_y, max_y) = pts.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p.y()), hi.max(p.y())));
+        assert!(min_y.abs() < 1e-9);
+        assert!((max_y - 10.0).abs() < 1e-9);
+        // Axis-aligned: every edge is horizontal or vertical
+        for (i, p) in pts.iter().enumerate() {
+            let q = pts[(i + 1) % pts.len()];
+            assert!((p.x() - q.x()).abs() < 1e-9 || (p.y() - q.y()).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn test_counters_become_holes() {
+        let sketches = text_outlines("oI", &test_font(), 1.0).unwrap();
+        assert_eq!(sketches.len(), 2);
+        assert_eq!(sketches[0].contours().len(), 2);
+        let face = sketches[0].to_face(&ToleranceContext::default()).unwrap();
+        assert_eq!(face.loops().len(), 2);
+
+        // 'I' follows the 0.6 advance of 'o'
+        let min_x = points(&sketches[1].contours()[0]).iter().map(|p| p.x()).fold(f64::MAX, f64::min);
+        assert!((min_x - 0.7).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rejects_bad_input() {
+        assert!(FontHandle::from_bytes(vec![0; 16]).is_err());
+        assert!(text_outlines("I", &test_font(), 0.0).is_err());
+    }
+}
@@ -30,9 +30,14 @@ pub enum SyncError {
     #[error("Feature recognition failed: {0}")]
     RecognitionFailed(String),
     
-    /// Topology resolution failed
-    #[error("Topology resolution failed: {0}")]
-    ResolutionFailed(String),
+    /// Neighbouring faces could not be reconnected around the edited faces
+    #[error("Topology resolution failed at faces {faces:?}: {reason}")]
+    ResolutionFailed {
+        /// Faces meeting where resolution failed
+        faces: Vec<u64>,
+        /// Why they could not be reconnected
+        reason: String,
+    },
     
     /// Invalid selection
     #[error("Invalid selection: {0}")]
@@ -58,9 +63,14 @@ pub enum SyncError {
     #[error("Degenerate geometry: {0}")]
     DegenerateGeometry(String),
     
-    /// Self-intersection detected
-    #[error("Self-intersection detected: {0}")]
-    SelfIntersection(String),
+    /// Edited faces would pass through each other
+    #[error("Self-intersection between faces {faces:?}, overlapping by {overlap}")]
+    SelfIntersection {
+        /// The faces that would cross
+        faces: Vec<u64>,
+        /// How far one face would pass the other
+        overlap: f64,
+    },
     
     /// Operation not supported
     #[error("Operation not supported: {0}")]
@@ -6,57 +6,339 @@
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
-use nova_math::ToleranceContext;
-use nova_topo::{Body, Face};
+use nova_geom::Line;
+use nova_math::{Point3, ToleranceContext, Transform3, Vec3};
+use nova_topo::{Body, Entity, EntityId, Face};
+use std::collections::HashMap;
 use thiserror::Error;
 
 pub mod error;
 
 pub use error::{SyncError, SyncResult};
+pub use face_edit::{MoveOptions, OffsetOptions, RotateOptions};
+pub use resolve::{ResolutionStrategy, TopologyResolver};
+pub use steering_wheel::SteeringWheel;
 
-/// Synchronous editing engine (stub)
+/// Kind of recorded synchronous edit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncOpType {
+    /// Faces moved
+    FaceMove,
+    /// Faces rotated
+    FaceRotate,
+    /// Faces offset
+    FaceOffset,
+}
+
+/// A recorded synchronous edit
+#[derive(Debug, Clone)]
+pub struct SyncOperation {
+    /// Sequence number, starting at 1
+    pub id: u64,
+    /// Kind of edit
+    pub op_type: SyncOpType,
+    /// Faces selected for the edit
+    pub affected_faces: Vec<EntityId>,
+    /// Faces moved along to keep live rules
+    pub coupled_faces: Vec<EntityId>,
+    /// Faces removed because the edit flattened them
+    pub collapsed_faces: Vec<EntityId>,
+    /// Transform applied to the selected faces
+    pub transform: Transform3,
+    /// Skipped when the history is rebuilt
+    pub suppressed: bool,
+    /// Resolved inputs needed to replay the edit
+    replay: Replay,
+}
+
+/// Inputs of a recorded edit, after snapping
 #[derive(Debug, Clone)]
-pub struct SyncEngine;
+enum Replay {
+    Move {
+        offset: Vec3,
+        resolver: TopologyResolver,
+    },
+    Rotate {
+        axis: Line,
+        angle: f64,
+        resolver: TopologyResolver,
+        maintain_rules: bool,
+        angle_tol: f64,
+    },
+    AlongNormals {
+        offsets: Vec<(EntityId, Vec3)>,
+        resolver: TopologyResolver,
+    },
+}
+
+/// Synchronous editing engine
+#[derive(Debug, Clone)]
+pub struct SyncEngine {
+    history: Vec<SyncOperation>,
+}
 
 impl SyncEngine {
     /// Create new sync engine
     pub fn new() -> Self {
-        Self
+        Self { history: Vec::new() }
     }
     
-    /// Move faces (stub)
+    /// Edits recorded so far, oldest first
+    pub fn history(&self) -> &[SyncOperation] {
+        &self.history
+    }
+    
+    /// Suppress or restore the recorded edit with sequence number `id`
+    pub fn set_suppressed(&mut self, id: u64, suppressed: bool) -> SyncResult<()> {
+        let op = self.history.iter_mut()
+            .find(|op| op.id == id)
+            .ok_or_else(|| SyncError::InvalidSelection(format!("no recorded edit {}", id)))?;
+        op.suppressed = suppressed;
+        Ok(())
+    }
+    
+    /// Replay every edit that is not suppressed, oldest first, starting
+    /// from `base`
+    ///
+    /// Edits refer to faces by id, so `base` should be the body the first
+    /// edit was made on. Snapping is not repeated; each edit replays the
+    /// offset or angle it resolved to when recorded.
+    pub fn rebuild(&self, base: &Body) -> SyncResult<Body> {
+        let mut body = base.transformed(&Transform3::identity());
+        for op in self.history.iter().filter(|op| !op.suppressed) {
+            match &op.replay {
+                Replay::Move { offset, resolver } => {
+                    resolver.resolve_move(&mut body, &op.affected_faces, *offset)?;
+                }
+                Replay::Rotate { axis, angle, resolver, maintain_rules, angle_tol } => {
+                    resolver.resolve_rotation(
+                        &mut body, &op.affected_faces, axis, *angle, *maintain_rules, *angle_tol,
+                    )?;
+                }
+                Replay::AlongNormals { offsets, resolver } => {
+                    for (id, offset) in offsets {
+                        resolver.resolve_move(&mut body, &[*id], *offset)?;
+                    }
+                }
+            }
+        }
+        Ok(body)
+    }
+    
+    /// Move faces by `offset`
+    ///
+    /// Planar faces that land within the snap tolerance of a parallel face
+    /// are made coplanar with it. The snap tolerance is taken from `options`
+    /// when set and from the context's resolution otherwise. Neighbouring
+    /// faces follow according to the options' resolution strategy. A move
+    /// carrying a planar face through a facing one fails with
+    /// `SelfIntersection`. The edit is recorded as a `FaceMove` operation.
     pub fn move_faces(
-        &self,
-        _body: &Body,
-        _faces: &[&Face],
-        _offset: nova_math::Vec3,
-        _tolerance: &ToleranceContext,
+        &mut self,
+        body: &Body,
+        faces: &[&Face],
+        offset: Vec3,
+        options: &MoveOptions,
+        tolerance: &ToleranceContext,
     ) -> SyncResult<Body> {
-        Err(SyncError::NotImplemented("Face move not yet implemented".to_string()))
+        let moved = selection(body, faces)?;
+        let resolver = TopologyResolver::new(options.tolerance.unwrap_or(tolerance.resabs))
+            .with_strategy(options.strategy);
+        let offset = resolver.snap_offset(body, &moved, offset, tolerance.angle_tol);
+        let moves: HashMap<EntityId, Vec3> = moved.iter().map(|&id| (id, offset)).collect();
+        resolver.check_clearance(body, &moves, tolerance.angle_tol)?;
+
+        let mut result = body.transformed(&Transform3::identity());
+        let collapsed = resolver.resolve_move(&mut result, &moved, offset)?;
+        self.history.push(SyncOperation {
+            id: self.history.len() as u64 + 1,
+            op_type: SyncOpType::FaceMove,
+            affected_faces: moved,
+            coupled_faces: Vec::new(),
+            collapsed_faces: collapsed,
+            transform: Transform3::from_translation(offset.x(), offset.y(), offset.z()),
+            suppressed: false,
+            replay: Replay::Move { offset, resolver },
+        });
+        Ok(result)
     }
     
-    /// Rotate faces (stub)
+    /// Rotate faces by `angle` radians about an axis
+    ///
+    /// Neighbouring planar faces are re-intersected with the rotated faces so
+    /// the body stays closed. With `maintain_rules`, neighbours that were
+    /// perpendicular or parallel to a rotated face are rotated too. The edit
+    /// is recorded as a `FaceRotate` operation.
+    #[allow(clippy::too_many_arguments)]
     pub fn rotate_faces(
-        &self,
-        _body: &Body,
-        _faces: &[&Face],
-        _axis_origin: nova_math::Point3,
-        _axis_direction: nova_math::Vec3,
-        _angle: f64,
-        _tolerance: &ToleranceContext,
+        &mut self,
+        body: &Body,
+        faces: &[&Face],
+        axis_origin: Point3,
+        axis_direction: Vec3,
+        angle: f64,
+        options: &RotateOptions,
+        tolerance: &ToleranceContext,
+    ) -> SyncResult<Body> {
+        let rotated = selection(body, faces)?;
+        let axis = Line::infinite(axis_origin, axis_direction)?;
+        let resolver = TopologyResolver::new(options.tolerance.unwrap_or(tolerance.resabs));
+
+        let mut result = body.transformed(&Transform3::identity());
+        let coupled = resolver.resolve_rotation(
+            &mut result, &rotated, &axis, angle, options.maintain_rules, tolerance.angle_tol,
+        )?;
+        self.history.push(SyncOperation {
+            id: self.history.len() as u64 + 1,
+            op_type: SyncOpType::FaceRotate,
+            affected_faces: rotated,
+            coupled_faces: coupled,
+            collapsed_faces: Vec::new(),
+            transform: resolve::rotation_about(&axis, angle),
+            suppressed: false,
+            replay: Replay::Rotate {
+                axis,
+                angle,
+                resolver,
+                maintain_rules: options.maintain_rules,
+                angle_tol: tolerance.angle_tol,
+            },
+        });
+        Ok(result)
+    }
+    
+    /// Rotate faces by dragging the wheel's rotation ring from `from` to `to`
+    #[allow(clippy::too_many_arguments)]
+    pub fn rotate_with_wheel(
+        &mut self,
+        body: &Body,
+        faces: &[&Face],
+        wheel: &SteeringWheel,
+        from: Point3,
+        to: Point3,
+        options: &RotateOptions,
+        tolerance: &ToleranceContext,
     ) -> SyncResult<Body> {
-        Err(SyncError::NotImplemented("Face rotate not yet implemented".to_string()))
+        let angle = wheel.ring_angle(from, to);
+        self.rotate_faces(body, faces, wheel.origin, wheel.axis, angle, options, tolerance)
     }
     
-    /// Offset faces (stub)
+    /// Apply several edits to `body` as one, keeping none of them unless
+    /// all succeed
+    ///
+    /// `edit` works on a copy of the body through the [`Transaction`]. If it
+    /// returns an error, or the edited body fails `validate_body`, the edits
+    /// it recorded are dropped from the history and `body` is left as it was.
+    pub fn transaction<F>(&mut self, body: &mut Body, tolerance: &ToleranceContext, edit: F) -> SyncResult<()>
+    where
+        F: FnOnce(&mut Transaction<'_>) -> SyncResult<()>,
+    {
+        let recorded = self.history.len();
+        let mut tx = Transaction {
+            engine: self,
+            body: body.transformed(&Transform3::identity()),
+            tolerance,
+        };
+        let outcome = edit(&mut tx).and_then(|()| {
+            let issues = nova_topo::validate_body(&tx.body, tolerance)?;
+            if issues.is_empty() {
+                Ok(())
+            } else {
+                Err(SyncError::WouldInvalidateSolid(issues.join("; ")))
+            }
+        });
+        let edited = tx.body;
+        match outcome {
+            Ok(()) => {
+                *body = edited;
+                Ok(())
+            }
+            Err(err) => {
+                self.history.truncate(recorded);
+                Err(err)
+            }
+        }
+    }
+    
+    /// Offset planar faces by `distance` along their outward normals
+    ///
+    /// A negative distance moves the faces into the material. Each face is
+    /// moved in turn with its neighbours stretched as by
+    /// [`move_faces`](Self::move_faces). An offset that carries a face
+    /// through a facing one, such as thinning a wall past its thickness,
+    /// fails with `SelfIntersection`. The edit is recorded as a `FaceOffset`
+    /// operation.
     pub fn offset_faces(
-        &self,
-        _body: &Body,
-        _faces: &[&Face],
-        _distance: f64,
-        _tolerance: &ToleranceContext,
+        &mut self,
+        body: &Body,
+        faces: &[&Face],
+        distance: f64,
+        options: &OffsetOptions,
+        tolerance: &ToleranceContext,
+    ) -> SyncResult<Body> {
+        let resolver = TopologyResolver::new(options.tolerance.unwrap_or(tolerance.resabs));
+        self.move_along_normals(body, faces, distance, resolver, tolerance, SyncOpType::FaceOffset)
+    }
+    
+    /// Move each planar face `distance` along its own outward normal
+    ///
+    /// Unlike [`move_faces`](Self::move_faces) there is no common direction,
+    /// so growing every face of a box enlarges it by twice `distance` in
+    /// each dimension. Edges shared between selected faces follow both, and
+    /// other neighbours follow according to the options' resolution strategy.
+    /// No snapping is done. The edit is recorded as a `FaceMove` operation.
+    pub fn move_faces_along_normals(
+        &mut self,
+        body: &Body,
+        faces: &[&Face],
+        distance: f64,
+        options: &MoveOptions,
+        tolerance: &ToleranceContext,
+    ) -> SyncResult<Body> {
+        let resolver = TopologyResolver::new(options.tolerance.unwrap_or(tolerance.resabs))
+            .with_strategy(options.strategy);
+        self.move_along_normals(body, faces, distance, resolver, tolerance, SyncOpType::FaceMove)
+    }
+    
+    /// Move each face along its outward normal in turn, after checking no
+    /// face passes through another, and record the edit as `op_type`
+    fn move_along_normals(
+        &mut self,
+        body: &Body,
+        faces: &[&Face],
+        distance: f64,
+        resolver: TopologyResolver,
+        tolerance: &ToleranceContext,
+        op_type: SyncOpType,
     ) -> SyncResult<Body> {
-        Err(SyncError::NotImplemented("Face offset not yet implemented".to_string()))
+        let selected = selection(body, faces)?;
+        let mut offsets = Vec::with_capacity(faces.len());
+        for face in faces {
+            if face.surface().is_some_and(|s| s.surface_type() != nova_geom::SurfaceType::Planar) {
+                return Err(SyncError::NotImplemented("Moving curved faces along their normals is not yet implemented".to_string()));
+            }
+            offsets.push((face.id(), face.outward_normal_at(0.0, 0.0, body) * distance));
+        }
+        resolver.check_clearance(body, &offsets.iter().copied().collect(), tolerance.angle_tol)?;
+
+        let mut result = body.transformed(&Transform3::identity());
+        let mut collapsed = Vec::new();
+        // Vertices shared by several faces are moved once for each of them
+        for (id, vector) in &offsets {
+            collapsed.extend(resolver.resolve_move(&mut result, &[*id], *vector)?);
+        }
+        self.history.push(SyncOperation {
+            id: self.history.len() as u64 + 1,
+            op_type,
+            affected_faces: selected,
+            coupled_faces: Vec::new(),
+            collapsed_faces: collapsed,
+            // Each face moves along its own normal
+            transform: Transform3::identity(),
+            suppressed: false,
+            replay: Replay::AlongNormals { offsets, resolver },
+        });
+        Ok(result)
     }
 }
 
@@ -66,6 +348,78 @@ impl Default for SyncEngine {
     }
 }
 
+/// Edits in progress inside [`SyncEngine::transaction`]
+///
+/// Faces are selected by id, since every edit replaces the working body.
+pub struct Transaction<'a> {
+    engine: &'a mut SyncEngine,
+    body: Body,
+    tolerance: &'a ToleranceContext,
+}
+
+impl Transaction<'_> {
+    /// The body with the edits made so far
+    pub fn body(&self) -> &Body {
+        &self.body
+    }
+    
+    /// Move the faces with ids `faces`, as [`SyncEngine::move_faces`]
+    pub fn move_faces(&mut self, faces: &[EntityId], offset: Vec3, options: &MoveOptions) -> SyncResult<()> {
+        let selected = select_faces(&self.body, faces)?;
+        let edited = self.engine.move_faces(&self.body, &selected, offset, options, self.tolerance)?;
+        self.body = edited;
+        Ok(())
+    }
+    
+    /// Offset the faces with ids `faces`, as [`SyncEngine::offset_faces`]
+    pub fn offset_faces(&mut self, faces: &[EntityId], distance: f64, options: &OffsetOptions) -> SyncResult<()> {
+        let selected = select_faces(&self.body, faces)?;
+        let edited = self.engine.offset_faces(&self.body, &selected, distance, options, self.tolerance)?;
+        self.body = edited;
+        Ok(())
+    }
+    
+    /// Rotate the faces with ids `faces`, as [`SyncEngine::rotate_faces`]
+    pub fn rotate_faces(
+        &mut self,
+        faces: &[EntityId],
+        axis_origin: Point3,
+        axis_direction: Vec3,
+        angle: f64,
+        options: &RotateOptions,
+    ) -> SyncResult<()> {
+        let selected = select_faces(&self.body, faces)?;
+        let edited = self.engine.rotate_faces(
+            &self.body, &selected, axis_origin, axis_direction, angle, options, self.tolerance,
+        )?;
+        self.body = edited;
+        Ok(())
+    }
+}
+
+/// Ids of the selected faces, all of which must belong to `body`
+fn selection(body: &Body, faces: &[&Face]) -> SyncResult<Vec<EntityId>> {
+    if faces.is_empty() {
+        return Err(SyncError::NoSelection);
+    }
+    let ids: Vec<EntityId> = faces.iter().map(|f| f.id()).collect();
+    select_faces(body, &ids)?;
+    Ok(ids)
+}
+
+/// Faces of `body` with the given ids, in the order given
+fn select_faces<'b>(body: &'b Body, ids: &[EntityId]) -> SyncResult<Vec<&'b Face>> {
+    let faces = body.faces();
+    ids.iter()
+        .map(|&id| {
+            faces.iter()
+                .find(|f| f.id() == id)
+                .copied()
+                .ok_or(SyncError::FaceNotFound(id.0))
+        })
+        .collect()
+}
+
 // Stub modules
 pub mod face_edit {
     //! Face editing operations (stub)
@@ -88,15 +442,34 @@ pub mod face_edit {
     
     /// Move options
     #[derive(Debug, Clone, Default)]
-    pub struct MoveOptions;
+    pub struct MoveOptions {
+        /// Snap tolerance for this edit, overriding the context's resolution
+        pub tolerance: Option<f64>,
+        /// How neighbouring faces follow the move
+        pub strategy: crate::ResolutionStrategy,
+    }
     
     /// Rotate options
-    #[derive(Debug, Clone, Default)]
-    pub struct RotateOptions;
+    #[derive(Debug, Clone)]
+    pub struct RotateOptions {
+        /// Snap tolerance for this edit, overriding the context's resolution
+        pub tolerance: Option<f64>,
+        /// Rotate neighbours to keep them perpendicular or parallel
+        pub maintain_rules: bool,
+    }
+    
+    impl Default for RotateOptions {
+        fn default() -> Self {
+            Self { tolerance: None, maintain_rules: true }
+        }
+    }
     
     /// Offset options
     #[derive(Debug, Clone, Default)]
-    pub struct OffsetOptions;
+    pub struct OffsetOptions {
+        /// Snap tolerance for this edit, overriding the context's resolution
+        pub tolerance: Option<f64>,
+    }
 }
 
 pub mod face_edit_impl {
@@ -221,11 +594,35 @@ pub mod feature_handle {
 }
 
 pub mod steering_wheel {
-    //! Steering wheel widget (stub)
+    //! Steering wheel widget
+    use nova_math::{Point3, Vec3};
     
-    /// Steering wheel (stub)
+    /// Steering wheel placed at `origin` with its primary axis along `axis`
     #[derive(Debug, Clone)]
-    pub struct SteeringWheel;
+    pub struct SteeringWheel {
+        /// Wheel centre, on the rotation axis
+        pub origin: Point3,
+        /// Unit primary axis, normal to the rotation ring
+        pub axis: Vec3,
+    }
+    
+    impl SteeringWheel {
+        /// Create a wheel at `origin` with its primary axis along `axis`
+        pub fn new(origin: Point3, axis: Vec3) -> Self {
+            Self { origin, axis: axis.normalized() }
+        }
+        
+        /// Signed angle in radians swept by dragging the rotation ring from
+        /// `from` to `to`, right-handed about the axis
+        pub fn ring_angle(&self, from: Point3, to: Point3) -> f64 {
+            let radial = |p: Point3| {
+                let d = p - self.origin;
+                d - self.axis * d.dot(&self.axis)
+            };
+            let (u, v) = (radial(from), radial(to));
+            self.axis.dot(&u.cross(&v)).atan2(u.dot(&v))
+        }
+    }
     
     /// Steering wheel axis
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -240,9 +637,966 @@ pub mod steering_wheel {
 }
 
 pub mod resolve {
-    //! Topology resolution (stub)
+    //! Topology resolution
+    use super::*;
+    use nova_geom::{CurveType, ExtrusionSurface, Line, PlanarSurface, Surface, SurfaceType};
+    use nova_math::Point3;
+    use nova_topo::{Coedge, Edge, Loop, Sense, TopologicalEntity, Vertex};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    
+    /// How neighbouring faces follow a face move
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    pub enum ResolutionStrategy {
+        /// Extend or trim the neighbours to the new position; the face count
+        /// is unchanged
+        #[default]
+        ExtendTrim,
+        /// Leave the neighbours in place and bridge each gap with a new face
+        InsertFaces,
+        /// Extend and trim while every stretched neighbour stays a plane,
+        /// otherwise insert faces rather than warp a neighbour into a
+        /// freeform surface
+        PreferAnalytic,
+    }
     
-    /// Topology resolver (stub)
+    /// Topology resolver
     #[derive(Debug, Clone)]
-    pub struct TopologyResolver;
+    pub struct TopologyResolver {
+        /// Distance within which edited faces snap onto neighbouring geometry
+        pub tolerance: f64,
+        /// Strategy used to reconnect neighbours
+        pub strategy: ResolutionStrategy,
+    }
+    
+    impl TopologyResolver {
+        /// Create a resolver snapping within `tolerance`
+        pub fn new(tolerance: f64) -> Self {
+            Self { tolerance, strategy: ResolutionStrategy::default() }
+        }
+        
+        /// Set the resolution strategy
+        pub fn with_strategy(mut self, strategy: ResolutionStrategy) -> Self {
+            self.strategy = strategy;
+            self
+        }
+        
+        /// Move the `moved` faces of `body` by `offset` and reconnect their
+        /// neighbours using the resolver's strategy
+        ///
+        /// When stretching brings a vertex within tolerance of another, the
+        /// two are merged and the faces this flattens are removed. Returns
+        /// the removed faces.
+        pub fn resolve_move(&self, body: &mut Body, moved: &[EntityId], offset: Vec3) -> SyncResult<Vec<EntityId>> {
+            let insert = match self.strategy {
+                ResolutionStrategy::ExtendTrim => false,
+                ResolutionStrategy::InsertFaces => true,
+                ResolutionStrategy::PreferAnalytic => !self.neighbours_stay_planar(body, moved, offset),
+            };
+            if insert {
+                insert_bridge_faces(body, moved, offset)?;
+                return Ok(Vec::new());
+            }
+            let before: HashMap<EntityId, Point3> = body.vertices_iter().map(|v| (v.id(), v.position())).collect();
+            stretch_neighbours(body, moved, offset)?;
+            collapse_coincident(body, &before, self.tolerance)
+        }
+        
+        /// Check that every unmoved face sharing a vertex with a moved face
+        /// is planar and contains `offset`, so stretching keeps it planar
+        fn neighbours_stay_planar(&self, body: &Body, moved: &[EntityId], offset: Vec3) -> bool {
+            let faces = body.faces();
+            let moved_vertices: Vec<EntityId> = faces.iter()
+                .filter(|f| moved.contains(&f.id()))
+                .flat_map(|f| face_vertices(f))
+                .collect();
+            faces.iter()
+                .filter(|f| !moved.contains(&f.id()))
+                .filter(|f| face_vertices(f).iter().any(|v| moved_vertices.contains(v)))
+                .all(|f| f.plane().is_some_and(|(_, n)| offset.dot(&n).abs() <= self.tolerance))
+        }
+        
+        /// Rotate the `moved` faces by `angle` radians about `axis` and
+        /// re-intersect the planes around them
+        ///
+        /// With `maintain_rules`, an adjacent face that was perpendicular or
+        /// parallel to a rotated face is rotated with it about its own unmoved
+        /// vertices, unless it contains the axis and so anchors the rotation.
+        /// Every vertex of a changed face is then placed where three of its
+        /// faces' planes meet. Returns the coupled faces.
+        pub fn resolve_rotation(
+            &self,
+            body: &mut Body,
+            moved: &[EntityId],
+            axis: &Line,
+            angle: f64,
+            maintain_rules: bool,
+            angle_tol: f64,
+        ) -> SyncResult<Vec<EntityId>> {
+            let faces = body.faces();
+            let mut planes: HashMap<EntityId, (Point3, Vec3)> = HashMap::new();
+            let mut incident: HashMap<EntityId, Vec<EntityId>> = HashMap::new();
+            let mut positions: HashMap<EntityId, Point3> = HashMap::new();
+            for face in &faces {
+                for v in face.loops().iter().flat_map(|lp| lp.coedges()).map(|c| c.start_vertex()) {
+                    incident.entry(v.id()).or_default().push(face.id());
+                    positions.insert(v.id(), v.position());
+                }
+                if let Some(plane) = face.plane() {
+                    planes.insert(face.id(), plane);
+                }
+            }
+            let moved_vertices: Vec<EntityId> = faces.iter()
+                .filter(|f| moved.contains(&f.id()))
+                .flat_map(|f| face_vertices(f))
+                .collect();
+
+            let rotation = rotation_about(axis, angle);
+            let mut transforms: HashMap<EntityId, Transform3> = moved.iter().map(|&id| (id, rotation)).collect();
+            let mut coupled = Vec::new();
+            if maintain_rules {
+                let (sin_tol, cos_tol) = (angle_tol.sin(), angle_tol.cos());
+                for face in faces.iter().filter(|f| !moved.contains(&f.id())) {
+                    let vertices = face_vertices(face);
+                    let Some(&(p, n)) = planes.get(&face.id()) else { continue };
+                    let anchors = n.dot(&axis.direction()).abs() <= sin_tol
+                        && (axis.origin() - p).dot(&n).abs() <= self.tolerance;
+                    if anchors || !vertices.iter().any(|v| moved_vertices.contains(v)) {
+                        continue;
+                    }
+                    let broken = moved.iter().filter_map(|id| planes.get(id)).any(|(_, m)| {
+                        let (before, after) = (n.dot(m).abs(), n.dot(&rotation.apply_to_vector(m)).abs());
+                        (before <= sin_tol && after > sin_tol) || (before >= cos_tol && after < cos_tol)
+                    });
+                    if broken {
+                        let fixed: Vec<Vec3> = vertices.iter()
+                            .filter(|v| !moved_vertices.contains(v))
+                            .map(|v| positions[v].to_vector())
+                            .collect();
+                        let pivot = match fixed.len() {
+                            0 => p,
+                            n => Point3::ORIGIN + fixed.into_iter().fold(Vec3::ZERO, |a, b| a + b) / n as f64,
+                        };
+                        transforms.insert(face.id(), rotation_about(&Line::infinite(pivot, axis.direction())?, angle));
+                        coupled.push(face.id());
+                    }
+                }
+            }
+
+            for (id, t) in &transforms {
+                if let Some((p, n)) = planes.get_mut(id) {
+                    *p = t.apply_to_point(p);
+                    *n = t.apply_to_vector(n);
+                }
+            }
+            let mut relocated = HashMap::new();
+            for (v, around) in &incident {
+                if !around.iter().any(|f| transforms.contains_key(f)) {
+                    continue;
+                }
+                for f in around {
+                    let curved = faces.iter().find(|face| face.id() == *f)
+                        .and_then(|face| face.surface())
+                        .is_some_and(|s| s.surface_type() != SurfaceType::Planar);
+                    if curved || !planes.contains_key(f) {
+                        return Err(SyncError::NotImplemented(
+                            "Rotating faces next to curved faces is not yet implemented".to_string(),
+                        ));
+                    }
+                }
+                let point = intersect_planes(around.iter().map(|f| planes[f]))
+                    .ok_or_else(|| SyncError::ResolutionFailed {
+                        faces: around.iter().map(|f| f.0).collect(),
+                        reason: format!("vertex {} is not fixed by their planes", v.0),
+                    })?;
+                relocated.insert(*v, point);
+            }
+            relocate_vertices(body, &relocated, &transforms)?;
+            Ok(coupled)
+        }
+    
+        
+        /// Adjust a move so moved planar faces ending up within tolerance of
+        /// a parallel, unmoved face become coplanar with it
+        ///
+        /// The closest such plane wins; faces are parallel when their normals
+        /// are within `angle_tol` radians.
+        pub fn snap_offset(&self, body: &Body, moved: &[EntityId], offset: Vec3, angle_tol: f64) -> Vec3 {
+            let faces = body.faces();
+            let planes = |moving: bool| {
+                faces.iter()
+                    .filter(move |f| moved.contains(&f.id()) == moving)
+                    .filter_map(|f| f.plane())
+                    .collect::<Vec<_>>()
+            };
+            let (moving, fixed) = (planes(true), planes(false));
+            let min_cos = angle_tol.cos();
+
+            let mut best: Option<Vec3> = None;
+            for (p, n) in &moving {
+                let p = *p + offset;
+                for (q, m) in &fixed {
+                    if n.dot(m).abs() < min_cos {
+                        continue;
+                    }
+                    let gap = (*q - p).dot(n);
+                    if gap.abs() <= self.tolerance && best.map_or(true, |b| gap.abs() < b.length()) {
+                        best = Some(*n * gap);
+                    }
+                }
+            }
+            offset + best.unwrap_or(Vec3::ZERO)
+        }
+
+        /// Check that moving faces by `moves` carries no planar face through
+        /// another facing it
+        ///
+        /// Two faces face each other when their outward normals are opposite
+        /// within `angle_tol` radians and their extents across that normal
+        /// overlap once moved. Such a pair crosses when the gap or wall
+        /// between their planes closes to within tolerance; the error gives
+        /// the pair and how far one would pass the other.
+        pub fn check_clearance(&self, body: &Body, moves: &HashMap<EntityId, Vec3>, angle_tol: f64) -> SyncResult<()> {
+            let planar: Vec<(&Face, Point3, Vec3)> = body.faces().into_iter()
+                .filter_map(|f| f.plane().map(|(p, _)| (f, p, f.outward_normal_at(0.0, 0.0, body))))
+                .filter(|(_, _, n)| !n.is_zero(1e-12))
+                .collect();
+            let min_cos = angle_tol.cos();
+            let shift = |face: &Face| moves.get(&face.id()).copied().unwrap_or(Vec3::ZERO);
+            for (i, &(f, p, n)) in planar.iter().enumerate() {
+                for &(g, q, m) in &planar[i + 1..] {
+                    let (df, dg) = (shift(f), shift(g));
+                    if df.is_zero(0.0) && dg.is_zero(0.0) || n.dot(&m) > -min_cos {
+                        continue;
+                    }
+                    // Negative across a wall, positive across a gap
+                    let before = (q - p).dot(&n);
+                    if before.abs() <= self.tolerance || !extents_overlap(f, df, g, dg, n, self.tolerance) {
+                        continue;
+                    }
+                    let after = before + (dg - df).dot(&n);
+                    let overlap = -after * before.signum();
+                    if overlap > -self.tolerance {
+                        return Err(SyncError::SelfIntersection { faces: vec![f.id().0, g.id().0], overlap });
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+    
+    /// Whether faces `f` and `g`, moved by `df` and `dg`, overlap when seen
+    /// along `normal`, comparing their vertex extents on two in-plane axes
+    fn extents_overlap(f: &Face, df: Vec3, g: &Face, dg: Vec3, normal: Vec3, tolerance: f64) -> bool {
+        let other = if normal.x().abs() < 0.9 { Vec3::X } else { Vec3::Y };
+        let u = normal.cross(&other).normalized();
+        let v = normal.cross(&u);
+        let extent = |face: &Face, shift: Vec3, axis: Vec3| {
+            face.loops().iter().flat_map(|lp| lp.coedges())
+                .map(|c| (c.start_vertex().position() + shift).to_vector().dot(&axis))
+                .fold((f64::MAX, f64::MIN), |(lo, hi), x| (lo.min(x), hi.max(x)))
+        };
+        [u, v].into_iter().all(|axis| {
+            let ((f_lo, f_hi), (g_lo, g_hi)) = (extent(f, df, axis), extent(g, dg, axis));
+            f_hi.min(g_hi) - f_lo.max(g_lo) > tolerance
+        })
+    }
+    
+    /// Translate every vertex of the `moved` faces, stretching the edges
+    /// running to their neighbours
+    fn stretch_neighbours(body: &mut Body, moved: &[EntityId], offset: Vec3) -> SyncResult<()> {
+        let positions: HashMap<EntityId, Point3> = body.faces().into_iter()
+            .filter(|f| moved.contains(&f.id()))
+            .flat_map(|f| f.loops().iter().flat_map(|lp| lp.coedges()).map(|c| c.start_vertex()).collect::<Vec<_>>())
+            .map(|v| (v.id(), v.position() + offset))
+            .collect();
+        let translation = Transform3::from_translation_vec(offset);
+        let transforms = moved.iter().map(|&id| (id, translation)).collect();
+        relocate_vertices(body, &positions, &transforms)
+    }
+    
+    /// Merge each vertex that has moved to within `tolerance` of another,
+    /// given every vertex's position `before`, and simplify around it
+    ///
+    /// Edges between merged vertices are dropped from their loops. A face
+    /// left with fewer than three coedges has collapsed: it is removed and
+    /// the two edges it held, which now join the same vertices, become one.
+    /// Returns the removed faces.
+    fn collapse_coincident(body: &mut Body, before: &HashMap<EntityId, Point3>, tolerance: f64) -> SyncResult<Vec<EntityId>> {
+        let vertices: Vec<Arc<Vertex>> = body.vertices_iter().map(|v| Arc::new(v.clone())).collect();
+        let mut merged: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+        for (i, a) in vertices.iter().enumerate() {
+            for b in &vertices[i + 1..] {
+                let was = |v: &Vertex| before.get(&v.id()).copied().unwrap_or(v.position());
+                if merged.contains_key(&a.id()) || merged.contains_key(&b.id())
+                    || a.position().distance_to(&b.position()) > tolerance
+                    || was(a).distance_to(&was(b)) <= tolerance
+                {
+                    continue;
+                }
+                // Keep whichever vertex stayed put
+                let (from, to) = if was(a) == a.position() { (b, a) } else { (a, b) };
+                merged.insert(from.id(), to.clone());
+            }
+        }
+        if merged.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut rebuilt: HashMap<EntityId, Arc<Edge>> = HashMap::new();
+        // Dropped edge to the edge replacing it, and whether they run opposite
+        let mut aliases: HashMap<EntityId, (Arc<Edge>, bool)> = HashMap::new();
+        let mut collapsed = Vec::new();
+        for shell in body.shells_mut() {
+            for face in shell.faces_mut().iter_mut() {
+                for lp in face.loops_mut().iter_mut() {
+                    for coedge in lp.coedges_mut().iter_mut() {
+                        let edge = coedge.edge();
+                        let (start, end) = (edge.start_vertex(), edge.end_vertex());
+                        if !merged.contains_key(&start.id()) && !merged.contains_key(&end.id()) {
+                            continue;
+                        }
+                        if let Some(e) = rebuilt.get(&edge.id()) {
+                            coedge.set_edge(e.clone());
+                            continue;
+                        }
+                        let target = |v: &Vertex| merged.get(&v.id()).cloned().unwrap_or_else(|| Arc::new(v.clone()));
+                        let (start, end) = (target(start), target(end));
+                        let mut new_edge = edge.remapped(start.clone(), end.clone());
+                        if start.id() != end.id() && edge.curve().is_some_and(|c| c.curve_type() == CurveType::Line) {
+                            new_edge.set_curve(Some(Arc::new(Line::segment(start.position(), end.position())?)));
+                        }
+                        let new_edge = Arc::new(new_edge);
+                        rebuilt.insert(edge.id(), new_edge.clone());
+                        coedge.set_edge(new_edge);
+                    }
+                    lp.coedges_mut().retain(|c| c.edge().start_vertex().id() != c.edge().end_vertex().id());
+                }
+                let degenerate = |lp: &Loop| lp.coedges().len() < 3;
+                if face.outer_loop().map_or(true, degenerate) {
+                    if let Some(lp) = face.outer_loop() {
+                        if let [first, second] = lp.coedges() {
+                            let (kept, dropped) = (first.edge(), second.edge());
+                            let share = kept.remapped(Arc::new(kept.start_vertex().clone()), Arc::new(kept.end_vertex().clone()));
+                            let flipped = kept.start_vertex().id() != dropped.start_vertex().id();
+                            aliases.insert(dropped.id(), (Arc::new(share), flipped));
+                        }
+                    }
+                    collapsed.push(face.id());
+                } else {
+                    face.loops_mut().retain(|lp| !degenerate(lp));
+                }
+            }
+            shell.faces_mut().retain(|f| !collapsed.contains(&f.id()));
+        }
+
+        for shell in body.shells_mut() {
+            for coedge in shell.faces_mut().iter_mut()
+                .flat_map(|f| f.loops_mut().iter_mut())
+                .flat_map(|lp| lp.coedges_mut().iter_mut())
+            {
+                if let Some((e, flipped)) = aliases.get(&coedge.edge().id()) {
+                    coedge.set_edge(e.clone());
+                    if *flipped {
+                        coedge.reverse_sense();
+                    }
+                }
+            }
+        }
+        Ok(collapsed)
+    }
+    
+    /// Move vertices to `positions`, rebuilding the edges touching them
+    ///
+    /// Faces in `transforms` have their surfaces carried along. Straight
+    /// edges are rebuilt between their new ends; other curves must follow
+    /// one of the face transforms.
+    fn relocate_vertices(
+        body: &mut Body,
+        positions: &HashMap<EntityId, Point3>,
+        transforms: &HashMap<EntityId, Transform3>,
+    ) -> SyncResult<()> {
+        let mut vertices: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+        let mut rebuilt: HashMap<EntityId, Arc<Edge>> = HashMap::new();
+        for shell in body.shells_mut() {
+            for face in shell.faces_mut() {
+                if let (Some(t), Some(surface)) = (transforms.get(&face.id()), face.surface().cloned()) {
+                    face.set_surface(Some(Arc::from(surface.transformed(t))));
+                }
+                for coedge in face.loops_mut().iter_mut().flat_map(|lp| lp.coedges_mut().iter_mut()) {
+                    let edge = coedge.edge();
+                    let (old_start, old_end) = (edge.start_vertex().position(), edge.end_vertex().position());
+                    if !positions.contains_key(&edge.start_vertex().id()) && !positions.contains_key(&edge.end_vertex().id()) {
+                        continue;
+                    }
+                    if let Some(e) = rebuilt.get(&edge.id()) {
+                        coedge.set_edge(e.clone());
+                        continue;
+                    }
+
+                    let mut vertex = |v: &Vertex| vertices.entry(v.id()).or_insert_with(|| {
+                        let mut m = v.clone();
+                        if let Some(&p) = positions.get(&v.id()) {
+                            m.set_position(p);
+                        }
+                        Arc::new(m)
+                    }).clone();
+                    let (start, end) = (vertex(edge.start_vertex()), vertex(edge.end_vertex()));
+                    let mut new_edge = edge.remapped(start.clone(), end.clone());
+                    if let Some(curve) = edge.curve() {
+                        if curve.curve_type() == CurveType::Line {
+                            new_edge.set_curve(Some(Arc::new(Line::segment(start.position(), end.position())?)));
+                        } else {
+                            let follows = |t: &&Transform3| {
+                                t.apply_to_point(&old_start).distance_to(&start.position()) < 1e-9
+                                    && t.apply_to_point(&old_end).distance_to(&end.position()) < 1e-9
+                            };
+                            let t = transforms.values().find(follows).ok_or_else(|| SyncError::NotImplemented(
+                                "Stretching curved edges is not yet implemented".to_string(),
+                            ))?;
+                            new_edge.set_curve(Some(Arc::from(curve.transformed(t))));
+                        }
+                    }
+                    let new_edge = Arc::new(new_edge);
+                    rebuilt.insert(edge.id(), new_edge.clone());
+                    coedge.set_edge(new_edge);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Give the `moved` faces their own translated vertices and edges, and
+    /// bridge every edge they shared with an unmoved face with a new face
+    ///
+    /// Each bridge runs from the old edge, still used by the neighbour, to
+    /// the moved copy, with new edges joining the old and moved vertices.
+    /// New edges are built on a placeholder vertex until the vertices they
+    /// join have recorded them, as in welding.
+    fn insert_bridge_faces(body: &mut Body, moved: &[EntityId], offset: Vec3) -> SyncResult<()> {
+        let translation = Transform3::from_translation_vec(offset);
+        let key = |e: &Edge| {
+            let (a, b) = (e.start_vertex().id(), e.end_vertex().id());
+            (a.min(b), a.max(b))
+        };
+        let fixed_edges: Vec<(EntityId, EntityId)> = body.faces().into_iter()
+            .filter(|f| !moved.contains(&f.id()))
+            .flat_map(|f| f.loops().iter().flat_map(|lp| lp.coedges().iter().map(|c| key(c.edge()))).collect::<Vec<_>>())
+            .collect();
+
+        let placeholder = Arc::new(Vertex::new(Point3::ORIGIN));
+        let unset = Arc::new(Edge::new(placeholder.clone(), placeholder.clone()));
+        // Old vertex id to its moved twin, and to the copy that starts its side edge
+        let mut twins: HashMap<EntityId, Vertex> = HashMap::new();
+        let mut anchors: HashMap<EntityId, Vertex> = HashMap::new();
+        // Old edge id to its moved copy, and old vertex id to its side edge
+        let mut copies: HashMap<EntityId, Edge> = HashMap::new();
+        let mut sides: HashMap<EntityId, Edge> = HashMap::new();
+        // Replacement coedges for the moved faces in order, with the old edge
+        // each copies, and each bridge's shell and loop
+        let mut uses: Vec<(Coedge, EntityId)> = Vec::new();
+        let mut bridges: Vec<(usize, [Coedge; 4])> = Vec::new();
+
+        for (shell_index, shell) in body.shells().iter().enumerate() {
+            for face in shell.faces().iter().filter(|f| moved.contains(&f.id())) {
+                for coedge in face.loops().iter().flat_map(|lp| lp.coedges()) {
+                    let edge = coedge.edge();
+                    let (start, end) = (edge.start_vertex(), edge.end_vertex());
+                    for v in [start, end] {
+                        twins.entry(v.id()).or_insert_with(|| Vertex::new(v.position() + offset));
+                    }
+                    let copy = copies.entry(edge.id()).or_insert_with(|| {
+                        let mut copy = Edge::new(placeholder.clone(), placeholder.clone());
+                        copy.set_curve(edge.curve().map(|curve| Arc::from(curve.transformed(&translation))));
+                        for v in [start, end] {
+                            twins.get_mut(&v.id()).unwrap().add_edge(copy.id());
+                        }
+                        copy
+                    });
+                    let sense = coedge.sense();
+                    let moved_use = Coedge::new(unset.clone(), sense);
+                    copy.add_coedge(moved_use.id());
+                    uses.push((moved_use, edge.id()));
+
+                    if !fixed_edges.contains(&key(edge)) {
+                        continue;
+                    }
+                    // Loop direction a -> b along the coedge
+                    let (a, b) = if sense.is_same() { (start, end) } else { (end, start) };
+                    for v in [a, b] {
+                        if sides.contains_key(&v.id()) {
+                            continue;
+                        }
+                        let twin = twins.get_mut(&v.id()).unwrap();
+                        let mut side = Edge::new(placeholder.clone(), placeholder.clone());
+                        side.set_curve(Some(Arc::new(Line::segment(v.position(), twin.position())?)));
+                        twin.add_edge(side.id());
+                        let mut anchor = v.clone();
+                        anchor.add_edge(side.id());
+                        anchors.insert(v.id(), anchor);
+                        sides.insert(v.id(), side);
+                    }
+                    // The old edge keeps the neighbour's coedge and this one,
+                    // which moves onto the bridge
+                    let loop_uses = [
+                        Coedge::new(unset.clone(), sense.reverse()),
+                        Coedge::new(unset.clone(), Sense::Opposite),
+                        coedge.clone(),
+                        Coedge::new(unset.clone(), Sense::Same),
+                    ];
+                    copies.get_mut(&edge.id()).unwrap().add_coedge(loop_uses[0].id());
+                    sides.get_mut(&a.id()).unwrap().add_coedge(loop_uses[1].id());
+                    sides.get_mut(&b.id()).unwrap().add_coedge(loop_uses[3].id());
+                    bridges.push((shell_index, loop_uses));
+                }
+            }
+        }
+
+        let twins: HashMap<EntityId, Arc<Vertex>> = twins.into_iter().map(|(id, v)| (id, Arc::new(v))).collect();
+        let anchors: HashMap<EntityId, Arc<Vertex>> = anchors.into_iter().map(|(id, v)| (id, Arc::new(v))).collect();
+        let old_edges: HashMap<EntityId, (EntityId, EntityId)> = body.edges_iter()
+            .filter(|e| copies.contains_key(&e.id()))
+            .map(|e| (e.id(), (e.start_vertex().id(), e.end_vertex().id())))
+            .collect();
+        let copies: HashMap<EntityId, Arc<Edge>> = copies.into_iter()
+            .map(|(id, e)| {
+                let (start, end) = old_edges[&id];
+                (id, Arc::new(e.remapped(twins[&start].clone(), twins[&end].clone())))
+            })
+            .collect();
+        let sides: HashMap<EntityId, Arc<Edge>> = sides.into_iter()
+            .map(|(id, e)| (id, Arc::new(e.remapped(anchors[&id].clone(), twins[&id].clone()))))
+            .collect();
+
+        let mut uses = uses.into_iter();
+        for shell in body.shells_mut() {
+            for face in shell.faces_mut().iter_mut().filter(|f| moved.contains(&f.id())) {
+                if let Some(surface) = face.surface().cloned() {
+                    face.set_surface(Some(Arc::from(surface.transformed(&translation))));
+                }
+                for coedge in face.loops_mut().iter_mut().flat_map(|lp| lp.coedges_mut().iter_mut()) {
+                    let (mut moved_use, edge_id) = uses.next().unwrap();
+                    moved_use.set_edge(copies[&edge_id].clone());
+                    *coedge = moved_use;
+                }
+            }
+        }
+        for (shell_index, mut loop_uses) in bridges {
+            let old = &loop_uses[2];
+            let (edge_id, a, b) = (old.edge().id(), old.start_vertex().id(), old.end_vertex().id());
+            loop_uses[0].set_edge(copies[&edge_id].clone());
+            loop_uses[1].set_edge(sides[&a].clone());
+            loop_uses[3].set_edge(sides[&b].clone());
+            let sense = loop_uses[2].sense();
+            let (surface, agrees) = bridge_surface(loop_uses[2].edge(), sense, offset)?;
+            let mut bridge = Face::with_surface(surface);
+            if !agrees {
+                bridge.reverse_orientation();
+            }
+            bridge.add_loop(Loop::from_coedges(loop_uses.into()));
+            body.shells_mut()[shell_index].add_face(bridge);
+        }
+        Ok(())
+    }
+    
+    /// Surface swept by `edge` moving along `offset`, and whether its normal
+    /// agrees with a bridge loop that runs along the edge in `sense`
+    ///
+    /// Straight edges sweep a plane through the bridge's corners; curved
+    /// ones sweep an extrusion surface following the edge's own direction.
+    fn bridge_surface(edge: &Edge, sense: Sense, offset: Vec3) -> SyncResult<(Arc<dyn Surface>, bool)> {
+        match edge.curve() {
+            Some(curve) if curve.curve_type() != CurveType::Line => {
+                Ok((Arc::new(ExtrusionSurface::new(curve.clone_box(), offset)?), sense.is_same()))
+            }
+            _ => {
+                let (start, end) = (edge.start_vertex().position(), edge.end_vertex().position());
+                let along = if sense.is_same() { end - start } else { start - end };
+                let plane = PlanarSurface::new(start, along, offset).or_else(|_| {
+                    // A bridge along the move has no area; any plane through the edge will do
+                    let axis = if along.x().abs() < 0.5 * along.length() { Vec3::X } else { Vec3::Y };
+                    PlanarSurface::new(start, along, axis)
+                })?;
+                Ok((Arc::new(plane), true))
+            }
+        }
+    }
+    
+    /// Rotation by `angle` radians about `axis`
+    pub(crate) fn rotation_about(axis: &Line, angle: f64) -> Transform3 {
+        let origin = axis.origin().to_vector();
+        Transform3::from_translation_vec(origin)
+            * Transform3::from_axis_angle(&axis.direction(), angle)
+            * Transform3::from_translation_vec(-origin)
+    }
+    
+    /// The point where the best-conditioned three of `planes` meet
+    fn intersect_planes(planes: impl Iterator<Item = (Point3, Vec3)>) -> Option<Point3> {
+        let planes: Vec<(Point3, Vec3)> = planes.collect();
+        let mut best: Option<(f64, Point3)> = None;
+        for i in 0..planes.len() {
+            for j in i + 1..planes.len() {
+                for k in j + 1..planes.len() {
+                    let [(p1, n1), (p2, n2), (p3, n3)] = [planes[i], planes[j], planes[k]];
+                    let det = n1.dot(&n2.cross(&n3));
+                    if det.abs() <= best.map_or(1e-9, |b| b.0) {
+                        continue;
+                    }
+                    let point = n2.cross(&n3) * p1.to_vector().dot(&n1)
+                        + n3.cross(&n1) * p2.to_vector().dot(&n2)
+                        + n1.cross(&n2) * p3.to_vector().dot(&n3);
+                    best = Some((det.abs(), Point3::ORIGIN + point / det));
+                }
+            }
+        }
+        best.map(|b| b.1)
+    }
+    
+    /// Ids of the vertices on a face's loops
+    fn face_vertices(face: &Face) -> Vec<EntityId> {
+        face.loops().iter().flat_map(|lp| lp.coedges()).map(|c| c.start_vertex().id()).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_move_face_snap_tolerance() {
+        // Two boxes side by side, the second's top 0.05 above the first's
+        let mut body = nova_topo::build_cube(2.0).unwrap();
+        let other = body.transformed(&Transform3::from_translation(5.0, 0.0, 0.05));
+        for shell in other.shells() {
+            body.add_shell(shell.clone());
+        }
+        let top = &body.shells()[0].faces()[1];
+        let mut engine = SyncEngine::new();
+        let context = ToleranceContext::default();
+        let top_z = |b: &Body| b.shells()[0].faces()[1].loops()[0].coedges()[0].start_vertex().position().z();
+
+        let loose = MoveOptions { tolerance: Some(0.02), ..Default::default() };
+        let snapped = engine.move_faces(&body, &[top], Vec3::new(0.0, 0.0, 0.04), &loose, &context).unwrap();
+        assert!((top_z(&snapped) - 1.05).abs() < 1e-12);
+
+        let tight = MoveOptions { tolerance: Some(1e-6), ..Default::default() };
+        let unsnapped = engine.move_faces(&body, &[top], Vec3::new(0.0, 0.0, 0.04), &tight, &context).unwrap();
+        assert!((top_z(&unsnapped) - 1.04).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_move_face_resolution_strategies() {
+        let body = welded_box();
+        let top = body.faces().into_iter()
+            .find(|f| f.loops()[0].coedges().iter().all(|c| c.start_vertex().position().z() > 0.5))
+            .unwrap();
+        let corner = top.loops()[0].coedges()[0].start_vertex().position();
+        let mut engine = SyncEngine::new();
+        let context = ToleranceContext::default();
+        let mut face_count = |strategy, offset| {
+            let options = MoveOptions { strategy, ..Default::default() };
+            let moved = engine.move_faces(&body, &[top], offset, &options, &context).unwrap();
+            assert!(nova_topo::validate_body(&moved, &context).unwrap().is_empty());
+            let moved_top = moved.faces().into_iter().find(|f| f.id() == top.id()).unwrap();
+            let top_start = moved_top.loops()[0].coedges()[0].start_vertex().position();
+            assert!((top_start - corner - offset).length() < 1e-12);
+            moved.faces().len()
+        };
+
+        let up = Vec3::new(0.0, 0.0, 0.5);
+        assert_eq!(face_count(ResolutionStrategy::ExtendTrim, up), 6);
+        assert_eq!(face_count(ResolutionStrategy::InsertFaces, up), 10);
+        // The sides contain a vertical move but not a sideways one
+        assert_eq!(face_count(ResolutionStrategy::PreferAnalytic, up), 6);
+        assert_eq!(face_count(ResolutionStrategy::PreferAnalytic, Vec3::new(0.3, 0.0, 0.0)), 10);
+    }
+
+    #[test]
+    fn test_bridge_faces_tessellate() {
+        let body = welded_box();
+        let top = body.faces().into_iter()
+            .find(|f| f.loops()[0].coedges().iter().all(|c| c.start_vertex().position().z() > 0.5))
+            .unwrap();
+        let options = MoveOptions { strategy: ResolutionStrategy::InsertFaces, ..Default::default() };
+        let moved = SyncEngine::new()
+            .move_faces(&body, &[top], Vec3::new(0.0, 0.0, 0.5), &options, &ToleranceContext::default())
+            .unwrap();
+
+        // The four bridges run from z = 1 to 1.5 around the 2 x 2 box
+        let bridges: Vec<&Face> = moved.faces().into_iter()
+            .filter(|f| f.loops()[0].coedges().iter().any(|c| c.start_vertex().position().z() > 1.25))
+            .filter(|f| f.loops()[0].coedges().iter().any(|c| c.start_vertex().position().z() < 1.25))
+            .collect();
+        assert_eq!(bridges.len(), 4);
+        assert!(nova_topo::validate_body(&moved, &ToleranceContext::default()).unwrap().is_empty());
+        let mut area = 0.0;
+        for bridge in bridges {
+            assert!(bridge.surface().is_some());
+            let mesh = nova_tess::tessellate_face(bridge, &nova_tess::TessellationOptions::default()).unwrap();
+            assert!(!mesh.triangles.is_empty());
+            for tri in &mesh.triangles {
+                let [a, b, c] = tri.indices.map(|i| mesh.vertices[i as usize].position);
+                let normal = (b - a).cross(&(c - a));
+                area += normal.length() / 2.0;
+                // Outward, away from the box's axis
+                let centre = Point3::new(0.0, 0.0, a.z());
+                assert!(normal.dot(&(a - centre)) > 0.0);
+            }
+        }
+        assert!((area - 4.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rebuild_skips_suppressed_move() {
+        let body = nova_topo::build_cube(2.0).unwrap();
+        let mut engine = SyncEngine::new();
+        let context = ToleranceContext::default();
+        let options = MoveOptions::default();
+        let face_z = |b: &Body, i: usize| b.shells()[0].faces()[i].loops()[0].coedges()[0].start_vertex().position().z();
+
+        let raised = engine.move_faces(&body, &[&body.shells()[0].faces()[1]], Vec3::new(0.0, 0.0, 0.5), &options, &context).unwrap();
+        let lowered = engine.move_faces(&raised, &[&raised.shells()[0].faces()[0]], Vec3::new(0.0, 0.0, -0.25), &options, &context).unwrap();
+        assert_eq!(engine.history().len(), 2);
+        assert!((face_z(&lowered, 1) - 1.5).abs() < 1e-12);
+
+        engine.set_suppressed(1, true).unwrap();
+        let rebuilt = engine.rebuild(&body).unwrap();
+        assert!((face_z(&rebuilt, 1) - 1.0).abs() < 1e-12);
+        assert!((face_z(&rebuilt, 0) + 1.25).abs() < 1e-12);
+        assert_connected(&rebuilt);
+
+        assert!(engine.set_suppressed(3, true).is_err());
+    }
+
+    /// Unit normal of a face's outer loop, up to sign
+    fn normal(face: &Face) -> Vec3 {
+        let p: Vec<Point3> = face.loops()[0].coedges().iter().map(|c| c.start_vertex().position()).collect();
+        (p[1] - p[0]).cross(&(p[2] - p[0])).normalized()
+    }
+
+    /// Check that every face is a closed planar loop and that vertices
+    /// shared between faces agree on their position
+    fn assert_connected(body: &Body) {
+        let mut positions: std::collections::HashMap<EntityId, Point3> = std::collections::HashMap::new();
+        for face in body.faces() {
+            let coedges = face.loops()[0].coedges();
+            let n = normal(face);
+            for (i, c) in coedges.iter().enumerate() {
+                let next = &coedges[(i + 1) % coedges.len()];
+                assert_eq!(c.end_vertex().id(), next.start_vertex().id());
+                let p = c.start_vertex().position();
+                assert!((p - coedges[0].start_vertex().position()).dot(&n).abs() < 1e-9);
+                let known = *positions.entry(c.start_vertex().id()).or_insert(p);
+                assert!(known.distance_to(&p) < 1e-9);
+            }
+        }
+    }
+
+    /// 2 x 2 x 2 box centred on the origin, welded so it validates
+    fn welded_box() -> Body {
+        use nova_topo::{Coedge, Edge, Loop, Sense, Vertex};
+        use std::sync::Arc;
+
+        let v: Vec<_> = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, -1.0))))
+            .into();
+        let coedges = (0..4)
+            .map(|i| Coedge::new(Arc::new(Edge::new(v[i].clone(), v[(i + 1) % 4].clone())), Sense::Same))
+            .collect();
+        let mut profile = Face::new();
+        profile.add_loop(Loop::from_coedges(coedges));
+        let mut body = nova_topo::EulerAdvanced::extrude_face(&profile, Vec3::Z, 2.0).unwrap();
+        nova_check::weld_vertices(&mut body, 1e-9);
+        body
+    }
+
+    #[test]
+    fn test_transaction_rolls_back() {
+        let mut body = welded_box();
+        let context = ToleranceContext::default();
+        assert!(nova_topo::validate_body(&body, &context).unwrap().is_empty());
+        let top = body.faces().into_iter()
+            .find(|f| f.loops()[0].coedges().iter().all(|c| c.start_vertex().position().z() > 0.5))
+            .unwrap()
+            .id();
+        let positions = |b: &Body| -> Vec<Point3> { b.vertices().iter().map(|v| v.position()).collect() };
+        let before = positions(&body);
+        let mut engine = SyncEngine::new();
+        let up = Vec3::new(0.0, 0.0, 0.5);
+        let stretch = MoveOptions::default();
+
+        // Dropping the top onto the bottom's far corner pinches the box at
+        // that vertex, which fails validation
+        let result = engine.transaction(&mut body, &context, |tx| {
+            tx.move_faces(&[top], up, &stretch)?;
+            tx.move_faces(&[top], Vec3::new(2.0, 2.0, -2.5), &stretch)
+        });
+        assert!(matches!(result, Err(SyncError::WouldInvalidateSolid(_))));
+        assert_eq!(positions(&body), before);
+        assert!(engine.history().is_empty());
+
+        // Offsetting the top through the bottom fails in the middle of the edit
+        let result = engine.transaction(&mut body, &context, |tx| {
+            tx.move_faces(&[top], up, &stretch)?;
+            tx.offset_faces(&[top], -3.0, &OffsetOptions::default())
+        });
+        assert!(matches!(result, Err(SyncError::SelfIntersection { .. })));
+        assert_eq!(positions(&body), before);
+        assert!(engine.history().is_empty());
+
+        engine.transaction(&mut body, &context, |tx| tx.move_faces(&[top], up, &stretch)).unwrap();
+        assert_eq!(engine.history().len(), 1);
+        let top_z = body.vertices().iter().map(|v| v.position().z()).fold(f64::MIN, f64::max);
+        assert!((top_z - 1.5).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_rotate_face_about_edge_with_wheel() {
+        let body = welded_box();
+        let faces = body.faces();
+        let top = faces.iter().find(|f| {
+            f.loops()[0].coedges().iter().all(|c| (c.start_vertex().position().z() - 1.0).abs() < 1e-12)
+        }).unwrap();
+        let back = faces.iter().find(|f| {
+            f.loops()[0].coedges().iter().all(|c| (c.start_vertex().position().y() - 1.0).abs() < 1e-12)
+        }).unwrap();
+        let context = ToleranceContext::default();
+        // Hinge on the top's front edge; drag the ring through 30 degrees
+        let wheel = SteeringWheel::new(Point3::new(0.0, -1.0, 1.0), Vec3::X);
+        let angle = 30f64.to_radians();
+        let from = wheel.origin + Vec3::Z;
+        let to = wheel.origin + Vec3::new(0.0, -angle.sin(), angle.cos());
+        assert!((wheel.ring_angle(from, to) - angle).abs() < 1e-12);
+
+        let find = |b: &Body, id: EntityId| b.faces().into_iter().find(|f| f.id() == id).map(normal).unwrap();
+        let mut engine = SyncEngine::new();
+        let free = RotateOptions { maintain_rules: false, ..Default::default() };
+        let tilted = engine.rotate_with_wheel(&body, &[top], &wheel, from, to, &free, &context).unwrap();
+        assert_connected(&tilted);
+        assert!(nova_topo::validate_body(&tilted, &context).unwrap().is_empty());
+        assert!((find(&tilted, top.id()).dot(&Vec3::Z).abs() - angle.cos()).abs() < 1e-9);
+        assert!((find(&tilted, back.id()).dot(&Vec3::Y).abs() - 1.0).abs() < 1e-9);
+        let hinge = tilted.vertices().into_iter()
+            .filter(|v| v.position().distance_to(&Point3::new(1.0, -1.0, 1.0)) < 1e-9)
+            .count();
+        assert!(hinge > 0);
+
+        let ruled = engine.rotate_with_wheel(&body, &[top], &wheel, from, to, &RotateOptions::default(), &context).unwrap();
+        assert_connected(&ruled);
+        assert!(nova_topo::validate_body(&ruled, &context).unwrap().is_empty());
+        let (top_n, back_n) = (find(&ruled, top.id()), find(&ruled, back.id()));
+        assert!((top_n.dot(&Vec3::Z).abs() - angle.cos()).abs() < 1e-9);
+        assert!(top_n.dot(&back_n).abs() < 1e-9);
+
+        let history = engine.history();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[1].op_type, SyncOpType::FaceRotate);
+        assert_eq!(history[1].affected_faces, vec![top.id()]);
+        assert_eq!(history[1].coupled_faces, vec![back.id()]);
+    }
+
+    #[test]
+    fn test_offset_faces_reports_self_intersection() {
+        let body = welded_box();
+        let context = ToleranceContext::default();
+        let faces = body.faces();
+        let at_z = |z: f64| *faces.iter()
+            .find(|f| f.loops()[0].coedges().iter().all(|c| (c.start_vertex().position().z() - z).abs() < 1e-12))
+            .unwrap();
+        let (top, bottom) = (at_z(1.0), at_z(-1.0));
+        let mut engine = SyncEngine::new();
+        let options = OffsetOptions::default();
+
+        let thinned = engine.offset_faces(&body, &[top], -0.5, &options, &context).unwrap();
+        assert!(nova_topo::validate_body(&thinned, &context).unwrap().is_empty());
+        let top_z = thinned.vertices().iter().map(|v| v.position().z()).fold(f64::MIN, f64::max);
+        assert!((top_z - 0.5).abs() < 1e-12);
+        assert_eq!(engine.history()[0].op_type, SyncOpType::FaceOffset);
+        let rebuilt = engine.rebuild(&body).unwrap();
+        assert_eq!(rebuilt.vertices().iter().map(|v| v.position()).collect::<Vec<_>>(),
+            thinned.vertices().iter().map(|v| v.position()).collect::<Vec<_>>());
+
+        // The box is 2 thick, so a 2.5 offset passes the bottom by 0.5
+        match engine.offset_faces(&body, &[top], -2.5, &options, &context) {
+            Err(SyncError::SelfIntersection { mut faces, overlap }) => {
+                faces.sort();
+                let mut expected = vec![top.id().0, bottom.id().0];
+                expected.sort();
+                assert_eq!(faces, expected);
+                assert!((overlap - 0.5).abs() < 1e-12, "{}", overlap);
+            }
+            other => panic!("expected a self-intersection, got {:?}", other.map(|_| ())),
+        }
+        // Offsetting both sides meets in the middle
+        let both = engine.offset_faces(&body, &[top, bottom], -1.0, &options, &context);
+        assert!(matches!(both, Err(SyncError::SelfIntersection { overlap, .. }) if overlap.abs() < 1e-12));
+        assert_eq!(engine.history().len(), 1);
+
+        let stray = welded_box();
+        let result = engine.offset_faces(&body, &[stray.faces()[0]], 0.5, &options, &context);
+        assert!(matches!(result, Err(SyncError::FaceNotFound(_))));
+    }
+
+    #[test]
+    fn test_grow_box_along_normals() {
+        let body = welded_box();
+        let context = ToleranceContext::default();
+        let faces = body.faces();
+        let mut engine = SyncEngine::new();
+        let d = 0.25;
+
+        let grown = engine.move_faces_along_normals(&body, &faces, d, &MoveOptions::default(), &context).unwrap();
+        assert!(nova_topo::validate_body(&grown, &context).unwrap().is_empty());
+        assert_eq!(grown.faces().len(), 6);
+        let bbox = grown.bounding_box();
+        for axis in 0..3 {
+            assert!((bbox.max[axis] - bbox.min[axis] - (2.0 + 2.0 * d)).abs() < 1e-12);
+            assert!((bbox.center()[axis]).abs() < 1e-12);
+        }
+        let volume = nova_topo::body_volume(&grown).unwrap();
+        assert!((volume - (2.0 + 2.0 * d).powi(3)).abs() < 1e-9);
+
+        let op = &engine.history()[0];
+        assert_eq!(op.op_type, SyncOpType::FaceMove);
+        assert_eq!(op.affected_faces.len(), 6);
+        assert_eq!(engine.rebuild(&body).unwrap().bounding_box(), bbox);
+    }
+
+    #[test]
+    fn test_move_collapses_step_riser() {
+        use nova_topo::{Coedge, Edge, Loop, Sense, Vertex};
+        use std::sync::Arc;
+
+        // L-shaped block: a 3 x 1 base with a 2 x 1 upright on its left
+        let v: Vec<_> = [(0.0, 0.0), (3.0, 0.0), (3.0, 1.0), (2.0, 1.0), (2.0, 2.0), (0.0, 2.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))))
+            .into();
+        let coedges = (0..6)
+            .map(|i| Coedge::new(Arc::new(Edge::new(v[i].clone(), v[(i + 1) % 6].clone())), Sense::Same))
+            .collect();
+        let mut profile = Face::new();
+        profile.add_loop(Loop::from_coedges(coedges));
+        let mut body = nova_topo::EulerAdvanced::extrude_face(&profile, Vec3::Z, 1.0).unwrap();
+        nova_check::weld_vertices(&mut body, 1e-9);
+        let context = ToleranceContext::default();
+        assert!(nova_topo::validate_body(&body, &context).unwrap().is_empty());
+
+        let faces = body.faces();
+        let all = |face: &Face, test: &dyn Fn(Point3) -> bool| {
+            face.loops()[0].coedges().iter().all(|c| test(c.start_vertex().position()))
+        };
+        let step = *faces.iter().find(|f| all(f, &|p| (p.y() - 1.0).abs() < 1e-12)).unwrap();
+        let riser = faces.iter().find(|f| all(f, &|p| (p.x() - 2.0).abs() < 1e-12)).unwrap().id();
+
+        // Raising the step to the upright's top flattens the riser between them
+        let mut engine = SyncEngine::new();
+        let raised = engine.move_faces(&body, &[step], Vec3::new(0.0, 1.0, 0.0), &MoveOptions::default(), &context).unwrap();
+        let issues = nova_topo::validate_body(&raised, &context).unwrap();
+        assert!(issues.is_empty(), "{:?}", issues);
+        assert_eq!(raised.faces().len(), 7);
+        assert_eq!(raised.vertices().len(), 10);
+        assert!(raised.faces().iter().all(|f| f.id() != riser));
+        assert!((nova_topo::body_volume(&raised).unwrap() - 6.0).abs() < 1e-12);
+        assert_eq!(engine.history()[0].collapsed_faces, vec![riser]);
+    }
 }
@@ -0,0 +1,343 @@
+//! Quadric error metric mesh decimation
+
+use crate::{Mesh, Triangle};
+use nova_math::{Point3, Vec3};
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+/// Smallest cosine allowed between a triangle's normal before and after a collapse
+const MIN_NORMAL_COS: f64 = 0.2;
+
+/// Symmetric 4x4 quadric stored as its upper triangle
+#[derive(Debug, Clone, Copy, Default)]
+struct Quadric([f64; 10]);
+
+impl Quadric {
+    /// Squared-distance quadric of the plane through `p` with unit normal `n`
+    fn from_plane(n: &Vec3, p: &Point3) -> Self {
+        let (a, b, c) = (n.x(), n.y(), n.z());
+        let d = -(a * p.x() + b * p.y() + c * p.z());
+        Quadric([
+            a * a, a * b, a * c, a * d,
+            b * b, b * c, b * d,
+            c * c, c * d,
+            d * d,
+        ])
+    }
+
+    fn add(&mut self, other: &Quadric) {
+        for (x, y) in self.0.iter_mut().zip(other.0.iter()) {
+            *x += y;
+        }
+    }
+
+    /// Sum of squared distances from `p` to the accumulated planes
+    fn error(&self, p: &Point3) -> f64 {
+        let q = &self.0;
+        let (x, y, z) = (p.x(), p.y(), p.z());
+        let e = q[0] * x * x + 2.0 * q[1] * x * y + 2.0 * q[2] * x * z + 2.0 * q[3] * x
+            + q[4] * y * y + 2.0 * q[5] * y * z + 2.0 * q[6] * y
+            + q[7] * z * z + 2.0 * q[8] * z
+            + q[9];
+        e.max(0.0)
+    }
+}
+
+/// A pending half-edge collapse of `from` into `to`
+#[derive(Debug, Clone, Copy)]
+struct Collapse {
+    cost: f64,
+    from: usize,
+    to: usize,
+    from_version: u32,
+    to_version: u32,
+}
+
+impl PartialEq for Collapse {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Collapse {}
+
+impl PartialOrd for Collapse {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Collapse {
+    // Reversed so the max-heap pops the cheapest collapse first
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.cost.total_cmp(&self.cost)
+            .then_with(|| other.from.cmp(&self.from))
+            .then_with(|| other.to.cmp(&self.to))
+    }
+}
+
+impl Mesh {
+    /// Reduce the triangle count by quadric-error half-edge collapses
+    ///
+    /// Stops once at most `target_ratio` of the triangles remain or no
+    /// collapse stays within `max_error`. The error of a collapse bounds the
+    /// distance from the surviving vertex to every original triangle plane
+    /// merged into it. Vertices on open boundaries, non-manifold edges and
+    /// face-group boundaries never move, so those edges are preserved.
+    /// Returns the number of triangles removed.
+    pub fn decimate(&mut self, target_ratio: f64, max_error: f64) -> usize {
+        let original = self.triangles.len();
+        let target = (original as f64 * target_ratio.clamp(0.0, 1.0)).ceil() as usize;
+        let n = self.vertices.len();
+
+        let mut quadrics = vec![Quadric::default(); n];
+        let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); n];
+        let mut edge_use: HashMap<(u32, u32), (usize, u32, bool)> = HashMap::new();
+        for (t, tri) in self.triangles.iter().enumerate() {
+            let [a, b, c] = tri.indices.map(|i| self.vertices[i as usize].position);
+            let normal = (b - a).cross(&(c - a));
+            if !normal.is_zero(1e-300) {
+                let q = Quadric::from_plane(&normal.normalized(), &a);
+                for &i in &tri.indices {
+                    quadrics[i as usize].add(&q);
+                }
+            }
+            for k in 0..3 {
+                let (i, j) = (tri.indices[k], tri.indices[(k + 1) % 3]);
+                let entry = edge_use.entry((i.min(j), i.max(j))).or_insert((0, tri.group, false));
+                entry.0 += 1;
+                entry.2 |= entry.1 != tri.group;
+            }
+            for &i in &tri.indices {
+                adjacency[i as usize].push(t);
+            }
+        }
+
+        let mut locked = vec![false; n];
+        for (&(i, j), &(count, _, mixed)) in &edge_use {
+            if count != 2 || mixed {
+                locked[i as usize] = true;
+                locked[j as usize] = true;
+            }
+        }
+
+        let mut alive = vec![true; self.triangles.len()];
+        let mut alive_count = original;
+        let mut version = vec![0u32; n];
+        let mut heap = BinaryHeap::new();
+
+        let neighbours = |adjacency: &[Vec<usize>], triangles: &[Triangle], alive: &[bool], v: usize| {
+            let mut out: Vec<usize> = adjacency[v]
+                .iter()
+                .filter(|&&t| alive[t])
+                .flat_map(|&t| triangles[t].indices.map(|i| i as usize))
+                .filter(|&w| w != v)
+                .collect();
+            out.sort_unstable();
+            out.dedup();
+            out
+        };
+        let push = |heap: &mut BinaryHeap<Collapse>, quadrics: &[Quadric], version: &[u32], from: usize, to: usize, positions: &[Point3]| {
+            let mut q = quadrics[from];
+            q.add(&quadrics[to]);
+            heap.push(Collapse {
+                cost: q.error(&positions[to]),
+                from,
+                to,
+                from_version: version[from],
+                to_version: version[to],
+            });
+        };
+
+        let positions: Vec<Point3> = self.vertices.iter().map(|v| v.position).collect();
+        for v in (0..n).filter(|&v| !locked[v]) {
+            for w in neighbours(&adjacency, &self.triangles, &alive, v) {
+                push(&mut heap, &quadrics, &version, v, w, &positions);
+            }
+        }
+
+        let max_cost = max_error * max_error;
+        while alive_count > target {
+            let Some(c) = heap.pop() else { break };
+            if c.cost > max_cost {
+                break;
+            }
+            let (u, v) = (c.from, c.to);
+            if c.from_version != version[u] || c.to_version != version[v] {
+                continue;
+            }
+
+            // Link condition: u and v share exactly the vertices opposite edge uv
+            let nu = neighbours(&adjacency, &self.triangles, &alive, u);
+            let nv = neighbours(&adjacency, &self.triangles, &alive, v);
+            let shared_tris: Vec<usize> = adjacency[u]
+                .iter()
+                .copied()
+                .filter(|&t| alive[t] && self.triangles[t].indices.contains(&(v as u32)))
+                .collect();
+            let common = nu.iter().filter(|w| nv.binary_search(w).is_ok()).count();
+            if shared_tris.is_empty() || common != shared_tris.len() {
+                continue;
+            }
+
+            // Reject collapses that fold a surviving triangle over
+            let folds = adjacency[u].iter().any(|&t| {
+                if !alive[t] || shared_tris.contains(&t) {
+                    return false;
+                }
+                let tri = &self.triangles[t];
+                let before = tri.indices.map(|i| positions[i as usize]);
+                let after = tri.indices.map(|i| if i as usize == u { positions[v] } else { positions[i as usize] });
+                let n0 = (before[1] - before[0]).cross(&(before[2] - before[0]));
+                let n1 = (after[1] - after[0]).cross(&(after[2] - after[0]));
+                n1.is_zero(1e-300) || n0.normalized().dot(&n1.normalized()) < MIN_NORMAL_COS
+            });
+            if folds {
+                continue;
+            }
+
+            for &t in &shared_tris {
+                alive[t] = false;
+                alive_count -= 1;
+            }
+            let moved: Vec<usize> = adjacency[u].iter().copied().filter(|&t| alive[t]).collect();
+            for t in moved {
+                for i in self.triangles[t].indices.iter_mut() {
+                    if *i as usize == u {
+                        *i = v as u32;
+                    }
+                }
+                adjacency[v].push(t);
+            }
+            adjacency[u].clear();
+            let qu = quadrics[u];
+            quadrics[v].add(&qu);
+            version[u] += 1;
+            version[v] += 1;
+
+            for w in neighbours(&adjacency, &self.triangles, &alive, v) {
+                version[w] += 1;
+                if !locked[v] {
+                    push(&mut heap, &quadrics, &version, v, w, &positions);
+                }
+                if !locked[w] {
+                    push(&mut heap, &quadrics, &version, w, v, &positions);
+                    for x in neighbours(&adjacency, &self.triangles, &alive, w) {
+                        if x != v {
+                            push(&mut heap, &quadrics, &version, w, x, &positions);
+                        }
+                    }
+                }
+            }
+        }
+
+        self.compact(&alive);
+        original - self.triangles.len()
+    }
+
+    /// Drop dead triangles and unreferenced vertices, refreshing normals
+    fn compact(&mut self, alive: &[bool]) {
+        let mut remap = vec![u32::MAX; self.vertices.len()];
+        let mut vertices = Vec::new();
+        let mut triangles = Vec::new();
+        for (t, tri) in self.triangles.iter().enumerate() {
+            if !alive[t] {
+                continue;
+            }
+            let indices = tri.indices.map(|i| {
+                if remap[i as usize] == u32::MAX {
+                    remap[i as usize] = vertices.len() as u32;
+                    vertices.push(self.vertices[i as usize]);
+                }
+                remap[i as usize]
+            });
+            let [a, b, c] = indices.map(|i| vertices[i as usize].position);
+            let normal = (b - a).cross(&(c - a));
+            let normal = if normal.is_zero(1e-300) { tri.normal } else { normal.normalized() };
+            triangles.push(Triangle { indices, normal, group: tri.group });
+        }
+        self.vertices = vertices;
+        self.triangles = triangles;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vertex;
+    use std::f64::consts::PI;
+
+    /// Welded latitude-longitude sphere of radius 1
+    fn sphere(segments: usize, rings: usize) -> Mesh {
+        let mut mesh = Mesh::new();
+        let vertex = |p: Point3| Vertex { position: p, normal: p.to_vector(), uv: (0.0, 0.0) };
+        let north = mesh.add_vertex(vertex(Point3::new(0.0, 0.0, 1.0)));
+        for r in 1..rings {
+            let theta = PI * r as f64 / rings as f64;
+            for s in 0..segments {
+                let phi = 2.0 * PI * s as f64 / segments as f64;
+                mesh.add_vertex(vertex(Point3::new(
+                    theta.sin() * phi.cos(),
+                    theta.sin() * phi.sin(),
+                    theta.cos(),
+                )));
+            }
+        }
+        let south = mesh.add_vertex(vertex(Point3::new(0.0, 0.0, -1.0)));
+
+        let at = |r: usize, s: usize| (1 + (r - 1) * segments + s % segments) as u32;
+        let add = |mesh: &mut Mesh, a: u32, b: u32, c: u32| {
+            let [pa, pb, pc] = [a, b, c].map(|i| mesh.vertices[i as usize].position);
+            let n = (pb - pa).cross(&(pc - pa)).normalized();
+            mesh.add_triangle(a, b, c, n);
+        };
+        for s in 0..segments {
+            add(&mut mesh, north, at(1, s), at(1, s + 1));
+            add(&mut mesh, south, at(rings - 1, s + 1), at(rings - 1, s));
+            for r in 1..rings - 1 {
+                add(&mut mesh, at(r, s), at(r + 1, s), at(r + 1, s + 1));
+                add(&mut mesh, at(r, s), at(r + 1, s + 1), at(r, s + 1));
+            }
+        }
+        mesh
+    }
+
+    #[test]
+    fn test_decimate_sphere() {
+        let mut mesh = sphere(64, 32);
+        let before = mesh.triangles.len();
+        let max_error = 0.05;
+
+        let removed = mesh.decimate(0.25, max_error);
+        assert_eq!(before - removed, mesh.triangles.len());
+        assert!(mesh.triangles.len() <= before / 4 + 1, "{} of {} left", mesh.triangles.len(), before);
+
+        // Vertices stay on the sphere; facet centroids stay close to it
+        for tri in &mesh.triangles {
+            let [a, b, c] = tri.indices.map(|i| mesh.vertices[i as usize].position);
+            let centroid = Point3::ORIGIN + (a.to_vector() + b.to_vector() + c.to_vector()) / 3.0;
+            assert!(1.0 - centroid.to_vector().length() < max_error);
+            assert!(tri.normal.dot(&centroid.to_vector()) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_decimate_keeps_group_boundaries() {
+        let mut mesh = sphere(16, 8);
+        let before_vertices = mesh.vertices.len();
+        for tri in mesh.triangles.iter_mut() {
+            let [a, b, c] = tri.indices.map(|i| mesh.vertices[i as usize].position);
+            tri.group = u32::from(a.z() + b.z() + c.z() > 0.0);
+        }
+        let equator: Vec<Point3> = mesh.vertices.iter()
+            .map(|v| v.position)
+            .filter(|p| p.z().abs() < 1e-12)
+            .collect();
+
+        mesh.decimate(0.1, 1.0);
+        assert!(mesh.vertices.len() < before_vertices);
+        for p in equator {
+            assert!(mesh.vertices.iter().any(|v| v.position == p));
+        }
+    }
+}
@@ -0,0 +1,656 @@
+//! Per-face tessellation
+
+use crate::triangulate::{triangulate_polygon, triangulate_with_points};
+use crate::{Mesh, TessResult, TessellationError, TessellationOptions, Vertex, WELD_TOLERANCE};
+use nova_geom::{CurveType, Surface, UVRange};
+use nova_math::{Point2, Point3, Vec3};
+use nova_topo::{newell_normal, Coedge, Edge, Face};
+use std::collections::{HashMap, HashSet};
+use std::f64::consts::FRAC_PI_2;
+
+/// Upper bound on segments per edge
+const MAX_EDGE_SEGMENTS: usize = 256;
+
+/// Upper bound on normals sampled along a triangle side while refining
+const MAX_SIDE_SAMPLES: usize = 16;
+
+/// Parameter spacing below which surface evaluations are shared
+const EVALUATION_QUANTUM: f64 = 1e-12;
+
+/// Tessellate a single face into a standalone mesh
+///
+/// All loops are sampled to within the chord tolerance and triangulated
+/// together in the surface parameter plane, or in the plane of the outer
+/// loop for faces without a surface, so inner loops come out as holes.
+/// Faces with fewer than three outer boundary points produce an empty mesh.
+pub fn tessellate_face(face: &Face, options: &TessellationOptions) -> TessResult<Mesh> {
+    let mut mesh = Mesh::new();
+    let loops: Vec<Vec<Point3>> = face
+        .loops()
+        .iter()
+        .map(|lp| lp.coedges().iter().flat_map(|coedge| coedge_points(coedge, options)).collect())
+        .collect();
+    match loops.first() {
+        Some(outer) if outer.len() >= 3 => {}
+        _ => return Ok(mesh),
+    }
+
+    let plane_normal = newell_normal(&loops[0]);
+    let surface = face.surface();
+    if surface.is_none() && plane_normal.is_zero(1e-12) {
+        return Err(TessellationError::NumericalError(
+            "face boundary has no area".to_string(),
+        ));
+    }
+    let plane_normal = plane_normal.normalized();
+    let (e1, e2) = plane_basis(&plane_normal);
+
+    let periods = match (surface, face.uv_extent()) {
+        (Some(surface), Some(extent)) => periods(surface.as_ref(), extent),
+        _ => (None, None),
+    };
+    let mut uv_loops: Vec<Vec<Point2>> = Vec::with_capacity(loops.len());
+    for lp in &loops {
+        // Boundary points move onto the surface, as `tessellate_edge_on_face` puts them
+        let mut positions = Vec::with_capacity(lp.len());
+        let mut uvs = Vec::with_capacity(lp.len());
+        for &position in lp {
+            let (position, uv) = match surface {
+                Some(surface) => {
+                    let (u, v, closest) = on_surface(surface.as_ref(), position)?;
+                    (closest, (u, v))
+                }
+                None => {
+                    let d = position.to_vector();
+                    (position, (d.dot(&e1), d.dot(&e2)))
+                }
+            };
+            positions.push(position);
+            uvs.push(uv);
+        }
+        if let Some(surface) = surface {
+            unwrap_seams(&mut uvs, surface.uv_range(), periods);
+        }
+
+        let mut uv_loop = Vec::with_capacity(lp.len());
+        for (&position, &uv) in positions.iter().zip(&uvs) {
+            let normal = if surface.is_some() { face.normal_at(uv.0, uv.1) } else { plane_normal };
+            mesh.add_vertex(Vertex { position, normal, uv });
+            uv_loop.push(Point2::new(uv.0, uv.1));
+        }
+        uv_loops.push(uv_loop);
+    }
+
+    // Triangles come out counter-clockwise in the parameter plane; follow the
+    // outer loop's winding there so the facets face the same way as the loop
+    let reverse = signed_area(&uv_loops[0]) < 0.0;
+    let triangles = match surface {
+        Some(surface) => refine(face, surface.as_ref(), &mut mesh, &uv_loops, periods, options),
+        None => triangulate_polygon(&uv_loops[0], &uv_loops[1..]),
+    };
+    for [a, b, c] in triangles {
+        let [a, b, c] = if reverse { [a, c, b] } else { [a, b, c] };
+        let (pa, pb, pc) = (
+            mesh.vertices[a].position,
+            mesh.vertices[b].position,
+            mesh.vertices[c].position,
+        );
+        let normal = (pb - pa).cross(&(pc - pa));
+        let normal = if normal.is_zero(1e-12) {
+            mesh.vertices[a].normal
+        } else {
+            normal.normalized()
+        };
+        mesh.add_triangle(a as u32, b as u32, c as u32, normal);
+    }
+    if options.weld_seams {
+        mesh = Mesh::welded(&[mesh], WELD_TOLERANCE);
+    }
+
+    Ok(mesh)
+}
+
+/// Triangulate the loops in the parameter plane, adding interior vertices
+/// where a facet strays from the surface by more than the angle tolerance
+///
+/// A facet strays when its normal and the surface normal sampled along its
+/// sides differ by more than the tolerance, so neighbouring facets turn by
+/// about that much at most. Each side is sampled at its middle at least,
+/// and more finely where the principal curvatures at the facet's centroid
+/// allow a turn of a right angle between samples. Each pass splits every
+/// straying facet at the middle of its longest side inside the face, or at
+/// its centroid when that side is on the boundary, which is shared with the
+/// neighbouring faces, and triangulates again. At most
+/// `max_subdivision_depth` passes are made, and facets no longer than the
+/// minimum facet size are left alone. Interior vertices are added to `mesh`
+/// after the boundary vertices, matching the returned indices.
+fn refine(
+    face: &Face,
+    surface: &dyn Surface,
+    mesh: &mut Mesh,
+    uv_loops: &[Vec<Point2>],
+    periods: (Option<f64>, Option<f64>),
+    options: &TessellationOptions,
+) -> Vec<[usize; 3]> {
+    let mut cache = SurfaceCache::new(face, surface, periods, options.cache_evaluations);
+    let mut boundary = HashSet::new();
+    let mut start = 0;
+    for lp in uv_loops {
+        for i in 0..lp.len() {
+            let (a, b) = (start + i, start + (i + 1) % lp.len());
+            boundary.insert((a.min(b), a.max(b)));
+        }
+        start += lp.len();
+    }
+
+    let mut interior: Vec<Point2> = Vec::new();
+    let mut triangles = triangulate_polygon(&uv_loops[0], &uv_loops[1..]);
+    for _ in 0..options.max_subdivision_depth {
+        let mut sides = HashSet::new();
+        let mut centroids = Vec::new();
+        for &tri in &triangles {
+            let (side, length) = (0..3)
+                .map(|k| {
+                    let (a, b) = (tri[k], tri[(k + 1) % 3]);
+                    ((a.min(b), a.max(b)), mesh.vertices[a].position.distance_to(&mesh.vertices[b].position))
+                })
+                .max_by(|x, y| x.1.total_cmp(&y.1))
+                .unwrap();
+            if length <= options.min_facet_size || !strays(&mut cache, &mesh.vertices, tri, options) {
+                continue;
+            }
+            if boundary.contains(&side) {
+                let corners = tri.map(|i| mesh.vertices[i].uv);
+                let centroid = corners.iter().fold((0.0, 0.0), |(u, v), c| (u + c.0 / 3.0, v + c.1 / 3.0));
+                centroids.push(centroid);
+            } else {
+                sides.insert(side);
+            }
+        }
+        if sides.is_empty() && centroids.is_empty() {
+            break;
+        }
+        let mut sides: Vec<(usize, usize)> = sides.into_iter().collect();
+        sides.sort_unstable();
+        let middles = sides.into_iter().map(|(a, b)| along(mesh.vertices[a].uv, mesh.vertices[b].uv, 0.5));
+        let added: Vec<(f64, f64)> = middles.chain(centroids).collect();
+        for &(u, v) in &added {
+            mesh.add_vertex(Vertex { position: cache.point(u, v), normal: cache.normal(u, v), uv: (u, v) });
+        }
+        interior.extend(added.into_iter().map(|(u, v)| Point2::new(u, v)));
+        triangles = triangulate_with_points(&uv_loops[0], &uv_loops[1..], &interior);
+    }
+    triangles
+}
+
+/// Check whether the facet on vertices `tri` strays from the surface by
+/// more than the angle tolerance, as `refine` describes
+fn strays(cache: &mut SurfaceCache<'_>, vertices: &[Vertex], tri: [usize; 3], options: &TessellationOptions) -> bool {
+    let corners = tri.map(|i| &vertices[i]);
+    let [a, b, c] = corners.map(|v| v.position);
+    let facet = (b - a).cross(&(c - a));
+    if facet.is_zero(1e-24) {
+        return false;
+    }
+    let outward = corners[0].normal + corners[1].normal + corners[2].normal;
+    let facet = if facet.dot(&outward) < 0.0 { -facet.normalized() } else { facet.normalized() };
+    let centroid = corners.iter().fold((0.0, 0.0), |(u, v), c| (u + c.uv.0 / 3.0, v + c.uv.1 / 3.0));
+    let (k1, k2) = cache.curvatures(centroid.0, centroid.1);
+    let curvature = k1.abs().max(k2.abs());
+    (0..3).any(|k| {
+        // Each side is sampled from its lower index, so neighbours share samples
+        let (i, j) = (tri[k].min(tri[(k + 1) % 3]), tri[k].max(tri[(k + 1) % 3]));
+        let (p, q) = (&vertices[i], &vertices[j]);
+        let length = p.position.distance_to(&q.position);
+        let samples = ((length * curvature / FRAC_PI_2).ceil() as usize).clamp(2, MAX_SIDE_SAMPLES);
+        facet.angle_to(&p.normal) > options.angle_tolerance
+            || (1..samples).any(|s| {
+                let (u, v) = along(p.uv, q.uv, s as f64 / samples as f64);
+                facet.angle_to(&cache.normal(u, v)) > options.angle_tolerance
+            })
+    })
+}
+
+/// Parameters a fraction `t` of the way from `a` to `b`
+fn along(a: (f64, f64), b: (f64, f64), t: f64) -> (f64, f64) {
+    (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t)
+}
+
+/// Points and normals of a face's surface, each evaluated once per
+/// parameters
+///
+/// Parameters past a seam are wrapped back into the surface's range and
+/// rounded to `EVALUATION_QUANTUM` for the lookup, so the samples sides
+/// share, and those of facets surviving a refinement pass, are evaluated
+/// once. A disabled cache evaluates on every call.
+struct SurfaceCache<'a> {
+    face: &'a Face,
+    surface: &'a dyn Surface,
+    range: UVRange,
+    periods: (Option<f64>, Option<f64>),
+    enabled: bool,
+    points: HashMap<(i64, i64), Point3>,
+    normals: HashMap<(i64, i64), Vec3>,
+}
+
+impl<'a> SurfaceCache<'a> {
+    fn new(face: &'a Face, surface: &'a dyn Surface, periods: (Option<f64>, Option<f64>), enabled: bool) -> Self {
+        Self { face, surface, range: surface.uv_range(), periods, enabled, points: HashMap::new(), normals: HashMap::new() }
+    }
+
+    /// Parameters wrapped into the range across the surface's seams
+    fn wrapped(&self, u: f64, v: f64) -> (f64, f64) {
+        let wrap = |value: f64, start: f64, period: Option<f64>| match period {
+            Some(period) => start + (value - start).rem_euclid(period),
+            None => value,
+        };
+        (wrap(u, self.range.u.start, self.periods.0), wrap(v, self.range.v.start, self.periods.1))
+    }
+
+    fn key(u: f64, v: f64) -> (i64, i64) {
+        ((u / EVALUATION_QUANTUM).round() as i64, (v / EVALUATION_QUANTUM).round() as i64)
+    }
+
+    /// Surface point at (u, v)
+    fn point(&mut self, u: f64, v: f64) -> Point3 {
+        let (u, v) = self.wrapped(u, v);
+        if !self.enabled {
+            return self.surface.evaluate(u, v);
+        }
+        *self.points.entry(Self::key(u, v)).or_insert_with(|| self.surface.evaluate(u, v))
+    }
+
+    /// Face normal at (u, v), following the face's orientation
+    fn normal(&mut self, u: f64, v: f64) -> Vec3 {
+        let (u, v) = self.wrapped(u, v);
+        if !self.enabled {
+            return self.face.normal_at(u, v);
+        }
+        *self.normals.entry(Self::key(u, v)).or_insert_with(|| self.face.normal_at(u, v))
+    }
+
+    /// Principal curvatures at (u, v), not cached
+    fn curvatures(&self, u: f64, v: f64) -> (f64, f64) {
+        let (u, v) = self.wrapped(u, v);
+        self.surface.principal_curvatures(u, v)
+    }
+}
+
+/// Periods in u and v of a surface that closes on itself in that direction
+///
+/// Closure is tested across the face's parameter `extent`, so a surface
+/// unbounded in one direction, like a full cylinder, still closes in the
+/// other.
+fn periods(surface: &dyn Surface, extent: UVRange) -> (Option<f64>, Option<f64>) {
+    let range = surface.uv_range();
+    let finite = |a: f64, b: f64| a.is_finite() && b.is_finite();
+    // Sample away from the edges of the range, where a sphere's poles collapse
+    let across = |start: f64, end: f64| [0.25, 0.5, 0.75].map(|f| start + (end - start) * f);
+    let closes_u = finite(range.u.start, range.u.end) && across(extent.v.start, extent.v.end).iter().all(|&v| {
+        surface.evaluate(range.u.start, v).distance_to(&surface.evaluate(range.u.end, v)) <= WELD_TOLERANCE
+    });
+    let closes_v = finite(range.v.start, range.v.end) && across(extent.u.start, extent.u.end).iter().all(|&u| {
+        surface.evaluate(u, range.v.start).distance_to(&surface.evaluate(u, range.v.end)) <= WELD_TOLERANCE
+    });
+    (
+        closes_u.then_some(range.u.end - range.u.start),
+        closes_v.then_some(range.v.end - range.v.start),
+    )
+}
+
+/// Shift a loop's parameters by whole periods so it runs continuously
+///
+/// A loop crossing the seam of a closed surface jumps by a period where the
+/// surface's parameters wrap; unwrapped, the points on either side of a seam
+/// edge get distinct parameters a period apart. The loop is then moved back
+/// by whole periods to start within `range`.
+fn unwrap_seams(uvs: &mut [(f64, f64)], range: UVRange, (u_period, v_period): (Option<f64>, Option<f64>)) {
+    if let Some(period) = u_period {
+        let unwrapped = unwrap(uvs.iter().map(|uv| uv.0).collect(), period, range.u.start);
+        uvs.iter_mut().zip(unwrapped).for_each(|(uv, u)| uv.0 = u);
+    }
+    if let Some(period) = v_period {
+        let unwrapped = unwrap(uvs.iter().map(|uv| uv.1).collect(), period, range.v.start);
+        uvs.iter_mut().zip(unwrapped).for_each(|(uv, v)| uv.1 = v);
+    }
+}
+
+/// One parameter of a loop made continuous modulo `period`, lowest value
+/// within a period above `start`
+fn unwrap(mut values: Vec<f64>, period: f64, start: f64) -> Vec<f64> {
+    for i in 1..values.len() {
+        let previous = values[i - 1];
+        values[i] -= period * ((values[i] - previous) / period).round();
+    }
+    let lowest = values.iter().copied().fold(f64::INFINITY, f64::min);
+    // Values a rounding error below the start stay where they are
+    let shift = period * ((lowest - start) / period + 1e-9).floor();
+    values.iter_mut().for_each(|value| *value -= shift);
+    values
+}
+
+/// Orthonormal in-plane axes with `e1 x e2 = normal`
+fn plane_basis(normal: &Vec3) -> (Vec3, Vec3) {
+    let helper = if normal.x().abs() < 0.9 { Vec3::X } else { Vec3::Y };
+    let e1 = helper.cross(normal).normalized();
+    let e2 = normal.cross(&e1);
+    (e1, e2)
+}
+
+/// Twice the signed area of a closed polygon
+fn signed_area(points: &[Point2]) -> f64 {
+    (0..points.len())
+        .map(|i| {
+            let (p, q) = (points[i], points[(i + 1) % points.len()]);
+            p.x() * q.y() - q.x() * p.y()
+        })
+        .sum()
+}
+
+/// Points along an edge of `face` from its start to its end vertex, lying
+/// on the face's surface
+///
+/// The edge is sampled as [`tessellate_face`] samples it and each point is
+/// moved to its closest point on the surface, so the points are the face
+/// mesh's own boundary vertices. A wireframe drawn through them stays on
+/// the facets even where the edge's curve strays off a curved surface.
+/// Faces without a surface give the points of the edge's curve.
+pub fn tessellate_edge_on_face(edge: &Edge, face: &Face, options: &TessellationOptions) -> TessResult<Vec<Point3>> {
+    let points = edge_points(edge, options);
+    match face.surface() {
+        Some(surface) => points.into_iter().map(|p| Ok(on_surface(surface.as_ref(), p)?.2)).collect(),
+        None => Ok(points),
+    }
+}
+
+/// Parameters of the closest point on `surface` to `point`, and that point
+fn on_surface(surface: &dyn Surface, point: Point3) -> TessResult<(f64, f64, Point3)> {
+    let (u, v, closest, _) = surface
+        .closest_point(&point)
+        .map_err(|e| TessellationError::NumericalError(e.to_string()))?;
+    Ok((u, v, closest))
+}
+
+/// Points along a coedge in loop direction, excluding its end point
+fn coedge_points(coedge: &Coedge, options: &TessellationOptions) -> Vec<Point3> {
+    let mut points = edge_points(coedge.edge(), options);
+    if coedge.sense().is_opposite() {
+        points.reverse();
+    }
+    points.pop();
+    points
+}
+
+/// Points along an edge from its start to its end vertex, within the
+/// chord tolerance
+pub(crate) fn edge_points(edge: &Edge, options: &TessellationOptions) -> Vec<Point3> {
+    match edge.curve() {
+        Some(curve) if curve.curve_type() != CurveType::Line => {
+            let range = curve.param_range();
+            let segments = curve_segments(curve.as_ref(), options);
+            (0..=segments)
+                .map(|i| curve.evaluate(range.start + (range.end - range.start) * i as f64 / segments as f64))
+                .collect()
+        }
+        _ => vec![edge.start_vertex().position(), edge.end_vertex().position()],
+    }
+}
+
+/// Number of chords needed to keep the sagitta under the chord tolerance
+/// and the turn between neighbouring chords under the angle tolerance
+fn curve_segments(curve: &dyn nova_geom::Curve, options: &TessellationOptions) -> usize {
+    let range = curve.param_range();
+    let length = curve.arc_length(range.end);
+    let curvature = (0..=4)
+        .map(|i| curve.curvature(range.start + (range.end - range.start) * i as f64 / 4.0))
+        .fold(0.0, f64::max);
+
+    // Sagitta of a chord of length s on radius r is about s^2 / (8 r)
+    let mut max_chord = options.max_facet_size;
+    if curvature > 0.0 {
+        max_chord = max_chord.min((8.0 * options.chord_tolerance / curvature).sqrt());
+    }
+    max_chord = max_chord.max(options.min_facet_size);
+
+    // Neighbouring chords turn by about the angle each one spans, s * k
+    let turning = ((length * curvature / options.angle_tolerance).ceil() as usize).max(1);
+    ((length / max_chord).ceil() as usize).max(turning).clamp(1, MAX_EDGE_SEGMENTS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use nova_geom::{CircularArc, Curve, PlanarSurface};
+    use nova_topo::{Edge, Loop, Sense};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_tessellate_square_face() {
+        let corners = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ];
+        let vertices: Vec<_> = corners.iter().map(|&p| Arc::new(nova_topo::Vertex::new(p))).collect();
+        let coedges = (0..4)
+            .map(|i| {
+                let edge = Edge::new(vertices[i].clone(), vertices[(i + 1) % 4].clone());
+                Coedge::new(Arc::new(edge), Sense::Same)
+            })
+            .collect();
+        let mut face = Face::new();
+        face.add_loop(Loop::from_coedges(coedges));
+
+        let mesh = tessellate_face(&face, &TessellationOptions::default()).unwrap();
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.triangles.len(), 2);
+        assert!((mesh.triangles[0].normal.z() - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_annulus_leaves_hole_empty() {
+        let circle_edge = |radius: f64| {
+            let circle = CircularArc::circle(Point3::ORIGIN, radius, Vec3::Z).unwrap();
+            let v = Arc::new(nova_topo::Vertex::new(circle.start_point()));
+            Arc::new(Edge::with_curve(v.clone(), v, Arc::new(circle)))
+        };
+        let mut face = Face::with_surface(Arc::new(
+            PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap(),
+        ));
+        face.add_loop(Loop::from_coedges(vec![Coedge::new(circle_edge(2.0), Sense::Same)]));
+        face.add_loop(Loop::from_coedges(vec![Coedge::new(circle_edge(1.0), Sense::Opposite)]));
+
+        let options = TessellationOptions { chord_tolerance: 1e-4, ..Default::default() };
+        let mesh = tessellate_face(&face, &options).unwrap();
+
+        let mut area = 0.0;
+        for tri in &mesh.triangles {
+            let [a, b, c] = tri.indices.map(|i| mesh.vertices[i as usize].position);
+            let centroid = Point3::new(
+                (a.x() + b.x() + c.x()) / 3.0,
+                (a.y() + b.y() + c.y()) / 3.0,
+                0.0,
+            );
+            assert!(centroid.distance_to(&Point3::ORIGIN) > 1.0);
+            let n = (b - a).cross(&(c - a));
+            assert!(n.z() > 0.0);
+            area += n.length() / 2.0;
+        }
+        assert!((area - 3.0 * std::f64::consts::PI).abs() < 5e-3);
+    }
+
+    #[test]
+    fn test_unbounded_surfaces_clamped_to_loops() {
+        // A square on an infinite plane, tilted so the plane's parameters aren't the world axes
+        let plane = PlanarSurface::new(Point3::new(5.0, 5.0, 5.0), Vec3::new(1.0, 1.0, 0.0), Vec3::Z).unwrap();
+        let corners = [(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)].map(|(u, v)| plane.evaluate(u, v));
+        let vertices: Vec<_> = corners.iter().map(|&p| Arc::new(nova_topo::Vertex::new(p))).collect();
+        let mut face = Face::with_surface(Arc::new(plane));
+        face.add_loop(Loop::from_coedges(
+            (0..4).map(|i| Coedge::new(Arc::new(Edge::new(vertices[i].clone(), vertices[(i + 1) % 4].clone())), Sense::Same)).collect(),
+        ));
+
+        let extent = face.uv_extent().unwrap();
+        let bounds = [extent.u.start, extent.u.end, extent.v.start, extent.v.end];
+        assert!(bounds.iter().zip([0.0, 2.0, 0.0, 1.0]).all(|(a, b)| (a - b).abs() < 1e-9), "{:?}", bounds);
+        let bbox = face.bounding_box();
+        let mut corner_box = nova_math::BoundingBox3::empty();
+        corners.iter().for_each(|p| corner_box.expand(p));
+        assert!(bbox.approx_eq(&corner_box, 1e-9));
+        let mesh = tessellate_face(&face, &TessellationOptions::default()).unwrap();
+        assert!(mesh.vertices.iter().all(|v| corner_box.contains(&v.position)));
+
+        // A tube swept endlessly from a circle still joins up across its seam
+        let tube = nova_geom::ExtrusionSurface::new(Box::new(CircularArc::circle(Point3::ORIGIN, 1.0, Vec3::Z).unwrap()), Vec3::Z).unwrap();
+        let circle = |z: f64| CircularArc::circle(Point3::new(0.0, 0.0, z), 1.0, Vec3::Z).unwrap();
+        let (bottom, top) = (Arc::new(nova_topo::Vertex::new(circle(0.0).start_point())), Arc::new(nova_topo::Vertex::new(circle(2.0).start_point())));
+        let seam = Arc::new(Edge::with_curve(bottom.clone(), top.clone(), Arc::new(nova_geom::Line::segment(bottom.position(), top.position()).unwrap())));
+        let mut side = Face::with_surface(Arc::new(tube));
+        side.add_loop(Loop::from_coedges(vec![
+            Coedge::new(Arc::new(Edge::with_curve(bottom.clone(), bottom, Arc::new(circle(0.0)))), Sense::Same),
+            Coedge::new(seam.clone(), Sense::Same),
+            Coedge::new(Arc::new(Edge::with_curve(top.clone(), top, Arc::new(circle(2.0)))), Sense::Opposite),
+            Coedge::new(seam, Sense::Opposite),
+        ]));
+        let bbox = side.bounding_box();
+        assert!(bbox.approx_eq(&nova_math::BoundingBox3::new(Point3::new(-1.0, -1.0, 0.0), Point3::new(1.0, 1.0, 2.0)), 1e-2));
+        let options = TessellationOptions { chord_tolerance: 1e-4, ..Default::default() };
+        let mesh = tessellate_face(&side, &options).unwrap();
+        let area: f64 = mesh.triangles.iter().map(|tri| {
+            let [a, b, c] = tri.indices.map(|i| mesh.vertices[i as usize].position);
+            (b - a).cross(&(c - a)).length() / 2.0
+        }).sum();
+        assert!((area - 4.0 * std::f64::consts::PI).abs() < 1e-2, "{}", area);
+    }
+
+    #[test]
+    fn test_edge_on_cylinder_lies_on_facets() {
+        // Side of a cylinder whose bottom circle was written slightly too
+        // wide, as an imported edge within tolerance of its surface can be
+        let cylinder = nova_geom::CylindricalSurface::bounded(Point3::ORIGIN, Vec3::Z, 1.0, Vec3::X, 0.0, 2.0, 0.0, std::f64::consts::TAU).unwrap();
+        let (bottom, top) = (Arc::new(nova_topo::Vertex::new(Point3::new(1.0, 0.0, 0.0))), Arc::new(nova_topo::Vertex::new(Point3::new(1.0, 0.0, 2.0))));
+        let circle = |radius: f64, z: f64| Arc::new(CircularArc::circle(Point3::new(0.0, 0.0, z), radius, Vec3::Z).unwrap());
+        let base = Arc::new(Edge::with_curve(bottom.clone(), bottom.clone(), circle(1.0005, 0.0)));
+        let seam = Arc::new(Edge::with_curve(bottom.clone(), top.clone(), Arc::new(nova_geom::Line::segment(bottom.position(), top.position()).unwrap())));
+        let mut face = Face::with_surface(Arc::new(cylinder));
+        face.add_loop(Loop::from_coedges(vec![
+            Coedge::new(base.clone(), Sense::Same),
+            Coedge::new(seam.clone(), Sense::Same),
+            Coedge::new(Arc::new(Edge::with_curve(top.clone(), top, circle(1.0, 2.0))), Sense::Opposite),
+            Coedge::new(seam, Sense::Opposite),
+        ]));
+
+        let options = TessellationOptions { chord_tolerance: 1e-3, ..Default::default() };
+        let mesh = tessellate_face(&face, &options).unwrap();
+        let points = tessellate_edge_on_face(&base, &face, &options).unwrap();
+        let raw = edge_points(&base, &options);
+        assert_eq!(points.len(), raw.len());
+        assert!(raw.iter().all(|p| (p.x().hypot(p.y()) - 1.0005).abs() < 1e-12));
+        for p in &points {
+            assert!((p.x().hypot(p.y()) - 1.0).abs() < 1e-12 && p.z().abs() < 1e-12);
+            let nearest = mesh.vertices.iter().map(|v| v.position.distance_to(p)).fold(f64::INFINITY, f64::min);
+            assert!(nearest < 1e-12, "{:?} is {} from the mesh", p, nearest);
+        }
+
+        // Without a surface the edge keeps its own points
+        let mut bare = Face::new();
+        bare.add_loop(face.loops()[0].clone());
+        assert_eq!(tessellate_edge_on_face(&base, &bare, &options).unwrap(), raw);
+    }
+
+    /// NURBS surface counting the points and normals evaluated on it
+    struct Counted {
+        inner: nova_geom::nurbs::NurbsSurface,
+        calls: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl Counted {
+        fn count(&self) {
+            self.calls.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        }
+    }
+
+    impl Surface for Counted {
+        fn evaluate(&self, u: f64, v: f64) -> Point3 {
+            self.count();
+            self.inner.evaluate(u, v)
+        }
+        fn derivatives(&self, u: f64, v: f64) -> (Vec3, Vec3) {
+            self.inner.derivatives(u, v)
+        }
+        fn normal(&self, u: f64, v: f64) -> Vec3 {
+            self.count();
+            self.inner.normal(u, v)
+        }
+        fn principal_curvatures(&self, u: f64, v: f64) -> (f64, f64) {
+            self.inner.principal_curvatures(u, v)
+        }
+        fn uv_range(&self) -> UVRange {
+            self.inner.uv_range()
+        }
+        fn u_isocurve(&self, u: f64) -> Option<Box<dyn nova_geom::Curve>> {
+            self.inner.u_isocurve(u)
+        }
+        fn v_isocurve(&self, v: f64) -> Option<Box<dyn nova_geom::Curve>> {
+            self.inner.v_isocurve(v)
+        }
+        fn closest_point(&self, point: &Point3) -> nova_geom::GeomResult<(f64, f64, Point3, f64)> {
+            self.inner.closest_point(point)
+        }
+        fn transform(&mut self, transform: &nova_math::Transform3) {
+            self.inner.transform(transform)
+        }
+        fn scale(&mut self, factor: f64) {
+            self.inner.scale(factor)
+        }
+        fn surface_type(&self) -> nova_geom::SurfaceType {
+            self.inner.surface_type()
+        }
+        fn clone_box(&self) -> Box<dyn Surface> {
+            Box::new(Counted { inner: self.inner.clone(), calls: self.calls.clone() })
+        }
+        fn as_any(&self) -> &dyn std::any::Any {
+            self
+        }
+    }
+
+    #[test]
+    fn test_cached_refinement_matches_uncached() {
+        // Biquadratic dome over [-1, 1]^2 with straight, flat borders
+        let row = |y: f64, z: f64| vec![
+            nova_math::Point4::new(-1.0, y, 0.0, 1.0),
+            nova_math::Point4::new(0.0, y, z, 1.0),
+            nova_math::Point4::new(1.0, y, 0.0, 1.0),
+        ];
+        let knots = vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0];
+        let dome = nova_geom::nurbs::NurbsSurface::new(2, 2, vec![row(-1.0, 0.0), row(0.0, 2.0), row(1.0, 0.0)], knots.clone(), knots).unwrap();
+
+        let calls = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let mut face = Face::with_surface(Arc::new(Counted { inner: dome, calls: calls.clone() }));
+        let corners = [(-1.0, -1.0), (1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)];
+        let vertices: Vec<_> = corners.iter().map(|&(x, y)| Arc::new(nova_topo::Vertex::new(Point3::new(x, y, 0.0)))).collect();
+        let coedges = (0..4)
+            .map(|i| Coedge::new(Arc::new(Edge::new(vertices[i].clone(), vertices[(i + 1) % 4].clone())), Sense::Same))
+            .collect();
+        face.add_loop(Loop::from_coedges(coedges));
+
+        let tessellate = |cache_evaluations: bool| {
+            calls.store(0, std::sync::atomic::Ordering::Relaxed);
+            let options = TessellationOptions { cache_evaluations, max_subdivision_depth: 8, ..TessellationOptions::with_angle_degrees(10.0) };
+            let mesh = tessellate_face(&face, &options).unwrap();
+            (mesh, calls.load(std::sync::atomic::Ordering::Relaxed))
+        };
+        let (cached, cached_calls) = tessellate(true);
+        let (uncached, uncached_calls) = tessellate(false);
+        assert!(cached.vertices.len() > 100, "{}", cached.vertices.len());
+        assert!(2 * cached_calls < uncached_calls, "{} vs {}", cached_calls, uncached_calls);
+
+        assert_eq!(cached.vertices.len(), uncached.vertices.len());
+        for (a, b) in cached.vertices.iter().zip(&uncached.vertices) {
+            assert!(a.position == b.position && a.normal == b.normal && a.uv == b.uv);
+        }
+        assert_eq!(cached.triangles.len(), uncached.triangles.len());
+        assert!(cached.triangles.iter().zip(&uncached.triangles).all(|(a, b)| a.indices == b.indices));
+    }
+}
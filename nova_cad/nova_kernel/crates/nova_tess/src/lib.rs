@@ -5,8 +5,29 @@
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
-use nova_math::{Point3, Vec3};
+mod decimate;
+mod face;
+mod orient;
+mod silhouette;
+mod triangulate;
+
+pub use face::{tessellate_edge_on_face, tessellate_face};
+pub use silhouette::{hidden_line_view, silhouette, DrawingView};
+pub use triangulate::{triangulate_polygon, triangulate_with_points};
+
+use nova_math::{Point3, SpatialHashGrid, Transform3, Vec3};
 use nova_topo::Body;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+/// Distance within which vertices of neighbouring faces are welded
+const WELD_TOLERANCE: f64 = 1e-9;
+
+/// Upper bound on coarsening passes when fitting a triangle budget
+const MAX_BUDGET_PASSES: usize = 32;
 
 /// Mesh vertex for tessellation
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -26,6 +47,8 @@ pub struct Triangle {
     pub indices: [u32; 3],
     /// Normal
     pub normal: Vec3,
+    /// Face group, the index of the B-Rep face this triangle came from
+    pub group: u32,
 }
 
 /// Tessellated mesh
@@ -55,6 +78,7 @@ impl Mesh {
         self.triangles.push(Triangle {
             indices: [i0, i1, i2],
             normal,
+            group: 0,
         });
     }
     
@@ -63,6 +87,66 @@ impl Mesh {
         self.vertices.clear();
         self.triangles.clear();
     }
+    
+    /// Move all vertices and normals by `transform`
+    pub fn transform(&mut self, transform: &Transform3) {
+        let mut positions: Vec<Point3> = self.vertices.iter().map(|v| v.position).collect();
+        let mut normals: Vec<Vec3> = self.vertices.iter().map(|v| v.normal).collect();
+        transform.apply_to_points(&mut positions);
+        transform.apply_to_vectors(&mut normals);
+        for ((v, position), normal) in self.vertices.iter_mut().zip(positions).zip(normals) {
+            v.position = position;
+            v.normal = normal;
+        }
+
+        let mut normals: Vec<Vec3> = self.triangles.iter().map(|t| t.normal).collect();
+        transform.apply_to_vectors(&mut normals);
+        for (t, normal) in self.triangles.iter_mut().zip(normals) {
+            t.normal = normal;
+        }
+    }
+    
+    /// Merge meshes in order, welding vertices that coincide within `tolerance`
+    ///
+    /// The first occurrence of a welded vertex is kept, so the result only
+    /// depends on the order of `meshes`. Triangles are grouped by the index of
+    /// the mesh they came from.
+    pub fn welded(meshes: &[Mesh], tolerance: f64) -> Mesh {
+        Self::weld(meshes, tolerance, true)
+    }
+
+    /// Merge meshes, welding vertices of the same mesh together only if
+    /// `within`, so that with it off each mesh keeps its seams
+    fn weld(meshes: &[Mesh], tolerance: f64, within: bool) -> Mesh {
+        let mut mesh = Mesh::new();
+        let mut grid: SpatialHashGrid<u32> = SpatialHashGrid::new(tolerance);
+
+        for (group, part) in meshes.iter().enumerate() {
+            let mut claimed: HashSet<u32> = HashSet::new();
+            let remap: Vec<u32> = part
+                .vertices
+                .iter()
+                .map(|v| {
+                    let existing = grid.query_near(v.position).into_iter().copied().find(|index| within || !claimed.contains(index));
+                    let index = existing.unwrap_or_else(|| {
+                        let index = mesh.add_vertex(*v);
+                        grid.insert(v.position, index);
+                        index
+                    });
+                    claimed.insert(index);
+                    index
+                })
+                .collect();
+            for tri in &part.triangles {
+                let [a, b, c] = tri.indices.map(|i| remap[i as usize]);
+                // Welding can collapse slivers thinner than the tolerance
+                if a != b && b != c && c != a {
+                    mesh.triangles.push(Triangle { indices: [a, b, c], normal: tri.normal, group: group as u32 });
+                }
+            }
+        }
+        mesh
+    }
 }
 
 /// Tessellation options
@@ -70,12 +154,25 @@ impl Mesh {
 pub struct TessellationOptions {
     /// Chord tolerance (maximum distance from curve to facet)
     pub chord_tolerance: f64,
-    /// Angle tolerance (maximum angle between adjacent facets)
+    /// Angle tolerance in radians (maximum angle between adjacent facets),
+    /// within (0, pi]
     pub angle_tolerance: f64,
     /// Minimum facet size
     pub min_facet_size: f64,
     /// Maximum facet size
     pub max_facet_size: f64,
+    /// Triangle budget; the chord tolerance is coarsened, then subdivision
+    /// cut back, until it is met
+    pub max_triangles: Option<usize>,
+    /// Share vertices along the seams of closed surfaces, for solids; when
+    /// off, each face keeps its own vertices with distinct UVs, for texturing
+    pub weld_seams: bool,
+    /// Most passes adding interior points where a curved face's facets turn
+    /// by more than the angle tolerance; 0 keeps only boundary points
+    pub max_subdivision_depth: usize,
+    /// Evaluate each surface point and normal once per face while refining;
+    /// off, every lookup evaluates the surface again, with the same result
+    pub cache_evaluations: bool,
 }
 
 impl Default for TessellationOptions {
@@ -85,7 +182,31 @@ impl Default for TessellationOptions {
             angle_tolerance: 15.0_f64.to_radians(),
             min_facet_size: 0.001,
             max_facet_size: 100.0,
+            max_triangles: None,
+            weld_seams: true,
+            max_subdivision_depth: 4,
+            cache_evaluations: true,
+        }
+    }
+}
+
+impl TessellationOptions {
+    /// Default options with an angle tolerance given in degrees
+    pub fn with_angle_degrees(degrees: f64) -> Self {
+        Self { angle_tolerance: degrees.to_radians(), ..Self::default() }
+    }
+    
+    /// Check the options before tessellating
+    ///
+    /// The angle tolerance must lie in (0, pi]; a larger value is most
+    /// likely degrees passed where radians are expected.
+    pub fn validate(&self) -> TessResult<()> {
+        if !(self.angle_tolerance > 0.0 && self.angle_tolerance <= std::f64::consts::PI) {
+            return Err(TessellationError::InvalidOptions(format!(
+                "Angle tolerance {} is outside (0, pi] radians", self.angle_tolerance
+            )));
         }
+        Ok(())
     }
 }
 
@@ -101,45 +222,170 @@ pub enum TessellationError {
     /// Unsupported geometry
     #[error("Unsupported geometry type")]
     UnsupportedGeometry,
+    /// Cancelled by the progress callback
+    #[error("Tessellation cancelled")]
+    Cancelled,
+    /// Options out of range
+    #[error("Invalid tessellation options: {0}")]
+    InvalidOptions(String),
 }
 
 /// Result type for tessellation operations
 pub type TessResult<T> = Result<T, TessellationError>;
 
+/// Tolerance and size of a finished tessellation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TessellationReport {
+    /// Chord tolerance actually used
+    pub chord_tolerance: f64,
+    /// Number of triangles produced
+    pub triangle_count: usize,
+    /// Whether the triangle budget, if any, was met
+    pub within_budget: bool,
+}
+
+/// Progress callback, given the fraction of faces done; returns false to cancel
+pub type ProgressCallback = Box<dyn Fn(f64) -> bool + Send + Sync>;
+
 /// Tessellator for B-Rep bodies
 pub struct Tessellator {
     options: TessellationOptions,
+    progress: Option<ProgressCallback>,
 }
 
 impl Tessellator {
     /// Create a new tessellator with default options
     pub fn new() -> Self {
-        Self {
-            options: TessellationOptions::default(),
-        }
+        Self::with_options(TessellationOptions::default())
     }
     
     /// Create a new tessellator with custom options
     pub fn with_options(options: TessellationOptions) -> Self {
-        Self { options }
+        Self { options, progress: None }
+    }
+    
+    /// Report progress after each face, cancelling when the callback returns false
+    ///
+    /// With the `parallel` feature the callback may run on any pool thread.
+    pub fn with_progress<F>(mut self, progress: F) -> Self
+    where
+        F: Fn(f64) -> bool + Send + Sync + 'static,
+    {
+        self.progress = Some(Box::new(progress));
+        self
     }
     
     /// Tessellate a body into a mesh
+    ///
+    /// With the `parallel` feature faces are tessellated on the rayon pool.
+    /// The welded result is identical to [`Tessellator::tessellate_serial`].
     pub fn tessellate(&self, body: &Body) -> TessResult<Mesh> {
-        // TODO: Implement actual tessellation
-        // For now, return an empty mesh
-        Ok(Mesh::new())
+        Ok(self.tessellate_with_report(body)?.0)
     }
     
-    /// Tessellate with custom options
+    /// Tessellate a body and report the chord tolerance actually used
+    ///
+    /// When `max_triangles` is set the chord tolerance is doubled until the
+    /// mesh fits the budget or cannot get any coarser, so the progress
+    /// callback sees one sweep over the faces per attempt. Options that do
+    /// not validate fail with [`TessellationError::InvalidOptions`].
+    pub fn tessellate_with_report(&self, body: &Body) -> TessResult<(Mesh, TessellationReport)> {
+        self.within_budget(body, self.options, |options| self.tessellate_pass(body, options))
+    }
+    
+    /// Tessellate a body one face at a time on the calling thread
+    pub fn tessellate_serial(&self, body: &Body) -> TessResult<Mesh> {
+        let (mesh, _) = self.within_budget(body, self.options, |options| self.tessellate_pass_serial(body, options))?;
+        Ok(mesh)
+    }
+    
+    /// Run `pass` from `options` with coarser chord tolerances, then fewer
+    /// subdivision passes, until the triangle budget is met
+    fn within_budget<F>(&self, body: &Body, mut options: TessellationOptions, pass: F) -> TessResult<(Mesh, TessellationReport)>
+    where
+        F: Fn(&TessellationOptions) -> TessResult<Mesh>,
+    {
+        options.validate()?;
+        let mut mesh = pass(&options)?;
+        if let Some(max) = options.max_triangles {
+            // Beyond the body's size a coarser chord tolerance changes nothing
+            let limit = body.bounding_box().diagonal();
+            let mut passes = 1;
+            while mesh.triangles.len() > max && passes < MAX_BUDGET_PASSES {
+                if options.chord_tolerance < limit {
+                    options.chord_tolerance *= 2.0;
+                } else if options.max_subdivision_depth > 0 {
+                    options.max_subdivision_depth -= 1;
+                } else {
+                    break;
+                }
+                mesh = pass(&options)?;
+                passes += 1;
+            }
+        }
+        let report = TessellationReport {
+            chord_tolerance: options.chord_tolerance,
+            triangle_count: mesh.triangles.len(),
+            within_budget: options.max_triangles.map_or(true, |max| mesh.triangles.len() <= max),
+        };
+        Ok((mesh, report))
+    }
+    
+    /// One sweep over the faces, on the rayon pool
+    #[cfg(feature = "parallel")]
+    fn tessellate_pass(&self, body: &Body, options: &TessellationOptions) -> TessResult<Mesh> {
+        let faces = body.faces();
+        let done = AtomicUsize::new(0);
+        let parts = faces
+            .par_iter()
+            .map(|face| self.tessellate_reporting(face, options, &done, faces.len()))
+            .collect::<TessResult<Vec<_>>>()?;
+        Ok(merge_faces(&parts, options))
+    }
+    
+    /// One sweep over the faces
+    #[cfg(not(feature = "parallel"))]
+    fn tessellate_pass(&self, body: &Body, options: &TessellationOptions) -> TessResult<Mesh> {
+        self.tessellate_pass_serial(body, options)
+    }
+    
+    /// One sweep over the faces on the calling thread
+    fn tessellate_pass_serial(&self, body: &Body, options: &TessellationOptions) -> TessResult<Mesh> {
+        let faces = body.faces();
+        let done = AtomicUsize::new(0);
+        let parts = faces
+            .iter()
+            .map(|face| self.tessellate_reporting(face, options, &done, faces.len()))
+            .collect::<TessResult<Vec<_>>>()?;
+        Ok(merge_faces(&parts, options))
+    }
+    
+    /// Tessellate one face and report progress over `total` faces
+    fn tessellate_reporting(
+        &self,
+        face: &nova_topo::Face,
+        options: &TessellationOptions,
+        done: &AtomicUsize,
+        total: usize,
+    ) -> TessResult<Mesh> {
+        let mesh = tessellate_face(face, options)?;
+        if let Some(progress) = &self.progress {
+            let count = done.fetch_add(1, Ordering::SeqCst) + 1;
+            if !progress(count as f64 / total as f64) {
+                return Err(TessellationError::Cancelled);
+            }
+        }
+        Ok(mesh)
+    }
+    
+    /// Tessellate with custom options in place of the tessellator's own
     pub fn tessellate_with_options(
         &self,
         body: &Body,
         options: TessellationOptions,
     ) -> TessResult<Mesh> {
-        let temp_options = self.options;
-        let result = self.tessellate(body);
-        result
+        let (mesh, _) = self.within_budget(body, options, |options| self.tessellate_pass(body, options))?;
+        Ok(mesh)
     }
 }
 
@@ -149,6 +395,12 @@ impl Default for Tessellator {
     }
 }
 
+/// Join per-face meshes into one body mesh, sharing vertices between
+/// faces and, if the options weld seams, within them
+fn merge_faces(parts: &[Mesh], options: &TessellationOptions) -> Mesh {
+    Mesh::weld(parts, WELD_TOLERANCE, options.weld_seams)
+}
+
 /// Tessellate a body with default options
 pub fn tessellate_body(body: &Body) -> TessResult<Mesh> {
     let tessellator = Tessellator::new();
@@ -201,4 +453,354 @@ mod tests {
         let tess = Tessellator::new();
         let _mesh = tess.tessellate(&Body::new());
     }
+
+    #[test]
+    fn test_welded_shares_vertices() {
+        let v = |x, y| Vertex { position: Point3::new(x, y, 0.0), normal: Vec3::Z, uv: (0.0, 0.0) };
+        let mut a = Mesh::new();
+        let mut b = Mesh::new();
+        for (mesh, pts) in [(&mut a, [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)]), (&mut b, [(1.0, 0.0), (1.0, 1.0), (0.0, 1.0)])] {
+            for (x, y) in pts {
+                mesh.add_vertex(v(x, y));
+            }
+            mesh.add_triangle(0, 1, 2, Vec3::Z);
+        }
+
+        let mesh = Mesh::welded(&[a, b], WELD_TOLERANCE);
+        assert_eq!(mesh.vertices.len(), 4);
+        assert_eq!(mesh.triangles[1].indices, [1, 3, 2]);
+    }
+
+    #[test]
+    fn test_cancel_after_first_face() {
+        let cube = nova_topo::build_cube(2.0).unwrap();
+        let calls = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let seen = calls.clone();
+        let tess = Tessellator::new().with_progress(move |fraction| {
+            seen.lock().unwrap().push(fraction);
+            false
+        });
+
+        let result = tess.tessellate_serial(&cube);
+        assert!(matches!(result, Err(TessellationError::Cancelled)));
+        let fractions = calls.lock().unwrap();
+        assert_eq!(fractions.len(), 1);
+        assert!((fractions[0] - 1.0 / 6.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_progress_reaches_completion() {
+        let cube = nova_topo::build_cube(2.0).unwrap();
+        let last = std::sync::Arc::new(AtomicUsize::new(0));
+        let seen = last.clone();
+        let tess = Tessellator::new().with_progress(move |fraction| {
+            seen.fetch_max((fraction * 6.0).round() as usize, Ordering::SeqCst);
+            true
+        });
+
+        let mesh = tess.tessellate(&cube).unwrap();
+        assert_eq!(mesh.triangles.len(), 12);
+        assert_eq!(last.load(Ordering::SeqCst), 6);
+    }
+
+    /// Sphere about the origin made of eight octant faces bounded by
+    /// quarter circles
+    fn octant_sphere(radius: f64) -> Body {
+        use nova_geom::{CircularArc, SphericalSurface};
+        use nova_topo::{Coedge, Edge, Face, Loop, Sense, Shell};
+        use std::sync::Arc;
+
+        let mut shell = Shell::new();
+        for octant in 0..8 {
+            let sign = |bit: usize| if octant & bit == 0 { 1.0 } else { -1.0 };
+            let corners = [Vec3::X * sign(1), Vec3::Y * sign(2), Vec3::Z * sign(4)];
+            let vertices = corners.map(|c| Arc::new(nova_topo::Vertex::new(Point3::ORIGIN + c * radius)));
+            let coedges = (0..3)
+                .map(|i| {
+                    let (a, b) = (corners[i], corners[(i + 1) % 3]);
+                    let arc = CircularArc::new(Point3::ORIGIN, radius, a.cross(&b), a, std::f64::consts::FRAC_PI_2).unwrap();
+                    let edge = Edge::with_curve(vertices[i].clone(), vertices[(i + 1) % 3].clone(), Arc::new(arc));
+                    Coedge::new(Arc::new(edge), Sense::Same)
+                })
+                .collect();
+
+            // Keep the octant away from the surface's poles and seam
+            let center = (corners[0] + corners[1] + corners[2]).normalized();
+            let axis = center.cross(&Vec3::X).normalized();
+            let surface = SphericalSurface::new(Point3::ORIGIN, radius, axis, center).unwrap();
+            let mut face = Face::with_surface(Arc::new(surface));
+            face.add_loop(Loop::from_coedges(coedges));
+            shell.add_face(face);
+        }
+        let mut body = Body::new();
+        body.add_shell(shell);
+        body
+    }
+
+    #[test]
+    fn test_angle_tolerance_validation() {
+        let body = Body::new();
+        let degrees_as_radians = TessellationOptions { angle_tolerance: 15.0, ..Default::default() };
+        let result = Tessellator::with_options(degrees_as_radians).tessellate(&body);
+        assert!(matches!(result, Err(TessellationError::InvalidOptions(_))));
+        let zero = TessellationOptions { angle_tolerance: 0.0, ..Default::default() };
+        assert!(zero.validate().is_err());
+
+        let options = TessellationOptions::with_angle_degrees(15.0);
+        assert!((options.angle_tolerance - 15.0_f64.to_radians()).abs() < 1e-15);
+        assert!(Tessellator::with_options(options).tessellate(&body).is_ok());
+    }
+
+    #[test]
+    fn test_angle_refinement_on_sphere() {
+        let sphere = octant_sphere(10.0);
+        let tessellate = |options: TessellationOptions| Tessellator::with_options(options).tessellate(&sphere).unwrap();
+        let fine = tessellate(TessellationOptions::with_angle_degrees(15.0));
+        let coarse = tessellate(TessellationOptions::with_angle_degrees(45.0));
+        assert!(fine.triangles.len() > 2 * coarse.triangles.len(), "{} vs {}", fine.triangles.len(), coarse.triangles.len());
+        let unrefined = tessellate(TessellationOptions { max_subdivision_depth: 0, ..TessellationOptions::with_angle_degrees(15.0) });
+        assert!(fine.triangles.len() > unrefined.triangles.len());
+
+        // Facets follow the surface to within the tolerance, on octants of
+        // either orientation
+        for v in &fine.vertices {
+            assert!((v.position.to_vector().length() - 10.0).abs() < 1e-9);
+        }
+        for tri in &fine.triangles {
+            for i in tri.indices {
+                let angle = tri.normal.angle_to(&fine.vertices[i as usize].position.to_vector());
+                assert!(angle.min(std::f64::consts::PI - angle) <= 15.0_f64.to_radians());
+            }
+        }
+        assert!(fine.is_closed());
+    }
+
+    #[test]
+    fn test_triangle_budget() {
+        let sphere = octant_sphere(1.0);
+        let options = TessellationOptions { chord_tolerance: 1e-5, ..Default::default() };
+        let fine = Tessellator::with_options(options).tessellate(&sphere).unwrap();
+        assert!(fine.triangles.len() > 500);
+
+        let budgeted = TessellationOptions { max_triangles: Some(500), ..options };
+        let (mesh, report) = Tessellator::with_options(budgeted).tessellate_with_report(&sphere).unwrap();
+        assert!(mesh.triangles.len() <= 500);
+        assert_eq!(report.triangle_count, mesh.triangles.len());
+        assert!(report.within_budget);
+        assert!(report.chord_tolerance > 1e-5);
+        for v in &mesh.vertices {
+            assert!((v.position.to_vector().length() - 1.0).abs() < 1e-9);
+        }
+    }
+
+    /// Cylinder of radius 1 and height 2 extruded from a circular disc
+    fn extruded_cylinder() -> Body {
+        use nova_geom::{CircularArc, Curve, PlanarSurface};
+        use nova_topo::{Coedge, Edge, EulerAdvanced, Face, Loop, Sense};
+        use std::sync::Arc;
+
+        let circle = CircularArc::circle(Point3::ORIGIN, 1.0, Vec3::Z).unwrap();
+        let v = Arc::new(nova_topo::Vertex::new(circle.start_point()));
+        let edge = Edge::with_curve(v.clone(), v, Arc::new(circle));
+        let mut disc = Face::with_surface(Arc::new(PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap()));
+        disc.add_loop(Loop::from_coedges(vec![Coedge::new(Arc::new(edge), Sense::Same)]));
+        EulerAdvanced::extrude_face(&disc, Vec3::Z, 2.0).unwrap()
+    }
+
+    #[test]
+    fn test_cylinder_seam() {
+        let cylinder = extruded_cylinder();
+        let welded = Tessellator::new().tessellate(&cylinder).unwrap();
+        assert!(welded.is_closed());
+        let mut positions: Vec<Point3> = Vec::new();
+        for v in &welded.vertices {
+            assert!(positions.iter().all(|p| p.distance_to(&v.position) > WELD_TOLERANCE));
+            positions.push(v.position);
+        }
+        // Chords of the default tolerance cut off a little of the volume
+        assert!((welded.signed_volume() - 2.0 * std::f64::consts::PI).abs() < 0.1);
+
+        // Unwelded, the seam's two vertices sit a whole turn apart in u
+        let options = TessellationOptions { weld_seams: false, ..Default::default() };
+        let side = cylinder.faces()[2];
+        let split = tessellate_face(side, &options).unwrap();
+        let on_seam: Vec<f64> = split.vertices.iter()
+            .filter(|v| v.position.distance_to(&Point3::new(1.0, 0.0, 0.0)) < 1e-9)
+            .map(|v| v.uv.0)
+            .collect();
+        assert_eq!(on_seam.len(), 2);
+        assert!(((on_seam[0] - on_seam[1]).abs() - 1.0).abs() < 1e-9);
+        assert!(!Tessellator::with_options(options).tessellate(&cylinder).unwrap().is_closed());
+        assert_eq!(tessellate_face(side, &TessellationOptions::default()).unwrap().vertices.len(), split.vertices.len() - 2);
+    }
+
+    /// Unit cube extruded from a square profile
+    fn extruded_square() -> Body {
+        use nova_geom::PlanarSurface;
+        use nova_topo::{Coedge, Edge, EulerAdvanced, Face, Loop, Sense};
+        use std::sync::Arc;
+
+        let square = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)]
+            .map(|(x, y)| Arc::new(nova_topo::Vertex::new(Point3::new(x, y, 0.0))));
+        let coedges = (0..4)
+            .map(|i| {
+                let edge = Edge::new(square[i].clone(), square[(i + 1) % 4].clone());
+                Coedge::new(Arc::new(edge), Sense::Same)
+            })
+            .collect();
+        let plane = PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap();
+        let mut profile = Face::with_surface(Arc::new(plane));
+        profile.add_loop(Loop::from_coedges(coedges));
+        EulerAdvanced::extrude_face(&profile, Vec3::Z, 1.0).unwrap()
+    }
+
+    /// Half cylinder of radius 1 around Z, on the +Y side, from z = 0 to 2
+    fn half_cylinder_face() -> nova_topo::Face {
+        use nova_geom::{CircularArc, CylindricalSurface};
+        use nova_topo::{Coedge, Edge, Face, Loop, Sense};
+        use std::f64::consts::PI;
+        use std::sync::Arc;
+
+        let surface = CylindricalSurface::bounded(Point3::ORIGIN, Vec3::Z, 1.0, Vec3::X, 0.0, 2.0, 0.0, PI).unwrap();
+        let v = [(1.0, 0.0), (-1.0, 0.0), (-1.0, 2.0), (1.0, 2.0)]
+            .map(|(x, z)| Arc::new(nova_topo::Vertex::new(Point3::new(x, 0.0, z))));
+        let arc = |z: f64| Arc::new(CircularArc::new(Point3::new(0.0, 0.0, z), 1.0, Vec3::Z, Vec3::X, PI).unwrap());
+        let edges = [
+            (Edge::with_curve(v[0].clone(), v[1].clone(), arc(0.0)), Sense::Same),
+            (Edge::new(v[1].clone(), v[2].clone()), Sense::Same),
+            (Edge::with_curve(v[3].clone(), v[2].clone(), arc(2.0)), Sense::Opposite),
+            (Edge::new(v[3].clone(), v[0].clone()), Sense::Same),
+        ];
+        let mut face = Face::with_surface(Arc::new(surface));
+        face.add_loop(Loop::from_coedges(
+            edges.into_iter().map(|(edge, sense)| Coedge::new(Arc::new(edge), sense)).collect(),
+        ));
+        face
+    }
+
+    #[test]
+    fn test_box_and_cylinders_watertight() {
+        let cube = extruded_square();
+        let mesh = Tessellator::new().tessellate(&cube).unwrap();
+        assert_eq!(mesh.triangles.len(), 12);
+        assert_eq!(mesh.vertices.len(), 8);
+        assert!(mesh.is_closed());
+        assert!((mesh.signed_volume() - 1.0).abs() < 1e-12);
+
+        // A finer angle tolerance splits the rims into more chords, and the
+        // options given here are the ones used
+        let cylinder = extruded_cylinder();
+        let coarse = tessellate_body(&cylinder).unwrap();
+        let fine = tessellate_body_with_options(&cylinder, TessellationOptions::with_angle_degrees(5.0)).unwrap();
+        assert!(coarse.is_closed() && fine.is_closed());
+        assert!(fine.triangles.len() > coarse.triangles.len());
+        let volume = 2.0 * std::f64::consts::PI;
+        assert!((fine.signed_volume() - volume).abs() < (coarse.signed_volume() - volume).abs());
+        let rim = fine.vertices.iter().filter(|v| v.position.z() == 0.0).count();
+        assert!(rim >= 72, "{}", rim);
+    }
+
+    #[test]
+    fn test_half_cylinder_normals() {
+        // Half a cylinder stays open across its flat side, with normals
+        // pointing out of the curved surface
+        let mesh = tessellate_face(&half_cylinder_face(), &TessellationOptions::default()).unwrap();
+        assert!(!mesh.is_closed());
+        for tri in &mesh.triangles {
+            let [a, b, c] = tri.indices.map(|i| mesh.vertices[i as usize].position);
+            let centroid = Point3::new((a.x() + b.x() + c.x()) / 3.0, (a.y() + b.y() + c.y()) / 3.0, 0.0);
+            assert!(centroid.distance_to(&Point3::ORIGIN) > 0.9);
+            assert!(tri.normal.dot(&centroid.to_vector()) > 0.0);
+        }
+        for v in &mesh.vertices {
+            let radial = Vec3::new(v.position.x(), v.position.y(), 0.0);
+            assert!((v.normal - radial).length() < 1e-9);
+            assert!((0.0..=1.0).contains(&v.uv.0) && (0.0..=1.0).contains(&v.uv.1));
+        }
+    }
+
+    #[test]
+    fn test_cylinder_silhouette() {
+        let cylinder = extruded_cylinder();
+        let outline = silhouette(&cylinder, Vec3::Y, 1e-3).unwrap();
+        assert_eq!(outline.len(), 4);
+        for polyline in &outline {
+            assert!(polyline.iter().all(|p| p.y().abs() < 1e-12));
+        }
+
+        // Two vertical lines along the sides
+        let mut sides: Vec<f64> = outline.iter()
+            .filter(|line| line.iter().all(|p| (p.x() - line[0].x()).abs() < 1e-9))
+            .map(|line| {
+                let heights: Vec<f64> = line.iter().map(|p| p.z()).collect();
+                assert!(heights.contains(&0.0) && heights.contains(&2.0));
+                line[0].x()
+            })
+            .collect();
+        sides.sort_by(f64::total_cmp);
+        assert_eq!(sides.len(), 2);
+        // Facets sit inside the true surface by up to the chord tolerance
+        assert!((sides[0] + 1.0).abs() < 1e-3 && (sides[1] - 1.0).abs() < 1e-3);
+
+        // Top and bottom circles seen edge-on, spanning the full width
+        let mut rims: Vec<f64> = outline.iter()
+            .filter(|arc| arc.iter().all(|p| (p.z() - arc[0].z()).abs() < 1e-9))
+            .map(|arc| {
+                let (lo, hi) = arc.iter().fold((f64::MAX, f64::MIN), |(lo, hi), p| (lo.min(p.x()), hi.max(p.x())));
+                assert!((lo + 1.0).abs() < 1e-3 && (hi - 1.0).abs() < 1e-3);
+                arc[0].z()
+            })
+            .collect();
+        rims.sort_by(f64::total_cmp);
+        assert_eq!(rims, vec![0.0, 2.0]);
+    }
+
+    #[test]
+    fn test_isometric_hidden_lines() {
+        let cube = nova_topo::build_cube(2.0).unwrap();
+        // Turn the (1, 1, 1) diagonal towards the viewer on +Z
+        let toward = Vec3::new(1.0, 1.0, 1.0).normalized();
+        let axis = toward.cross(&Vec3::Z).normalized();
+        let view = Transform3::from_axis_angle(&axis, toward.dot(&Vec3::Z).acos());
+        let drawing = hidden_line_view(&cube, &view, 1e-6).unwrap();
+
+        let project = |x: f64, y: f64, z: f64| {
+            let p = view.apply_to_point(&Point3::new(x, y, z));
+            nova_math::Point2::new(p.x(), p.y())
+        };
+        // The far corner and its neighbours; edges meeting there are hidden
+        let far = project(-1.0, -1.0, -1.0);
+        let behind = [project(1.0, -1.0, -1.0), project(-1.0, 1.0, -1.0), project(-1.0, -1.0, 1.0)];
+        let is_back_edge = |line: &Vec<nova_math::Point2>| {
+            let ends = [line[0], line[line.len() - 1]];
+            ends.iter().any(|p| p.distance_to(&far) < 1e-9)
+                && ends.iter().any(|p| behind.iter().any(|b| p.distance_to(b) < 1e-9))
+        };
+
+        assert!(!drawing.hidden.is_empty());
+        assert!(drawing.hidden.iter().all(is_back_edge));
+        assert!(drawing.visible.iter().all(|line| !is_back_edge(line)));
+        for b in &behind {
+            assert!(drawing.hidden.iter().any(|line| line.iter().any(|p| p.distance_to(b) < 1e-9)));
+        }
+        // Visible lines reach all six corners of the outline and the near corner
+        let near = project(1.0, 1.0, 1.0);
+        assert!(drawing.visible.iter().any(|line| line.iter().any(|p| p.distance_to(&near) < 1e-9)));
+        assert!(drawing.visible.len() >= 9);
+    }
+
+    #[cfg(feature = "parallel")]
+    #[test]
+    fn test_parallel_matches_serial() {
+        let cube = nova_topo::build_cube(2.0).unwrap();
+        let tess = Tessellator::new();
+
+        let parallel = tess.tessellate(&cube).unwrap();
+        let serial = tess.tessellate_serial(&cube).unwrap();
+
+        assert_eq!(parallel.vertices, serial.vertices);
+        assert_eq!(parallel.triangles, serial.triangles);
+        assert_eq!(serial.vertices.len(), 8);
+        assert_eq!(serial.triangles.len(), 12);
+    }
 }
@@ -0,0 +1,162 @@
+//! Triangle winding repair
+
+use crate::Mesh;
+use std::collections::{HashMap, VecDeque};
+
+impl Mesh {
+    /// Signed volume enclosed by the triangles, positive for outward winding
+    pub fn signed_volume(&self) -> f64 {
+        self.triangles
+            .iter()
+            .map(|tri| {
+                let [a, b, c] = tri.indices.map(|i| self.vertices[i as usize].position.to_vector());
+                a.dot(&b.cross(&c)) / 6.0
+            })
+            .sum()
+    }
+
+    /// Check that every triangle edge is shared by exactly two triangles
+    pub fn is_closed(&self) -> bool {
+        let mut uses: HashMap<(u32, u32), usize> = HashMap::new();
+        for tri in &self.triangles {
+            for k in 0..3 {
+                let (a, b) = (tri.indices[k], tri.indices[(k + 1) % 3]);
+                *uses.entry((a.min(b), a.max(b))).or_default() += 1;
+            }
+        }
+        !uses.is_empty() && uses.values().all(|&n| n == 2)
+    }
+
+    /// Rewind triangles so neighbours agree and closed parts face outward
+    ///
+    /// Each edge-connected component is flood-filled from its first triangle,
+    /// flipping neighbours that traverse a shared edge in the same direction.
+    /// Non-manifold edges are not crossed. A component whose signed volume
+    /// comes out negative is then flipped as a whole. Returns the number of
+    /// triangles flipped.
+    pub fn make_consistent(&mut self) -> usize {
+        let mut edges: HashMap<(u32, u32), Vec<usize>> = HashMap::new();
+        for (t, tri) in self.triangles.iter().enumerate() {
+            for k in 0..3 {
+                let (a, b) = (tri.indices[k], tri.indices[(k + 1) % 3]);
+                edges.entry((a.min(b), a.max(b))).or_default().push(t);
+            }
+        }
+
+        let mut flipped = vec![false; self.triangles.len()];
+        let mut visited = vec![false; self.triangles.len()];
+        for seed in 0..self.triangles.len() {
+            if visited[seed] {
+                continue;
+            }
+            visited[seed] = true;
+            let mut component = vec![seed];
+            let mut queue = VecDeque::from([seed]);
+            while let Some(t) = queue.pop_front() {
+                let indices = self.triangles[t].indices;
+                for k in 0..3 {
+                    let (a, b) = (indices[k], indices[(k + 1) % 3]);
+                    let [t1, t2] = edges[&(a.min(b), a.max(b))][..] else {
+                        continue;
+                    };
+                    let n = if t1 == t { t2 } else { t1 };
+                    if visited[n] {
+                        continue;
+                    }
+                    visited[n] = true;
+                    if has_directed_edge(&self.triangles[n].indices, a, b) {
+                        self.flip(n);
+                        flipped[n] = !flipped[n];
+                    }
+                    component.push(n);
+                    queue.push_back(n);
+                }
+            }
+
+            let volume: f64 = component
+                .iter()
+                .map(|&t| {
+                    let [a, b, c] = self.triangles[t].indices.map(|i| self.vertices[i as usize].position.to_vector());
+                    a.dot(&b.cross(&c))
+                })
+                .sum();
+            if volume < 0.0 {
+                for &t in &component {
+                    self.flip(t);
+                    flipped[t] = !flipped[t];
+                }
+            }
+        }
+
+        flipped.iter().filter(|&&f| f).count()
+    }
+
+    fn flip(&mut self, t: usize) {
+        let tri = &mut self.triangles[t];
+        tri.indices.swap(1, 2);
+        tri.normal = -tri.normal;
+    }
+}
+
+fn has_directed_edge(indices: &[u32; 3], a: u32, b: u32) -> bool {
+    (0..3).any(|k| indices[k] == a && indices[(k + 1) % 3] == b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vertex;
+    use nova_math::{Point3, Vec3};
+
+    /// Unit box with outward-wound triangles
+    fn box_mesh() -> Mesh {
+        let mut mesh = Mesh::new();
+        for i in 0..8 {
+            let p = Point3::new((i & 1) as f64, ((i >> 1) & 1) as f64, ((i >> 2) & 1) as f64);
+            mesh.add_vertex(Vertex { position: p, normal: Vec3::ZERO, uv: (0.0, 0.0) });
+        }
+        let quads = [
+            [0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4],
+            [2, 6, 7, 3], [0, 4, 6, 2], [1, 3, 7, 5],
+        ];
+        for [a, b, c, d] in quads {
+            for [i, j, k] in [[a, b, c], [a, c, d]] {
+                let [p, q, r] = [i, j, k].map(|i: u32| mesh.vertices[i as usize].position);
+                mesh.add_triangle(i, j, k, (q - p).cross(&(r - p)).normalized());
+            }
+        }
+        mesh
+    }
+
+    #[test]
+    fn test_make_consistent_box() {
+        let mut mesh = box_mesh();
+        assert!((mesh.signed_volume() - 1.0).abs() < 1e-12);
+        for t in (0..mesh.triangles.len()).step_by(2) {
+            mesh.flip(t);
+        }
+
+        assert_eq!(mesh.make_consistent(), 6);
+        let mut directed = std::collections::HashSet::new();
+        for tri in &mesh.triangles {
+            for k in 0..3 {
+                assert!(directed.insert((tri.indices[k], tri.indices[(k + 1) % 3])));
+            }
+        }
+        assert!((mesh.signed_volume() - 1.0).abs() < 1e-12);
+        for tri in &mesh.triangles {
+            let [p, q, r] = tri.indices.map(|i| mesh.vertices[i as usize].position);
+            assert!(tri.normal.dot(&(q - p).cross(&(r - p))) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_make_consistent_turns_inside_out_box() {
+        let mut mesh = box_mesh();
+        for t in 0..mesh.triangles.len() {
+            mesh.flip(t);
+        }
+        assert_eq!(mesh.make_consistent(), 12);
+        assert!((mesh.signed_volume() - 1.0).abs() < 1e-12);
+    }
+}
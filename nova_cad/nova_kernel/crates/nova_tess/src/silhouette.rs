@@ -0,0 +1,199 @@
+//! Silhouettes and hidden-line removal for drawing views
+
+use crate::face::edge_points;
+use crate::{tessellate_edge_on_face, tessellate_face, Mesh, TessResult, TessellationOptions};
+use nova_math::{Point2, Point3, Transform3, Vec3};
+use nova_topo::{Body, Entity, EntityId};
+use std::collections::{HashMap, HashSet};
+
+/// Pieces each drawn segment is cut into, per body diagonal of length,
+/// for the visibility test
+const HIDDEN_LINE_RESOLUTION: f64 = 128.0;
+
+/// Barycentric slack so points on a facet's edge count as covered by it
+const FACET_EDGE_SLACK: f64 = 1e-9;
+
+/// Edges of a projected drawing split by visibility
+#[derive(Debug, Clone, Default)]
+pub struct DrawingView {
+    /// Polylines not covered by any face nearer the viewer
+    pub visible: Vec<Vec<Point2>>,
+    /// Polylines behind some face
+    pub hidden: Vec<Vec<Point2>>,
+}
+
+/// Outline of `body` seen along `view_dir`, as polylines projected onto the
+/// plane through the origin perpendicular to it
+///
+/// Each face is tessellated to `tol` and contributes the chains of mesh
+/// edges across which its facets turn from facing the viewer to facing
+/// away. Every B-Rep edge bounding a face is added as well, except seams
+/// lying on a single face. No hidden lines are removed.
+pub fn silhouette(body: &Body, view_dir: Vec3, tol: f64) -> TessResult<Vec<Vec<Point3>>> {
+    let view = view_dir.normalized();
+    let project = |p: Point3| p - view * p.to_vector().dot(&view);
+    let Outline { polylines, .. } = outline(body, view, tol)?;
+    Ok(polylines.into_iter().map(|line| line.into_iter().map(project).collect()).collect())
+}
+
+/// Drawing of `body` with edges split into visible and hidden parts
+///
+/// `view` maps the body into view space, where the drawing plane is XY and
+/// the viewer looks down the negative Z axis from far away, so larger Z is
+/// nearer. The lines are those of [`silhouette`]; each is cut into short
+/// pieces that are hidden when a facet lies over them by more than `tol`.
+/// Facets are tested by brute force.
+pub fn hidden_line_view(body: &Body, view: &Transform3, tol: f64) -> TessResult<DrawingView> {
+    let body = body.transformed(view);
+    let Outline { polylines, facets } = outline(&body, -Vec3::Z, tol)?;
+    let step = body.bounding_box().diagonal() / HIDDEN_LINE_RESOLUTION;
+    let flat = |p: Point3| Point2::new(p.x(), p.y());
+
+    let mut drawing = DrawingView::default();
+    for line in &polylines {
+        let mut run: Vec<Point2> = Vec::new();
+        let mut run_hidden = false;
+        for pair in line.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let pieces = (a.distance_to(&b) / step).ceil().max(1.0) as usize;
+            for k in 0..pieces {
+                let (p, q) = (a.lerp(&b, k as f64 / pieces as f64), a.lerp(&b, (k + 1) as f64 / pieces as f64));
+                let hidden = facets.iter().any(|facet| covers(facet, p.lerp(&q, 0.5), tol));
+                if hidden != run_hidden && !run.is_empty() {
+                    let finished = std::mem::take(&mut run);
+                    if run_hidden { drawing.hidden.push(finished) } else { drawing.visible.push(finished) }
+                }
+                if run.is_empty() {
+                    run.push(flat(p));
+                }
+                run.push(flat(q));
+                run_hidden = hidden;
+            }
+        }
+        if run.len() >= 2 {
+            if run_hidden { drawing.hidden.push(run) } else { drawing.visible.push(run) }
+        }
+    }
+    Ok(drawing)
+}
+
+/// Drawn lines of a body before projection
+struct Outline {
+    /// Silhouette chains and B-Rep edges
+    polylines: Vec<Vec<Point3>>,
+    /// Facets of every face
+    facets: Vec<[Point3; 3]>,
+}
+
+/// Outline of `body` seen along unit `view`
+fn outline(body: &Body, view: Vec3, tol: f64) -> TessResult<Outline> {
+    let options = TessellationOptions { chord_tolerance: tol, weld_seams: true, ..TessellationOptions::default() };
+
+    let mut polylines = Vec::new();
+    let mut facets = Vec::new();
+    let mut seams: HashSet<EntityId> = HashSet::new();
+    let mut drawn: HashSet<EntityId> = HashSet::new();
+    for face in body.faces_iter() {
+        seams.extend(face.seam_edges());
+
+        let mesh = tessellate_face(face, &options)?;
+        for chain in chain_segments(&turning_edges(&mesh, &view)) {
+            polylines.push(chain.iter().map(|&i| mesh.vertices[i as usize].position).collect());
+        }
+        facets.extend(mesh.triangles.iter().map(|tri| tri.indices.map(|i| mesh.vertices[i as usize].position)));
+
+        // Edges follow the first face they bound, on its facets
+        for lp in face.loops() {
+            for coedge in lp.coedges() {
+                let edge = coedge.edge();
+                if !seams.contains(&edge.id()) && drawn.insert(edge.id()) {
+                    polylines.push(tessellate_edge_on_face(edge, face, &options)?);
+                }
+            }
+        }
+    }
+
+    for edge in body.edges_iter().filter(|edge| !seams.contains(&edge.id()) && !drawn.contains(&edge.id())) {
+        polylines.push(edge_points(edge, &options));
+    }
+    Ok(Outline { polylines, facets })
+}
+
+/// Whether `facet` lies over `point` in XY and nearer than it in Z by more
+/// than `tol`
+fn covers(facet: &[Point3; 3], point: Point3, tol: f64) -> bool {
+    let [a, b, c] = *facet;
+    let det = (b.x() - a.x()) * (c.y() - a.y()) - (c.x() - a.x()) * (b.y() - a.y());
+    // Facets seen edge-on hide nothing
+    if det.abs() < 1e-12 {
+        return false;
+    }
+    let (dx, dy) = (point.x() - a.x(), point.y() - a.y());
+    let u = (dx * (c.y() - a.y()) - (c.x() - a.x()) * dy) / det;
+    let v = ((b.x() - a.x()) * dy - dx * (b.y() - a.y())) / det;
+    if u < -FACET_EDGE_SLACK || v < -FACET_EDGE_SLACK || u + v > 1.0 + FACET_EDGE_SLACK {
+        return false;
+    }
+    let depth = a.z() + u * (b.z() - a.z()) + v * (c.z() - a.z());
+    depth > point.z() + tol
+}
+
+/// Mesh edges shared by a facet facing the viewer and one facing away
+fn turning_edges(mesh: &Mesh, view: &Vec3) -> Vec<(u32, u32)> {
+    let mut facing: HashMap<(u32, u32), Vec<bool>> = HashMap::new();
+    for tri in &mesh.triangles {
+        // Vertex normals follow the face orientation, whatever the winding
+        let outward = tri.indices.iter().fold(Vec3::ZERO, |sum, &i| sum + mesh.vertices[i as usize].normal);
+        let normal = if tri.normal.dot(&outward) < 0.0 { -tri.normal } else { tri.normal };
+        let front = normal.dot(view) < 0.0;
+        for k in 0..3 {
+            let (a, b) = (tri.indices[k], tri.indices[(k + 1) % 3]);
+            facing.entry((a.min(b), a.max(b))).or_default().push(front);
+        }
+    }
+    let mut edges: Vec<(u32, u32)> = facing
+        .into_iter()
+        .filter(|(_, sides)| sides.len() == 2 && sides[0] != sides[1])
+        .map(|(edge, _)| edge)
+        .collect();
+    edges.sort_unstable();
+    edges
+}
+
+/// Join segments sharing end points into polylines, breaking at branches
+fn chain_segments(segments: &[(u32, u32)]) -> Vec<Vec<u32>> {
+    let mut neighbours: HashMap<u32, Vec<usize>> = HashMap::new();
+    for (i, &(a, b)) in segments.iter().enumerate() {
+        neighbours.entry(a).or_default().push(i);
+        neighbours.entry(b).or_default().push(i);
+    }
+    let mut used = vec![false; segments.len()];
+    let mut chains = Vec::new();
+
+    // Open chains start at their ends, closed loops anywhere
+    let mut starts: Vec<u32> = neighbours.iter().filter(|(_, s)| s.len() != 2).map(|(&v, _)| v).collect();
+    starts.sort_unstable();
+    starts.extend(segments.iter().map(|&(a, _)| a));
+    for start in starts {
+        while let Some(&first) = neighbours[&start].iter().find(|&&s| !used[s]) {
+            let mut chain = vec![start];
+            let (mut at, mut segment) = (start, first);
+            loop {
+                used[segment] = true;
+                let (a, b) = segments[segment];
+                at = if a == at { b } else { a };
+                chain.push(at);
+                let next = &neighbours[&at];
+                if next.len() != 2 {
+                    break;
+                }
+                match next.iter().find(|&&s| !used[s]) {
+                    Some(&s) => segment = s,
+                    None => break,
+                }
+            }
+            chains.push(chain);
+        }
+    }
+    chains
+}
@@ -0,0 +1,455 @@
+//! Constrained triangulation of polygons with holes in the parameter plane
+
+use nova_math::{incircle, orient2d, Point2};
+use std::collections::{HashMap, VecDeque};
+
+/// Maximum number of Delaunay flip sweeps
+const MAX_FLIP_PASSES: usize = 64;
+
+/// Most flips tried recovering a constraint, per edge it first crosses
+const MAX_RECOVERY_FLIPS: usize = 64;
+
+/// Triangulate a polygon with holes, keeping every loop edge
+///
+/// Indices refer to `outer` followed by each hole in order. Loops may be given
+/// in either winding; the returned triangles are counter-clockwise. The result
+/// is the constrained Delaunay triangulation of the loops: points are inserted
+/// into a covering triangle one by one, loop edges are recovered by flipping
+/// the edges that cross them, and triangles outside the outer loop or inside
+/// a hole are dropped.
+pub fn triangulate_polygon(outer: &[Point2], holes: &[Vec<Point2>]) -> Vec<[usize; 3]> {
+    triangulate_with_points(outer, holes, &[])
+}
+
+/// Triangulate a polygon with holes as [`triangulate_polygon`] does, with
+/// `interior` points inside the polygon as extra vertices
+///
+/// Indices of the interior points follow those of the last hole.
+pub fn triangulate_with_points(outer: &[Point2], holes: &[Vec<Point2>], interior: &[Point2]) -> Vec<[usize; 3]> {
+    if outer.len() < 3 {
+        return Vec::new();
+    }
+    let mut points: Vec<Point2> = outer.to_vec();
+    let mut rings = vec![(0..outer.len()).collect::<Vec<_>>()];
+    for hole in holes {
+        let start = points.len();
+        points.extend_from_slice(hole);
+        rings.push((start..points.len()).collect());
+    }
+    points.extend_from_slice(interior);
+
+    // Repeated points share the first copy's index
+    let mut canonical: Vec<usize> = (0..points.len()).collect();
+    let mut first: HashMap<(u64, u64), usize> = HashMap::new();
+    for (i, p) in points.iter().enumerate() {
+        canonical[i] = *first.entry((p.x().to_bits(), p.y().to_bits())).or_insert(i);
+    }
+
+    // The predicates' epsilon is absolute, so work at unit size
+    let count = points.len();
+    let mut points = unit_box(&points);
+    points.extend(super_triangle());
+    let mut triangles = vec![[count, count + 1, count + 2]];
+    for (i, &c) in canonical.iter().enumerate() {
+        if c == i {
+            insert_point(&points, &mut triangles, i);
+        }
+    }
+
+    let mut constraints: Vec<(usize, usize)> = Vec::new();
+    for ring in &rings {
+        for i in 0..ring.len() {
+            let (a, b) = (canonical[ring[i]], canonical[ring[(i + 1) % ring.len()]]);
+            if a != b {
+                recover_edge(&points, &mut triangles, a, b, &mut constraints);
+            }
+        }
+    }
+    constraints.sort_unstable();
+    constraints.dedup();
+
+    let mut triangles = inside_triangles(&triangles, &constraints, count);
+    delaunay_flip(&points, &mut triangles, &constraints);
+    triangles
+}
+
+/// Points moved and uniformly scaled to fit the square [-0.5, 0.5]^2
+fn unit_box(points: &[Point2]) -> Vec<Point2> {
+    let (mut lo, mut hi) = ([f64::INFINITY; 2], [f64::NEG_INFINITY; 2]);
+    for p in points {
+        lo = [lo[0].min(p.x()), lo[1].min(p.y())];
+        hi = [hi[0].max(p.x()), hi[1].max(p.y())];
+    }
+    let (cx, cy) = (0.5 * (lo[0] + hi[0]), 0.5 * (lo[1] + hi[1]));
+    let size = (hi[0] - lo[0]).max(hi[1] - lo[1]);
+    let scale = if size > 0.0 { 1.0 / size } else { 1.0 };
+    points.iter().map(|p| Point2::new((p.x() - cx) * scale, (p.y() - cy) * scale)).collect()
+}
+
+/// Counter-clockwise triangle well clear of the unit box
+fn super_triangle() -> [Point2; 3] {
+    [Point2::new(-16.0, -16.0), Point2::new(16.0, -16.0), Point2::new(0.0, 16.0)]
+}
+
+/// Bowyer-Watson insertion: replace the triangles whose circumcircle holds
+/// point `p` with a fan from `p` to the cavity's boundary
+///
+/// The cavity grows past any boundary edge `p` doesn't see strictly from
+/// inside, so no fan triangle comes out flat or inverted.
+fn insert_point(points: &[Point2], triangles: &mut Vec<[usize; 3]>, p: usize) {
+    let pp = &points[p];
+    let mut in_cavity: Vec<bool> = triangles.iter()
+        .map(|&[a, b, c]| {
+            incircle(&points[a], &points[b], &points[c], pp).is_positive()
+                || in_triangle(pp, &points[a], &points[b], &points[c])
+        })
+        .collect();
+    let boundary = loop {
+        let mut boundary: Vec<(usize, usize)> = Vec::new();
+        for (t, &[a, b, c]) in triangles.iter().enumerate() {
+            if !in_cavity[t] {
+                continue;
+            }
+            for (u, v) in [(a, b), (b, c), (c, a)] {
+                // An edge shared by two cavity triangles is interior to the cavity
+                if let Some(k) = boundary.iter().position(|&e| e == (v, u)) {
+                    boundary.swap_remove(k);
+                } else {
+                    boundary.push((u, v));
+                }
+            }
+        }
+        let hidden: Vec<usize> = boundary.iter()
+            .filter(|&&(u, v)| !orient2d(&points[u], &points[v], pp).is_positive())
+            .filter_map(|&(u, v)| triangle_with_edge(triangles, v, u))
+            .filter(|&t| !in_cavity[t])
+            .collect();
+        if hidden.is_empty() {
+            break boundary;
+        }
+        for t in hidden {
+            in_cavity[t] = true;
+        }
+    };
+    let mut cavity = in_cavity.into_iter();
+    triangles.retain(|_| !cavity.next().unwrap_or(false));
+    triangles.extend(boundary.into_iter().map(|(u, v)| [u, v, p]));
+}
+
+/// Check if `p` lies inside or on counter-clockwise triangle `abc`
+fn in_triangle(p: &Point2, a: &Point2, b: &Point2, c: &Point2) -> bool {
+    !orient2d(a, b, p).is_negative()
+        && !orient2d(b, c, p).is_negative()
+        && !orient2d(c, a, p).is_negative()
+}
+
+/// Index of the triangle holding directed edge `a -> b`
+fn triangle_with_edge(triangles: &[[usize; 3]], a: usize, b: usize) -> Option<usize> {
+    triangles.iter().position(|t| (0..3).any(|k| t[k] == a && t[(k + 1) % 3] == b))
+}
+
+/// Make `a-b` an edge of the triangulation by flipping the edges crossing it
+///
+/// A point lying on the segment splits it in two. Each edge kept is added
+/// to `constraints`.
+fn recover_edge(
+    points: &[Point2],
+    triangles: &mut [[usize; 3]],
+    a: usize,
+    b: usize,
+    constraints: &mut Vec<(usize, usize)>,
+) {
+    let (pa, pb) = (&points[a], &points[b]);
+    let between = |p: &Point2| {
+        let along = (p.x() - pa.x()) * (pb.x() - pa.x()) + (p.y() - pa.y()) * (pb.y() - pa.y());
+        along > 0.0 && along < distance2(pa, pb)
+    };
+    let on_segment = triangles.iter().flatten()
+        .copied()
+        .filter(|&p| p != a && p != b && orient2d(pa, pb, &points[p]).is_zero() && between(&points[p]))
+        .min_by(|&p, &q| distance2(pa, &points[p]).total_cmp(&distance2(pa, &points[q])));
+    if let Some(mid) = on_segment {
+        recover_edge(points, triangles, a, mid, constraints);
+        recover_edge(points, triangles, mid, b, constraints);
+        return;
+    }
+    constraints.push((a.min(b), a.max(b)));
+
+    let mut crossing: Vec<(usize, usize)> = Vec::new();
+    for t in triangles.iter() {
+        for k in 0..3 {
+            let (u, v) = (t[k], t[(k + 1) % 3]);
+            if u < v && segments_cross(pa, pb, &points[u], &points[v]) {
+                crossing.push((u, v));
+            }
+        }
+    }
+
+    let mut budget = MAX_RECOVERY_FLIPS * (crossing.len() + 1);
+    while let Some((u, v)) = crossing.first().copied() {
+        crossing.remove(0);
+        if budget == 0 {
+            break;
+        }
+        budget -= 1;
+        let (Some(t1), Some(t2)) = (triangle_with_edge(triangles, u, v), triangle_with_edge(triangles, v, u)) else {
+            continue;
+        };
+        let opposite = |t: [usize; 3]| t.into_iter().find(|&p| p != u && p != v);
+        let (Some(w1), Some(w2)) = (opposite(triangles[t1]), opposite(triangles[t2])) else {
+            continue;
+        };
+        // Only a convex quad u-w2-v-w1 can swap its diagonal
+        let (pu, pv, p1, p2) = (&points[u], &points[v], &points[w1], &points[w2]);
+        if !(orient2d(pu, p2, p1).is_positive() && orient2d(p2, pv, p1).is_positive()) {
+            crossing.push((u, v));
+            continue;
+        }
+        triangles[t1] = [u, w2, w1];
+        triangles[t2] = [w2, v, w1];
+        if segments_cross(pa, pb, p1, p2) {
+            crossing.push((w1.min(w2), w1.max(w2)));
+        }
+    }
+}
+
+/// Triangles enclosed by an odd number of constraint loops
+///
+/// Flood fill from the covering triangle's corners, counting the
+/// constraint edges crossed on the way in.
+fn inside_triangles(triangles: &[[usize; 3]], constraints: &[(usize, usize)], count: usize) -> Vec<[usize; 3]> {
+    let key = |a: usize, b: usize| (a.min(b), a.max(b));
+    let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for (t, tri) in triangles.iter().enumerate() {
+        for k in 0..3 {
+            edges.entry(key(tri[k], tri[(k + 1) % 3])).or_default().push(t);
+        }
+    }
+
+    let mut depth: Vec<Option<usize>> = vec![None; triangles.len()];
+    let mut queue: VecDeque<(usize, usize)> = triangles.iter().enumerate()
+        .filter(|(_, tri)| tri.iter().any(|&p| p >= count))
+        .map(|(t, _)| (t, 0))
+        .collect();
+    while let Some((t, d)) = queue.pop_front() {
+        if depth[t].is_some() {
+            continue;
+        }
+        depth[t] = Some(d);
+        let tri = triangles[t];
+        for k in 0..3 {
+            let edge = key(tri[k], tri[(k + 1) % 3]);
+            for &n in &edges[&edge] {
+                if depth[n].is_none() {
+                    // Same-depth neighbours first, so each triangle gets its least depth
+                    if constraints.binary_search(&edge).is_ok() {
+                        queue.push_back((n, d + 1));
+                    } else {
+                        queue.push_front((n, d));
+                    }
+                }
+            }
+        }
+    }
+    triangles.iter().zip(&depth)
+        .filter(|(_, d)| d.is_some_and(|d| d % 2 == 1))
+        .map(|(tri, _)| *tri)
+        .collect()
+}
+
+fn distance2(a: &Point2, b: &Point2) -> f64 {
+    (a.x() - b.x()).powi(2) + (a.y() - b.y()).powi(2)
+}
+
+/// Check if two segments cross at a point interior to both
+fn segments_cross(a: &Point2, b: &Point2, c: &Point2, d: &Point2) -> bool {
+    let d1 = orient2d(a, b, c).as_i32();
+    let d2 = orient2d(a, b, d).as_i32();
+    let d3 = orient2d(c, d, a).as_i32();
+    let d4 = orient2d(c, d, b).as_i32();
+    d1 * d2 < 0 && d3 * d4 < 0
+}
+
+/// Flip unconstrained edges until every triangle is locally Delaunay
+fn delaunay_flip(points: &[Point2], triangles: &mut [[usize; 3]], constraints: &[(usize, usize)]) {
+    let key = |a: usize, b: usize| (a.min(b), a.max(b));
+
+    for _ in 0..MAX_FLIP_PASSES {
+        let mut edges: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+        for (t, tri) in triangles.iter().enumerate() {
+            for k in 0..3 {
+                edges.entry(key(tri[k], tri[(k + 1) % 3])).or_default().push(t);
+            }
+        }
+
+        let mut keys: Vec<_> = edges.keys().copied().collect();
+        keys.sort_unstable();
+
+        let mut flipped = false;
+        let mut touched = vec![false; triangles.len()];
+        for edge in keys {
+            if constraints.contains(&edge) {
+                continue;
+            }
+            let [t1, t2] = edges[&edge][..] else {
+                continue;
+            };
+            if touched[t1] || touched[t2] {
+                continue;
+            }
+
+            // Rotate so t1 = (a, b, c) and t2 = (b, a, d)
+            let tri1 = triangles[t1];
+            let k = (0..3).find(|&k| key(tri1[k], tri1[(k + 1) % 3]) == edge).unwrap();
+            let (a, b, c) = (tri1[k], tri1[(k + 1) % 3], tri1[(k + 2) % 3]);
+            let Some(&d) = triangles[t2].iter().find(|&&p| p != a && p != b) else {
+                continue;
+            };
+
+            let (pa, pb, pc, pd) = (&points[a], &points[b], &points[c], &points[d]);
+            if incircle(pa, pb, pc, pd).is_positive()
+                && orient2d(pa, pd, pc).is_positive()
+                && orient2d(pd, pb, pc).is_positive()
+            {
+                triangles[t1] = [a, d, c];
+                triangles[t2] = [d, b, c];
+                touched[t1] = true;
+                touched[t2] = true;
+                flipped = true;
+            }
+        }
+        if !flipped {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Twice the signed area of a closed polygon
+    fn signed_area(points: &[Point2]) -> f64 {
+        (0..points.len())
+            .map(|i| {
+                let p = points[i];
+                let q = points[(i + 1) % points.len()];
+                p.x() * q.y() - q.x() * p.y()
+            })
+            .sum()
+    }
+
+    fn square(half: f64) -> Vec<Point2> {
+        vec![
+            Point2::new(-half, -half),
+            Point2::new(half, -half),
+            Point2::new(half, half),
+            Point2::new(-half, half),
+        ]
+    }
+
+    fn area(points: &[Point2], tri: &[usize; 3]) -> f64 {
+        signed_area(&[points[tri[0]], points[tri[1]], points[tri[2]]]) / 2.0
+    }
+
+    /// Check every triangle is counter-clockwise and together they tile `expected` area
+    fn assert_tiles(points: &[Point2], tris: &[[usize; 3]], expected: f64) {
+        assert!(tris.iter().all(|t| area(points, t) > 0.0), "{:?}", tris);
+        let total: f64 = tris.iter().map(|t| area(points, t)).sum();
+        assert!((total - expected).abs() < 1e-12, "{} != {}", total, expected);
+    }
+
+    #[test]
+    fn test_concave_polygon() {
+        // L-shape
+        let outer = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(2.0, 0.0),
+            Point2::new(2.0, 1.0),
+            Point2::new(1.0, 1.0),
+            Point2::new(1.0, 2.0),
+            Point2::new(0.0, 2.0),
+        ];
+        let tris = triangulate_polygon(&outer, &[]);
+        assert_eq!(tris.len(), 4);
+        assert_tiles(&outer, &tris, 3.0);
+
+        // Same outline, clockwise and starting at the reflex corner
+        let mut reversed: Vec<Point2> = outer.iter().rev().copied().collect();
+        reversed.rotate_left(2);
+        let tris = triangulate_polygon(&reversed, &[]);
+        assert_eq!(tris.len(), 4);
+        assert_tiles(&reversed, &tris, 3.0);
+    }
+
+    #[test]
+    fn test_star_polygon() {
+        // Five points at radius 1 and five reflex corners at radius 0.4
+        let star: Vec<Point2> = (0..10)
+            .map(|i| {
+                let angle = i as f64 * std::f64::consts::PI / 5.0;
+                let r = if i % 2 == 0 { 1.0 } else { 0.4 };
+                Point2::new(r * angle.cos(), r * angle.sin())
+            })
+            .collect();
+        let tris = triangulate_polygon(&star, &[]);
+        assert_eq!(tris.len(), 8);
+        assert_tiles(&star, &tris, signed_area(&star) / 2.0);
+    }
+
+    #[test]
+    fn test_concave_polygon_with_two_holes() {
+        // U-shape with a square hole in each arm
+        let outer = vec![
+            Point2::new(0.0, 0.0),
+            Point2::new(5.0, 0.0),
+            Point2::new(5.0, 4.0),
+            Point2::new(3.0, 4.0),
+            Point2::new(3.0, 2.0),
+            Point2::new(2.0, 2.0),
+            Point2::new(2.0, 4.0),
+            Point2::new(0.0, 4.0),
+        ];
+        let hole = |x: f64, y: f64| vec![
+            Point2::new(x, y),
+            Point2::new(x + 1.0, y),
+            Point2::new(x + 1.0, y + 1.0),
+            Point2::new(x, y + 1.0),
+        ];
+        let holes = vec![hole(0.5, 2.5), hole(3.5, 2.5)];
+        let tris = triangulate_polygon(&outer, &holes);
+
+        let mut points = outer.clone();
+        points.extend(holes.concat());
+        assert_eq!(tris.len(), points.len() + 2 * holes.len() - 2);
+        assert_tiles(&points, &tris, 18.0 - 2.0);
+    }
+
+    #[test]
+    fn test_square_with_hole() {
+        let outer = square(2.0);
+        let hole = square(1.0);
+        let tris = triangulate_polygon(&outer, std::slice::from_ref(&hole));
+
+        let mut points = outer.clone();
+        points.extend(hole);
+        assert_tiles(&points, &tris, 12.0);
+        assert_eq!(tris.len(), 8);
+    }
+
+    #[test]
+    fn test_interior_points() {
+        let outer = square(2.0);
+        let hole = square(1.0);
+        let interior = [Point2::new(1.5, 0.0), Point2::new(-1.5, 1.5), Point2::new(0.0, -1.5)];
+        let tris = triangulate_with_points(&outer, std::slice::from_ref(&hole), &interior);
+
+        let mut points = outer.clone();
+        points.extend(hole);
+        points.extend(interior);
+        assert_tiles(&points, &tris, 12.0);
+        // Each point inside a triangle adds two more
+        assert_eq!(tris.len(), 8 + 2 * interior.len());
+        assert!((8..11).all(|i| tris.iter().flatten().any(|&p| p == i)));
+    }
+}
@@ -1,16 +1,46 @@
 //! B-Rep body structures: Body, Shell, Face, Loop, Coedge, Edge, Vertex
 
-use crate::{EntityId, Entity, TopologicalEntity, GeometricEntity, Orientation, Sense, new_entity_id};
-use nova_math::{Point3, Vec3, Transform3, BoundingBox3};
-use nova_geom::{Curve, Surface};
+use crate::{Color, EntityId, Entity, TopologicalEntity, GeometricEntity, Orientation, Sense, new_entity_id,
+            TopoResult, TopologyError};
+use nova_math::{Point3, Vec3, Transform3, BoundingBox3, Units};
+use nova_geom::{Curve, CurveType, Surface, SurfaceType, UVRange};
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
+/// What kind of object a body's shells make up
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BodyType {
+    /// Every shell is closed and encloses volume
+    Solid,
+    /// Every shell is a set of faces with a free boundary
+    Sheet,
+    /// Every shell is loose edges without faces
+    Wire,
+    /// Shells of more than one kind, or no shells at all
+    Mixed,
+}
+
+/// How the two faces at an edge meet, seen from outside the material
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeConvexity {
+    /// The faces fold away from each other, as round the outside of a box
+    Convex,
+    /// The faces fold toward each other, as round the floor of a pocket
+    Concave,
+    /// The faces meet tangent to each other
+    Smooth,
+    /// The edge does not join exactly two faces, or a normal is undefined
+    Unknown,
+}
+
 /// A solid body composed of shells
 #[derive(Debug, Clone)]
 pub struct Body {
     id: EntityId,
     shells: Vec<Shell>,
     transforms: Vec<Transform3>,
+    placement: Transform3,
+    color: Option<Color>,
 }
 
 impl Body {
@@ -20,9 +50,96 @@ impl Body {
             id: new_entity_id(),
             shells: Vec::new(),
             transforms: Vec::new(),
+            placement: Transform3::identity(),
+            color: None,
         }
     }
     
+    /// Wire body with one shell holding an edge along each curve
+    ///
+    /// Curve ends closer than `tol` share a vertex, and every vertex records
+    /// the edges meeting at it.
+    pub fn wire(curves: &[Box<dyn Curve>], tol: f64) -> Body {
+        let mut vertices: Vec<Vertex> = Vec::new();
+        let mut vertex_at = |p: Point3| match vertices.iter().position(|v| v.position.distance_to(&p) <= tol) {
+            Some(i) => i,
+            None => {
+                vertices.push(Vertex::new(p));
+                vertices.len() - 1
+            }
+        };
+        let ends: Vec<(usize, usize)> = curves.iter().map(|c| (vertex_at(c.start_point()), vertex_at(c.end_point()))).collect();
+        let ids: Vec<EntityId> = curves.iter().map(|_| new_entity_id()).collect();
+        for (&(a, b), &id) in ends.iter().zip(&ids) {
+            vertices[a].add_edge(id);
+            vertices[b].add_edge(id);
+        }
+        let vertices: Vec<Arc<Vertex>> = vertices.into_iter().map(Arc::new).collect();
+
+        let mut shell = Shell::new();
+        for ((curve, &(a, b)), &id) in curves.iter().zip(&ends).zip(&ids) {
+            let mut edge = Edge::with_curve(vertices[a].clone(), vertices[b].clone(), Arc::from(curve.clone_box()));
+            edge.id = id;
+            shell.add_wire(Arc::new(edge));
+        }
+        let mut body = Body::new();
+        body.add_shell(shell);
+        body
+    }
+
+    /// Color of faces that have none of their own
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Set the color of faces that have none of their own
+    pub fn set_color(&mut self, color: Option<Color>) {
+        self.color = color;
+    }
+
+    /// Transform from the body's local frame to where its geometry now sits
+    ///
+    /// Geometry is stored already placed, so this records the placement
+    /// rather than being applied on top of it.
+    pub fn placement(&self) -> Transform3 {
+        self.placement
+    }
+
+    /// Move the body so its local frame sits at `placement`
+    pub fn set_placement(&mut self, placement: Transform3) {
+        let mut moved = self.transformed(&(placement * self.placement.inverse()));
+        moved.placement = placement;
+        *self = moved;
+    }
+
+    /// Copy of the body in its own frame, with the placement undone
+    pub fn local_geometry(&self) -> Body {
+        let mut local = self.transformed(&self.placement.inverse());
+        local.placement = Transform3::identity();
+        local
+    }
+
+    /// Copy of the body in world coordinates, which is how it is stored
+    pub fn world_geometry(&self) -> Body {
+        self.clone()
+    }
+
+    /// Color `face` of this body is shown in: its own, else the body's
+    pub fn face_color(&self, face: &Face) -> Option<Color> {
+        face.color.or(self.color)
+    }
+
+    /// Give every face without a color of its own the body's color
+    ///
+    /// Faces keep their appearance when moved into another body.
+    pub fn bake_color(&mut self) {
+        if let Some(color) = self.color {
+            for face in self.shells.iter_mut().flat_map(|shell| shell.faces.iter_mut()) {
+                face.color.get_or_insert(color);
+            }
+        }
+    }
+
     /// Get all shells
     pub fn shells(&self) -> &[Shell] {
         &self.shells
@@ -40,11 +157,12 @@ impl Body {
     
     /// Get all faces
     pub fn faces(&self) -> Vec<&Face> {
-        let mut faces = Vec::new();
-        for shell in &self.shells {
-            faces.extend(shell.faces());
-        }
-        faces
+        self.faces_iter().collect()
+    }
+    
+    /// Iterate over the faces of every shell
+    pub fn faces_iter(&self) -> impl Iterator<Item = &Face> + '_ {
+        self.shells.iter().flat_map(|shell| shell.faces())
     }
     
     /// Get all loops
@@ -73,35 +191,41 @@ impl Body {
     
     /// Get all edges
     pub fn edges(&self) -> Vec<&Edge> {
-        let mut edges = Vec::new();
-        for shell in &self.shells {
-            for face in shell.faces() {
-                for lp in face.loops() {
-                    for coedge in lp.coedges() {
-                        if !edges.iter().any(|e: &&Edge| e.id() == coedge.edge().id()) {
-                            edges.push(coedge.edge());
-                        }
-                    }
-                }
-            }
-        }
-        edges
+        self.edges_iter().collect()
+    }
+    
+    /// Iterate over the edges, each once in the order faces first use them,
+    /// followed by the shells' wire edges
+    pub fn edges_iter(&self) -> impl Iterator<Item = &Edge> + '_ {
+        let mut seen = HashSet::new();
+        self.faces_iter()
+            .flat_map(coedges_of_face)
+            .map(Coedge::edge)
+            .chain(self.shells.iter().flat_map(|shell| shell.wires.iter().map(|edge| &**edge)))
+            .filter(move |edge| seen.insert(edge.id()))
     }
     
     /// Get all vertices
     pub fn vertices(&self) -> Vec<&Vertex> {
-        let mut vertices = Vec::new();
-        for edge in self.edges() {
-            let start = edge.start_vertex();
-            let end = edge.end_vertex();
-            if !vertices.iter().any(|v: &&Vertex| v.id() == start.id()) {
-                vertices.push(start);
-            }
-            if !vertices.iter().any(|v: &&Vertex| v.id() == end.id()) {
-                vertices.push(end);
-            }
-        }
-        vertices
+        self.vertices_iter().collect()
+    }
+    
+    /// Iterate over the vertices, each once in the order edges first use them
+    pub fn vertices_iter(&self) -> impl Iterator<Item = &Vertex> + '_ {
+        let mut seen = HashSet::new();
+        self.edges_iter()
+            .flat_map(|edge| [edge.start_vertex(), edge.end_vertex()])
+            .filter(move |vertex| seen.insert(vertex.id()))
+    }
+    
+    /// Faces grouped into connected pieces, two faces joining when they
+    /// share a vertex
+    ///
+    /// A closed piece is one boundary surface: the outside of a lump or the
+    /// wall of a void. Pieces are ordered by their first face, and faces
+    /// keep their body order within a piece.
+    pub fn face_components(&self) -> Vec<Vec<&Face>> {
+        connected_faces(self.faces())
     }
     
     /// Check if the body is a solid (has outer shell)
@@ -113,6 +237,29 @@ impl Body {
     pub fn is_empty(&self) -> bool {
         self.shells.is_empty()
     }
+
+    /// Classify the body by its shells
+    ///
+    /// A shell with only wire edges is a wire, a closed shell of faces is a
+    /// solid and any other shell of faces is a sheet. The body takes its
+    /// shells' kind when they all agree and is `Mixed` otherwise.
+    pub fn body_type(&self) -> BodyType {
+        let mut kinds = self.shells.iter().map(|shell| {
+            if shell.faces.is_empty() && !shell.wires.is_empty() {
+                BodyType::Wire
+            } else if shell.wires.is_empty() && shell.is_closed() {
+                BodyType::Solid
+            } else if shell.wires.is_empty() && !shell.faces.is_empty() {
+                BodyType::Sheet
+            } else {
+                BodyType::Mixed
+            }
+        });
+        let Some(first) = kinds.next() else {
+            return BodyType::Mixed;
+        };
+        if kinds.all(|kind| kind == first) { first } else { BodyType::Mixed }
+    }
     
     /// Transform the body
     pub fn transform(&mut self, transform: &Transform3) {
@@ -120,14 +267,192 @@ impl Body {
         // Note: Actual transformation of geometry would be applied lazily
     }
     
+    /// Copy of the body with all geometry moved by `transform`
+    ///
+    /// Vertex positions are transformed together in one batch. Shared
+    /// vertices and edges stay shared and keep their ids. The placement
+    /// moves with the geometry.
+    pub fn transformed(&self, transform: &Transform3) -> Body {
+        let mut body = Body {
+            id: self.id,
+            shells: self.shells.iter().map(|shell| Shell {
+                id: shell.id,
+                faces: shell.faces.iter().map(|face| Face {
+                    id: face.id,
+                    surface: face.surface.clone(),
+                    loops: face.loops.clone(),
+                    orientation: face.orientation,
+                    color: face.color,
+                }).collect(),
+                wires: shell.wires.clone(),
+                is_outer: shell.is_outer,
+            }).collect(),
+            transforms: self.transforms.clone(),
+            placement: *transform * self.placement,
+            color: self.color,
+        };
+        let vertices = self.moved_vertices(|points| transform.apply_to_points(points));
+        body.remap_geometry(&vertices, &|c| c.transform(transform), &|s| s.transform(transform));
+        body
+    }
+
+    /// Uniformly scale all geometry about the world origin
+    ///
+    /// Shared vertices and edges stay shared and keep their ids.
+    pub fn scale(&mut self, factor: f64) {
+        let vertices = self.moved_vertices(|points| {
+            for p in points {
+                *p = Point3::ORIGIN + p.to_vector() * factor;
+            }
+        });
+        self.remap_geometry(&vertices, &|c| c.scale(factor), &|s| s.scale(factor));
+
+        for t in self.transforms.iter_mut().chain(std::iter::once(&mut self.placement)) {
+            *t = Transform3::new(&t.rotation(), t.translation() * factor);
+        }
+    }
+
+    /// Copies of all vertices, keeping ids, with positions updated by `f` in one batch
+    fn moved_vertices(&self, f: impl FnOnce(&mut [Point3])) -> HashMap<EntityId, Arc<Vertex>> {
+        let originals = self.vertices();
+        let mut positions: Vec<Point3> = originals.iter().map(|v| v.position).collect();
+        f(&mut positions);
+        originals
+            .into_iter()
+            .zip(positions)
+            .map(|(v, position)| (v.id, Arc::new(Vertex { position, ..v.clone() })))
+            .collect()
+    }
+
+    /// Rebuild every edge on `vertices`, passing curves and surfaces through the maps
+    fn remap_geometry(
+        &mut self,
+        vertices: &HashMap<EntityId, Arc<Vertex>>,
+        map_curve: &dyn Fn(&mut dyn Curve),
+        map_surface: &dyn Fn(&mut dyn Surface),
+    ) {
+        let mut edges: HashMap<EntityId, Arc<Edge>> = HashMap::new();
+        for shell in &mut self.shells {
+            for face in &mut shell.faces {
+                if let Some(surface) = &face.surface {
+                    let mut mapped = surface.clone_box();
+                    map_surface(mapped.as_mut());
+                    face.surface = Some(Arc::from(mapped));
+                }
+                for lp in &mut face.loops {
+                    for coedge in &mut lp.coedges {
+                        let edge = edges
+                            .entry(coedge.edge.id)
+                            .or_insert_with(|| Arc::new(coedge.edge.mapped(vertices, map_curve)))
+                            .clone();
+                        coedge.edge = edge;
+                    }
+                }
+            }
+            for wire in &mut shell.wires {
+                *wire = edges
+                    .entry(wire.id)
+                    .or_insert_with(|| Arc::new(wire.mapped(vertices, map_curve)))
+                    .clone();
+            }
+        }
+    }
+
+    /// Convert all lengths from one unit system to another
+    pub fn convert_units(&mut self, from: Units, to: Units) {
+        self.scale(from.factor_to(to));
+    }
+    
+    /// The `k` faces nearest `point` and their distances, nearest first
+    ///
+    /// Builds a [`FaceBvh`](crate::FaceBvh) for the one query; keep one
+    /// around instead when asking many.
+    pub fn nearest_faces(&self, point: &Point3, k: usize) -> Vec<(EntityId, f64)> {
+        use crate::SpatialQuery;
+        crate::FaceBvh::new(self).nearest(point, k)
+    }
+
     /// Compute bounding box
+    ///
+    /// This is the union of the face boxes and the vertices, so it covers
+    /// curved edges and faces and not just the corners.
     pub fn bounding_box(&self) -> BoundingBox3 {
-        let mut bbox = BoundingBox3::empty();
+        let mut bbox = self.faces_iter().fold(BoundingBox3::empty(), |bbox, face| bbox.union(&face.bounding_box()));
         for vertex in self.vertices() {
             bbox.expand(&vertex.position());
         }
         bbox
     }
+
+    /// Smallest sphere enclosing the vertices, as (center, radius)
+    pub fn bounding_sphere(&self) -> (Point3, f64) {
+        let points: Vec<Point3> = self.vertices().iter().map(|v| v.position()).collect();
+        nova_math::bounding_sphere(&points)
+    }
+
+    /// Smallest cylinder along `axis` enclosing the vertices, as (center, radius, height)
+    pub fn bounding_cylinder(&self, axis: &Vec3) -> (Point3, f64, f64) {
+        let points: Vec<Point3> = self.vertices().iter().map(|v| v.position()).collect();
+        nova_math::bounding_cylinder(&points, axis)
+    }
+}
+
+/// Area-weighted normal of a closed polygon, by Newell's method
+///
+/// The polygon winds counterclockwise about the result, whose length is
+/// twice its area; zero when it encloses none.
+pub fn newell_normal(points: &[Point3]) -> Vec3 {
+    let mut normal = Vec3::ZERO;
+    for (i, p) in points.iter().enumerate() {
+        let q = points[(i + 1) % points.len()];
+        normal += Vec3::new(
+            (p.y() - q.y()) * (p.z() + q.z()),
+            (p.z() - q.z()) * (p.x() + q.x()),
+            (p.x() - q.x()) * (p.y() + q.y()),
+        );
+    }
+    normal
+}
+
+/// Iterate over the coedges of every loop of a face
+pub fn coedges_of_face(face: &Face) -> impl Iterator<Item = &Coedge> + '_ {
+    face.loops().iter().flat_map(|lp| lp.coedges())
+}
+
+/// Split `faces` into groups joined through shared vertices, each in the
+/// order given and ordered by its first face
+pub(crate) fn connected_faces(faces: Vec<&Face>) -> Vec<Vec<&Face>> {
+    let mut by_vertex: HashMap<EntityId, Vec<usize>> = HashMap::new();
+    for (i, face) in faces.iter().enumerate() {
+        for coedge in coedges_of_face(face) {
+            by_vertex.entry(coedge.start_vertex().id()).or_default().push(i);
+        }
+    }
+
+    let mut visited = vec![false; faces.len()];
+    let mut components = Vec::new();
+    for seed in 0..faces.len() {
+        if visited[seed] {
+            continue;
+        }
+        visited[seed] = true;
+        let mut members = Vec::new();
+        let mut stack = vec![seed];
+        while let Some(i) = stack.pop() {
+            members.push(i);
+            for coedge in coedges_of_face(faces[i]) {
+                for &j in &by_vertex[&coedge.start_vertex().id()] {
+                    if !visited[j] {
+                        visited[j] = true;
+                        stack.push(j);
+                    }
+                }
+            }
+        }
+        members.sort_unstable();
+        components.push(members.into_iter().map(|i| faces[i]).collect());
+    }
+    components
 }
 
 impl Default for Body {
@@ -146,11 +471,12 @@ impl Entity for Body {
     }
 }
 
-/// A shell is a connected set of faces
+/// A shell is a connected set of faces, or of loose wire edges
 #[derive(Debug, Clone)]
 pub struct Shell {
     id: EntityId,
     faces: Vec<Face>,
+    wires: Vec<Arc<Edge>>,
     is_outer: bool,
 }
 
@@ -160,6 +486,7 @@ impl Shell {
         Self {
             id: new_entity_id(),
             faces: Vec::new(),
+            wires: Vec::new(),
             is_outer: true,
         }
     }
@@ -190,6 +517,29 @@ impl Shell {
     pub fn add_face(&mut self, face: Face) {
         self.faces.push(face);
     }
+
+    /// Get the loose edges not bounding any face
+    pub fn wires(&self) -> &[Arc<Edge>] {
+        &self.wires
+    }
+
+    /// Add a loose edge
+    pub fn add_wire(&mut self, edge: Arc<Edge>) {
+        self.wires.push(edge);
+    }
+
+    /// Check if the faces close up with no free boundary
+    ///
+    /// Edges are matched by their end and middle points rather than by id,
+    /// so neighbouring faces built on separate but coincident edges still
+    /// close. Every edge must bound exactly two coedges.
+    pub fn is_closed(&self) -> bool {
+        let mut uses: HashMap<[i64; 9], usize> = HashMap::new();
+        for coedge in self.faces.iter().flat_map(coedges_of_face) {
+            *uses.entry(edge_key(coedge.edge())).or_default() += 1;
+        }
+        !uses.is_empty() && uses.values().all(|&n| n == 2)
+    }
     
     /// Check if this is the outer shell
     pub fn is_outer(&self) -> bool {
@@ -200,6 +550,50 @@ impl Shell {
     pub fn set_outer(&mut self, outer: bool) {
         self.is_outer = outer;
     }
+    
+    /// Compute bounding box, the union of the face boxes
+    pub fn bounding_box(&self) -> BoundingBox3 {
+        self.faces.iter().fold(BoundingBox3::empty(), |bbox, face| bbox.union(&face.bounding_box()))
+    }
+    
+    /// Signed volume enclosed by the faces, positive when their normals point outward
+    ///
+    /// Each loop is treated as a planar polygon through its vertices, so the
+    /// result is exact for polyhedral shells.
+    pub fn signed_volume(&self) -> f64 {
+        let mut volume = 0.0;
+        for face in &self.faces {
+            let mut face_volume = 0.0;
+            for lp in &face.loops {
+                let points: Vec<Vec3> = lp.coedges.iter().map(|c| c.start_vertex().position.to_vector()).collect();
+                // Fan of tetrahedra against the origin; inner loops wind the other way and subtract
+                for i in 1..points.len().saturating_sub(1) {
+                    face_volume += points[0].dot(&points[i].cross(&points[i + 1])) / 6.0;
+                }
+            }
+            volume += if face.orientation.is_reversed() { -face_volume } else { face_volume };
+        }
+        volume
+    }
+}
+
+/// Grid that edge end and middle points are snapped to when matching edges
+const EDGE_MATCH_GRID: f64 = 1e-6;
+
+/// Position of an edge independent of its direction, snapped to `EDGE_MATCH_GRID`
+fn edge_key(edge: &Edge) -> [i64; 9] {
+    let snap = |p: Point3| [p.x(), p.y(), p.z()].map(|x| (x / EDGE_MATCH_GRID).round() as i64);
+    let (start, end) = (edge.start_vertex.position, edge.end_vertex.position);
+    let middle = match &edge.curve {
+        Some(curve) if curve.param_range().length().is_finite() => {
+            let range = curve.param_range();
+            curve.evaluate((range.start + range.end) / 2.0)
+        }
+        _ => start.midpoint(&end),
+    };
+    let (a, b) = (snap(start).min(snap(end)), snap(start).max(snap(end)));
+    let m = snap(middle);
+    [a[0], a[1], a[2], b[0], b[1], b[2], m[0], m[1], m[2]]
 }
 
 impl Default for Shell {
@@ -224,6 +618,7 @@ pub struct Face {
     surface: Option<Arc<dyn Surface>>,
     loops: Vec<Loop>,
     orientation: Orientation,
+    color: Option<Color>,
 }
 
 impl std::fmt::Debug for Face {
@@ -233,6 +628,7 @@ impl std::fmt::Debug for Face {
             .field("surface", &self.surface.is_some())
             .field("loops", &self.loops)
             .field("orientation", &self.orientation)
+            .field("color", &self.color)
             .finish()
     }
 }
@@ -241,13 +637,17 @@ impl Clone for Face {
     fn clone(&self) -> Self {
         Self {
             id: new_entity_id(),
-            surface: None, // Cannot clone dyn Surface
+            surface: self.surface.clone(),
             loops: self.loops.clone(),
             orientation: self.orientation,
+            color: self.color,
         }
     }
 }
 
+/// Parameter width below which a face's loops are taken to span nothing
+const UV_EXTENT_TOL: f64 = 1e-9;
+
 impl Face {
     /// Create a new face
     pub fn new() -> Self {
@@ -256,6 +656,7 @@ impl Face {
             surface: None,
             loops: Vec::new(),
             orientation: Orientation::Forward,
+            color: None,
         }
     }
     
@@ -286,6 +687,20 @@ impl Face {
         self.loops.first()
     }
     
+    /// A point on a flat face and its unit normal, or None for a curved face
+    ///
+    /// The normal is `normal_at`, so it follows the plane of the surface, or
+    /// the outer loop's winding without one, and the face's orientation.
+    /// Faces with no outer loop or no area have no plane.
+    pub fn plane(&self) -> Option<(Point3, Vec3)> {
+        if self.surface.as_ref().is_some_and(|s| s.surface_type() != SurfaceType::Planar) {
+            return None;
+        }
+        let origin = self.outer_loop()?.coedges.first()?.start_vertex().position;
+        let normal = self.normal_at(0.0, 0.0);
+        (!normal.is_zero(0.0)).then_some((origin, normal))
+    }
+
     /// Get inner loops (all loops except first)
     pub fn inner_loops(&self) -> &[Loop] {
         if self.loops.len() > 1 {
@@ -305,15 +720,149 @@ impl Face {
         self.surface = surface;
     }
     
+    /// Color of this face, if it has its own
+    pub fn color(&self) -> Option<Color> {
+        self.color
+    }
+
+    /// Set the color of this face
+    pub fn set_color(&mut self, color: Option<Color>) {
+        self.color = color;
+    }
+    
     /// Check if the face has a surface
     pub fn has_surface(&self) -> bool {
         self.surface.is_some()
     }
     
+    /// Check whether the face normal agrees with its surface normal
+    ///
+    /// This is STEP's `same_sense` flag, stored as the face orientation.
+    pub fn same_sense(&self) -> bool {
+        !self.orientation.is_reversed()
+    }
+
+    /// Set whether the face normal agrees with its surface normal
+    pub fn set_same_sense(&mut self, same_sense: bool) {
+        self.orientation = if same_sense { Orientation::Forward } else { Orientation::Reversed };
+    }
+
+    /// Parameter box the face covers on its surface
+    ///
+    /// Each direction spans the face's loops, clamped to the surface's
+    /// range, so faces on unbounded surfaces such as planes get a finite
+    /// box. Where the loops span no width in a bounded direction, as along a
+    /// seam, the surface's whole range is kept. Returns `None` for faces
+    /// without a surface or boundary.
+    pub fn uv_extent(&self) -> Option<UVRange> {
+        let surface = self.surface.as_ref()?;
+        let mut extent: Option<[f64; 4]> = None;
+        for point in self.loops.iter().flat_map(|lp| &lp.coedges).flat_map(|c| crate::measure::edge_points(c.edge())) {
+            let Ok((u, v, _, _)) = surface.closest_point(&point) else { continue };
+            let [u0, u1, v0, v1] = extent.get_or_insert([u, u, v, v]);
+            (*u0, *u1, *v0, *v1) = (u0.min(u), u1.max(u), v0.min(v), v1.max(v));
+        }
+        let [u0, u1, v0, v1] = extent?;
+        let range = surface.uv_range();
+        let clamp = |start: f64, end: f64, r: nova_geom::ParamRange| {
+            let bounded = r.start.is_finite() && r.end.is_finite();
+            if bounded && end - start <= UV_EXTENT_TOL { (r.start, r.end) } else { (start.max(r.start), end.min(r.end)) }
+        };
+        let ((u0, u1), (v0, v1)) = (clamp(u0, u1, range.u), clamp(v0, v1, range.v));
+        Some(UVRange::new(u0, u1, v0, v1))
+    }
+
+    /// Box around the face's edges and, on a curved surface, the surface's
+    /// own box over its [`uv_extent`](Self::uv_extent)
+    ///
+    /// The surface box takes in bulges that no edge or vertex reaches, such
+    /// as the side of a cylinder between straight edges. Curved faces that
+    /// fill only part of their parameter box get a box somewhat larger than
+    /// they are.
+    pub fn bounding_box(&self) -> BoundingBox3 {
+        let mut bbox = BoundingBox3::empty();
+        for point in self.loops.iter().flat_map(|lp| &lp.coedges).flat_map(|c| crate::measure::edge_points(c.edge())) {
+            bbox.expand(&point);
+        }
+        let curved = self.surface.as_ref().filter(|s| s.surface_type() != SurfaceType::Planar);
+        if let (Some(surface), Some(extent)) = (curved, self.uv_extent()) {
+            bbox = bbox.union(&surface.bounding_box_over(&extent));
+        }
+        bbox
+    }
+
+    /// Nearest point to `point` on the face within its loops, and its distance
+    ///
+    /// Unlike the surface's own closest point this never lands in the part
+    /// of the surface the loops trim away.
+    pub fn closest_point(&self, point: &Point3) -> (Point3, f64) {
+        crate::measure::face_closest(self, *point)
+    }
+
+    /// Edges the face's loops run along once each way, such as the seam
+    /// closing the side of a full cylinder
+    pub fn seam_edges(&self) -> Vec<EntityId> {
+        let mut senses: HashMap<EntityId, Vec<Sense>> = HashMap::new();
+        for coedge in self.loops.iter().flat_map(|lp| &lp.coedges) {
+            senses.entry(coedge.edge.id).or_default().push(coedge.sense);
+        }
+        let mut seams: Vec<EntityId> = senses
+            .into_iter()
+            .filter(|(_, senses)| senses.len() == 2 && senses[0] != senses[1])
+            .map(|(id, _)| id)
+            .collect();
+        seams.sort_unstable();
+        seams
+    }
+
     /// Get the normal at a point on the face
     pub fn normal(&self, u: f64, v: f64) -> Option<Vec3> {
         self.surface.as_ref().map(|s| s.normal(u, v))
     }
+
+    /// Get the point at (u, v) on the face's surface
+    pub fn point_on(&self, u: f64, v: f64) -> Option<Point3> {
+        self.surface.as_ref().map(|s| s.evaluate(u, v))
+    }
+
+    /// Unit normal at (u, v), flipped when the face is reversed
+    ///
+    /// Faces without a surface use the normal of their outer loop's winding
+    /// and ignore (u, v). Returns zero when neither gives a direction.
+    pub fn normal_at(&self, u: f64, v: f64) -> Vec3 {
+        let normal = match &self.surface {
+            Some(surface) => surface.normal(u, v),
+            None => self.outer_loop().map_or(Vec3::ZERO, Loop::newell_normal),
+        };
+        if normal.is_zero(1e-12) {
+            return Vec3::ZERO;
+        }
+        let normal = normal.normalized();
+        if self.orientation.is_reversed() { -normal } else { normal }
+    }
+
+    /// Normal at (u, v) pointing away from the material of `body`
+    ///
+    /// This is `normal_at`, flipped when the shell holding the face encloses
+    /// volume with the wrong sign for its role: negative for an outer shell
+    /// or positive for a void.
+    pub fn outward_normal_at(&self, u: f64, v: f64, body: &Body) -> Vec3 {
+        let normal = self.normal_at(u, v);
+        let shell = body.shells.iter().find(|s| s.faces.iter().any(|f| f.id == self.id));
+        match shell {
+            Some(shell) if (shell.signed_volume() < 0.0) == shell.is_outer => -normal,
+            _ => normal,
+        }
+    }
+    
+    /// Other faces of `body` sharing an edge with this face
+    pub fn adjacent_faces(&self, body: &Body) -> Vec<EntityId> {
+        let edges: HashSet<EntityId> = coedges_of_face(self).map(|coedge| coedge.edge().id).collect();
+        body.faces_iter()
+            .filter(|face| face.id != self.id && coedges_of_face(face).any(|coedge| edges.contains(&coedge.edge().id)))
+            .map(|face| face.id)
+            .collect()
+    }
 }
 
 impl Default for Face {
@@ -428,6 +977,87 @@ impl Loop {
         true
     }
     
+    /// Check that consecutive coedges meet within `tol` along their geometry
+    ///
+    /// Coedges are followed along their edges' curves where the curves are
+    /// bounded, and between their vertices otherwise. A loop of one coedge
+    /// is closed when its curve is.
+    pub fn is_geometrically_closed(&self, tol: f64) -> bool {
+        if let [coedge] = &self.coedges[..] {
+            if let Some(curve) = coedge.edge().curve() {
+                return curve.is_closed(tol);
+            }
+        }
+        !self.coedges.is_empty() && (0..self.coedges.len()).all(|i| {
+            let (_, end) = self.coedges[i].geometric_ends();
+            let (start, _) = self.coedges[(i + 1) % self.coedges.len()].geometric_ends();
+            end.distance_to(&start) <= tol
+        })
+    }
+    
+    /// Chain coedges head-to-tail, flipping senses where needed
+    ///
+    /// The first coedge keeps its place and sense. Each following coedge is
+    /// the one with an endpoint nearest the current tail. Fails, leaving the
+    /// loop unchanged, if the coedges don't close into one chain within `tol`.
+    pub fn reorder_and_orient(&mut self, tol: f64) -> TopoResult<()> {
+        let mut remaining = self.coedges.clone();
+        if remaining.is_empty() {
+            return Ok(());
+        }
+
+        let first = remaining.remove(0);
+        let head = first.start_vertex().position();
+        let mut tail = first.end_vertex().position();
+        let mut ordered = Vec::with_capacity(self.coedges.len());
+        ordered.push(first);
+
+        while !remaining.is_empty() {
+            let mut best: Option<(usize, bool, f64)> = None;
+            for (i, coedge) in remaining.iter().enumerate() {
+                let keep = coedge.start_vertex().position().distance_to(&tail);
+                let flip = coedge.end_vertex().position().distance_to(&tail);
+                let (flipped, dist) = if flip < keep { (true, flip) } else { (false, keep) };
+                if dist <= tol && best.map_or(true, |(_, _, d)| dist < d) {
+                    best = Some((i, flipped, dist));
+                }
+            }
+
+            let Some((i, flipped, _)) = best else {
+                return Err(TopologyError::Inconsistency(format!(
+                    "loop breaks after {} of {} coedges",
+                    ordered.len(),
+                    self.coedges.len()
+                )));
+            };
+            let mut coedge = remaining.remove(i);
+            if flipped {
+                coedge.reverse_sense();
+            }
+            tail = coedge.end_vertex().position();
+            ordered.push(coedge);
+        }
+
+        if tail.distance_to(&head) > tol {
+            return Err(TopologyError::Inconsistency("loop does not close".to_string()));
+        }
+        self.coedges = ordered;
+        Ok(())
+    }
+    
+    /// Area-weighted normal of the polygon through the loop's vertices
+    ///
+    /// See [`newell_normal`]; curved edges contribute only their chords.
+    pub fn newell_normal(&self) -> Vec3 {
+        let points: Vec<Point3> = self.coedges.iter().map(|c| c.start_vertex().position).collect();
+        newell_normal(&points)
+    }
+
+    /// Sum of the lengths of the loop's edges
+    pub fn perimeter(&self) -> f64 {
+        self.coedges.iter().map(|c| c.edge().length()).sum()
+    }
+
     /// Get the number of coedges
     pub fn len(&self) -> usize {
         self.coedges.len()
@@ -478,6 +1108,11 @@ impl Coedge {
         &self.edge
     }
     
+    /// Replace the edge this coedge uses
+    pub fn set_edge(&mut self, edge: Arc<Edge>) {
+        self.edge = edge;
+    }
+    
     /// Get the sense
     pub fn sense(&self) -> Sense {
         self.sense
@@ -510,6 +1145,18 @@ impl Coedge {
             self.edge.start_vertex()
         }
     }
+
+    /// Start and end of the coedge's curve, or of its vertices when the
+    /// edge has no bounded curve
+    fn geometric_ends(&self) -> (Point3, Point3) {
+        let (start, end) = match self.edge.curve() {
+            Some(curve) if curve.param_range().start.is_finite() && curve.param_range().end.is_finite() => {
+                (curve.start_point(), curve.end_point())
+            }
+            _ => (self.edge.start_vertex().position(), self.edge.end_vertex().position()),
+        };
+        if self.sense.is_same() { (start, end) } else { (end, start) }
+    }
 }
 
 impl Entity for Coedge {
@@ -551,7 +1198,7 @@ impl Clone for Edge {
             id: new_entity_id(),
             start_vertex: self.start_vertex.clone(),
             end_vertex: self.end_vertex.clone(),
-            curve: None, // Cannot clone dyn Curve
+            curve: self.curve.clone(),
             tolerance: self.tolerance,
             coedges: self.coedges.clone(),
         }
@@ -608,6 +1255,71 @@ impl Edge {
         self.tolerance = tolerance;
     }
     
+    /// Copy of this edge between new vertices, keeping its id, curve and coedges
+    pub fn remapped(&self, start: Arc<Vertex>, end: Arc<Vertex>) -> Edge {
+        Edge {
+            id: self.id,
+            start_vertex: start,
+            end_vertex: end,
+            curve: self.curve.clone(),
+            tolerance: self.tolerance,
+            coedges: self.coedges.clone(),
+        }
+    }
+    
+    /// Faces of `body` with a coedge on this edge, two for a manifold edge
+    pub fn faces<'a>(&self, body: &'a Body) -> Vec<&'a Face> {
+        body.faces_iter()
+            .filter(|face| coedges_of_face(face).any(|coedge| coedge.edge().id() == self.id))
+            .collect()
+    }
+    
+    /// How the two faces of `body` at this edge meet
+    ///
+    /// The faces' normals are compared at the middle of the edge. Normals
+    /// within `tol` radians of each other are [`EdgeConvexity::Smooth`];
+    /// otherwise the way the second face turns from the first about the
+    /// edge, as the first face's loop runs along it, tells convex from
+    /// concave.
+    pub fn convexity(&self, body: &Body, tol: f64) -> EdgeConvexity {
+        let [first, second] = self.faces(body)[..] else {
+            return EdgeConvexity::Unknown;
+        };
+        let mut points = crate::measure::edge_points(self);
+        let chord = self.end_vertex.position() - self.start_vertex.position();
+        if points.len() > 1 && (points[points.len() - 1] - points[0]).dot(&chord) < 0.0 {
+            points.reverse();
+        }
+        let (middle, along) = match points.len() {
+            0 | 1 => return EdgeConvexity::Unknown,
+            2 => (points[0] + (points[1] - points[0]) * 0.5, points[1] - points[0]),
+            n => (points[n / 2], points[n / 2 + 1] - points[n / 2 - 1]),
+        };
+        let uv = |face: &Face| face.surface().map_or(Some((0.0, 0.0)), |s| s.closest_point(&middle).ok().map(|(u, v, _, _)| (u, v)));
+        let (Some((u1, v1)), Some((u2, v2))) = (uv(first), uv(second)) else {
+            return EdgeConvexity::Unknown;
+        };
+        let (n1, n2) = (first.normal_at(u1, v1), second.normal_at(u2, v2));
+        if n1.is_zero(0.0) || n2.is_zero(0.0) || along.is_zero(0.0) {
+            return EdgeConvexity::Unknown;
+        }
+        if n1.angle_to(&n2) <= tol {
+            return EdgeConvexity::Smooth;
+        }
+        let Some(coedge) = coedges_of_face(first).find(|c| c.edge().id == self.id) else {
+            return EdgeConvexity::Unknown;
+        };
+        // Loops wind about the surface's own normal, which a reversed face
+        // flips, and an inside-out shell swaps the answer
+        let along = if coedge.sense().is_opposite() != first.orientation.is_reversed() { -along } else { along };
+        let inside_out = first.outward_normal_at(u1, v1, body).dot(&n1) < 0.0;
+        if (n1.cross(&n2).dot(&along) > 0.0) != inside_out {
+            EdgeConvexity::Convex
+        } else {
+            EdgeConvexity::Concave
+        }
+    }
+    
     /// Get coedges using this edge
     pub fn coedges(&self) -> &[EntityId] {
         &self.coedges
@@ -626,12 +1338,30 @@ impl Edge {
     }
     
     /// Get the length of the edge
+    ///
+    /// This is the arc length of the curve between the parameters nearest
+    /// the two vertices, or the whole curve for an edge closing on a single
+    /// vertex. Lines and edges without a curve use the straight distance.
     pub fn length(&self) -> f64 {
-        if let Some(curve) = &self.curve {
-            let range = curve.param_range();
-            curve.arc_length(range.end)
+        let (start, end) = (self.start_vertex.position(), self.end_vertex.position());
+        let Some(curve) = self.curve.as_ref().filter(|c| c.curve_type() != CurveType::Line) else {
+            return start.distance_to(&end);
+        };
+        let range = curve.param_range();
+        let total = curve.arc_length(range.end) - curve.arc_length(range.start);
+        if self.start_vertex.id == self.end_vertex.id {
+            return total;
+        }
+        let (Ok((t0, _, _)), Ok((t1, _, _))) = (curve.closest_point(&start), curve.closest_point(&end)) else {
+            return start.distance_to(&end);
+        };
+        let between = curve.arc_length(t1) - curve.arc_length(t0);
+        // An edge on a closed curve may run across its seam
+        let closed = curve.start_point().distance_to(&curve.end_point()) <= self.tolerance;
+        if between < 0.0 && closed {
+            total + between
         } else {
-            self.start_vertex.position().distance_to(&self.end_vertex.position())
+            between.abs()
         }
     }
     
@@ -639,11 +1369,52 @@ impl Edge {
     pub fn evaluate(&self, t: f64) -> Option<Point3> {
         self.curve.as_ref().map(|c| c.evaluate(t))
     }
+
+    /// Midpoint of the edge's curve, or of its chord when it has none or the
+    /// curve is unbounded, like an imported line
+    pub fn midpoint(&self) -> Point3 {
+        match &self.curve {
+            Some(curve) if curve.param_range().length().is_finite() => {
+                let range = curve.param_range();
+                curve.evaluate((range.start + range.end) / 2.0)
+            }
+            _ => self.start_vertex().position().midpoint(&self.end_vertex().position()),
+        }
+    }
+
+    /// Curve parameter of the point on the edge nearest `p`
+    ///
+    /// Returns `None` when the edge has no curve or `p` lies further than
+    /// `tol` from it. The distance is checked by evaluating the curve at the
+    /// found parameter rather than trusting the projection.
+    pub fn parameter_at_point(&self, p: &Point3, tol: f64) -> Option<f64> {
+        let curve = self.curve.as_ref()?;
+        let (t, _, _) = curve.closest_point(p).ok()?;
+        (t.is_finite() && curve.evaluate(t).distance_to(p) <= tol).then_some(t)
+    }
     
     /// Check if the edge is degenerate (zero length)
     pub fn is_degenerate(&self) -> bool {
         self.start_vertex.position().distance_to(&self.end_vertex.position()) < self.tolerance
     }
+
+    /// Copy keeping ids, on the matching vertices in `vertices` and with the
+    /// curve passed through `map_curve`
+    fn mapped(&self, vertices: &HashMap<EntityId, Arc<Vertex>>, map_curve: &dyn Fn(&mut dyn Curve)) -> Edge {
+        let vertex = |v: &Arc<Vertex>| vertices.get(&v.id).cloned().unwrap_or_else(|| v.clone());
+        Edge {
+            id: self.id,
+            start_vertex: vertex(&self.start_vertex),
+            end_vertex: vertex(&self.end_vertex),
+            curve: self.curve.as_ref().map(|curve| {
+                let mut mapped = curve.clone_box();
+                map_curve(mapped.as_mut());
+                Arc::from(mapped)
+            }),
+            tolerance: self.tolerance,
+            coedges: self.coedges.clone(),
+        }
+    }
 }
 
 impl Entity for Edge {
@@ -734,6 +1505,27 @@ impl Vertex {
     pub fn coincides_with(&self, other: &Vertex) -> bool {
         self.position.distance_to(&other.position) < self.tolerance.max(other.tolerance)
     }
+    
+    /// Edges of `body` starting or ending at this vertex
+    pub fn adjacent_edges(&self, body: &Body) -> Vec<EntityId> {
+        body.edges_iter()
+            .filter(|edge| self.bounds(edge))
+            .map(|edge| edge.id)
+            .collect()
+    }
+    
+    /// Faces of `body` with a boundary edge at this vertex
+    pub fn adjacent_faces(&self, body: &Body) -> Vec<EntityId> {
+        body.faces_iter()
+            .filter(|face| coedges_of_face(face).any(|coedge| self.bounds(coedge.edge())))
+            .map(|face| face.id)
+            .collect()
+    }
+    
+    /// Check if this vertex is an end of `edge`
+    fn bounds(&self, edge: &Edge) -> bool {
+        edge.start_vertex.id == self.id || edge.end_vertex.id == self.id
+    }
 }
 
 impl Entity for Vertex {
@@ -780,6 +1572,56 @@ mod tests {
         assert_eq!(edge.length(), 1.0);
     }
 
+    #[test]
+    fn test_edge_parameter_at_point() {
+        let (a, b) = (Point3::new(0.0, 0.0, 0.0), Point3::new(4.0, 0.0, 0.0));
+        let line = nova_geom::Line::segment(a, b).unwrap();
+        let edge = Edge::with_curve(Arc::new(Vertex::new(a)), Arc::new(Vertex::new(b)), Arc::new(line));
+        let t = edge.parameter_at_point(&Point3::new(2.0, 0.0, 0.0), 1e-9).unwrap();
+        assert!(edge.evaluate(t).unwrap().distance_to(&Point3::new(2.0, 0.0, 0.0)) < 1e-9);
+        assert!(edge.parameter_at_point(&Point3::new(2.0, 0.1, 0.0), 1e-9).is_none());
+
+        let half = nova_geom::CircularArc::new(Point3::ORIGIN, 1.0, Vec3::Z, Vec3::X, std::f64::consts::PI).unwrap();
+        let (start, end) = (Arc::new(Vertex::new(Point3::new(1.0, 0.0, 0.0))), Arc::new(Vertex::new(Point3::new(-1.0, 0.0, 0.0))));
+        let arc = Edge::with_curve(start, end, Arc::new(half));
+        let t = arc.parameter_at_point(&Point3::new(0.0, 1.0, 0.0), 1e-9).unwrap();
+        assert!((t - 0.5).abs() < 1e-9);
+        assert!(arc.parameter_at_point(&Point3::new(0.0, -1.0, 0.0), 1e-9).is_none());
+        assert!(Edge::new(Arc::new(Vertex::new(a)), Arc::new(Vertex::new(b))).parameter_at_point(&a, 1e-9).is_none());
+    }
+
+    #[test]
+    fn test_face_closest_point_stays_inside_trim() {
+        use nova_geom::nurbs::NurbsSurface;
+        use nova_math::Point4;
+        // A 4 x 4 bilinear sheet trimmed to the square [1, 2] x [1, 2]
+        let points = [0.0, 4.0].iter()
+            .map(|&x| [0.0, 4.0].iter().map(|&y| Point4::new(x, y, 0.0, 1.0)).collect())
+            .collect();
+        let knots = vec![0.0, 0.0, 1.0, 1.0];
+        let sheet = NurbsSurface::new(1, 1, points, knots.clone(), knots).unwrap();
+        let corners = [(1.0, 1.0), (2.0, 1.0), (2.0, 2.0), (1.0, 2.0)]
+            .map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let coedges = (0..4).map(|i| {
+            let (a, b) = (corners[i].clone(), corners[(i + 1) % 4].clone());
+            let line = nova_geom::Line::segment(a.position(), b.position()).unwrap();
+            Coedge::new(Arc::new(Edge::with_curve(a, b, Arc::new(line))), Sense::Same)
+        }).collect();
+        let mut face = Face::with_surface(Arc::new(sheet));
+        face.add_loop(Loop::from_coedges(coedges));
+
+        let query = Point3::new(3.0, 1.5, 1.0);
+        let (_, _, untrimmed, _) = face.surface().unwrap().closest_point(&query).unwrap();
+        assert!(untrimmed.x() > 2.5);
+        let (point, distance) = face.closest_point(&query);
+        assert!(point.distance_to(&Point3::new(2.0, 1.5, 0.0)) < 1e-6);
+        assert!((distance - 2.0_f64.sqrt()).abs() < 1e-6);
+
+        let (point, distance) = face.closest_point(&Point3::new(1.25, 1.75, 2.0));
+        assert!(point.distance_to(&Point3::new(1.25, 1.75, 0.0)) < 1e-6);
+        assert!((distance - 2.0).abs() < 1e-6);
+    }
+
     #[test]
     fn test_loop() {
         let v1 = Arc::new(Vertex::new(Point3::new(0.0, 0.0, 0.0)));
@@ -799,6 +1641,343 @@ mod tests {
         
         let lp = Loop::from_coedges(vec![c1, c2, c3, c4]);
         assert!(lp.is_closed());
+        assert!((lp.perimeter() - 4.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_loop_geometric_closure() {
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let coedges: Vec<Coedge> = (0..4)
+            .map(|i| {
+                let (a, b) = (corners[i].clone(), corners[(i + 1) % 4].clone());
+                let line = nova_geom::Line::segment(a.position(), b.position()).unwrap();
+                Coedge::new(Arc::new(Edge::with_curve(a, b, Arc::new(line))), Sense::Same)
+            })
+            .collect();
+        assert!(Loop::from_coedges(coedges.clone()).is_geometrically_closed(1e-9));
+        assert!(!Loop::from_coedges(coedges[..3].to_vec()).is_geometrically_closed(1e-9));
+
+        let v = corners[1].clone();
+        let circle = nova_geom::CircularArc::circle(Point3::ORIGIN, 1.0, Vec3::Z).unwrap();
+        let edge = Edge::with_curve(v.clone(), v.clone(), Arc::new(circle));
+        assert!(Loop::from_coedges(vec![Coedge::new(Arc::new(edge), Sense::Same)]).is_geometrically_closed(1e-9));
+        let half = nova_geom::CircularArc::new(Point3::ORIGIN, 1.0, Vec3::Z, Vec3::X, std::f64::consts::PI).unwrap();
+        let edge = Edge::with_curve(v.clone(), v, Arc::new(half));
+        assert!(!Loop::from_coedges(vec![Coedge::new(Arc::new(edge), Sense::Same)]).is_geometrically_closed(1e-9));
+    }
+
+    #[test]
+    fn test_outward_normals_of_box() {
+        // build_cube winds its loops inward
+        let cube = crate::euler::build_cube(2.0).unwrap();
+        for face in cube.faces() {
+            let outward = face.outward_normal_at(0.0, 0.0, &cube);
+            let points: Vec<Point3> = face.outer_loop().unwrap().coedges().iter().map(|c| c.start_vertex().position()).collect();
+            let center = points.iter().fold(Vec3::ZERO, |sum, p| sum + p.to_vector()) / points.len() as f64;
+            assert!((outward.dot(&center) - 1.0).abs() < 1e-9);
+            assert!((face.normal_at(0.0, 0.0) + outward).is_zero(1e-12));
+        }
+
+        // An extruded square carries planar surfaces
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let mut lp = Loop::new();
+        for i in 0..4 {
+            lp.add_coedge(Coedge::new(Arc::new(Edge::new(corners[i].clone(), corners[(i + 1) % 4].clone())), Sense::Same));
+        }
+        let plane = nova_geom::PlanarSurface::new(Point3::ORIGIN, Vec3::X, Vec3::Y).unwrap();
+        let mut profile = Face::with_surface(Arc::new(plane));
+        profile.add_loop(lp);
+        let block = crate::EulerAdvanced::extrude_face(&profile, Vec3::Z, 1.0).unwrap();
+        let center = Point3::new(0.5, 0.5, 0.5);
+        for face in block.faces() {
+            let surface = face.surface().unwrap();
+            let (u, v, _, _) = surface.closest_point(&face.outer_loop().unwrap().coedges()[0].start_vertex().position()).unwrap();
+            let (u, v) = (u + 0.1, v + 0.1);
+            let outward = face.outward_normal_at(u, v, &block);
+            assert!((face.point_on(u, v).unwrap() - center).dot(&outward) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_box_traversal() {
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let mut lp = Loop::new();
+        for i in 0..4 {
+            lp.add_coedge(Coedge::new(Arc::new(Edge::new(corners[i].clone(), corners[(i + 1) % 4].clone())), Sense::Same));
+        }
+        let mut profile = Face::new();
+        profile.add_loop(lp);
+        let block = crate::EulerAdvanced::extrude_face(&profile, Vec3::Z, 1.0).unwrap();
+
+        assert_eq!(block.faces_iter().count(), 6);
+        assert_eq!(block.edges_iter().count(), 12);
+        assert_eq!(block.vertices_iter().count(), 8);
+        assert!(block.faces_iter().all(|face| coedges_of_face(face).count() == 4));
+        for edge in block.edges_iter() {
+            let faces = edge.faces(&block);
+            assert_eq!(faces.len(), 2);
+            assert_ne!(faces[0].id(), faces[1].id());
+        }
+    }
+
+    #[test]
+    fn test_box_adjacency() {
+        let corners = [(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)].map(|(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0))));
+        let mut lp = Loop::new();
+        for i in 0..4 {
+            lp.add_coedge(Coedge::new(Arc::new(Edge::new(corners[i].clone(), corners[(i + 1) % 4].clone())), Sense::Same));
+        }
+        let mut profile = Face::new();
+        profile.add_loop(lp);
+        let block = crate::EulerAdvanced::extrude_face(&profile, Vec3::Z, 1.0).unwrap();
+
+        for vertex in block.vertices_iter() {
+            assert_eq!(vertex.adjacent_faces(&block).len(), 3);
+            assert_eq!(vertex.adjacent_edges(&block).len(), 3);
+        }
+        let faces = block.faces();
+        for face in &faces {
+            let neighbours = face.adjacent_faces(&block);
+            assert_eq!(neighbours.len(), 4);
+            assert!(!neighbours.contains(&face.id()));
+        }
+        // The bottom borders every side but not the top
+        assert!(!faces[0].adjacent_faces(&block).contains(&faces[1].id()));
+    }
+
+    #[test]
+    fn test_circular_edge_length() {
+        let r = 2.5;
+        let v = Arc::new(Vertex::new(Point3::new(r, 0.0, 0.0)));
+        let circle = nova_geom::CircularArc::circle(Point3::ORIGIN, r, Vec3::Z).unwrap();
+        let edge = Edge::with_curve(v.clone(), v, Arc::new(circle));
+        assert!((edge.length() - 2.0 * std::f64::consts::PI * r).abs() < 1e-9);
+
+        // A quarter of the same circle, bounded by its vertices
+        let a = Arc::new(Vertex::new(Point3::new(0.0, r, 0.0)));
+        let b = Arc::new(Vertex::new(Point3::new(-r, 0.0, 0.0)));
+        let quarter = Edge::with_curve(a, b, Arc::new(circle));
+        assert!((quarter.length() - std::f64::consts::FRAC_PI_2 * r).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_reorder_and_orient_shuffled_loop() {
+        let corners = [
+            Point3::new(0.0, 0.0, 0.0),
+            Point3::new(1.0, 0.0, 0.0),
+            Point3::new(1.0, 1.0, 0.0),
+            Point3::new(0.0, 1.0, 0.0),
+        ];
+        let v: Vec<_> = corners.iter().map(|&p| Arc::new(Vertex::new(p))).collect();
+        let e: Vec<_> = (0..4)
+            .map(|i| Arc::new(Edge::new(v[i].clone(), v[(i + 1) % 4].clone())))
+            .collect();
+
+        // Shuffled, with two coedges running the wrong way
+        let mut lp = Loop::from_coedges(vec![
+            Coedge::new(e[0].clone(), Sense::Same),
+            Coedge::new(e[2].clone(), Sense::Opposite),
+            Coedge::new(e[3].clone(), Sense::Same),
+            Coedge::new(e[1].clone(), Sense::Opposite),
+        ]);
+        assert!(!lp.is_closed());
+
+        lp.reorder_and_orient(1e-9).unwrap();
+        assert!(lp.is_closed());
+        let order: Vec<_> = lp.coedges().iter().map(|c| c.edge().id()).collect();
+        assert_eq!(order, vec![e[0].id(), e[1].id(), e[2].id(), e[3].id()]);
+        assert!(lp.coedges().iter().all(|c| c.sense() == Sense::Same));
+
+        let mut open = Loop::from_coedges(vec![
+            Coedge::new(e[0].clone(), Sense::Same),
+            Coedge::new(e[1].clone(), Sense::Same),
+        ]);
+        assert!(open.reorder_and_orient(1e-9).is_err());
+        assert_eq!(open.coedges()[0].edge().id(), e[0].id());
+    }
+
+    #[test]
+    fn test_convert_units_inch_cube_to_mm() {
+        let mut cube = crate::euler::build_cube(1.0).unwrap();
+        let edges_before = cube.edges().len();
+        let vertices_before = cube.vertices().len();
+
+        cube.convert_units(Units::Inches, Units::Millimeters);
+
+        let bbox = cube.bounding_box();
+        let size = bbox.extents();
+        assert!((size.x() - 25.4).abs() < 1e-9);
+        assert!((size.y() - 25.4).abs() < 1e-9);
+        assert!((size.z() - 25.4).abs() < 1e-9);
+        assert_eq!(cube.edges().len(), edges_before);
+        assert_eq!(cube.vertices().len(), vertices_before);
+    }
+
+    /// Edge of `body` running along Y through (x, z)
+    fn edge_along_y(body: &Body, x: f64, z: f64) -> &Edge {
+        body.edges().into_iter()
+            .find(|e| [e.start_vertex(), e.end_vertex()].iter().all(|v| {
+                let p = v.position();
+                (p.x() - x).abs() < 1e-9 && (p.z() - z).abs() < 1e-9
+            }))
+            .unwrap()
+    }
+
+    /// Profile in the XZ plane through `points`, extruded 2 along -Y
+    fn extruded_profile(points: &[(f64, f64)], arcs: &[(usize, Point3)]) -> Body {
+        let v: Vec<_> = points.iter().map(|&(x, z)| Arc::new(Vertex::new(Point3::new(x, 0.0, z)))).collect();
+        let coedges = (0..v.len()).map(|i| {
+            let (a, b) = (v[i].clone(), v[(i + 1) % v.len()].clone());
+            let edge = match arcs.iter().find(|(at, _)| *at == i) {
+                Some(&(_, center)) => {
+                    let from = a.position() - center;
+                    let normal = from.cross(&(b.position() - center)).normalized();
+                    let arc = nova_geom::CircularArc::new(center, from.length(), normal, from, std::f64::consts::FRAC_PI_2).unwrap();
+                    Edge::with_curve(a, b, Arc::new(arc))
+                }
+                None => Edge::new(a, b),
+            };
+            Coedge::new(Arc::new(edge), Sense::Same)
+        }).collect();
+        let mut profile = Face::new();
+        profile.add_loop(Loop::from_coedges(coedges));
+        crate::EulerAdvanced::extrude_face(&profile, -Vec3::Y, 2.0).unwrap()
+    }
+
+    #[test]
+    fn test_edge_convexity_of_box_and_channel() {
+        let block = extruded_profile(&[(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (0.0, 1.0)], &[]);
+        assert_eq!(block.edges().len(), 12);
+        assert!(block.edges().iter().all(|e| e.convexity(&block, 1e-6) == EdgeConvexity::Convex));
+
+        // U channel whose floor at z = 1 meets its walls in concave edges
+        let channel = extruded_profile(&[(0.0, 0.0), (3.0, 0.0), (3.0, 2.0), (2.0, 2.0), (2.0, 1.0), (1.0, 1.0), (1.0, 2.0), (0.0, 2.0)], &[]);
+        for (x, z) in [(1.0, 1.0), (2.0, 1.0)] {
+            assert_eq!(edge_along_y(&channel, x, z).convexity(&channel, 1e-6), EdgeConvexity::Concave);
+        }
+        for (x, z) in [(0.0, 0.0), (3.0, 2.0), (2.0, 2.0), (1.0, 2.0)] {
+            assert_eq!(edge_along_y(&channel, x, z).convexity(&channel, 1e-6), EdgeConvexity::Convex);
+        }
+        let concave = channel.edges().iter().filter(|e| e.convexity(&channel, 1e-6) == EdgeConvexity::Concave).count();
+        assert_eq!(concave, 2);
+    }
+
+    #[test]
+    fn test_edge_convexity_at_tangent_round() {
+        // Block with its top right corner rounded by a quarter circle
+        // tangent to the right side and the top
+        let rounded = extruded_profile(&[(0.0, 0.0), (2.0, 0.0), (2.0, 1.0), (1.0, 2.0), (0.0, 2.0)], &[(2, Point3::new(1.0, 0.0, 1.0))]);
+        for (x, z) in [(2.0, 1.0), (1.0, 2.0)] {
+            assert_eq!(edge_along_y(&rounded, x, z).convexity(&rounded, 1e-6), EdgeConvexity::Smooth);
+        }
+        assert_eq!(edge_along_y(&rounded, 0.0, 2.0).convexity(&rounded, 1e-6), EdgeConvexity::Convex);
+    }
+
+    #[test]
+    fn test_bounding_sphere_and_cylinder_of_box() {
+        let offset = Vec3::new(5.0, -2.0, 3.0);
+        let cube = crate::euler::build_cube(2.0).unwrap().transformed(&Transform3::from_translation_vec(offset));
+        let center = Point3::ORIGIN + offset;
+
+        let (sphere_center, radius) = cube.bounding_sphere();
+        assert!(sphere_center.distance_to(&center) < 1e-9);
+        assert!((radius - cube.bounding_box().diagonal() / 2.0).abs() < 1e-9);
+
+        let (cylinder_center, radius, height) = cube.bounding_cylinder(&Vec3::Z);
+        assert!(cylinder_center.distance_to(&center) < 1e-9);
+        assert!((radius - 2.0_f64.sqrt()).abs() < 1e-9);
+        assert!((height - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_scale_keeps_vertices_on_curves() {
+        let v = Arc::new(Vertex::new(Point3::new(1.0, 0.0, 1.0)));
+        let circle = nova_geom::CircularArc::circle(Point3::new(0.0, 0.0, 1.0), 1.0, Vec3::Z).unwrap();
+        let edge = Arc::new(Edge::with_curve(v.clone(), v, Arc::new(circle)));
+        let mut face = Face::new();
+        face.add_loop(Loop::from_coedges(vec![Coedge::new(edge, Sense::Same)]));
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut body = Body::new();
+        body.add_shell(shell);
+
+        body.scale(2.0);
+
+        let edge = body.edges()[0];
+        assert_eq!(edge.start_vertex().position(), Point3::new(2.0, 0.0, 2.0));
+        let (_, _, dist) = edge.curve().unwrap().closest_point(&edge.start_vertex().position()).unwrap();
+        assert!(dist < 1e-9);
+    }
+
+    #[test]
+    fn test_transformed_moves_vertices_and_curves() {
+        let v = Arc::new(Vertex::new(Point3::new(1.0, 0.0, 0.0)));
+        let circle = nova_geom::CircularArc::circle(Point3::ORIGIN, 1.0, Vec3::Z).unwrap();
+        let edge = Arc::new(Edge::with_curve(v.clone(), v.clone(), Arc::new(circle)));
+        let mut face = Face::new();
+        face.add_loop(Loop::from_coedges(vec![Coedge::new(edge, Sense::Same)]));
+        let mut shell = Shell::new();
+        shell.add_face(face);
+        let mut body = Body::new();
+        body.add_shell(shell);
+
+        let t = Transform3::new(
+            &nova_math::Quaternion::from_axis_angle(&Vec3::X, std::f64::consts::FRAC_PI_2),
+            Vec3::new(0.0, 0.0, 5.0),
+        );
+        let moved = body.transformed(&t);
+
+        let edge = moved.edges()[0];
+        assert_eq!(edge.start_vertex().id(), v.id());
+        assert!(edge.start_vertex().position().distance_to(&Point3::new(1.0, 0.0, 5.0)) < 1e-12);
+        assert!(edge.curve().unwrap().evaluate(0.25).distance_to(&Point3::new(0.0, 0.0, 6.0)) < 1e-9);
+        assert_eq!(body.edges()[0].start_vertex().position(), Point3::new(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_placement_moves_with_geometry() {
+        let cube = crate::euler::build_cube(2.0).unwrap();
+        let corner = |body: &Body| body.vertices().iter().map(|v| v.position())
+            .fold(Point3::new(f64::INFINITY, f64::INFINITY, f64::INFINITY), |a, b| Point3::new(a.x().min(b.x()), a.y().min(b.y()), a.z().min(b.z())));
+        let placed = cube.transformed(&Transform3::from_translation(10.0, 0.0, 0.0));
+        assert!(placed.placement().approx_eq(&Transform3::from_translation(10.0, 0.0, 0.0), 1e-12, 1e-12));
+        assert!(corner(&placed.local_geometry()).distance_to(&corner(&cube)) < 1e-12);
+        assert!(corner(&placed.world_geometry()).distance_to(&Point3::new(9.0, -1.0, -1.0)) < 1e-12);
+
+        let mut moved = placed.clone();
+        let turn = Transform3::from_axis_angle(&Vec3::Z, std::f64::consts::FRAC_PI_2);
+        moved.set_placement(turn);
+        assert!(moved.placement().approx_eq(&turn, 1e-12, 1e-12));
+        assert!(corner(&moved).distance_to(&corner(&cube)) < 1e-9);
+        assert!(corner(&moved.local_geometry()).distance_to(&corner(&cube)) < 1e-9);
+    }
+
+    #[test]
+    fn test_body_types() {
+        let cube = crate::build_cube(2.0).unwrap();
+        assert_eq!(cube.body_type(), BodyType::Solid);
+
+        let mut sheet = Body::new();
+        let mut shell = Shell::new();
+        shell.add_face(cube.faces()[0].clone());
+        sheet.add_shell(shell);
+        assert_eq!(sheet.body_type(), BodyType::Sheet);
+        assert_eq!(crate::body_volume(&sheet), Err(TopologyError::NotSolid(BodyType::Sheet)));
+
+        let corners = [Point3::ORIGIN, Point3::new(1.0, 0.0, 0.0), Point3::new(1.0, 1.0, 0.0)].map(|p| Arc::new(Vertex::new(p)));
+        let mut wire = Body::new();
+        let mut shell = Shell::new();
+        shell.add_wire(Arc::new(Edge::new(corners[0].clone(), corners[1].clone())));
+        shell.add_wire(Arc::new(Edge::new(corners[1].clone(), corners[2].clone())));
+        wire.add_shell(shell);
+        assert_eq!(wire.body_type(), BodyType::Wire);
+        assert_eq!(wire.edges().len(), 2);
+        assert_eq!(wire.vertices().len(), 3);
+
+        let mut mixed = cube.clone();
+        mixed.add_shell(wire.shells()[0].clone());
+        assert_eq!(mixed.body_type(), BodyType::Mixed);
+        assert_eq!(Body::new().body_type(), BodyType::Mixed);
     }
 
     #[test]
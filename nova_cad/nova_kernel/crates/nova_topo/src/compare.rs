@@ -0,0 +1,330 @@
+//! Geometric comparison of bodies independent of entity ids
+
+use crate::{Body, Face, Loop, Entity, EntityId, TopologicalEntity};
+use nova_math::{Point3, ToleranceContext};
+
+/// A single difference found while comparing two bodies
+#[derive(Debug, Clone, PartialEq)]
+pub enum BodyDifference {
+    /// Shell counts differ
+    ShellCount {
+        /// Count in the first body
+        first: usize,
+        /// Count in the second body
+        second: usize,
+    },
+    /// Face counts differ
+    FaceCount {
+        /// Count in the first body
+        first: usize,
+        /// Count in the second body
+        second: usize,
+    },
+    /// Edge counts differ
+    EdgeCount {
+        /// Count in the first body
+        first: usize,
+        /// Count in the second body
+        second: usize,
+    },
+    /// Vertex counts differ
+    VertexCount {
+        /// Count in the first body
+        first: usize,
+        /// Count in the second body
+        second: usize,
+    },
+    /// A vertex of the first body has no coincident vertex in the second
+    UnmatchedVertex {
+        /// Vertex id in the first body
+        vertex: EntityId,
+        /// Vertex position
+        position: Point3,
+    },
+    /// A face of the first body has no equivalent face in the second
+    UnmatchedFace {
+        /// Face id in the first body
+        face: EntityId,
+    },
+}
+
+/// Structured result of comparing two bodies
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BodyDiff {
+    /// Differences found, empty if the bodies are equivalent
+    pub differences: Vec<BodyDifference>,
+}
+
+impl BodyDiff {
+    /// Check if no differences were found
+    pub fn is_equivalent(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// Check if two bodies are geometrically equivalent up to tolerance
+pub fn bodies_equivalent(a: &Body, b: &Body, tol: &ToleranceContext) -> bool {
+    compare_bodies(a, b, tol).is_equivalent()
+}
+
+/// Compare two bodies geometrically, ignoring entity ids and ordering
+///
+/// Faces are matched by surface type, surface geometry, orientation and
+/// loop shape. Loops match if their coedges visit the same points in the
+/// same cyclic order, starting anywhere.
+pub fn compare_bodies(a: &Body, b: &Body, tol: &ToleranceContext) -> BodyDiff {
+    let mut diff = BodyDiff::default();
+
+    let counts = [
+        (a.shells().len(), b.shells().len()),
+        (a.faces().len(), b.faces().len()),
+        (a.edges().len(), b.edges().len()),
+        (a.vertices().len(), b.vertices().len()),
+    ];
+    for (i, (first, second)) in counts.into_iter().enumerate() {
+        if first != second {
+            diff.differences.push(match i {
+                0 => BodyDifference::ShellCount { first, second },
+                1 => BodyDifference::FaceCount { first, second },
+                2 => BodyDifference::EdgeCount { first, second },
+                _ => BodyDifference::VertexCount { first, second },
+            });
+        }
+    }
+
+    let eps = tol.resabs();
+
+    // Vertices: one-to-one matching by position
+    let b_vertices = b.vertices();
+    let mut used = vec![false; b_vertices.len()];
+    for vertex in a.vertices() {
+        let position = vertex.position();
+        let found = b_vertices.iter().enumerate().position(|(j, other)| {
+            !used[j] && other.position().distance_to(&position) <= eps
+        });
+        match found {
+            Some(j) => used[j] = true,
+            None => diff.differences.push(BodyDifference::UnmatchedVertex {
+                vertex: vertex.id(),
+                position,
+            }),
+        }
+    }
+
+    // Faces: one-to-one matching by geometry and loop shape
+    let b_faces = b.faces();
+    let mut used = vec![false; b_faces.len()];
+    for face in a.faces() {
+        let found = b_faces.iter().enumerate().position(|(j, other)| {
+            !used[j] && faces_equivalent(face, other, tol)
+        });
+        match found {
+            Some(j) => used[j] = true,
+            None => diff.differences.push(BodyDifference::UnmatchedFace { face: face.id() }),
+        }
+    }
+
+    diff
+}
+
+/// Check if two faces are geometrically equivalent
+fn faces_equivalent(a: &Face, b: &Face, tol: &ToleranceContext) -> bool {
+    if a.orientation() != b.orientation() || a.loops().len() != b.loops().len() {
+        return false;
+    }
+
+    let eps = tol.resabs();
+    match (a.surface(), b.surface()) {
+        (None, None) => {}
+        (Some(sa), Some(sb)) => {
+            if sa.surface_type() != sb.surface_type() {
+                return false;
+            }
+            // Boundary points of `a` must lie on `b` with matching normals
+            for point in a.loops().iter().flat_map(loop_points) {
+                let (Ok((ua, va, _, _)), Ok((ub, vb, _, dist))) =
+                    (sa.closest_point(&point), sb.closest_point(&point))
+                else {
+                    return false;
+                };
+                if dist > eps {
+                    return false;
+                }
+                let cos = sa.normal(ua, va).dot(&sb.normal(ub, vb));
+                if cos < tol.angle_tol().cos() {
+                    return false;
+                }
+            }
+        }
+        _ => return false,
+    }
+
+    let mut used = vec![false; b.loops().len()];
+    a.loops().iter().all(|la| {
+        let found = b.loops().iter().enumerate().position(|(j, lb)| {
+            !used[j] && loops_equivalent(la, lb, eps)
+        });
+        match found {
+            Some(j) => {
+                used[j] = true;
+                true
+            }
+            None => false,
+        }
+    })
+}
+
+/// Check if two loops visit the same points in the same cyclic order
+fn loops_equivalent(a: &Loop, b: &Loop, eps: f64) -> bool {
+    let pa = loop_points(a);
+    let pb = loop_points(b);
+    if pa.len() != pb.len() {
+        return false;
+    }
+    if pa.is_empty() {
+        return true;
+    }
+
+    // Each coedge contributes two points, so rotate by whole coedges
+    (0..pb.len()).step_by(2).any(|shift| {
+        pa.iter()
+            .enumerate()
+            .all(|(i, p)| p.distance_to(&pb[(i + shift) % pb.len()]) <= eps)
+    })
+}
+
+/// Start point and midpoint of every coedge in a loop, in traversal order
+fn loop_points(lp: &Loop) -> Vec<Point3> {
+    lp.coedges()
+        .iter()
+        .flat_map(|coedge| [coedge.start_vertex().position(), coedge.edge().midpoint()])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::euler::build_cube;
+    use crate::{Coedge, Edge, Shell, Vertex, Sense};
+    use nova_geom::{CircularArc, CylindricalSurface, Line, PlanarSurface};
+    use nova_math::{Transform3, Vec3};
+    use std::collections::HashMap;
+    use std::sync::Arc;
+
+    /// Rebuild a body with every vertex moved, preserving vertex and edge sharing
+    fn transformed_copy(body: &Body, transform: &Transform3) -> Body {
+        let mut vertices: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+        let mut edges: HashMap<EntityId, Arc<Edge>> = HashMap::new();
+        let mut map_vertex = |v: &Vertex| {
+            vertices.entry(v.id())
+                .or_insert_with(|| Arc::new(Vertex::new(transform.apply_to_point(&v.position()))))
+                .clone()
+        };
+
+        let mut out = Body::new();
+        for shell in body.shells() {
+            let mut new_shell = Shell::new();
+            for face in shell.faces() {
+                let mut new_face = Face::new();
+                for lp in face.loops() {
+                    let mut new_loop = Loop::new();
+                    for coedge in lp.coedges() {
+                        let edge = coedge.edge();
+                        let new_edge = match edges.get(&edge.id()) {
+                            Some(e) => e.clone(),
+                            None => {
+                                let start = map_vertex(edge.start_vertex());
+                                let end = map_vertex(edge.end_vertex());
+                                let e = Arc::new(Edge::new(start, end));
+                                edges.insert(edge.id(), e.clone());
+                                e
+                            }
+                        };
+                        new_loop.add_coedge(Coedge::new(new_edge, coedge.sense()));
+                    }
+                    new_face.add_loop(new_loop);
+                }
+                new_shell.add_face(new_face);
+            }
+            out.add_shell(new_shell);
+        }
+        out
+    }
+
+    fn build_cylinder(radius: f64, height: f64) -> Body {
+        let bottom_v = Arc::new(Vertex::new(Point3::new(radius, 0.0, 0.0)));
+        let top_v = Arc::new(Vertex::new(Point3::new(radius, 0.0, height)));
+        let top_center = Point3::new(0.0, 0.0, height);
+
+        let bottom_circle = Arc::new(Edge::with_curve(
+            bottom_v.clone(),
+            bottom_v.clone(),
+            Arc::new(CircularArc::circle(Point3::ORIGIN, radius, Vec3::Z).unwrap()),
+        ));
+        let top_circle = Arc::new(Edge::with_curve(
+            top_v.clone(),
+            top_v.clone(),
+            Arc::new(CircularArc::circle(top_center, radius, Vec3::Z).unwrap()),
+        ));
+        let seam = Arc::new(Edge::with_curve(
+            bottom_v.clone(),
+            top_v.clone(),
+            Arc::new(Line::segment(bottom_v.position(), top_v.position()).unwrap()),
+        ));
+
+        let mut shell = Shell::new();
+
+        let mut bottom = Face::with_surface(Arc::new(
+            PlanarSurface::new(Point3::ORIGIN, Vec3::Y, Vec3::X).unwrap(),
+        ));
+        bottom.add_loop(Loop::from_coedges(vec![Coedge::new(bottom_circle.clone(), Sense::Opposite)]));
+        shell.add_face(bottom);
+
+        let mut top = Face::with_surface(Arc::new(
+            PlanarSurface::new(top_center, Vec3::X, Vec3::Y).unwrap(),
+        ));
+        top.add_loop(Loop::from_coedges(vec![Coedge::new(top_circle.clone(), Sense::Same)]));
+        shell.add_face(top);
+
+        let mut side = Face::with_surface(Arc::new(
+            CylindricalSurface::new(Point3::ORIGIN, Vec3::Z, radius, Vec3::X).unwrap(),
+        ));
+        side.add_loop(Loop::from_coedges(vec![
+            Coedge::new(bottom_circle, Sense::Same),
+            Coedge::new(seam.clone(), Sense::Same),
+            Coedge::new(top_circle, Sense::Opposite),
+            Coedge::new(seam, Sense::Opposite),
+        ]));
+        shell.add_face(side);
+
+        let mut body = Body::new();
+        body.add_shell(shell);
+        body
+    }
+
+    #[test]
+    fn test_translated_round_trip_is_equivalent() {
+        let tol = ToleranceContext::default();
+        let cube = build_cube(10.0).unwrap();
+
+        let offset = Transform3::from_translation(1.3, -2.7, 0.9);
+        let moved = transformed_copy(&cube, &offset);
+        let back = transformed_copy(&moved, &offset.inverse());
+
+        assert!(bodies_equivalent(&cube, &back, &tol));
+        assert!(!bodies_equivalent(&cube, &moved, &tol));
+    }
+
+    #[test]
+    fn test_box_and_cylinder_differ() {
+        let tol = ToleranceContext::default();
+        let cube = build_cube(2.0).unwrap();
+        let cylinder = build_cylinder(1.0, 2.0);
+
+        assert!(bodies_equivalent(&cylinder, &cylinder, &tol));
+
+        let diff = compare_bodies(&cube, &cylinder, &tol);
+        assert!(!diff.is_equivalent());
+        assert!(diff.differences.contains(&BodyDifference::FaceCount { first: 6, second: 3 }));
+    }
+}
@@ -2,6 +2,7 @@
 
 use crate::{Body, Shell, Face, Loop, Coedge, Edge, Vertex, EntityId, Sense, TopoResult, TopologyError, Entity};
 use nova_math::Point3;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Errors that can occur during Euler operations
@@ -255,16 +256,24 @@ pub fn build_cube(size: f64) -> TopoResult<Body> {
     let mut body = Body::new();
     let mut shell = Shell::new();
     
+    // Each edge is made by the first face to use it and reversed by the second
+    let mut edges: HashMap<(usize, usize), Arc<Edge>> = HashMap::new();
     for face_verts in face_indices {
         let mut face = Face::new();
         let mut lp = Loop::new();
         
-        // Create edges for this face
         for i in 0..4 {
-            let v1 = Arc::new(vertices[face_verts[i]].clone());
-            let v2 = Arc::new(vertices[face_verts[(i + 1) % 4]].clone());
-            let edge = Arc::new(Edge::new(v1, v2));
-            let coedge = Coedge::new(edge, Sense::Same);
+            let (a, b) = (face_verts[i], face_verts[(i + 1) % 4]);
+            let coedge = match edges.get(&(b, a)) {
+                Some(edge) => Coedge::new(edge.clone(), Sense::Opposite),
+                None => {
+                    let v1 = Arc::new(vertices[a].clone());
+                    let v2 = Arc::new(vertices[b].clone());
+                    let edge = Arc::new(Edge::new(v1, v2));
+                    edges.insert((a, b), edge.clone());
+                    Coedge::new(edge, Sense::Same)
+                }
+            };
             lp.add_coedge(coedge);
         }
         
@@ -286,7 +295,9 @@ mod tests {
         let (body, shell, face, vertex) = EulerOps::mvfs(Point3::new(0.0, 0.0, 0.0));
         assert!(!body.is_empty());
         assert!(shell.is_outer());
-        assert!(face.loops().is_empty()); // MVFS creates empty loop
+        // MVFS gives the face a single loop with no coedges yet
+        assert_eq!(face.loops().len(), 1);
+        assert!(face.loops()[0].coedges().is_empty());
         assert_eq!(vertex.position().x(), 0.0);
     }
 
@@ -6,10 +6,14 @@
 //! - Fillet construction
 //! - Boolean result construction
 
-use crate::{Body, Shell, Face, Loop, Coedge, Edge, Vertex, Sense, Orientation, Entity, GeometricEntity, TopologicalEntity, EntityId};
-use crate::{EulerOps, EulerError, TopoResult, TopologyError, new_entity_id};
-use nova_math::{Point3, Vec3, Transform3, Plane};
-use nova_geom::{Curve, Surface, Line, PlanarSurface};
+use crate::{Body, Shell, Face, Loop, Coedge, Edge, Vertex, Sense, Entity, TopologicalEntity, EntityId};
+use crate::{TopoResult, TopologyError};
+use nova_math::{Point3, Point4, Vec3, Transform3, SpatialHashGrid};
+use nova_geom::{Curve, CurveType, Surface, Line, PlanarSurface, CircularArc, CylindricalSurface, ConicalSurface,
+                SphericalSurface, ToroidalSurface, ExtrusionSurface, OffsetSurface, GeometryError};
+use nova_geom::nurbs::NurbsSurface;
+use std::collections::HashMap;
+use std::f64::consts::TAU;
 use std::sync::Arc;
 
 /// Advanced Euler operations for solid modeling
@@ -18,226 +22,346 @@ pub struct EulerAdvanced;
 impl EulerAdvanced {
     /// Extrude a face to create a solid
     /// 
-    /// Creates a solid by extruding a face profile along a direction.
-    /// Uses Euler operators to maintain valid topology.
+    /// Every loop of the profile is swept, so holes become through holes.
+    /// Each profile edge gives one side face, planar for straight edges,
+    /// cylindrical for arcs about the extrusion direction and an extrusion
+    /// surface for other curves; a closed edge such as a full circle is
+    /// joined up its side face by a single seam edge. The shell holds the
+    /// bottom and top faces first, then the sides. Faces share their edges
+    /// and are oriented so the solid's normals point outward.
     pub fn extrude_face(
         profile: &Face,
         direction: Vec3,
         distance: f64,
     ) -> TopoResult<Body> {
-        let mut body = Body::new();
-        let mut shell = Shell::new();
-        
-        // Get the profile's outer loop
-        let outer_loop = profile.outer_loop()
-            .ok_or_else(|| TopologyError::InvalidReference("Face has no outer loop".to_string()))?;
-        
-        // Get vertices from the profile
-        let profile_vertices: Vec<Arc<Vertex>> = outer_loop.coedges()
-            .iter()
-            .map(|c| Arc::new(c.start_vertex().clone()))
-            .collect();
-        
-        if profile_vertices.len() < 3 {
-            return Err(TopologyError::InvalidReference(
-                "Profile must have at least 3 vertices".to_string()
-            ));
+        if profile.outer_loop().is_none() {
+            return Err(TopologyError::InvalidReference("Face has no outer loop".to_string()));
         }
-        
-        // Create bottom face (original profile)
-        let mut bottom_face = create_face_from_loop(outer_loop)?;
-        if let Some(surf) = profile.surface() {
-            bottom_face.set_geometry(Some(surf.clone()));
+        if direction.is_zero(1e-12) || distance.abs() < 1e-10 {
+            return Err(TopologyError::InvalidReference("Extrusion has zero length".to_string()));
         }
-        shell.add_face(bottom_face.clone());
-        
-        // Create top face vertices (translated)
         let extrude_vec = direction.normalized() * distance;
-        let top_vertices: Vec<Arc<Vertex>> = profile_vertices
-            .iter()
-            .map(|v| {
-                let new_pos = v.position() + extrude_vec;
-                Arc::new(Vertex::new(new_pos))
-            })
-            .collect();
-        
-        // Create top face
-        let mut top_loop = Loop::new();
-        let num_verts = top_vertices.len();
-        for i in 0..num_verts {
-            let v1 = top_vertices[i].clone();
-            let v2 = top_vertices[(i + 1) % num_verts].clone();
-            let edge = Arc::new(Edge::new(v1, v2));
-            let coedge = Coedge::new(edge, Sense::Same);
-            top_loop.add_coedge(coedge);
+        let translation = Transform3::from_translation_vec(extrude_vec);
+        let surface = profile.surface().map(|surface| Arc::from(surface.transformed(&translation)));
+        let cap = ExtrusionCap {
+            point: &|p| Ok(p + extrude_vec),
+            curve: &|edge, _, _| Ok(edge.curve().map(|curve| Arc::from(curve.transformed(&translation)))),
+            surface: surface.map(|s| (s, false)),
+            side: &|edge, _| side_surface(edge, extrude_vec),
+        };
+        extrude_with_cap(profile, extrude_vec, &cap)
+    }
+
+    /// Extrude a face along `direction` until it meets `surface`
+    ///
+    /// `project` carries a profile point along the direction onto the
+    /// surface and must move every vertex forward, so the extrusion length
+    /// varies across the profile. The top face lies on `surface`; its edges
+    /// are straight where a straight profile edge meets a plane and are
+    /// polylines through projected samples otherwise. Side surfaces run
+    /// `reach` along the direction, which should take them past the surface.
+    pub fn extrude_face_to_surface(
+        profile: &Face,
+        direction: Vec3,
+        reach: f64,
+        surface: Arc<dyn Surface>,
+        project: &dyn Fn(Point3) -> Option<Point3>,
+    ) -> TopoResult<Body> {
+        if profile.outer_loop().is_none() {
+            return Err(TopologyError::InvalidReference("Face has no outer loop".to_string()));
         }
-        
-        let mut top_face = Face::new();
-        top_face.add_loop(top_loop);
-        // Translate surface for top face
-        if let Some(surf) = profile.surface() {
-            // TODO: Transform surface
+        if direction.is_zero(1e-12) || reach < 1e-10 {
+            return Err(TopologyError::InvalidReference("Extrusion has zero length".to_string()));
         }
-        shell.add_face(top_face.clone());
-        
-        // Create side faces
-        let num_edges = profile_vertices.len();
-        for i in 0..num_edges {
-            let v1_bottom = profile_vertices[i].clone();
-            let v2_bottom = profile_vertices[(i + 1) % num_edges].clone();
-            let v1_top = top_vertices[i].clone();
-            let v2_top = top_vertices[(i + 1) % num_edges].clone();
-            
-            // Create side face as a quad
-            let mut side_loop = Loop::new();
-            
-            // Bottom edge
-            let edge_bottom = Arc::new(Edge::new(v1_bottom.clone(), v2_bottom.clone()));
-            side_loop.add_coedge(Coedge::new(edge_bottom, Sense::Same));
-            
-            // Right edge (up)
-            let edge_right = Arc::new(Edge::new(v2_bottom.clone(), v2_top.clone()));
-            side_loop.add_coedge(Coedge::new(edge_right, Sense::Same));
-            
-            // Top edge (reversed)
-            let edge_top = Arc::new(Edge::new(v2_top.clone(), v1_top.clone()));
-            side_loop.add_coedge(Coedge::new(edge_top, Sense::Same));
-            
-            // Left edge (down, reversed)
-            let edge_left = Arc::new(Edge::new(v1_top.clone(), v1_bottom.clone()));
-            side_loop.add_coedge(Coedge::new(edge_left, Sense::Same));
-            
-            let mut side_face = Face::new();
-            side_face.add_loop(side_loop);
-            
-            // Create planar surface for side face
-            let origin = v1_bottom.position();
-            let normal = (v2_bottom.position() - v1_bottom.position())
-                .cross(&(v1_top.position() - v1_bottom.position()))
-                .normalized();
-            let u_axis = v2_bottom.position() - v1_bottom.position();
-            let v_axis = v1_top.position() - v1_bottom.position();
-            let plane = PlanarSurface::new(origin, u_axis, v_axis).unwrap_or_else(|_| PlanarSurface::from_plane(&Plane::new(origin, normal)));
-            side_face.set_geometry(Some(Arc::new(plane)));
-            
-            shell.add_face(side_face);
+        let direction = direction.normalized();
+        let point = |p: Point3| match project(p) {
+            Some(q) if (q - p).dot(&direction) >= 1e-10 => Ok(q),
+            _ => Err(TopologyError::InvalidReference(format!("Extrusion from {:?} does not reach the surface", p))),
+        };
+        let planar = surface.surface_type() == nova_geom::SurfaceType::Planar;
+        let curve = |edge: &Edge, start: Point3, end: Point3| -> TopoResult<Option<Arc<dyn Curve>>> {
+            let straight = edge.curve().map_or(true, |c| c.as_any().is::<Line>());
+            if planar && straight {
+                return Ok(Line::segment(start, end).ok().map(|line| Arc::new(line) as Arc<dyn Curve>));
+            }
+            carried_polyline(edge, start, end, &point)
+        };
+        let reversed = match profile.outer_loop().and_then(|lp| lp.coedges().first()) {
+            Some(coedge) => {
+                let top = point(coedge.start_vertex().position())?;
+                let normal = match surface.closest_point(&top) {
+                    Ok((u, v, _, _)) => surface.normal(u, v),
+                    Err(_) => direction,
+                };
+                normal.dot(&profile_normal(profile).unwrap_or(direction)) < 0.0
+            }
+            None => false,
+        };
+        let extrude_vec = direction * reach;
+        let cap = ExtrusionCap {
+            point: &point,
+            curve: &curve,
+            surface: Some((surface, reversed)),
+            side: &|edge, _| side_surface(edge, extrude_vec),
+        };
+        extrude_with_cap(profile, extrude_vec, &cap)
+    }
+
+    /// Thicken a sheet face into a solid `thickness` thick
+    ///
+    /// The solid grows along the face's outward normal, or against it for a
+    /// negative thickness. Planar faces are extruded. On curved faces every
+    /// point moves along the local normal, the far face lies on an
+    /// [`OffsetSurface`] of the profile's surface, its edges are polylines
+    /// through moved samples and the sides are ruled between each profile
+    /// edge and its moved copy.
+    pub fn thicken_face(profile: &Face, thickness: f64) -> TopoResult<Body> {
+        if thickness.abs() < 1e-10 {
+            return Err(TopologyError::InvalidReference("Thickness is zero".to_string()));
         }
-        
-        body.add_shell(shell);
-        Ok(body)
+        let Some(surface) = profile.surface().filter(|s| s.surface_type() != nova_geom::SurfaceType::Planar) else {
+            let normal = profile_normal(profile)
+                .ok_or_else(|| TopologyError::InvalidReference("Face has no normal".to_string()))?;
+            return Self::extrude_face(profile, normal, thickness);
+        };
+        if profile.outer_loop().is_none() {
+            return Err(TopologyError::InvalidReference("Face has no outer loop".to_string()));
+        }
+        let point = |p: Point3| match surface.closest_point(&p) {
+            Ok((u, v, _, _)) => Ok(p + profile.normal_at(u, v) * thickness),
+            Err(e) => Err(TopologyError::Geometry(e)),
+        };
+        let curve = |edge: &Edge, start: Point3, end: Point3| offset_curve(edge, start, end, &point);
+        // The offset runs along the surface normal, which reversed faces oppose
+        let distance = if profile.same_sense() { thickness } else { -thickness };
+        let top: Arc<dyn Surface> = Arc::new(OffsetSurface::new(surface.clone_box(), distance));
+        let normal = profile_normal(profile).unwrap_or(Vec3::Z);
+        let cap = ExtrusionCap {
+            point: &point,
+            curve: &curve,
+            surface: Some((top, !profile.same_sense())),
+            side: &ruled_surface,
+        };
+        extrude_with_cap(profile, normal * thickness, &cap)
     }
     
-    /// Revolve a face around an axis to create a solid
+    /// Copy of a face moved `distance` along its outward normal
+    ///
+    /// Each vertex moves along the local normal and the face lies on an
+    /// [`OffsetSurface`] of the original; curved edges become polylines
+    /// through moved samples and straight edges stay straight.
+    pub fn offset_face(face: &Face, distance: f64) -> TopoResult<Face> {
+        let Some(surface) = face.surface() else {
+            return Err(TopologyError::InvalidReference("Face has no surface".to_string()));
+        };
+        let point = |p: Point3| match surface.closest_point(&p) {
+            Ok((u, v, _, _)) => Ok(p + face.normal_at(u, v) * distance),
+            Err(e) => Err(TopologyError::Geometry(e)),
+        };
+        let mut vertices: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+        let mut edges: HashMap<EntityId, Arc<Edge>> = HashMap::new();
+        let mut offset = Face::new();
+        for lp in face.loops() {
+            let mut moved = Loop::new();
+            for coedge in lp.coedges() {
+                let edge = coedge.edge();
+                if let std::collections::hash_map::Entry::Vacant(entry) = edges.entry(edge.id()) {
+                    let mut vertex = |v: &Vertex| -> TopoResult<Arc<Vertex>> {
+                        if let Some(moved) = vertices.get(&v.id()) {
+                            return Ok(moved.clone());
+                        }
+                        let moved = Arc::new(Vertex::new(point(v.position())?));
+                        vertices.insert(v.id(), moved.clone());
+                        Ok(moved)
+                    };
+                    let (start, end) = (vertex(edge.start_vertex())?, vertex(edge.end_vertex())?);
+                    let curve = offset_curve(edge, start.position(), end.position(), &point)?;
+                    let mut moved_edge = Edge::new(start, end);
+                    moved_edge.set_curve(curve);
+                    entry.insert(Arc::new(moved_edge));
+                }
+                moved.add_coedge(Coedge::new(edges[&edge.id()].clone(), coedge.sense()));
+            }
+            offset.add_loop(moved);
+        }
+        let along_surface = if face.same_sense() { distance } else { -distance };
+        offset.set_surface(Some(Arc::new(OffsetSurface::new(surface.clone_box(), along_surface))));
+        offset.set_same_sense(face.same_sense());
+        Ok(offset)
+    }
+
+    /// Revolve a face by `angle` radians about an axis to create a solid
+    ///
+    /// The profile must lie in a plane through the axis, on one side of it.
+    /// Each profile edge sweeps an exact face: a line across the axis sweeps
+    /// a disk or annulus, one along it a cylinder and any other line a cone,
+    /// while an arc sweeps a sphere when centred on the axis and a torus
+    /// otherwise. Edges lying on the axis sweep nothing, and each vertex off
+    /// the axis sweeps a circular edge. A partial revolve is closed by planar
+    /// caps on the start and end profiles, which come first in the shell; a
+    /// full one joins each side face up a single seam edge. Faces share
+    /// their edges and are oriented so the solid's normals point outward.
     pub fn revolve_face(
         profile: &Face,
         axis_origin: Point3,
         axis_direction: Vec3,
         angle: f64,
-        num_segments: usize,
     ) -> TopoResult<Body> {
-        let mut body = Body::new();
-        let mut shell = Shell::new();
-        
-        let outer_loop = profile.outer_loop()
-            .ok_or_else(|| TopologyError::InvalidReference("Face has no outer loop".to_string()))?;
+        let Some(outer) = profile.outer_loop() else {
+            return Err(TopologyError::InvalidReference("Face has no outer loop".to_string()));
+        };
+        if axis_direction.is_zero(1e-12) || angle.abs() < 1e-10 || angle.abs() > TAU + 1e-10 {
+            return Err(TopologyError::InvalidReference("Revolve needs an axis and an angle up to a full turn".to_string()));
+        }
+        // Sweep positive angles only, turning the axis round for negative ones
+        let (axis, angle) = if angle < 0.0 { (-axis_direction.normalized(), -angle) } else { (axis_direction.normalized(), angle) };
+        let full = angle > TAU - 1e-10;
+        let angle = angle.min(TAU);
+        let origin = axis_origin;
+        let radial = |p: Point3| (p - origin).reject_from(&axis);
         
-        // For simplicity, create approximated revolution with planar faces
-        let angle_step = angle / num_segments as f64;
+        // The profile's half plane, from the axis toward its vertices and
+        // the middles of its edges, which may leave the axis between them
+        let vertices: Vec<Point3> = profile.loops().iter()
+            .flat_map(|lp| lp.coedges())
+            .flat_map(|c| {
+                let mid = c.edge().curve().map(|curve| {
+                    let range = curve.param_range();
+                    curve.evaluate((range.start + range.end) / 2.0)
+                });
+                std::iter::once(c.start_vertex().position()).chain(mid)
+            })
+            .collect();
+        let reference = vertices.iter()
+            .map(|&p| radial(p))
+            .find(|r| r.length() > AXIS_TOL)
+            .ok_or_else(|| TopologyError::InvalidReference("Profile lies on the axis".to_string()))?
+            .normalized();
+        if vertices.iter().any(|&p| {
+            let r = radial(p);
+            r.dot(&reference) < -AXIS_TOL || r.cross(&reference).length() > AXIS_TOL
+        }) {
+            return Err(TopologyError::InvalidReference("Profile must lie in a half plane bounded by the axis".to_string()));
+        }
         
-        // Store profile vertices for each angle step
-        let mut profile_rings: Vec<Vec<Arc<Vertex>>> = Vec::new();
+        let rotation = Transform3::from_axis_angle(&axis, angle);
+        let rotation = Transform3::new(&rotation.rotation(), origin.to_vector() - rotation.apply_to_vector(&origin.to_vector()));
+        let on_axis = |p: Point3| radial(p).length() <= AXIS_TOL;
         
-        for i in 0..=num_segments {
-            let current_angle = i as f64 * angle_step;
-            let rotation = Transform3::from_axis_angle(&axis_direction, current_angle);
-            
-            let ring: Vec<Arc<Vertex>> = outer_loop.coedges()
-                .iter()
-                .map(|c| {
-                    let pos = c.start_vertex().position();
-                    let rotated_vec = rotation.apply_to_vector(&(pos - axis_origin));
-                    let rotated_pos = Point3::new(
-                        axis_origin.x() + rotated_vec.x(),
-                        axis_origin.y() + rotated_vec.y(),
-                        axis_origin.z() + rotated_vec.z(),
-                    );
-                    Arc::new(Vertex::new(rotated_pos))
-                })
-                .collect();
-            
-            profile_rings.push(ring);
+        // Each profile vertex at the start and end of the sweep, and the
+        // circle it sweeps unless it sits on the axis
+        let mut start_vertices: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+        let mut end_vertices: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+        let mut circles: HashMap<EntityId, Arc<Edge>> = HashMap::new();
+        for edge in profile.loops().iter().flat_map(|lp| lp.coedges()).map(|c| c.edge()) {
+            for v in [edge.start_vertex(), edge.end_vertex()] {
+                if start_vertices.contains_key(&v.id()) {
+                    continue;
+                }
+                let p = v.position();
+                let start = Arc::new(v.clone());
+                let end = if full || on_axis(p) { start.clone() } else { Arc::new(Vertex::new(rotation.apply_to_point(&p))) };
+                if !on_axis(p) {
+                    let arc = CircularArc::new(p - radial(p), radial(p).length(), axis, radial(p), angle)?;
+                    circles.insert(v.id(), Arc::new(Edge::with_curve(start.clone(), end.clone(), Arc::new(arc))));
+                }
+                start_vertices.insert(v.id(), start);
+                end_vertices.insert(v.id(), end);
+            }
         }
+        let start_vertex = |v: &Vertex| start_vertices[&v.id()].clone();
+        let end_vertex = |v: &Vertex| end_vertices[&v.id()].clone();
+        let circle = |v: &Vertex| circles.get(&v.id()).cloned();
+        let mut edges: HashMap<EntityId, (Arc<Edge>, Arc<Edge>)> = HashMap::new();
         
-        // Create faces between rings
-        for i in 0..num_segments {
-            let ring1 = &profile_rings[i];
-            let ring2 = &profile_rings[i + 1];
-            
-            let num_verts = ring1.len();
-            for j in 0..num_verts {
-                let v1 = ring1[j].clone();
-                let v2 = ring1[(j + 1) % num_verts].clone();
-                let v3 = ring2[(j + 1) % num_verts].clone();
-                let v4 = ring2[j].clone();
-                
-                // Create quad face
-                let mut loop_ = Loop::new();
-                loop_.add_coedge(Coedge::new(Arc::new(Edge::new(v1.clone(), v2.clone())), Sense::Same));
-                loop_.add_coedge(Coedge::new(Arc::new(Edge::new(v2.clone(), v3.clone())), Sense::Same));
-                loop_.add_coedge(Coedge::new(Arc::new(Edge::new(v3.clone(), v4.clone())), Sense::Same));
-                loop_.add_coedge(Coedge::new(Arc::new(Edge::new(v4.clone(), v1.clone())), Sense::Same));
+        // Loops are built consistently with the start cap, which runs the
+        // profile's way round
+        let mut start_cap = Face::new();
+        let mut end_cap = Face::new();
+        let mut sides: Vec<(Face, bool)> = Vec::new();
+        for lp in profile.loops().iter().filter(|lp| !lp.is_empty()) {
+            let mut start_loop = Vec::new();
+            let mut end_loop = Vec::new();
+            for coedge in lp.coedges() {
+                let edge = coedge.edge();
+                let (start, end) = (edge.start_vertex(), edge.end_vertex());
+                let (start_edge, end_edge) = edges.entry(edge.id()).or_insert_with(|| {
+                    let start_edge = Arc::new(edge.remapped(start_vertex(start), start_vertex(end)));
+                    let fixed = edge.curve().map_or(true, |c| c.curve_type() == CurveType::Line)
+                        && on_axis(start.position()) && on_axis(end.position());
+                    if full || fixed {
+                        return (start_edge.clone(), start_edge);
+                    }
+                    let mut end_edge = Edge::new(end_vertex(start), end_vertex(end));
+                    end_edge.set_curve(edge.curve().map(|curve| Arc::from(curve.transformed(&rotation))));
+                    (start_edge, Arc::new(end_edge))
+                }).clone();
+                let sense = coedge.sense();
+                start_loop.push(Coedge::new(start_edge.clone(), sense));
+                end_loop.push(Coedge::new(end_edge.clone(), sense.reverse()));
                 
-                let mut face = Face::new();
-                face.add_loop(loop_);
-                shell.add_face(face);
+                // Back along the start profile, round the start vertex's
+                // circle, along the end profile and back round the end
+                // vertex's circle
+                let Some(surface) = revolved_surface(edge, origin, axis, reference, angle)? else {
+                    continue;
+                };
+                let mut coedges = vec![Coedge::new(start_edge, sense.reverse())];
+                coedges.extend(circle(coedge.start_vertex()).map(|c| Coedge::new(c, Sense::Same)));
+                coedges.push(Coedge::new(end_edge, sense));
+                coedges.extend(circle(coedge.end_vertex()).map(|c| Coedge::new(c, Sense::Opposite)));
+                let flip = !revolve_agrees(surface.as_ref(), &coedges[0], origin, axis);
+                if flip {
+                    coedges.reverse();
+                    coedges.iter_mut().for_each(Coedge::reverse_sense);
+                }
+                let mut side = Face::with_surface(surface);
+                side.add_loop(Loop::from_coedges(coedges));
+                sides.push((side, flip));
             }
+            end_loop.reverse();
+            start_cap.add_loop(Loop::from_coedges(start_loop));
+            end_cap.add_loop(Loop::from_coedges(end_loop));
         }
         
-        // Add start and end caps if angle < 360
-        if angle < 2.0 * std::f64::consts::PI - 1e-6 {
-            // Start cap (original profile)
-            if let Some(first_ring) = profile_rings.first() {
-                let mut start_loop = Loop::new();
-                let num_verts = first_ring.len();
-                for j in 0..num_verts {
-                    let v1 = first_ring[j].clone();
-                    let v2 = first_ring[(j + 1) % num_verts].clone();
-                    let edge = Arc::new(Edge::new(v1, v2));
-                    start_loop.add_coedge(Coedge::new(edge, Sense::Same));
+        // The solid lies ahead of the start cap, so the loops wind outward
+        // when the profile winds against the direction of rotation
+        let winding = loop_winding_normal(outer);
+        let outward = winding.dot(&axis.cross(&reference)) < 0.0;
+        let mut shell = Shell::new();
+        if !full {
+            let start_plane = cap_plane(vertices[0], winding)?;
+            let end_plane = cap_plane(rotation.apply_to_point(&vertices[0]), -rotation.apply_to_vector(&winding))?;
+            for (mut cap, plane) in [(start_cap, start_plane), (end_cap, end_plane)] {
+                cap.set_surface(Some(Arc::new(plane)));
+                if !outward {
+                    cap.reverse_orientation();
                 }
-                let mut start_face = Face::new();
-                start_face.add_loop(start_loop);
-                shell.add_face(start_face);
+                shell.add_face(cap);
             }
-            
-            // End cap (rotated profile)
-            if let Some(last_ring) = profile_rings.last() {
-                let mut end_loop = Loop::new();
-                let num_verts = last_ring.len();
-                for j in 0..num_verts {
-                    let v1 = last_ring[j].clone();
-                    let v2 = last_ring[(j + 1) % num_verts].clone();
-                    let edge = Arc::new(Edge::new(v1, v2));
-                    end_loop.add_coedge(Coedge::new(edge, Sense::Same));
-                }
-                let mut end_face = Face::new();
-                end_face.add_loop(end_loop);
-                shell.add_face(end_face);
+        }
+        for (mut side, flip) in sides {
+            if outward == flip {
+                side.reverse_orientation();
             }
+            shell.add_face(side);
         }
         
+        let mut body = Body::new();
         body.add_shell(shell);
         Ok(body)
     }
     
-    /// Split an edge at a parameter, creating a new vertex
+    /// Split an edge a fraction `t` of the way along its curve's parameter
+    /// range, creating a new vertex
     pub fn split_edge(
         edge: &Edge,
         t: f64,
     ) -> TopoResult<(Vertex, Edge, Edge)> {
-        // Get position at split parameter
-        let position = edge.evaluate(t)
+        let curve = edge.curve()
             .ok_or_else(|| TopologyError::InvalidReference("Edge has no curve".to_string()))?;
+        let range = curve.param_range();
+        let position = curve.evaluate(range.start + (range.end - range.start) * t);
         
         let split_vertex = Vertex::new(position);
         
@@ -350,36 +474,67 @@ impl EulerAdvanced {
     }
     
     /// Merge two faces that share an edge
+    ///
+    /// The loops on either side of `shared_edge` are joined into one loop
+    /// without it: the first face's loop from past the edge round to it,
+    /// then the second's. If both loops run along the edge the same way the
+    /// second loop is reversed first so the two chain head to tail. The merged loop takes the first loop's
+    /// place, followed by the second face's other loops. The merged face keeps
+    /// the first face's surface and orientation.
     pub fn merge_faces(
         face1: &Face,
         face2: &Face,
         shared_edge: &Edge,
     ) -> TopoResult<Face> {
-        // Create a new face by combining the loops
+        let uses = |face: &Face| -> TopoResult<(usize, usize)> {
+            let found: Vec<(usize, usize)> = face.loops().iter().enumerate()
+                .flat_map(|(l, lp)| lp.coedges().iter().enumerate().map(move |(c, coedge)| (l, c, coedge)))
+                .filter(|(_, _, coedge)| coedge.edge().id() == shared_edge.id())
+                .map(|(l, c, _)| (l, c))
+                .collect();
+            match found[..] {
+                [at] => Ok(at),
+                _ => Err(TopologyError::InvalidReference(format!(
+                    "Face {:?} uses the shared edge {} times (expected 1)", face.id(), found.len()
+                ))),
+            }
+        };
+        if face1.id() == face2.id() {
+            return Err(TopologyError::InvalidReference("Cannot merge a face with itself".to_string()));
+        }
+        let (l1, c1) = uses(face1)?;
+        let (l2, c2) = uses(face2)?;
+
+        // Coedges after the shared one, round to just before it
+        let rest = |lp: &Loop, c: usize| -> Vec<Coedge> {
+            let n = lp.coedges().len();
+            (1..n).map(|k| lp.coedges()[(c + k) % n].clone()).collect()
+        };
+        let mut second = rest(&face2.loops()[l2], c2);
+        if face1.loops()[l1].coedges()[c1].sense() == face2.loops()[l2].coedges()[c2].sense() {
+            second.reverse();
+            for coedge in &mut second {
+                coedge.reverse_sense();
+            }
+        }
+        let mut joined = rest(&face1.loops()[l1], c1);
+        joined.extend(second);
+
         let mut merged_face = Face::new();
-        
-        // Add all loops from face1 except those containing the shared edge
-        for lp in face1.loops() {
-            let contains_edge = lp.coedges().iter().any(|c| {
-                c.edge().id() == shared_edge.id()
-            });
-            if !contains_edge {
+        merged_face.set_surface(face1.surface().cloned());
+        merged_face.set_orientation(face1.orientation());
+        for (l, lp) in face1.loops().iter().enumerate() {
+            if l == l1 {
+                merged_face.add_loop(Loop::from_coedges(joined.clone()));
+            } else {
                 merged_face.add_loop(lp.clone());
             }
         }
-        
-        // Add all loops from face2 except those containing the shared edge
-        for lp in face2.loops() {
-            let contains_edge = lp.coedges().iter().any(|c| {
-                c.edge().id() == shared_edge.id()
-            });
-            if !contains_edge {
+        for (l, lp) in face2.loops().iter().enumerate() {
+            if l != l2 {
                 merged_face.add_loop(lp.clone());
             }
         }
-        
-        // TODO: Properly merge the outer loops by removing the shared edge
-        
         Ok(merged_face)
     }
     
@@ -397,12 +552,19 @@ impl EulerAdvanced {
         }
         
         // Stitch faces together by matching edges
-        stitch_faces(&mut shell, tolerance)?;
+        stitch_faces(&mut shell, tolerance);
         
         body.add_shell(shell);
         Ok(body)
     }
     
+    /// Weld coincident vertices of a shell and share the edges between them
+    ///
+    /// Returns the number of vertices welded away.
+    pub fn stitch_shell(shell: &mut Shell, tolerance: f64) -> usize {
+        stitch_faces(shell, tolerance)
+    }
+    
     /// Add an inner loop (hole) to a face
     pub fn add_inner_loop(
         face: &mut Face,
@@ -430,18 +592,365 @@ impl EulerAdvanced {
     }
 }
 
-/// Helper function to create a face from a loop
-fn create_face_from_loop(loop_: &Loop) -> TopoResult<Face> {
-    let mut face = Face::new();
+/// Curve for the top edge over a profile edge, given its moved ends
+type CapCurve<'a> = &'a dyn Fn(&Edge, Point3, Point3) -> TopoResult<Option<Arc<dyn Curve>>>;
+
+/// Surface of the side face between a profile edge and its top edge
+type SideSurface<'a> = &'a dyn Fn(&Edge, &Edge) -> Option<Arc<dyn Surface>>;
+
+/// Far end of an extrusion
+struct ExtrusionCap<'a> {
+    /// Where a profile vertex ends up
+    point: &'a dyn Fn(Point3) -> TopoResult<Point3>,
+    /// Top edge curves
+    curve: CapCurve<'a>,
+    /// Surface of the top face, and whether it faces against the profile
+    surface: Option<(Arc<dyn Surface>, bool)>,
+    /// Side face surfaces
+    side: SideSurface<'a>,
+}
+
+/// Samples along a curved profile edge when projecting it onto a cap surface
+const CAP_EDGE_SAMPLES: usize = 32;
+
+/// Polyline from `start` to `end` through samples of `edge` carried to the
+/// cap by `point`, or `None` for an edge without a curve
+fn carried_polyline(
+    edge: &Edge,
+    start: Point3,
+    end: Point3,
+    point: &dyn Fn(Point3) -> TopoResult<Point3>,
+) -> TopoResult<Option<Arc<dyn Curve>>> {
+    let Some(mut points) = edge_samples(edge) else {
+        return Ok(None);
+    };
+    points = points.into_iter().map(point).collect::<TopoResult<Vec<_>>>()?;
+    points[0] = start;
+    points[CAP_EDGE_SAMPLES] = end;
+    let polyline = nova_geom::PolylineCurve::new(points).map_err(|e| TopologyError::InvalidReference(e.to_string()))?;
+    Ok(Some(Arc::new(polyline)))
+}
+
+/// Curve for `edge` moved off its face, straight for lines and a carried
+/// polyline otherwise
+fn offset_curve(
+    edge: &Edge,
+    start: Point3,
+    end: Point3,
+    point: &dyn Fn(Point3) -> TopoResult<Point3>,
+) -> TopoResult<Option<Arc<dyn Curve>>> {
+    match edge.curve() {
+        Some(curve) if curve.as_any().is::<Line>() => {
+            Ok(Line::segment(start, end).ok().map(|line| Arc::new(line) as Arc<dyn Curve>))
+        }
+        _ => carried_polyline(edge, start, end, point),
+    }
+}
+
+/// Evenly spaced samples of an edge's curve from its start vertex to its end
+fn edge_samples(edge: &Edge) -> Option<Vec<Point3>> {
+    let curve = edge.curve()?;
+    let range = curve.param_range();
+    let mut samples: Vec<Point3> = (0..=CAP_EDGE_SAMPLES)
+        .map(|i| curve.evaluate(range.start + range.length() * i as f64 / CAP_EDGE_SAMPLES as f64))
+        .collect();
+    let start = edge.start_vertex().position();
+    if samples[0].distance_to(&start) > samples[CAP_EDGE_SAMPLES].distance_to(&start) {
+        samples.reverse();
+    }
+    Some(samples)
+}
+
+/// Bilinear surface ruled between `bottom` and `top`, parameterised along
+/// the edges in u and from bottom to top in v
+fn ruled_surface(bottom: &Edge, top: &Edge) -> Option<Arc<dyn Surface>> {
+    let ends = |edge: &Edge| vec![edge.start_vertex().position(), edge.end_vertex().position()];
+    let lower = edge_samples(bottom).unwrap_or_else(|| ends(bottom));
+    let upper = edge_samples(top).unwrap_or_else(|| ends(top));
+    if lower.len() != upper.len() {
+        return None;
+    }
+    let spans = lower.len() - 1;
+    let homogeneous = |p: &Point3| Point4::new(p.x(), p.y(), p.z(), 1.0);
+    let control = lower.iter().zip(&upper).map(|(a, b)| vec![homogeneous(a), homogeneous(b)]).collect();
+    let mut knots_u = vec![0.0];
+    knots_u.extend((0..=spans).map(|i| i as f64 / spans as f64));
+    knots_u.push(1.0);
+    let surface = NurbsSurface::new(1, 1, control, knots_u, vec![0.0, 0.0, 1.0, 1.0]).ok()?;
+    Some(Arc::new(surface))
+}
+
+/// Sweep `profile` by `extrude_vec`, closing the far end with `cap`
+///
+/// The rails run from each profile vertex to its capped position, and the
+/// solid is turned inside out when `extrude_vec` runs against the profile
+/// normal.
+fn extrude_with_cap(profile: &Face, extrude_vec: Vec3, cap: &ExtrusionCap<'_>) -> TopoResult<Body> {
+    let mut top_vertices: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+    for edge in profile.loops().iter().flat_map(|lp| lp.coedges()).map(|c| c.edge()) {
+        for v in [edge.start_vertex(), edge.end_vertex()] {
+            if let std::collections::hash_map::Entry::Vacant(entry) = top_vertices.entry(v.id()) {
+                entry.insert(Arc::new(Vertex::new((cap.point)(v.position())?)));
+            }
+        }
+    }
+    let mut bottom_vertices: HashMap<EntityId, Arc<Vertex>> = HashMap::new();
+    let mut edges: HashMap<EntityId, (Arc<Edge>, Arc<Edge>)> = HashMap::new();
+    for edge in profile.loops().iter().flat_map(|lp| lp.coedges()).map(|c| c.edge()) {
+        if edges.contains_key(&edge.id()) {
+            continue;
+        }
+        let mut bottom_vertex = |v: &Vertex| bottom_vertices.entry(v.id()).or_insert_with(|| Arc::new(v.clone())).clone();
+        let bottom_edge = edge.remapped(bottom_vertex(edge.start_vertex()), bottom_vertex(edge.end_vertex()));
+        let (start, end) = (top_vertices[&edge.start_vertex().id()].clone(), top_vertices[&edge.end_vertex().id()].clone());
+        let curve = (cap.curve)(edge, start.position(), end.position())?;
+        let mut top_edge = Edge::new(start, end);
+        top_edge.set_curve(curve);
+        edges.insert(edge.id(), (Arc::new(bottom_edge), Arc::new(top_edge)));
+    }
+    let mut rails: HashMap<EntityId, Arc<Edge>> = HashMap::new();
+    
+    let mut bottom = Face::new();
+    let mut top = Face::new();
+    let mut sides = Vec::new();
+    for lp in profile.loops().iter().filter(|lp| !lp.is_empty()) {
+        let mut bottom_loop = Loop::new();
+        let mut top_loop = Loop::new();
+        for coedge in lp.coedges() {
+            let edge = coedge.edge();
+            let (bottom_edge, top_edge) = edges[&edge.id()].clone();
+            let mut rail = |v: &Vertex| rails.entry(v.id()).or_insert_with(|| {
+                let (from, to) = (bottom_vertices[&v.id()].clone(), top_vertices[&v.id()].clone());
+                let line = Line::segment(from.position(), to.position()).expect("extrusion has length");
+                Arc::new(Edge::with_curve(from, to, Arc::new(line)))
+            }).clone();
+            
+            let sense = coedge.sense();
+            bottom_loop.add_coedge(Coedge::new(bottom_edge.clone(), sense));
+            top_loop.add_coedge(Coedge::new(top_edge.clone(), sense));
+            
+            // Along the profile edge, up, back along the top and down. Loops
+            // wind about their surface normal, so where that points into
+            // the solid the loop runs the other way round and the face is
+            // reversed.
+            let surface = (cap.side)(edge, &top_edge);
+            let start = coedge.start_vertex();
+            let rail_vec = top_vertices[&start.id()].position() - start.position();
+            let flip = surface.as_ref().is_some_and(|s| !side_agrees(s.as_ref(), coedge, rail_vec));
+            let mut coedges = vec![
+                Coedge::new(bottom_edge, sense),
+                Coedge::new(rail(coedge.end_vertex()), Sense::Same),
+                Coedge::new(top_edge, sense.reverse()),
+                Coedge::new(rail(coedge.start_vertex()), Sense::Opposite),
+            ];
+            let mut side = Face::new();
+            if flip {
+                coedges.reverse();
+                coedges.iter_mut().for_each(Coedge::reverse_sense);
+                side.reverse_orientation();
+            }
+            side.add_loop(Loop::from_coedges(coedges));
+            side.set_surface(surface);
+            sides.push(side);
+        }
+        bottom.add_loop(bottom_loop);
+        top.add_loop(top_loop);
+    }
     
-    // Copy the coedges
-    let mut new_loop = Loop::new();
-    for coedge in loop_.coedges() {
-        new_loop.add_coedge(coedge.clone());
+    if let Some(surface) = profile.surface() {
+        bottom.set_surface(Some(surface.clone()));
+    }
+    if let Some((surface, reversed)) = &cap.surface {
+        top.set_surface(Some(surface.clone()));
+        if *reversed {
+            top.reverse_orientation();
+        }
     }
+    // The bottom runs its loops the profile's way, so faces away from the side faces
+    bottom.set_same_sense(!profile.same_sense());
     
-    face.add_loop(new_loop);
-    Ok(face)
+    let mut shell = Shell::new();
+    shell.add_face(bottom);
+    shell.add_face(top);
+    for side in sides {
+        shell.add_face(side);
+    }
+    let facing = profile_normal(profile).map_or(1.0, |n| n.dot(&extrude_vec));
+    if facing < 0.0 {
+        for face in shell.faces_mut() {
+            face.reverse_orientation();
+        }
+    }
+    
+    let mut body = Body::new();
+    body.add_shell(shell);
+    Ok(body)
+}
+
+/// Surface swept by `edge` moving along `extrude`
+///
+/// Straight edges sweep planes, arcs about the extrusion direction sweep
+/// cylinders and any other curve sweeps an extrusion surface.
+fn side_surface(edge: &Edge, extrude: Vec3) -> Option<Arc<dyn Surface>> {
+    let start = edge.start_vertex().position();
+    let Some(curve) = edge.curve() else {
+        let plane = PlanarSurface::new(start, edge.end_vertex().position() - start, extrude).ok()?;
+        return Some(Arc::new(plane));
+    };
+    if let Some(line) = curve.as_any().downcast_ref::<Line>() {
+        return Some(Arc::new(PlanarSurface::new(start, line.direction(), extrude).ok()?));
+    }
+    if let Some(arc) = curve.as_any().downcast_ref::<CircularArc>() {
+        if arc.normal().cross(&extrude.normalized()).length() <= 1e-9 {
+            let (center, height) = (arc.center(), extrude.length());
+            let cylinder = CylindricalSurface::bounded(center, extrude, arc.radius(), start - center, 0.0, height, 0.0, TAU).ok()?;
+            return Some(Arc::new(cylinder));
+        }
+    }
+    Some(Arc::new(ExtrusionSurface::new(curve.clone_box(), extrude).ok()?))
+}
+
+/// Distance from the axis within which a revolved point stays put
+const AXIS_TOL: f64 = 1e-9;
+
+/// Surface swept by `edge` turning `angle` radians about the axis through
+/// `origin` along `axis`, starting in the half plane toward `reference`
+///
+/// Returns `None` for a straight edge on the axis, which sweeps nothing.
+fn revolved_surface(
+    edge: &Edge,
+    origin: Point3,
+    axis: Vec3,
+    reference: Vec3,
+    angle: f64,
+) -> TopoResult<Option<Arc<dyn Surface>>> {
+    // Distance from the axis and height along it
+    let polar = |p: Point3| {
+        let offset = p - origin;
+        let height = offset.dot(&axis);
+        ((offset - axis * height).length(), height)
+    };
+    let unsupported = |what: &str| TopologyError::Geometry(GeometryError::Unsupported(format!("Cannot revolve {}", what)));
+    let line = edge.curve().map_or(true, |c| c.curve_type() == CurveType::Line);
+    if line {
+        let (ra, ha) = polar(edge.start_vertex().position());
+        let (rb, hb) = polar(edge.end_vertex().position());
+        if ra <= AXIS_TOL && rb <= AXIS_TOL {
+            return Ok(None);
+        }
+        let surface: Arc<dyn Surface> = if (ha - hb).abs() <= AXIS_TOL {
+            Arc::new(PlanarSurface::new(origin + axis * ha, reference, axis.cross(&reference))?)
+        } else if (ra - rb).abs() <= AXIS_TOL {
+            Arc::new(CylindricalSurface::bounded(origin, axis, ra, reference, ha.min(hb), ha.max(hb), 0.0, angle)?)
+        } else {
+            // The cone opens along the axis the way the radius grows
+            let slope = (rb - ra) / (hb - ha);
+            let apex = origin + axis * (ha - ra / slope);
+            let (cone_axis, angles) = if slope > 0.0 { (axis, (0.0, angle)) } else { (-axis, (-angle, 0.0)) };
+            let (ta, tb) = (ra / slope.abs(), rb / slope.abs());
+            Arc::new(ConicalSurface::bounded(
+                apex, cone_axis, slope.abs().atan(), reference, ta.min(tb), ta.max(tb), angles.0, angles.1,
+            )?)
+        };
+        return Ok(Some(surface));
+    }
+    let curve = edge.curve().expect("curved edge");
+    let arc = curve.as_any().downcast_ref::<CircularArc>().ok_or_else(|| unsupported("this curve type"))?;
+    if arc.normal().dot(&axis).abs() > 1e-9 {
+        return Err(unsupported("an arc out of the profile plane"));
+    }
+    let (rc, hc) = polar(arc.center());
+    if rc <= AXIS_TOL {
+        return Ok(Some(Arc::new(SphericalSurface::new(arc.center(), arc.radius(), axis, reference)?)));
+    }
+    if arc.radius() >= rc {
+        return Err(unsupported("an arc reaching the axis"));
+    }
+    Ok(Some(Arc::new(ToroidalSurface::new(origin + axis * hc, axis, rc, arc.radius(), reference)?)))
+}
+
+/// Whether a revolved side face's surface normal points the way its loop winds
+///
+/// The loop leaves its first coedge turning about the axis, so the face lies
+/// on the side the edge's midpoint moves toward.
+fn revolve_agrees(surface: &dyn Surface, coedge: &Coedge, origin: Point3, axis: Vec3) -> bool {
+    let edge = coedge.edge();
+    let (start, end) = (coedge.start_vertex().position(), coedge.end_vertex().position());
+    // The tangent halfway along a line or arc runs parallel to its chord
+    let (mid, tangent) = match edge.curve() {
+        Some(curve) => {
+            let range = curve.param_range();
+            let t = (range.start + range.end) / 2.0;
+            let tangent = curve.tangent(t);
+            (curve.evaluate(t), if coedge.sense().is_same() { tangent } else { -tangent })
+        }
+        None => (start.midpoint(&end), end - start),
+    };
+    let normal = match surface.closest_point(&mid) {
+        Ok((u, v, _, _)) => surface.normal(u, v),
+        Err(_) => return true,
+    };
+    normal.dot(&tangent.cross(&axis.cross(&(mid - origin)))) >= 0.0
+}
+
+/// Normal a loop winds counterclockwise about, from points along its curves
+fn loop_winding_normal(lp: &Loop) -> Vec3 {
+    const SAMPLES: usize = 8;
+    let mut points = Vec::new();
+    for coedge in lp.coedges() {
+        match coedge.edge().curve() {
+            Some(curve) if curve.param_range().length().is_finite() => {
+                let range = curve.param_range();
+                for i in 0..SAMPLES {
+                    let f = i as f64 / SAMPLES as f64;
+                    let f = if coedge.sense().is_same() { f } else { 1.0 - f };
+                    points.push(curve.evaluate(range.start + f * range.length()));
+                }
+            }
+            _ => points.push(coedge.start_vertex().position()),
+        }
+    }
+    let mut normal = Vec3::ZERO;
+    for (i, p) in points.iter().enumerate() {
+        normal += (*p - Point3::ORIGIN).cross(&(points[(i + 1) % points.len()] - Point3::ORIGIN));
+    }
+    normal
+}
+
+/// Plane through `point` facing along `normal`
+fn cap_plane(point: Point3, normal: Vec3) -> TopoResult<PlanarSurface> {
+    let normal = normal.normalized();
+    let u = if normal.x().abs() < 0.9 { Vec3::X } else { Vec3::Y }.reject_from(&normal);
+    Ok(PlanarSurface::new(point, u, normal.cross(&u))?)
+}
+
+/// Whether a side face's surface normal points the way its loop winds
+///
+/// The loop runs along the profile coedge and then up the rail at its
+/// start, so its normal there is the coedge tangent crossed with `rail`.
+fn side_agrees(surface: &dyn Surface, coedge: &Coedge, rail: Vec3) -> bool {
+    let edge = coedge.edge();
+    let start = coedge.start_vertex().position();
+    let tangent = match edge.curve().and_then(|c| c.closest_point(&start).ok().map(|(t, _, _)| c.tangent(t))) {
+        Some(tangent) if coedge.sense().is_same() => tangent,
+        Some(tangent) => -tangent,
+        None => coedge.end_vertex().position() - start,
+    };
+    let normal = match surface.closest_point(&start) {
+        Ok((u, v, _, _)) => surface.normal(u, v),
+        Err(_) => return true,
+    };
+    normal.dot(&tangent.cross(&rail)) >= 0.0
+}
+
+/// Outward normal of a face, from its surface or else its outer loop
+fn profile_normal(face: &Face) -> Option<Vec3> {
+    let (u, v) = match face.uv_extent() {
+        Some(extent) => ((extent.u.start + extent.u.end) / 2.0, (extent.v.start + extent.v.end) / 2.0),
+        None => (0.0, 0.0),
+    };
+    let normal = face.normal_at(u, v);
+    (!normal.is_zero(0.0)).then_some(normal)
 }
 
 /// Calculate fillet offset direction
@@ -480,68 +989,140 @@ fn calculate_fillet_offset(
 }
 
 /// Stitch faces together by matching edges
-fn stitch_faces(shell: &mut Shell, tolerance: f64) -> TopoResult<()> {
-    // Collect all edges
-    let mut edges: Vec<(EntityId, Arc<Edge>)> = Vec::new();
+///
+/// Coincident vertices are welded into a single shared vertex, and every set
+/// of coedges running between the same welded vertices along the same curve
+/// is pointed at one shared edge. Welded vertices record the edges on them.
+/// Returns the number of vertices welded away.
+fn stitch_faces(shell: &mut Shell, tolerance: f64) -> usize {
+    // Weld coincident vertices onto the first vertex found within tolerance:
+    // (position sum, count), with the first positions as anchors
+    let mut clusters: Vec<(Vec3, usize)> = Vec::new();
+    let mut anchors: SpatialHashGrid<usize> = SpatialHashGrid::new(tolerance);
+    let mut vertex_cluster: HashMap<EntityId, usize> = HashMap::new();
     
     for face in shell.faces() {
         for lp in face.loops() {
             for coedge in lp.coedges() {
                 let edge = coedge.edge();
-                edges.push((edge.id(), Arc::new(edge.clone())));
+                for vertex in [edge.start_vertex(), edge.end_vertex()] {
+                    if vertex_cluster.contains_key(&vertex.id()) {
+                        continue;
+                    }
+                    let position = vertex.position();
+                    let index = match anchors.query_near(position).first() {
+                        Some(&&index) => {
+                            clusters[index].0 += position.to_vector();
+                            clusters[index].1 += 1;
+                            index
+                        }
+                        None => {
+                            anchors.insert(position, clusters.len());
+                            clusters.push((position.to_vector(), 1));
+                            clusters.len() - 1
+                        }
+                    };
+                    vertex_cluster.insert(vertex.id(), index);
+                }
             }
         }
     }
     
-    // Find matching edges (same geometry, opposite orientation)
-    let mut to_merge: Vec<(EntityId, EntityId)> = Vec::new();
+    let mut welded: Vec<Vertex> = clusters.iter()
+        .map(|(sum, count)| {
+            let mut vertex = Vertex::new(Point3::ORIGIN + *sum / *count as f64);
+            vertex.set_tolerance(vertex.tolerance().max(tolerance));
+            vertex
+        })
+        .collect();
+    
+    // Group coedges that share welded end vertices and a matching curve,
+    // looking groups up by their unordered end vertices
+    let mut groups: Vec<EdgeGroup> = Vec::new();
+    let mut groups_between: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
     
-    for (i, (id1, edge1)) in edges.iter().enumerate() {
-        for (id2, edge2) in edges.iter().skip(i + 1) {
-            if edges_match(edge1.as_ref(), edge2.as_ref(), tolerance) {
-                to_merge.push((*id1, *id2));
+    for (fi, face) in shell.faces().iter().enumerate() {
+        for (li, lp) in face.loops().iter().enumerate() {
+            for (ci, coedge) in lp.coedges().iter().enumerate() {
+                let edge = coedge.edge();
+                let start = vertex_cluster[&edge.start_vertex().id()];
+                let end = vertex_cluster[&edge.end_vertex().id()];
+                let between = groups_between.entry((start.min(end), start.max(end))).or_default();
+                
+                let existing = between.iter()
+                    .copied()
+                    .find(|&g| curves_match(&groups[g].template, edge, tolerance));
+                
+                match existing {
+                    Some(g) => {
+                        let reversed = groups[g].start != start;
+                        groups[g].members.push((fi, li, ci, coedge.id(), reversed));
+                    }
+                    None => {
+                        between.push(groups.len());
+                        groups.push(EdgeGroup {
+                            start,
+                            end,
+                            template: edge.clone(),
+                            curve: edge.curve().cloned(),
+                            members: vec![(fi, li, ci, coedge.id(), false)],
+                        });
+                    }
+                }
             }
         }
     }
     
-    // In a full implementation, we would merge the matching edges
-    // and update the coedges to point to the shared edge
-    
-    Ok(())
-}
-
-/// Check if two edges match (same geometry)
-fn edges_match(edge1: &Edge, edge2: &Edge, tolerance: f64) -> bool {
-    // Check if vertices match (possibly reversed)
-    let v1_start = edge1.start_vertex().position();
-    let v1_end = edge1.end_vertex().position();
-    let v2_start = edge2.start_vertex().position();
-    let v2_end = edge2.end_vertex().position();
-    
-    // Same direction
-    let same = v1_start.distance_to(&v2_start) < tolerance && 
-               v1_end.distance_to(&v2_end) < tolerance;
+    // One shared edge per group, built on placeholder vertices until the
+    // welded vertices have recorded their edges
+    let placeholder = Arc::new(Vertex::new(Point3::ORIGIN));
+    let mut edges = Vec::with_capacity(groups.len());
+    for group in &groups {
+        let mut edge = Edge::new(placeholder.clone(), placeholder.clone());
+        edge.set_curve(group.curve.clone());
+        edge.set_tolerance(group.template.tolerance().max(tolerance));
+        for &(_, _, _, coedge_id, _) in &group.members {
+            edge.add_coedge(coedge_id);
+        }
+        welded[group.start].add_edge(edge.id());
+        welded[group.end].add_edge(edge.id());
+        edges.push(edge);
+    }
+    let welded: Vec<Arc<Vertex>> = welded.into_iter().map(Arc::new).collect();
     
-    // Reversed
-    let reversed = v1_start.distance_to(&v2_end) < tolerance && 
-                   v1_end.distance_to(&v2_start) < tolerance;
+    // Replace each group's edges with its shared edge
+    for (group, edge) in groups.into_iter().zip(edges) {
+        let edge = Arc::new(edge.remapped(welded[group.start].clone(), welded[group.end].clone()));
+        
+        for (fi, li, ci, _, reversed) in group.members {
+            let coedge = &mut shell.faces_mut()[fi].loops_mut()[li].coedges_mut()[ci];
+            coedge.set_edge(edge.clone());
+            if reversed {
+                coedge.reverse_sense();
+            }
+        }
+    }
     
-    same || reversed
+    vertex_cluster.len() - clusters.len()
 }
 
-/// Extension trait for Point3
-trait Point3Ext {
-    fn lerp(&self, other: &Self, t: f64) -> Self;
+/// Coedges collected for a single shared edge during stitching
+struct EdgeGroup {
+    /// Welded start vertex index
+    start: usize,
+    /// Welded end vertex index
+    end: usize,
+    /// First edge found, used for geometric comparison
+    template: Edge,
+    /// Curve carried over to the shared edge
+    curve: Option<Arc<dyn Curve>>,
+    /// (face, loop, coedge) indices, coedge id and whether the edge runs backwards
+    members: Vec<(usize, usize, usize, EntityId, bool)>,
 }
 
-impl Point3Ext for Point3 {
-    fn lerp(&self, other: &Self, t: f64) -> Self {
-        Point3::new(
-            self.x() * (1.0 - t) + other.x() * t,
-            self.y() * (1.0 - t) + other.y() * t,
-            self.z() * (1.0 - t) + other.z() * t,
-        )
-    }
+/// Check if two edges with coincident end vertices follow the same path
+fn curves_match(edge1: &Edge, edge2: &Edge, tolerance: f64) -> bool {
+    edge1.midpoint().distance_to(&edge2.midpoint()) <= tolerance
 }
 
 /// Extension trait for Curve
@@ -586,8 +1167,8 @@ mod tests {
     fn test_split_edge() {
         let v1 = Arc::new(Vertex::new(Point3::new(0.0, 0.0, 0.0)));
         let v2 = Arc::new(Vertex::new(Point3::new(2.0, 0.0, 0.0)));
-        let line = Line::from_points(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 0.0, 0.0));
-        let mut edge = Edge::with_curve(v1.clone(), v2.clone(), Arc::new(line));
+        let line = Line::segment(Point3::new(0.0, 0.0, 0.0), Point3::new(2.0, 0.0, 0.0)).unwrap();
+        let edge = Edge::with_curve(v1.clone(), v2.clone(), Arc::new(line));
         
         let (split_v, edge1, edge2) = EulerAdvanced::split_edge(&edge, 0.5).unwrap();
         
@@ -624,6 +1205,50 @@ mod tests {
         assert_eq!(face_count, 4);
     }
 
+    #[test]
+    fn test_stitch_faces_shares_edge() {
+        let v0 = Vertex::new(Point3::new(0.0, 0.0, 0.0));
+        let v1 = Vertex::new(Point3::new(1.0, 0.0, 0.0));
+        let v2 = Vertex::new(Point3::new(0.5, 1.0, 0.0));
+        let v3 = Vertex::new(Point3::new(0.5, -1.0, 0.0));
+        // Near-coincident copies of the shared edge's vertices
+        let v0b = Vertex::new(Point3::new(0.0, 1e-8, 0.0));
+        let v1b = Vertex::new(Point3::new(1.0, 0.0, 1e-8));
+        
+        let faces = vec![
+            create_triangle_face(&v0, &v1, &v2),
+            create_triangle_face(&v1b, &v0b, &v3),
+        ];
+        
+        let body = EulerAdvanced::create_solid_from_faces(&faces, 1e-6).unwrap();
+        
+        assert_eq!(body.vertices().len(), 4);
+        assert_eq!(body.edges().len(), 5);
+        
+        let is_shared = |c: &&Coedge| {
+            let a = c.edge().start_vertex().position();
+            let b = c.edge().end_vertex().position();
+            ((a.x() + b.x()) - 1.0).abs() < 1e-6 && a.y().abs() < 1e-6 && b.y().abs() < 1e-6
+        };
+        let shared: Vec<&Coedge> = body.coedges().into_iter().filter(is_shared).collect();
+        assert_eq!(shared.len(), 2);
+        assert_eq!(shared[0].edge().id(), shared[1].edge().id());
+        assert_eq!(shared[0].edge().coedges().len(), 2);
+        assert!(shared[0].edge().coedges().contains(&shared[0].id()));
+        assert!(shared[0].edge().coedges().contains(&shared[1].id()));
+        
+        for vertex in body.vertices() {
+            assert!(vertex.edges().iter().all(|id| body.edges().iter().any(|e| e.id() == *id)));
+        }
+        assert_eq!(shared[0].start_vertex().edges().len(), 3);
+        
+        // The two faces traverse the shared edge in opposite directions
+        assert_ne!(shared[0].start_vertex().id(), shared[1].start_vertex().id());
+        for lp in body.loops() {
+            assert!(lp.is_closed());
+        }
+    }
+
     fn create_triangle_face(v1: &Vertex, v2: &Vertex, v3: &Vertex) -> Face {
         let mut face = Face::new();
         let mut lp = Loop::new();
@@ -9,17 +9,25 @@
 
 use nova_math::{Point3, Vec3, Transform3, BoundingBox3, ToleranceContext};
 use nova_geom::{Curve, Surface, GeometryError};
+use std::collections::HashSet;
 use std::sync::atomic::{AtomicU64, Ordering};
+use serde::{Deserialize, Serialize};
 
 mod entity;
 mod body;
 mod euler;
 mod euler_advanced;
+mod compare;
+mod measure;
+mod query;
 
 pub use entity::{EntityId, Entity, TopologicalEntity, GeometricEntity};
-pub use body::{Body, Shell, Face, Loop, Coedge, Edge, Vertex};
-pub use euler::{EulerOps, EulerError};
+pub use body::{Body, BodyType, EdgeConvexity, Shell, Face, Loop, Coedge, Edge, Vertex, coedges_of_face, newell_normal};
+pub use euler::{EulerOps, EulerError, build_cube};
 pub use euler_advanced::EulerAdvanced;
+pub use compare::{bodies_equivalent, compare_bodies, BodyDiff, BodyDifference};
+pub use measure::{closest_point_on_body, measure_angle, measure_distance, measure_radius};
+pub use query::{FaceBvh, SpatialQuery};
 
 /// Global entity ID counter
 static ENTITY_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
@@ -47,13 +55,20 @@ pub enum TopologyError {
     /// Geometry error
     #[error("Geometry error: {0}")]
     Geometry(#[from] GeometryError),
+
+    /// Operation needs a solid body
+    #[error("Expected a solid body, found {0:?}")]
+    NotSolid(BodyType),
 }
 
 /// Result type for topology operations
 pub type TopoResult<T> = Result<T, TopologyError>;
 
+/// RGBA color, each channel from 0 to 1
+pub type Color = [f32; 4];
+
 /// Orientation of a topological entity
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Orientation {
     /// Forward orientation
     Forward,
@@ -96,7 +111,7 @@ impl Default for Orientation {
 }
 
 /// Sense flag for coedges (direction within a loop)
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum Sense {
     /// Same direction as edge
     Same,
@@ -134,19 +149,35 @@ impl Default for Sense {
 pub fn validate_body(body: &Body, tolerance: &ToleranceContext) -> TopoResult<Vec<String>> {
     let mut issues = Vec::new();
     
-    // Check Euler-Poincaré formula
-    let v = body.vertices().len();
-    let e = body.edges().len();
-    let f = body.faces().len();
-    let s = body.shells().len();
+    // Euler-Poincaré for each connected boundary surface, with inner loops
+    // as rings: V - E + 2F - L = 2 - 2G for some genus G >= 0
+    for (i, faces) in body.face_components().iter().enumerate() {
+        let counts = LumpSummary::of_surface(faces);
+        if counts.genus.is_none() {
+            issues.push(format!(
+                "Euler characteristic mismatch in component {}: V-E+2F-L={}, expected 2 - 2 x genus",
+                i, counts.euler()
+            ));
+        }
+    }
     
-    // For a solid body: V - E + F = 2 (for each shell)
-    let euler = v as i32 - e as i32 + f as i32;
-    if s > 0 && euler != 2 * s as i32 {
-        issues.push(format!(
-            "Euler characteristic mismatch: V-E+F={}, expected {} for {} shell(s)",
-            euler, 2 * s, s
-        ));
+    // Each lump is one outer shell plus the void shells inside it
+    let outer: Vec<BoundingBox3> = body.shells().iter()
+        .filter(|s| s.is_outer())
+        .map(|s| s.bounding_box())
+        .collect();
+    if outer.is_empty() && !body.shells().is_empty() {
+        issues.push("Body has void shells but no outer shell".to_string());
+    }
+    for shell in body.shells().iter().filter(|s| !s.is_outer()) {
+        let bbox = shell.bounding_box();
+        let containing = outer.iter().filter(|b| b.contains_bbox(&bbox)).count();
+        if containing != 1 {
+            issues.push(format!(
+                "Void shell {:?} lies inside {} outer shells (expected 1)",
+                shell.id(), containing
+            ));
+        }
     }
     
     // Check that all edges have two coedges (manifold)
@@ -177,15 +208,139 @@ pub fn validate_body(body: &Body, tolerance: &ToleranceContext) -> TopoResult<Ve
     Ok(issues)
 }
 
-/// Compute bounding box of a body
-pub fn body_bounding_box(body: &Body) -> BoundingBox3 {
-    let mut bbox = BoundingBox3::empty();
+/// Cell counts and genus of one lump: an outer boundary surface and the
+/// voids inside it
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LumpSummary {
+    /// Distinct vertices
+    pub vertices: usize,
+    /// Distinct edges
+    pub edges: usize,
+    /// Faces
+    pub faces: usize,
+    /// Loops, outer and inner
+    pub loops: usize,
+    /// Boundary surfaces, the outer one plus one per void
+    pub shells: usize,
+    /// Handles through the lump, from V - E + 2F - L = 2 (S - G), or `None`
+    /// when the counts give no whole, non-negative genus
+    pub genus: Option<u32>,
+}
+
+impl LumpSummary {
+    /// Counts for a single connected boundary surface
+    fn of_surface(faces: &[&Face]) -> Self {
+        let mut vertices = HashSet::new();
+        let mut edges = HashSet::new();
+        for coedge in faces.iter().flat_map(|face| coedges_of_face(face)) {
+            vertices.insert(coedge.start_vertex().id());
+            edges.insert(coedge.edge().id());
+        }
+        let mut summary = LumpSummary {
+            vertices: vertices.len(),
+            edges: edges.len(),
+            faces: faces.len(),
+            loops: faces.iter().map(|face| face.loops().len()).sum(),
+            shells: 1,
+            genus: None,
+        };
+        summary.genus = summary.computed_genus();
+        summary
+    }
     
-    for vertex in body.vertices() {
-        bbox.expand(&vertex.position());
+    /// Add a void's surface to the lump
+    fn add_void(&mut self, void: &LumpSummary) {
+        self.vertices += void.vertices;
+        self.edges += void.edges;
+        self.faces += void.faces;
+        self.loops += void.loops;
+        self.shells += void.shells;
+        self.genus = self.computed_genus();
+    }
+    
+    /// V - E + 2F - L, which is 2 (S - G)
+    pub fn euler(&self) -> i64 {
+        self.vertices as i64 - self.edges as i64 + 2 * self.faces as i64 - self.loops as i64
     }
     
-    bbox
+    fn computed_genus(&self) -> Option<u32> {
+        let euler = self.euler();
+        if euler % 2 != 0 {
+            return None;
+        }
+        u32::try_from(self.shells as i64 - euler / 2).ok()
+    }
+}
+
+/// Topology of every lump in a body
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TopologySummary {
+    /// One entry per lump, in the order of their outer shells
+    pub lumps: Vec<LumpSummary>,
+}
+
+impl TopologySummary {
+    /// Total genus of the body, if every lump has one
+    pub fn genus(&self) -> Option<u32> {
+        self.lumps.iter().map(|lump| lump.genus).sum()
+    }
+}
+
+/// Count the cells of each lump and derive its genus
+///
+/// Lumps are the connected pieces of the outer shells. Each void shell
+/// piece joins the first lump whose bounding box contains it, or stands
+/// alone when none does.
+pub fn topology_summary(body: &Body) -> TopologySummary {
+    let bounds = |faces: &[&Face]| {
+        let mut bbox = BoundingBox3::empty();
+        for coedge in faces.iter().flat_map(|face| coedges_of_face(face)) {
+            bbox.expand(&coedge.start_vertex().position());
+        }
+        bbox
+    };
+    let pieces = |outer: bool| {
+        body.shells().iter()
+            .filter(move |shell| shell.is_outer() == outer)
+            .flat_map(|shell| body::connected_faces(shell.faces().iter().collect()))
+    };
+
+    let mut lumps: Vec<(BoundingBox3, LumpSummary)> = pieces(true)
+        .map(|faces| (bounds(&faces), LumpSummary::of_surface(&faces)))
+        .collect();
+    for faces in pieces(false) {
+        let (bbox, void) = (bounds(&faces), LumpSummary::of_surface(&faces));
+        match lumps.iter_mut().find(|(outer, _)| outer.contains_bbox(&bbox)) {
+            Some((_, lump)) => lump.add_void(&void),
+            None => lumps.push((bbox, void)),
+        }
+    }
+    TopologySummary { lumps: lumps.into_iter().map(|(_, lump)| lump).collect() }
+}
+
+/// Compute bounding box of a body
+///
+/// Faces on unbounded surfaces are boxed over the extent of their loops;
+/// see [`Face::bounding_box`].
+pub fn body_bounding_box(body: &Body) -> BoundingBox3 {
+    body.bounding_box()
+}
+
+/// Compute the enclosed volume of a body
+///
+/// Void shells are subtracted from the outer shells whichever way their
+/// faces are oriented. Fails with `NotSolid` unless every shell is closed.
+pub fn body_volume(body: &Body) -> TopoResult<f64> {
+    match body.body_type() {
+        BodyType::Solid => Ok(body.shells()
+            .iter()
+            .map(|shell| {
+                let volume = shell.signed_volume().abs();
+                if shell.is_outer() { volume } else { -volume }
+            })
+            .sum()),
+        other => Err(TopologyError::NotSolid(other)),
+    }
 }
 
 #[cfg(test)]
@@ -220,4 +375,91 @@ mod tests {
         assert!(opp.is_opposite());
         assert_eq!(same.reverse(), opp);
     }
+
+    #[test]
+    fn test_hollow_box_volume() {
+        let mut body = build_cube(2.0).unwrap();
+        let mut cavity = build_cube(1.0).unwrap().shells()[0].clone();
+        cavity.set_outer(false);
+        body.add_shell(cavity);
+
+        assert!((body_volume(&body).unwrap() - 7.0).abs() < 1e-12);
+        let issues = validate_body(&body, &ToleranceContext::default()).unwrap();
+        assert!(!issues.iter().any(|i| i.contains("outer shell")));
+    }
+
+    #[test]
+    fn test_disjoint_lumps_validate() {
+        let stitched = |bodies: &[Body]| {
+            let mut shell = Shell::new();
+            for face in bodies.iter().flat_map(|b| b.faces()) {
+                shell.add_face(face.clone());
+            }
+            EulerAdvanced::stitch_shell(&mut shell, 1e-9);
+            let mut body = Body::new();
+            body.add_shell(shell);
+            body
+        };
+        let near = build_cube(2.0).unwrap();
+        let far = build_cube(2.0).unwrap().transformed(&Transform3::from_translation(5.0, 0.0, 0.0));
+
+        // Two boxes sharing one shell
+        let pair = stitched(&[near.clone(), far]);
+        assert_eq!(pair.face_components().len(), 2);
+        assert_eq!(validate_body(&pair, &ToleranceContext::default()).unwrap(), Vec::<String>::new());
+
+        // A box with its top face missing
+        let mut open = stitched(&[near]);
+        open.shells_mut()[0].faces_mut().remove(1);
+        let issues = validate_body(&open, &ToleranceContext::default()).unwrap();
+        assert!(issues.iter().any(|i| i.contains("Euler characteristic mismatch in component 0")), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_genus_of_box_and_frame() {
+        use std::sync::Arc;
+
+        // 3 x 3 x 1 slab with a 1 x 1 hole through it
+        let square = |half: f64, reversed: bool| {
+            let mut corners = vec![(-half, -half), (half, -half), (half, half), (-half, half)];
+            if reversed {
+                corners.reverse();
+            }
+            let v: Vec<_> = corners.iter().map(|&(x, y)| Arc::new(Vertex::new(Point3::new(x, y, 0.0)))).collect();
+            Loop::from_coedges((0..4).map(|i| Coedge::new(Arc::new(Edge::new(v[i].clone(), v[(i + 1) % 4].clone())), Sense::Same)).collect())
+        };
+        let mut profile = Face::new();
+        profile.add_loop(square(1.5, false));
+        profile.add_loop(square(0.5, true));
+        let mut frame = EulerAdvanced::extrude_face(&profile, Vec3::Z, 1.0).unwrap();
+        EulerAdvanced::stitch_shell(&mut frame.shells_mut()[0], 1e-9);
+
+        let summary = topology_summary(&frame);
+        assert_eq!(summary.lumps.len(), 1);
+        let lump = summary.lumps[0];
+        assert_eq!((lump.vertices, lump.edges, lump.faces, lump.loops, lump.shells), (16, 24, 10, 12, 1));
+        assert_eq!(lump.genus, Some(1));
+
+        let mut cube = build_cube(2.0).unwrap();
+        EulerAdvanced::stitch_shell(&mut cube.shells_mut()[0], 1e-9);
+        let mut cavity = build_cube(1.0).unwrap().shells()[0].clone();
+        EulerAdvanced::stitch_shell(&mut cavity, 1e-9);
+        cavity.set_outer(false);
+        cube.add_shell(cavity);
+        let summary = topology_summary(&cube);
+        assert_eq!(summary.lumps.len(), 1);
+        assert_eq!(summary.lumps[0].shells, 2);
+        assert_eq!(summary.genus(), Some(0));
+    }
+
+    #[test]
+    fn test_void_outside_outer_shell() {
+        let mut body = build_cube(1.0).unwrap();
+        let mut cavity = build_cube(2.0).unwrap().shells()[0].clone();
+        cavity.set_outer(false);
+        body.add_shell(cavity);
+
+        let issues = validate_body(&body, &ToleranceContext::default()).unwrap();
+        assert!(issues.iter().any(|i| i.contains("inside 0 outer shells")));
+    }
 }
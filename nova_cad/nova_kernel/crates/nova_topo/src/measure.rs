@@ -0,0 +1,352 @@
+//! Measurements between topological entities and nearest-point queries
+
+use crate::{Body, Face, Edge, Vertex, Entity, EntityId};
+use nova_geom::{CurveType, SurfaceType};
+use nova_math::{BoundingBox3, Point3, Vec3};
+
+/// Chords per edge when sampling curved edges
+const EDGE_SAMPLES: usize = 64;
+
+/// Grid divisions per direction when searching the inside of a trimmed face
+const TRIM_SAMPLES: usize = 32;
+
+/// Angle in radians between the normals of two planar faces
+///
+/// Faces without a surface use the winding of their outer loop. The result
+/// lies in [0, pi], so adjacent faces of a box measure pi / 2 and opposite
+/// faces pi. Returns `None` when either face is curved or has no direction.
+pub fn measure_angle(a: &Face, b: &Face) -> Option<f64> {
+    let (na, nb) = (planar_normal(a)?, planar_normal(b)?);
+    Some(na.dot(&nb).clamp(-1.0, 1.0).acos())
+}
+
+/// Minimum distance between two vertices, edges or faces of `body`
+///
+/// Distances to vertices and from points to faces are exact for lines and
+/// planes; curved edges are sampled into chords, and edge or face pairs
+/// compare each one's boundary against the other. Returns NaN when either
+/// id is not in `body`.
+pub fn measure_distance(a: EntityId, b: EntityId, body: &Body) -> f64 {
+    let (Some(a), Some(b)) = (find(body, a), find(body, b)) else {
+        return f64::NAN;
+    };
+    let one_way = |from: &Measured, to: &Measured| {
+        from.samples().iter().map(|&p| to.distance_to(p)).fold(f64::INFINITY, f64::min)
+    };
+    one_way(&a, &b).min(one_way(&b, &a))
+}
+
+/// Nearest point to `p` on any face of `body`, with the face's id and the distance
+///
+/// Faces are visited nearest bounding box first and skipped once their box
+/// lies further away than the best point so far; curved faces may bulge
+/// past the box of their boundary, so they are always visited. An empty
+/// body gives `p` itself, the null id and an infinite distance.
+pub fn closest_point_on_body(body: &Body, p: &Point3) -> (Point3, EntityId, f64) {
+    let mut faces: Vec<(f64, &Face)> = body.faces().into_iter()
+        .map(|face| {
+            let flat = face.surface().map_or(true, |s| s.surface_type() == SurfaceType::Planar);
+            let bound = if flat { face_box(face).distance_to_point(p) } else { 0.0 };
+            (bound, face)
+        })
+        .collect();
+    faces.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let mut best = (*p, EntityId::NULL, f64::INFINITY);
+    for (bound, face) in faces {
+        if bound > best.2 {
+            break;
+        }
+        let (point, distance) = face_closest(face, *p);
+        if distance < best.2 {
+            best = (point, face.id(), distance);
+        }
+    }
+    best
+}
+
+/// Radius of a cylindrical or spherical face
+pub fn measure_radius(face: &Face) -> Option<f64> {
+    let surface = face.surface()?;
+    if !matches!(surface.surface_type(), SurfaceType::Cylindrical | SurfaceType::Spherical) {
+        return None;
+    }
+    let range = surface.uv_range();
+    let mid = |start: f64, end: f64| if start.is_finite() && end.is_finite() { (start + end) / 2.0 } else { 0.0 };
+    let (k1, k2) = surface.principal_curvatures(mid(range.u.start, range.u.end), mid(range.v.start, range.v.end));
+    let curvature = k1.abs().max(k2.abs());
+    (curvature > 0.0).then(|| 1.0 / curvature)
+}
+
+/// An entity found in a body by id
+enum Measured<'a> {
+    Vertex(&'a Vertex),
+    Edge(&'a Edge),
+    Face(&'a Face),
+}
+
+impl Measured<'_> {
+    /// Points covering the entity, or its boundary for a face
+    fn samples(&self) -> Vec<Point3> {
+        match self {
+            Measured::Vertex(v) => vec![v.position()],
+            Measured::Edge(e) => edge_points(e),
+            Measured::Face(f) => f.loops().iter().flat_map(|lp| lp.coedges()).flat_map(|c| edge_points(c.edge())).collect(),
+        }
+    }
+
+    /// Distance from `point` to the entity
+    fn distance_to(&self, point: Point3) -> f64 {
+        match self {
+            Measured::Vertex(v) => v.position().distance_to(&point),
+            Measured::Edge(e) => polyline_closest(&edge_points(e), point).1,
+            Measured::Face(f) => face_closest(f, point).1,
+        }
+    }
+}
+
+/// Look up a vertex, edge or face of `body`
+fn find(body: &Body, id: EntityId) -> Option<Measured<'_>> {
+    if let Some(v) = body.vertices().into_iter().find(|v| v.id() == id) {
+        return Some(Measured::Vertex(v));
+    }
+    if let Some(e) = body.edges().into_iter().find(|e| e.id() == id) {
+        return Some(Measured::Edge(e));
+    }
+    body.faces().into_iter().find(|f| f.id() == id).map(Measured::Face)
+}
+
+/// Unit normal of a planar or surfaceless face
+fn planar_normal(face: &Face) -> Option<Vec3> {
+    if face.surface().is_some_and(|s| s.surface_type() != SurfaceType::Planar) {
+        return None;
+    }
+    let normal = face.normal_at(0.0, 0.0);
+    (!normal.is_zero(1e-12)).then_some(normal)
+}
+
+/// Bounding box of a face's sampled boundary
+fn face_box(face: &Face) -> BoundingBox3 {
+    let mut bbox = BoundingBox3::empty();
+    for coedge in face.loops().iter().flat_map(|lp| lp.coedges()) {
+        for point in edge_points(coedge.edge()) {
+            bbox.expand(&point);
+        }
+    }
+    bbox
+}
+
+/// Points along an edge from its start to its end vertex
+pub(crate) fn edge_points(edge: &Edge) -> Vec<Point3> {
+    let (start, end) = (edge.start_vertex().position(), edge.end_vertex().position());
+    match edge.curve() {
+        Some(curve) if curve.param_range().length().is_finite() && curve.curve_type() != CurveType::Line => {
+            let range = curve.param_range();
+            (0..=EDGE_SAMPLES)
+                .map(|i| curve.evaluate(range.start + range.length() * i as f64 / EDGE_SAMPLES as f64))
+                .collect()
+        }
+        _ => vec![start, end],
+    }
+}
+
+/// Nearest point to `point` on a polyline and its distance
+fn polyline_closest(points: &[Point3], point: Point3) -> (Point3, f64) {
+    if points.len() == 1 {
+        return (points[0], points[0].distance_to(&point));
+    }
+    points.windows(2).map(|w| {
+        let chord = w[1] - w[0];
+        let length = chord.length_squared();
+        let t = if length > 0.0 { ((point - w[0]).dot(&chord) / length).clamp(0.0, 1.0) } else { 0.0 };
+        let foot = w[0] + chord * t;
+        (foot, foot.distance_to(&point))
+    }).fold((point, f64::INFINITY), |best, next| if next.1 < best.1 { next } else { best })
+}
+
+/// Nearest point to `point` on a face and its distance
+///
+/// This is the foot of the perpendicular to the face's surface when that
+/// lands inside the boundary. Otherwise it is the nearer of the boundary
+/// and a grid over the surface parameters that lie inside the loops.
+pub(crate) fn face_closest(face: &Face, point: Point3) -> (Point3, f64) {
+    let boundary: Vec<Vec<Point3>> = face.loops().iter()
+        .map(|lp| lp.coedges().iter().flat_map(|c| {
+            let mut points = edge_points(c.edge());
+            if c.sense().is_opposite() {
+                points.reverse();
+            }
+            points.pop();
+            points
+        }).collect())
+        .collect();
+    let to_boundary = boundary.iter()
+        .filter(|lp| !lp.is_empty())
+        .map(|lp| {
+            let mut closed = lp.clone();
+            closed.push(lp[0]);
+            polyline_closest(&closed, point)
+        })
+        .fold((point, f64::INFINITY), |best, next| if next.1 < best.1 { next } else { best });
+
+    // Flatten the boundary and the foot into the surface's parameters, or
+    // the outer loop's plane for faces without a surface
+    let flatten: Box<dyn Fn(Point3) -> Option<(f64, f64)>> = match face.surface() {
+        Some(surface) => Box::new(move |p| surface.closest_point(&p).ok().map(|(u, v, _, _)| (u, v))),
+        None => {
+            let normal = face.normal_at(0.0, 0.0);
+            if normal.is_zero(1e-12) {
+                return to_boundary;
+            }
+            let e1 = if normal.x().abs() < 0.9 { Vec3::X } else { Vec3::Y }.reject_from(&normal).normalized();
+            let e2 = normal.cross(&e1);
+            Box::new(move |p| Some((p.to_vector().dot(&e1), p.to_vector().dot(&e2))))
+        }
+    };
+    let foot = match face.surface() {
+        Some(surface) => surface.closest_point(&point).ok().map(|(_, _, foot, _)| foot),
+        None => {
+            let normal = face.normal_at(0.0, 0.0);
+            boundary.first().and_then(|lp| lp.first()).map(|&origin| point - normal * (point - origin).dot(&normal))
+        }
+    };
+    let Some(foot) = foot else {
+        return to_boundary;
+    };
+    let Some(uv) = flatten(foot) else {
+        return to_boundary;
+    };
+    let polygons: Vec<Vec<(f64, f64)>> = boundary.iter()
+        .map(|lp| lp.iter().filter_map(|&p| flatten(p)).collect())
+        .collect();
+    if uv_inside(&polygons, uv) {
+        let foot = match face.surface() {
+            Some(surface) => refine_inside(surface.as_ref(), &polygons, point, uv).0,
+            None => foot,
+        };
+        let through = foot.distance_to(&point);
+        return if through <= to_boundary.1 { (foot, through) } else { to_boundary };
+    }
+
+    // The foot is trimmed away, so look for a nearer point in the part of
+    // the surface the loops keep
+    let (Some(surface), Some(extent)) = (face.surface(), face.uv_extent()) else {
+        return to_boundary;
+    };
+    let mut best = to_boundary;
+    let mut start = None;
+    for i in 0..=TRIM_SAMPLES {
+        for j in 0..=TRIM_SAMPLES {
+            let u = extent.u.start + extent.u.length() * i as f64 / TRIM_SAMPLES as f64;
+            let v = extent.v.start + extent.v.length() * j as f64 / TRIM_SAMPLES as f64;
+            if !uv_inside(&polygons, (u, v)) {
+                continue;
+            }
+            let sample = surface.evaluate(u, v);
+            let distance = sample.distance_to(&point);
+            if distance < best.1 {
+                best = (sample, distance);
+                start = Some((u, v));
+            }
+        }
+    }
+    match start {
+        Some(uv) => refine_inside(surface.as_ref(), &polygons, point, uv),
+        None => best,
+    }
+}
+
+/// Gauss-Newton descent from `uv` towards the foot of `point` on `surface`,
+/// stopping before a step would leave the loops
+fn refine_inside(surface: &dyn nova_geom::Surface, polygons: &[Vec<(f64, f64)>], point: Point3, (mut u, mut v): (f64, f64)) -> (Point3, f64) {
+    for _ in 0..8 {
+        let (su, sv) = surface.derivatives(u, v);
+        let r = point - surface.evaluate(u, v);
+        let (a, b, c) = (su.dot(&su), su.dot(&sv), sv.dot(&sv));
+        let det = a * c - b * b;
+        if det.abs() < 1e-20 {
+            break;
+        }
+        let (ru, rv) = (su.dot(&r), sv.dot(&r));
+        let (du, dv) = ((c * ru - b * rv) / det, (a * rv - b * ru) / det);
+        let next = (u + du, v + dv);
+        if !uv_inside(polygons, next) || surface.evaluate(next.0, next.1).distance_to(&point) > r.length() {
+            break;
+        }
+        (u, v) = next;
+        if du.abs() + dv.abs() < 1e-14 {
+            break;
+        }
+    }
+    let foot = surface.evaluate(u, v);
+    (foot, foot.distance_to(&point))
+}
+
+/// Whether `uv` lies inside the loops, by even-odd crossings over every
+/// loop so holes count as outside
+fn uv_inside(polygons: &[Vec<(f64, f64)>], uv: (f64, f64)) -> bool {
+    let mut inside = false;
+    for polygon in polygons {
+        for i in 0..polygon.len() {
+            let ((x0, y0), (x1, y1)) = (polygon[i], polygon[(i + 1) % polygon.len()]);
+            if (y0 > uv.1) != (y1 > uv.1) && uv.0 < x0 + (uv.1 - y0) / (y1 - y0) * (x1 - x0) {
+                inside = !inside;
+            }
+        }
+    }
+    inside
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_cube;
+    use nova_geom::CylindricalSurface;
+    use std::f64::consts::{FRAC_PI_2, PI};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_box_face_angles_and_distances() {
+        let body = build_cube(10.0).unwrap();
+        let faces = body.faces();
+        let (bottom, top, right) = (faces[0], faces[1], faces[5]);
+        assert!((measure_angle(bottom, right).unwrap() - FRAC_PI_2).abs() < 1e-12);
+        assert!((measure_angle(bottom, top).unwrap() - PI).abs() < 1e-12);
+
+        assert!((measure_distance(bottom.id(), top.id(), &body) - 10.0).abs() < 1e-12);
+        assert!(measure_distance(bottom.id(), right.id(), &body).abs() < 1e-12);
+        let corner = top.outer_loop().unwrap().coedges()[0].start_vertex().id();
+        assert!((measure_distance(corner, bottom.id(), &body) - 10.0).abs() < 1e-12);
+        assert!(measure_distance(corner, EntityId(u64::MAX), &body).is_nan());
+    }
+
+    #[test]
+    fn test_closest_point_outside_box() {
+        let body = build_cube(2.0).unwrap();
+        // In front of the right face, nearer it than the top
+        let (point, face, distance) = closest_point_on_body(&body, &Point3::new(3.0, 0.2, 0.5));
+        assert!(point.distance_to(&Point3::new(1.0, 0.2, 0.5)) < 1e-12);
+        assert_eq!(face, body.faces()[5].id());
+        assert!((distance - 2.0).abs() < 1e-12);
+
+        // Past a corner, the corner itself is nearest
+        let (point, _, distance) = closest_point_on_body(&body, &Point3::new(2.0, 2.0, 2.0));
+        assert!(point.distance_to(&Point3::new(1.0, 1.0, 1.0)) < 1e-12);
+        assert!((distance - 3.0f64.sqrt()).abs() < 1e-12);
+
+        let (_, face, distance) = closest_point_on_body(&Body::new(), &Point3::ORIGIN);
+        assert_eq!(face, EntityId::NULL);
+        assert!(distance.is_infinite());
+    }
+
+    #[test]
+    fn test_cylinder_face_radius() {
+        let cylinder = CylindricalSurface::bounded(Point3::ORIGIN, Vec3::Z, 2.5, Vec3::X, 0.0, 1.0, 0.0, PI).unwrap();
+        let face = Face::with_surface(Arc::new(cylinder));
+        assert!((measure_radius(&face).unwrap() - 2.5).abs() < 1e-12);
+        assert!(measure_angle(&face, &face).is_none());
+
+        let cube = build_cube(1.0).unwrap();
+        assert!(measure_radius(cube.faces()[0]).is_none());
+    }
+}
@@ -0,0 +1,181 @@
+//! Spatial queries over the faces of a body
+
+use crate::measure::face_closest;
+use crate::{Body, Face, Entity, EntityId};
+use nova_math::{BoundingBox3, Point3};
+
+/// Most faces held by a leaf of a [`FaceBvh`]
+const LEAF_FACES: usize = 2;
+
+/// Nearest-entity and range queries around a point
+pub trait SpatialQuery {
+    /// The `k` entities nearest `point` and their distances, nearest first
+    fn nearest(&self, point: &Point3, k: usize) -> Vec<(EntityId, f64)>;
+
+    /// Entities no further than `r` from `point`, in ascending id order
+    fn within_radius(&self, point: &Point3, r: f64) -> Vec<EntityId>;
+}
+
+/// Bounding volume hierarchy over a body's faces
+///
+/// Each node splits its faces at the median of their box centers along its
+/// longest axis. Distances are to the trimmed faces, so a query point facing
+/// a hole measures to the hole's rim.
+pub struct FaceBvh<'a> {
+    faces: Vec<(&'a Face, BoundingBox3)>,
+    nodes: Vec<BvhNode>,
+}
+
+/// Box around `faces[start..end]` and the nodes splitting it, if any
+struct BvhNode {
+    bbox: BoundingBox3,
+    start: usize,
+    end: usize,
+    children: Option<(usize, usize)>,
+}
+
+impl<'a> FaceBvh<'a> {
+    /// Build the hierarchy over every face of `body`
+    pub fn new(body: &'a Body) -> Self {
+        let faces = body.faces().into_iter().map(|face| (face, face.bounding_box())).collect();
+        let mut bvh = Self { faces, nodes: Vec::new() };
+        if !bvh.faces.is_empty() {
+            bvh.build(0, bvh.faces.len());
+        }
+        bvh
+    }
+
+    /// Number of faces in the hierarchy
+    pub fn len(&self) -> usize {
+        self.faces.len()
+    }
+
+    /// Whether the hierarchy holds no faces
+    pub fn is_empty(&self) -> bool {
+        self.faces.is_empty()
+    }
+
+    fn build(&mut self, start: usize, end: usize) -> usize {
+        let bbox = self.faces[start..end].iter().fold(BoundingBox3::empty(), |acc, (_, b)| acc.union(b));
+        let index = self.nodes.len();
+        self.nodes.push(BvhNode { bbox, start, end, children: None });
+        if end - start > LEAF_FACES {
+            let axis = bbox.longest_axis();
+            self.faces[start..end].sort_by(|a, b| a.1.center()[axis].total_cmp(&b.1.center()[axis]));
+            let mid = (start + end) / 2;
+            let left = self.build(start, mid);
+            let right = self.build(mid, end);
+            self.nodes[index].children = Some((left, right));
+        }
+        index
+    }
+
+    fn nearest_in(&self, node: usize, point: &Point3, k: usize, found: &mut Vec<(EntityId, f64)>) {
+        let node = &self.nodes[node];
+        if found.len() == k && node.bbox.distance_to_point(point) > found[k - 1].1 {
+            return;
+        }
+        match node.children {
+            Some((left, right)) => {
+                // Nearer child first so the other is more often pruned
+                let (near, far) = if self.nodes[left].bbox.distance_to_point(point) <= self.nodes[right].bbox.distance_to_point(point) {
+                    (left, right)
+                } else {
+                    (right, left)
+                };
+                self.nearest_in(near, point, k, found);
+                self.nearest_in(far, point, k, found);
+            }
+            None => {
+                for (face, _) in &self.faces[node.start..node.end] {
+                    let distance = face_closest(face, *point).1;
+                    let at = found.partition_point(|&(_, d)| d <= distance);
+                    if at < k {
+                        found.insert(at, (face.id(), distance));
+                        found.truncate(k);
+                    }
+                }
+            }
+        }
+    }
+
+    fn within_in(&self, node: usize, point: &Point3, r: f64, found: &mut Vec<EntityId>) {
+        let node = &self.nodes[node];
+        if node.bbox.distance_to_point(point) > r {
+            return;
+        }
+        match node.children {
+            Some((left, right)) => {
+                self.within_in(left, point, r, found);
+                self.within_in(right, point, r, found);
+            }
+            None => found.extend(self.faces[node.start..node.end].iter()
+                .filter(|(face, _)| face_closest(face, *point).1 <= r)
+                .map(|(face, _)| face.id())),
+        }
+    }
+}
+
+impl SpatialQuery for FaceBvh<'_> {
+    fn nearest(&self, point: &Point3, k: usize) -> Vec<(EntityId, f64)> {
+        let mut found = Vec::with_capacity(k.min(self.faces.len()));
+        if k > 0 && !self.nodes.is_empty() {
+            self.nearest_in(0, point, k, &mut found);
+        }
+        found
+    }
+
+    fn within_radius(&self, point: &Point3, r: f64) -> Vec<EntityId> {
+        let mut found = Vec::new();
+        if !self.nodes.is_empty() {
+            self.within_in(0, point, r, &mut found);
+        }
+        found.sort();
+        found
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::build_cube;
+
+    #[test]
+    fn test_nearest_faces_of_box() {
+        let body = build_cube(2.0).unwrap();
+        let faces = body.faces();
+        let bvh = FaceBvh::new(&body);
+        assert_eq!(bvh.len(), 6);
+
+        // In front of the right face, nearer it than the top
+        let nearest = bvh.nearest(&Point3::new(3.0, 0.2, 0.5), 1);
+        assert_eq!(nearest.len(), 1);
+        assert_eq!(nearest[0].0, faces[5].id());
+        assert!((nearest[0].1 - 2.0).abs() < 1e-12);
+
+        let all = bvh.nearest(&Point3::new(3.0, 0.2, 0.5), 10);
+        assert_eq!(all.len(), 6);
+        assert!(all.windows(2).all(|w| w[0].1 <= w[1].1));
+        assert_eq!(body.nearest_faces(&Point3::new(3.0, 0.2, 0.5), 2), all[..2].to_vec());
+    }
+
+    #[test]
+    fn test_faces_within_radius_of_box() {
+        let body = build_cube(2.0).unwrap();
+        let faces = body.faces();
+        let bvh = FaceBvh::new(&body);
+        let point = Point3::new(1.5, 0.0, 0.0);
+
+        // Only the right face is half a unit away; its four neighbours are
+        // sqrt(0.5^2 + 1^2) away at their shared edges, the left face 2.5
+        assert_eq!(bvh.within_radius(&point, 0.6), vec![faces[5].id()]);
+        let left = faces.iter()
+            .find(|face| face.outer_loop().unwrap().coedges().iter().all(|c| c.start_vertex().position().x() < 0.0))
+            .unwrap();
+        let mut sides: Vec<EntityId> = faces.iter().map(|face| face.id()).filter(|&id| id != left.id()).collect();
+        sides.sort();
+        assert_eq!(bvh.within_radius(&point, 1.2), sides);
+        assert_eq!(bvh.within_radius(&point, 3.0).len(), 6);
+        assert!(bvh.within_radius(&point, 0.4).is_empty());
+    }
+}